@@ -0,0 +1,99 @@
+//! Benchmarks of encoding images. Run with `cargo bench --features
+//! term_image,rayon` to compare sequential and parallel encoding.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use termal::image::{
+    push_sixel, push_texel_half, push_texel_quater, ImgNearest, RawImg,
+};
+
+/// Creates RGB image with colorful pattern.
+fn pattern(w: usize, h: usize) -> RawImg {
+    let mut data = Vec::with_capacity(w * h * 3);
+    for y in 0..h {
+        for x in 0..w {
+            data.push((x * 255 / w) as u8);
+            data.push((y * 255 / h) as u8);
+            data.push(((x ^ y) & 0xff) as u8);
+        }
+    }
+    RawImg::from_rgb(data, w, h)
+}
+
+/// Measures the average time of `f`.
+fn bench(name: &str, mut f: impl FnMut()) {
+    let mut iters = 0;
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(2) {
+        f();
+        iters += 1;
+    }
+    let time = start.elapsed() / iters;
+    println!("{name:<24} {time:>12.2?}");
+}
+
+fn main() {
+    let img = pattern(1920, 1080);
+    println!("image: {}x{}", 1920, 1080);
+
+    bench("sixel", || {
+        let mut res = String::new();
+        push_sixel(&mut res, black_box(&img));
+        black_box(res);
+    });
+    bench("texel half 200", || {
+        let mut res = String::new();
+        push_texel_half(black_box(&img), &mut res, "\n", Some(200), None);
+        black_box(res);
+    });
+    bench("texel quater 200", || {
+        let mut res = String::new();
+        push_texel_quater(black_box(&img), &mut res, "\n", Some(200), None);
+        black_box(res);
+    });
+
+    #[cfg(feature = "rayon")]
+    {
+        use termal::image::{
+            push_sixel_par, push_texel_half_par, push_texel_quater_par,
+        };
+
+        bench("sixel par", || {
+            let mut res = String::new();
+            push_sixel_par(&mut res, black_box(&img));
+            black_box(res);
+        });
+        bench("texel half 200 par", || {
+            let mut res = String::new();
+            push_texel_half_par(
+                black_box(&img),
+                &mut res,
+                "\n",
+                Some(200),
+                None,
+            );
+            black_box(res);
+        });
+        bench("texel quater 200 par", || {
+            let mut res = String::new();
+            push_texel_quater_par(
+                black_box(&img),
+                &mut res,
+                "\n",
+                Some(200),
+                None,
+            );
+            black_box(res);
+        });
+    }
+
+    let img = ImgNearest(img);
+    bench("texel half 200 nearest", || {
+        let mut res = String::new();
+        push_texel_half(black_box(&img), &mut res, "\n", Some(200), None);
+        black_box(res);
+    });
+}
@@ -0,0 +1,58 @@
+//! Benchmarks of scanning text with control sequences. Run with
+//! `cargo bench --features term_text`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use termal::{formatc, term_text::TermText};
+
+/// Creates large colored log.
+fn colored_log(lines: usize) -> String {
+    let mut res = String::new();
+    for i in 0..lines {
+        res += &formatc!(
+            "{'gr}2025-01-01 12:00:{:02}{'_} {'g bold}INFO{'_} \
+            {'b}termal::bench{'_}: processed item {i} in {'y}{}ms{'_}\n",
+            i % 60,
+            i % 1000,
+        );
+    }
+    res
+}
+
+/// Measures the throughput of `f` on `text`.
+fn bench(name: &str, text: &str, mut f: impl FnMut(&str)) {
+    let mut iters = 0;
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(2) {
+        f(black_box(text));
+        iters += 1;
+    }
+    let time = start.elapsed() / iters;
+    let mbs = text.len() as f64 / time.as_secs_f64() / 1_000_000.;
+    println!("{name:<20} {time:>12.2?} {mbs:>10.1} MB/s");
+}
+
+fn main() {
+    let log = colored_log(20_000);
+    let plain: String = log
+        .lines()
+        .map(|l| TermText::new(l).strip_control() + "\n")
+        .collect();
+    println!("colored log: {} bytes", log.len());
+
+    bench("strip_control", &log, |s| {
+        black_box(TermText::new(s).strip_control());
+    });
+    bench("metadata", &log, |s| {
+        black_box(TermText::new(s).get_metadata());
+    });
+    bench("metadata plain", &plain, |s| {
+        black_box(TermText::new(s).get_metadata());
+    });
+    bench("spans", &log, |s| {
+        black_box(TermText::new(s).spans().count());
+    });
+}
@@ -0,0 +1,51 @@
+use std::{env::args, io::Write};
+
+use termal::{
+    codes, demo, eprintacln,
+    error::Result,
+    raw::{raw_mode_guard, Terminal},
+};
+
+fn main() -> Result<()> {
+    match args().nth(1).as_deref() {
+        Some("colors") => colors(),
+        Some("styles") => styles(),
+        Some("keys") => keys(),
+        None => {
+            colors()?;
+            styles()?;
+            keys()
+        }
+        Some(a) => {
+            eprintacln!(
+                "{'r}error: {'_}unknown demo `{a}`. Expected `colors`, \
+                `styles` or `keys`."
+            );
+            Ok(())
+        }
+    }
+}
+
+fn colors() -> Result<()> {
+    let _guard = raw_mode_guard()?;
+    let mut term = Terminal::stdio();
+    for page in demo::color_chart() {
+        term.print(codes::CLEAR)?;
+        term.print(page)?;
+        term.print("\nPress any key to continue.")?;
+        term.flush()?;
+        term.read()?;
+    }
+    term.println(codes::CLEAR)?;
+    Ok(())
+}
+
+fn styles() -> Result<()> {
+    print!("{}", demo::style_matrix());
+    Ok(())
+}
+
+fn keys() -> Result<()> {
+    let _guard = raw_mode_guard()?;
+    demo::key_tester(&mut Terminal::stdio())
+}
@@ -0,0 +1,5 @@
+use termal::diagnostics;
+
+fn main() {
+    print!("{}", diagnostics::report());
+}
@@ -24,6 +24,7 @@ fn single(name: &str) -> Result<()> {
         "codes::CARRIAGE_RETURN" => codes::show_carriage_return(),
         "codes::DELETE" => codes::show_delete(),
         "codes::move_to!" => codes::show_move_to(),
+        "codes::ENABLE_FOCUS_EVENT" => codes::show_enable_focus_event(),
         _ => {
             eprintacln!("{'r}error: {'_}unknown feature `{name}`.");
             Ok(())
@@ -41,5 +42,6 @@ fn all() -> Result<()> {
     codes::show_carriage_return()?;
     codes::show_delete()?;
     codes::show_move_to()?;
+    codes::show_enable_focus_event()?;
     Ok(())
 }
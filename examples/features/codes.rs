@@ -1,7 +1,12 @@
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
 use termal::{
     codes,
     error::Result,
-    raw::{enable_raw_mode, term_size},
+    raw::{enable_raw_mode, term_size, Terminal},
     reset_terminal,
 };
 
@@ -91,7 +96,7 @@ pub fn show_move_to() -> Result<()> {
 
     let txt = "centered";
     let size = term_size()?;
-    let x = (size.char_width - txt.len() + 1) / 2;
+    let x = (size.char_width - txt.len()).div_ceil(2);
     let y = size.char_height / 2;
     // If one of arguments is not literal, produces string.
     let center: String = codes::move_to!(x, y);
@@ -110,3 +115,30 @@ pub fn show_move_to() -> Result<()> {
 
     Ok(())
 }
+
+pub fn show_enable_focus_event() -> Result<()> {
+    enable_raw_mode()?;
+    print!("{}", codes::ENABLE_FOCUS_EVENT);
+    print!("Switch focus to and from the terminal (waiting 5 s):\n\r");
+    _ = io::stdout().flush();
+
+    // The terminal will now send focus events when the focus changes.
+    let mut term = Terminal::stdio();
+    let end = Instant::now() + Duration::from_secs(5);
+    while let Some(evt) =
+        term.read_timeout(end.saturating_duration_since(Instant::now()))?
+    {
+        match evt.focus() {
+            Some(true) => print!("focus gained\n\r"),
+            Some(false) => print!("focus lost\n\r"),
+            None => {}
+        }
+        _ = io::stdout().flush();
+    }
+
+    // Focus events are also disabled by `reset_terminal`.
+    print!("{}", codes::DISABLE_FOCUS_EVENT);
+    reset_terminal();
+
+    Ok(())
+}
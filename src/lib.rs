@@ -31,9 +31,13 @@
 //! The colors can be set either by the [color commands](#color-commands) or
 //! by hex color:
 //!
-//! The hex color starts with `#` and can contain either 1, 2, 3 or 6 hex
-//! digits. They are interpreted as follows:
+//! The hex color starts with `#` and can contain either 1, 2, 3, 4, 6 or 8
+//! hex digits. They are interpreted as follows:
+//! - **8 digits:** RGBA color, the alpha is ignored (e.g. `#FF000080` is
+//!   pure red)
 //! - **6 digits:** normal 6 digit RGB color (e.g `#FF0000` is pure red)
+//! - **4 digits:** RGBA color, the alpha is ignored (e.g. `#ABC8` has the
+//!   same result as `#ABC`)
 //! - **3 digits:** 3 digit RGB color, each digit is repeated twice (e.g.
 //!   `#ABC` has the same result as `#AABBCC`)
 //! - **2 digits:** the two digits are repeated 3 times to form one of 256
@@ -41,13 +45,16 @@
 //! - **1 digit:** the digit is repeated 6 times to form one of 16 shades of
 //!   gray (e.g. `#A` has the same result as `#AAAAAA`)
 //!
+//! The same rules are used at runtime by [`Rgb::from_hex`].
+//!
 //! If you want to set the foregorund color, you just type the hex (e.g.
 //! `#FF0000` will set the foreground color to pure red). In order to set the
 //! background color, you can append `_` to the color (e.g. `#FF0000_` will set
 //! the background color to pure red).
 //!
 //! If you want to set the underline color, just type the same as background
-//! color, but use `u` instead of the `_`.
+//! color, but use `u` instead of the `_`. Alternatively, you can use the
+//! `ucolor` command with the hex color (e.g. `ucolor#FF0000`).
 //!
 //! ### Ascii commands
 //! - `bell`: console bell (create sound)
@@ -75,11 +82,22 @@
 //!   argument, default value is `1`
 //! - `move_to_column`, `mc`: moves the cursor to the given x coordinate, has
 //!   one argument, default value is `0`
+//! - `move_to_row`, `mrow`: moves the cursor to the given y coordinate, has
+//!   one argument, default value is `0`
+//! - `scroll_up`, `scu`: scrolls the content up by the given amount, has one
+//!   argument, default value is `1`
+//! - `scroll_down`, `scd`: scrolls the content down by the given amount, has
+//!   one argument, default value is `1`
+//! - `forward_tab`, `ftab`: moves the cursor forward by the given number of
+//!   tab stops, has one argument, default value is `1`
 //! + `move_up_scrl`, `mus`: moves the cursor up by one line, scrolling if
-//!    needed
+//!   needed
 //! + `save_cur`, `save`, `s`: saves the current cursor position (single slot,
 //!   not stack)
 //! + `load_cur`, `load`, `l`: loads the last saved cursor position
+//! + `set_tab`, `stab`: sets tab stop at the cursor position
+//! + `clear_tab`, `ctab`: clears tab stop at the cursor position
+//! + `clear_all_tabs`, `ctabs`: clears all tab stops
 //!
 //! ### Erase commands
 //! - `erase_to_end`, `e_`: erases from the cursor to the end of the screen
@@ -106,9 +124,12 @@
 //! - `double_underline`, `dunderline`, `dun`: sets the style to double
 //!   underline
 //! - `overline`, `ol`: sets the style to overline
+//! - `curly_underline`, `curly`: sets the style to curly underline
+//! - `dotted_underline`, `dotted`: sets the style to dotted underline
+//! - `dashed_underline`, `dashed`: sets the style to dashed underline
 //! + `_bold`: resets bold and faint
 //! + `_italic`, `_i`: resets italic
-//! + `_underline`, `_u`: resets underline and double underline
+//! + `_underline`, `_u`: resets all underline styles
 //! + `_blinking`, `_blink`: resets blinking
 //! + `_inverse`: resets inverse
 //! + `_invisible`, `_invis`: resets invisible
@@ -153,7 +174,8 @@
 //! - `bg`: sets the background color to one of the 256 colors, has one
 //!   argument
 //! - `ucolor`, `uc`: sets the underline color to one of the 256 colors, has
-//!   one argument.
+//!   one argument. The argument may also be hex color (e.g. `ucolor#FF0000`)
+//!   to set rgb underline color.
 //!
 //! ### Other
 //! - `line_wrap`, `wrap`: enable line wrapping
@@ -177,6 +199,9 @@
 //! *no color*: [`formatnc`], [`printnc`], [`printncln`], [`eprintnc`],
 //! [`eprintncln`], [`writenc`] and [`writencln`].
 //!
+//! The uncoloring macros remove only the commands in the template. Use
+//! [`formatnc_strict`] to also remove control sequences from the arguments.
+//!
 //! ## The conditionally coloring macros
 //! Theese are same as the normal coloring macros except they take additional
 //! first argument that tells whether the output should be colored or not.
@@ -185,6 +210,24 @@
 //! of the `n` to signify *maybe color*:[`formatmc`], [`printmc`],
 //! [`printmcln`], [`eprintmc`], [`eprintmcln`], [`writemc`] and [`writemcln`].
 //!
+//! The uncolored output may use different template given after `else`. This
+//! is useful when the uncolored output should have different markers instead
+//! of just the colors stripped. The arguments are shared by both templates,
+//! so both must use all of them.
+//! ```rust
+//! use termal::*;
+//!
+//! let s = "careful";
+//! assert_eq!(formatmc!(false, "{'bold}{s}{'_}", else "*{s}*"), "*careful*");
+//! assert_eq!(
+//!     formatmc!(true, "{'bold}{}{'_}", else "*{}*", s),
+//!     formatc!("{'bold}careful{'_}"),
+//! );
+//! ```
+//!
+//! The automatically coloring macros and [`lazyc`] also accept the `else`
+//! template.
+//!
 //! ## Automatically coloring macros.
 //! Theese are same as the normal coloring macros except the will not color the
 //! output if it detects that the output stream is not terminal.
@@ -249,6 +292,27 @@
 //! let a = formatc!("{'move_to5,7}");
 //! ```
 //!
+//! ### Named styles
+//! Styles that are used in many places can be defined once with
+//! [`define_style!`] and used as `{'@name}`:
+//! ```rust
+//! use termal::*;
+//!
+//! define_style!(err = "red bold", note = "#888 italic");
+//!
+//! printcln!("{'@err}error{'_}: {'@note}something happened{'_}");
+//! ```
+//!
+//! ### Palette colors
+//! Colors of the current palette (see [`theme`]) are used as `{'t:name}`.
+//! They are resolved when the string is formatted, so they follow the
+//! palette selected at runtime (e.g. by the background of the terminal):
+//! ```rust
+//! use termal::*;
+//!
+//! printcln!("{'t:error}error{'_}: {'t:dim}something happened{'_}");
+//! ```
+//!
 //! ### Gradients
 //! Youn can create gradients with the function `termal::gradient`:
 //! ```rust
@@ -262,6 +326,8 @@
 pub use termal_core::*;
 pub use termal_proc as proc;
 
+pub mod prelude;
+
 /// Works as [`println!`], in addition can generate ansi escape codes.
 /// To generate the ansi codes use `"{'...}"`.
 ///
@@ -274,10 +340,10 @@ pub use termal_proc as proc;
 #[macro_export]
 macro_rules! printcln {
     ($l:literal $(,)?) => {
-        println!("{}", $crate::proc::colorize!($l));
+        println!("{}", $crate::proc::colorize!(crate = $crate; $l));
     };
     ($l:literal, $($e:expr),+ $(,)?) => {
-        println!("{}", $crate::proc::colorize!($l, $($e),+));
+        println!("{}", $crate::proc::colorize!(crate = $crate; $l, $($e),+));
     };
 }
 
@@ -293,10 +359,10 @@ macro_rules! printcln {
 #[macro_export]
 macro_rules! printc {
     ($l:literal $(,)?) => {
-        print!("{}", $crate::proc::colorize!($l));
+        print!("{}", $crate::proc::colorize!(crate = $crate; $l));
     };
     ($l:literal, $($e:expr),+ $(,)?) => {
-        print!("{}", $crate::proc::colorize!($l, $($e),+));
+        print!("{}", $crate::proc::colorize!(crate = $crate; $l, $($e),+));
     };
 }
 
@@ -312,10 +378,10 @@ macro_rules! printc {
 #[macro_export]
 macro_rules! eprintcln {
     ($l:literal $(,)?) => {
-        eprintln!("{}", $crate::proc::colorize!($l));
+        eprintln!("{}", $crate::proc::colorize!(crate = $crate; $l));
     };
     ($l:literal, $($e:expr),+ $(,)?) => {
-        eprintln!("{}", $crate::proc::colorize!($l, $($e),+));
+        eprintln!("{}", $crate::proc::colorize!(crate = $crate; $l, $($e),+));
     };
 }
 
@@ -331,10 +397,10 @@ macro_rules! eprintcln {
 #[macro_export]
 macro_rules! eprintc {
     ($l:literal $(,)?) => {
-        eprint!("{}", $crate::proc::colorize!($l));
+        eprint!("{}", $crate::proc::colorize!(crate = $crate; $l));
     };
     ($l:literal, $($e:expr),+ $(,)?) => {
-        eprint!("{}", $crate::proc::colorize!($l, $($e),+));
+        eprint!("{}", $crate::proc::colorize!(crate = $crate; $l, $($e),+));
     };
 }
 
@@ -350,10 +416,10 @@ macro_rules! eprintc {
 #[macro_export]
 macro_rules! formatc {
     ($l:literal $(,)?) => {
-        $crate::proc::colorize!($l)
+        $crate::proc::colorize!(crate = $crate; $l)
     };
     ($l:literal, $($e:expr),+ $(,)?) => {
-        $crate::proc::colorize!($l, $($e),+)
+        $crate::proc::colorize!(crate = $crate; $l, $($e),+)
     };
 }
 
@@ -362,10 +428,14 @@ macro_rules! formatc {
 #[macro_export]
 macro_rules! writecln {
     ($f:expr, $l:literal $(,)?) => {
-        writeln!($f, "{}", $crate::proc::colorize!($l))
+        writeln!($f, "{}", $crate::proc::colorize!(crate = $crate; $l))
     };
     ($f:expr, $l:literal, $($e:expr),+ $(,)?) => {
-        writeln!($f, "{}", $crate::proc::colorize!($l, $($e),+))
+        writeln!(
+            $f,
+            "{}",
+            $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+        )
     };
 }
 
@@ -374,10 +444,10 @@ macro_rules! writecln {
 #[macro_export]
 macro_rules! writec {
     ($f:expr, $l:literal $(,)?) => {
-        write!($f, "{}", $crate::proc::colorize!($l))
+        write!($f, "{}", $crate::proc::colorize!(crate = $crate; $l))
     };
     ($f:expr, $l:literal, $($e:expr),+ $(,)?) => {
-        write!($f, "{}", $crate::proc::colorize!($l, $($e),+))
+        write!($f, "{}", $crate::proc::colorize!(crate = $crate; $l, $($e),+))
     };
 }
 
@@ -471,6 +541,31 @@ macro_rules! formatnc {
     };
 }
 
+/// Works as [`formatnc!`], but it also removes control sequences that come
+/// from the arguments (e.g. colored values). The result is completely clean
+/// of control sequences, so it can be safely written to logs and files.
+///
+/// # Examples
+/// ```
+/// use termal::*;
+///
+/// let name = formatc!("{'y}bob{'_}");
+/// assert_eq!(formatnc!("{'b}user:{'_} {name}"), format!("user: {name}"));
+/// assert_eq!(formatnc_strict!("{'b}user:{'_} {name}"), "user: bob");
+/// ```
+#[cfg(feature = "term_text")]
+#[macro_export]
+macro_rules! formatnc_strict {
+    ($l:literal $(,)?) => {
+        $crate::term_text::TermText::new($crate::proc::uncolor!($l))
+            .strip_control()
+    };
+    ($l:literal, $($e:expr),+ $(,)?) => {
+        $crate::term_text::TermText::new($crate::proc::uncolor!($l, $($e),+))
+            .strip_control()
+    };
+}
+
 /// Works as [`writeln!`], skips terminal commands in `"{'...}"`.
 #[macro_export]
 macro_rules! writencln {
@@ -503,16 +598,36 @@ macro_rules! writenc {
 /// ```
 #[macro_export]
 macro_rules! printmcln {
+    ($cond:expr, $l:literal, else $el:literal $(,)?) => {
+        if $cond {
+            println!("{}", $crate::proc::colorize!(crate = $crate; $l));
+        } else {
+            println!("{}", $crate::proc::uncolor!($el));
+        }
+    };
+    ($cond:expr, $l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        if $cond {
+            println!(
+                "{}",
+                $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+            );
+        } else {
+            println!("{}", $crate::proc::uncolor!($el, $($e),+));
+        }
+    };
     ($cond:expr, $l:literal $(,)?) => {
         if $cond {
-            println!("{}", $crate::proc::colorize!($l));
+            println!("{}", $crate::proc::colorize!(crate = $crate; $l));
         } else {
             println!("{}", $crate::proc::uncolor!($l));
         }
     };
     ($cond:expr, $l:literal, $($e:expr),+ $(,)?) => {
         if $cond {
-            println!("{}", $crate::proc::colorize!($l, $($e),+));
+            println!(
+                "{}",
+                $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+            );
         } else {
             println!("{}", $crate::proc::uncolor!($l, $($e),+));
         }
@@ -529,16 +644,30 @@ macro_rules! printmcln {
 /// ```
 #[macro_export]
 macro_rules! printmc {
+    ($cond:expr, $l:literal, else $el:literal $(,)?) => {
+        if $cond {
+            print!("{}", $crate::proc::colorize!(crate = $crate; $l));
+        } else {
+            print!("{}", $crate::proc::uncolor!($el));
+        }
+    };
+    ($cond:expr, $l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        if $cond {
+            print!("{}", $crate::proc::colorize!(crate = $crate; $l, $($e),+));
+        } else {
+            print!("{}", $crate::proc::uncolor!($el, $($e),+));
+        }
+    };
     ($cond:expr, $l:literal $(,)?) => {
         if $cond {
-            print!("{}", $crate::proc::colorize!($l));
+            print!("{}", $crate::proc::colorize!(crate = $crate; $l));
         } else {
             print!("{}", $crate::proc::uncolor!($l));
         }
     };
     ($cond:expr, $l:literal, $($e:expr),+ $(,)?) => {
         if $cond {
-            print!("{}", $crate::proc::colorize!($l, $($e),+));
+            print!("{}", $crate::proc::colorize!(crate = $crate; $l, $($e),+));
         } else {
             print!("{}", $crate::proc::uncolor!($l, $($e),+));
         }
@@ -556,16 +685,36 @@ macro_rules! printmc {
 /// ```
 #[macro_export]
 macro_rules! eprintmcln {
+    ($cond:expr, $l:literal, else $el:literal $(,)?) => {
+        if $cond {
+            eprintln!("{}", $crate::proc::colorize!(crate = $crate; $l));
+        } else {
+            eprintln!("{}", $crate::proc::uncolor!($el));
+        }
+    };
+    ($cond:expr, $l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        if $cond {
+            eprintln!(
+                "{}",
+                $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+            );
+        } else {
+            eprintln!("{}", $crate::proc::uncolor!($el, $($e),+));
+        }
+    };
     ($cond:expr, $l:literal $(,)?) => {
         if $cond {
-            eprintln!("{}", $crate::proc::colorize!($l));
+            eprintln!("{}", $crate::proc::colorize!(crate = $crate; $l));
         } else {
             eprintln!("{}", $crate::proc::uncolor!($l));
         }
     };
     ($cond:expr, $l:literal, $($e:expr),+ $(,)?) => {
         if $cond {
-            eprintln!("{}", $crate::proc::colorize!($l, $($e),+));
+            eprintln!(
+                "{}",
+                $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+            );
         } else {
             eprintln!("{}", $crate::proc::uncolor!($l, $($e),+));
         }
@@ -582,16 +731,36 @@ macro_rules! eprintmcln {
 /// ```
 #[macro_export]
 macro_rules! eprintmc {
+    ($cond:expr, $l:literal, else $el:literal $(,)?) => {
+        if $cond {
+            eprint!("{}", $crate::proc::colorize!(crate = $crate; $l));
+        } else {
+            eprint!("{}", $crate::proc::uncolor!($el));
+        }
+    };
+    ($cond:expr, $l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        if $cond {
+            eprint!(
+                "{}",
+                $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+            );
+        } else {
+            eprint!("{}", $crate::proc::uncolor!($el, $($e),+));
+        }
+    };
     ($cond:expr, $l:literal $(,)?) => {
         if $cond {
-            eprint!("{}", $crate::proc::colorize!($l));
+            eprint!("{}", $crate::proc::colorize!(crate = $crate; $l));
         } else {
             eprint!("{}", $crate::proc::uncolor!($l));
         }
     };
     ($cond:expr, $l:literal, $($e:expr),+ $(,)?) => {
         if $cond {
-            eprint!("{}", $crate::proc::colorize!($l, $($e),+));
+            eprint!(
+                "{}",
+                $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+            );
         } else {
             eprint!("{}", $crate::proc::uncolor!($l, $($e),+));
         }
@@ -606,37 +775,109 @@ macro_rules! eprintmc {
 /// // Generate 'hello' (not in yellow, the terminal commands are skipped):
 /// printmcln!(false, "{'yellow}hello{'reset}");
 /// ```
+///
+/// The uncolored output may use different template:
+/// ```
+/// use termal::*;
+///
+/// assert_eq!(formatmc!(false, "{'y}ok{'_}", else "OK"), "OK");
+/// ```
 #[macro_export]
 macro_rules! formatmc {
+    ($cond:expr, $l:literal, else $el:literal $(,)?) => {
+        if $cond {
+            $crate::proc::colorize!(crate = $crate; $l)
+        } else {
+            $crate::proc::uncolor!($el)
+        }
+    };
+    ($cond:expr, $l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        if $cond {
+            $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+        } else {
+            $crate::proc::uncolor!($el, $($e),+)
+        }
+    };
     ($cond:expr, $l:literal $(,)?) => {
         if $cond {
-            $crate::proc::colorize!($l)
+            $crate::proc::colorize!(crate = $crate; $l)
         } else {
             $crate::proc::uncolor!($l)
         }
     };
     ($cond:expr, $l:literal, $($e:expr),+ $(,)?) => {
         if $cond {
-            $crate::proc::colorize!($l, $($e),+)
+            $crate::proc::colorize!(crate = $crate; $l, $($e),+)
         } else {
             $crate::proc::uncolor!($l, $($e),+)
         }
     };
 }
 
+/// Defines named styles that can be used in the colorize macros (e.g.
+/// [`formatc!`]) as `{'@name}`. Each definition has the form
+/// `[vis] name = "commands"` where the commands are the same as in `{'...}`.
+/// The named styles may be combined with other commands (e.g.
+/// `{'@err underline}`).
+///
+/// The styles are constants, so they must be in scope where they are used.
+/// To theme the output centrally, define them with `pub` in single module
+/// and import them with `use theme::*`.
+///
+/// # Examples
+/// ```
+/// use termal::*;
+///
+/// mod theme {
+///     termal::define_style!(pub err = "red bold"; pub warn = "yellow");
+/// }
+/// use theme::*;
+///
+/// assert_eq!(formatc!("{'@err}e{'_}"), formatc!("{'red bold}e{'_}"));
+/// assert_eq!(formatnc!("{'@warn}w{'_}"), "w");
+/// ```
+#[macro_export]
+macro_rules! define_style {
+    ($($t:tt)*) => {
+        $crate::proc::define_style!($($t)*);
+    };
+}
+
 /// Works as [`writeln!`], conditionally skips terminal commands in `"{'...}"`.
 #[macro_export]
 macro_rules! writemcln {
+    ($f:expr, $cond:expr, $l:literal, else $el:literal $(,)?) => {
+        if $cond {
+            writeln!($f, "{}", $crate::proc::colorize!(crate = $crate; $l))
+        } else {
+            writeln!($f, "{}", $crate::proc::uncolor!($el))
+        }
+    };
+    ($f:expr, $cond:expr, $l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        if $cond {
+            writeln!(
+                $f,
+                "{}",
+                $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+            )
+        } else {
+            writeln!($f, "{}", $crate::proc::uncolor!($el, $($e),+))
+        }
+    };
     ($f:expr, $cond:expr, $l:literal $(,)?) => {
         if $cond {
-            writeln!($f, "{}", $crate::proc::colorize!($l))
+            writeln!($f, "{}", $crate::proc::colorize!(crate = $crate; $l))
         } else {
             writeln!($f, "{}", $crate::proc::uncolor!($l))
         }
     };
     ($f:expr, $cond:expr, $l:literal, $($e:expr),+ $(,)?) => {
         if $cond {
-            writeln!($f, "{}", $crate::proc::colorize!($l, $($e),+))
+            writeln!(
+                $f,
+                "{}",
+                $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+            )
         } else {
             writeln!($f, "{}", $crate::proc::uncolor!($l, $($e),+))
         }
@@ -646,16 +887,38 @@ macro_rules! writemcln {
 /// Works as [`write!`], conditionally skips terminal commands in `"{'...}"`.
 #[macro_export]
 macro_rules! writemc {
+    ($f:expr, $cond:expr, $l:literal, else $el:literal $(,)?) => {
+        if $cond {
+            write!($f, "{}", $crate::proc::colorize!(crate = $crate; $l))
+        } else {
+            write!($f, "{}", $crate::proc::uncolor!($el))
+        }
+    };
+    ($f:expr, $cond:expr, $l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        if $cond {
+            write!(
+                $f,
+                "{}",
+                $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+            )
+        } else {
+            write!($f, "{}", $crate::proc::uncolor!($el, $($e),+))
+        }
+    };
     ($f:expr, $cond:expr, $l:literal $(,)?) => {
         if $cond {
-            write!($f, "{}", $crate::proc::colorize!($l))
+            write!($f, "{}", $crate::proc::colorize!(crate = $crate; $l))
         } else {
             write!($f, "{}", $crate::proc::uncolor!($l))
         }
     };
     ($f:expr, $cond:expr, $l:literal, $($e:expr),+ $(,)?) => {
         if $cond {
-            write!($f, "{}", $crate::proc::colorize!($l, $($e),+))
+            write!(
+                $f,
+                "{}",
+                $crate::proc::colorize!(crate = $crate; $l, $($e),+)
+            )
         } else {
             write!($f, "{}", $crate::proc::uncolor!($l, $($e),+))
         }
@@ -663,8 +926,9 @@ macro_rules! writemc {
 }
 
 /// Works as [`println!`], in addition can generate ansi escape codes.
-/// To generate the ansi codes use `"{'...}"`. This will not use the ansi codes
-/// if stdout is not terminal.
+/// To generate the ansi codes use `"{'...}"`. The ansi codes are used only
+/// if [`use_color`] allows them for stdout (see [`color_choice`] and
+/// [`env_color_choice`]).
 ///
 /// # Examples
 /// ```
@@ -674,15 +938,38 @@ macro_rules! writemc {
 /// ```
 #[macro_export]
 macro_rules! printacln {
+    ($l:literal, else $el:literal $(,)?) => {
+        $crate::printmcln!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
+            $l,
+            else $el,
+        );
+    };
+    ($l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        $crate::printmcln!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
+            $l,
+            else $el,
+            $($e),+,
+        );
+    };
     ($l:literal $(,)?) => {
         $crate::printmcln!(
-            std::io::IsTerminal::is_terminal(&std::io::stdout()),
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
             $l,
         );
     };
     ($l:literal, $($e:expr),+ $(,)?) => {
         $crate::printmcln!(
-            std::io::IsTerminal::is_terminal(&std::io::stdout()),
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
             $l,
             $($e),+,
         );
@@ -690,8 +977,9 @@ macro_rules! printacln {
 }
 
 /// Works as [`print!`], in addition can generate ansi escape codes.
-/// To generate the ansi codes use `"{'...}"`. This will not use the ansi codes
-/// if stdout is not terminal.
+/// To generate the ansi codes use `"{'...}"`. The ansi codes are used only
+/// if [`use_color`] allows them for stdout (see [`color_choice`] and
+/// [`env_color_choice`]).
 ///
 /// # Examples
 /// ```
@@ -701,15 +989,38 @@ macro_rules! printacln {
 /// ```
 #[macro_export]
 macro_rules! printac {
+    ($l:literal, else $el:literal $(,)?) => {
+        $crate::printmc!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
+            $l,
+            else $el,
+        );
+    };
+    ($l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        $crate::printmc!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
+            $l,
+            else $el,
+            $($e),+,
+        );
+    };
     ($l:literal $(,)?) => {
         $crate::printmc!(
-            std::io::IsTerminal::is_terminal(&std::io::stdout()),
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
             $l,
         );
     };
     ($l:literal, $($e:expr),+ $(,)?) => {
         $crate::printmc!(
-            std::io::IsTerminal::is_terminal(&std::io::stdout()),
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
             $l,
             $($e),+,
         );
@@ -717,8 +1028,9 @@ macro_rules! printac {
 }
 
 /// Works as [`eprintln!`], in addition can generate ansi escape codes.
-/// To generate the ansi codes use `"{'...}"`. This will not use the ansi codes
-/// if stdout is not terminal.
+/// To generate the ansi codes use `"{'...}"`. The ansi codes are used only
+/// if [`use_color`] allows them for stderr (see [`color_choice`] and
+/// [`env_color_choice`]).
 ///
 /// # Examples
 /// ```
@@ -728,15 +1040,38 @@ macro_rules! printac {
 /// ```
 #[macro_export]
 macro_rules! eprintacln {
+    ($l:literal, else $el:literal $(,)?) => {
+        $crate::eprintmcln!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stderr(),
+            )),
+            $l,
+            else $el,
+        );
+    };
+    ($l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        $crate::eprintmcln!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stderr(),
+            )),
+            $l,
+            else $el,
+            $($e),+,
+        );
+    };
     ($l:literal $(,)?) => {
         $crate::eprintmcln!(
-            std::io::IsTerminal::is_terminal(&std::io::stderr()),
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stderr(),
+            )),
             $l,
         );
     };
     ($l:literal, $($e:expr),+ $(,)?) => {
         $crate::eprintmcln!(
-            std::io::IsTerminal::is_terminal(&std::io::stderr()),
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stderr(),
+            )),
             $l,
             $($e),+,
         );
@@ -744,8 +1079,9 @@ macro_rules! eprintacln {
 }
 
 /// Works as [`eprint!`], in addition can generate ansi escape codes.
-/// To generate the ansi codes use `"{'...}"`. This will not use the ansi codes
-/// if stdout is not terminal.
+/// To generate the ansi codes use `"{'...}"`. The ansi codes are used only
+/// if [`use_color`] allows them for stderr (see [`color_choice`] and
+/// [`env_color_choice`]).
 ///
 /// # Examples
 /// ```
@@ -755,21 +1091,143 @@ macro_rules! eprintacln {
 /// ```
 #[macro_export]
 macro_rules! eprintac {
+    ($l:literal, else $el:literal $(,)?) => {
+        $crate::eprintmc!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stderr(),
+            )),
+            $l,
+            else $el,
+        );
+    };
+    ($l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        $crate::eprintmc!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stderr(),
+            )),
+            $l,
+            else $el,
+            $($e),+,
+        );
+    };
     ($l:literal $(,)?) => {
         $crate::eprintmc!(
-            std::io::IsTerminal::is_terminal(&std::io::stderr()),
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stderr(),
+            )),
             $l,
         );
     };
     ($l:literal, $($e:expr),+ $(,)?) => {
         $crate::eprintmc!(
-            std::io::IsTerminal::is_terminal(&std::io::stderr()),
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stderr(),
+            )),
             $l,
             $($e),+,
         );
     };
 }
 
+/// Works as [`format!`], in addition can generate ansi escape codes.
+/// To generate the ansi codes use `"{'...}"`. The ansi codes are used only
+/// if [`use_color`] allows them for stdout (see [`color_choice`] and
+/// [`env_color_choice`]).
+///
+/// # Examples
+/// ```
+/// use termal::*;
+///
+/// let s = with_color_choice(ColorChoice::Never, || {
+///     formatac!("{'yellow}hello{'reset}")
+/// });
+/// assert_eq!(s, "hello");
+/// ```
+#[macro_export]
+macro_rules! formatac {
+    ($l:literal, else $el:literal $(,)?) => {
+        $crate::formatmc!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
+            $l,
+            else $el,
+        )
+    };
+    ($l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        $crate::formatmc!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
+            $l,
+            else $el,
+            $($e),+,
+        )
+    };
+    ($l:literal $(,)?) => {
+        $crate::formatmc!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
+            $l,
+        )
+    };
+    ($l:literal, $($e:expr),+ $(,)?) => {
+        $crate::formatmc!(
+            $crate::use_color(std::io::IsTerminal::is_terminal(
+                &std::io::stdout(),
+            )),
+            $l,
+            $($e),+,
+        )
+    };
+}
+
+/// Creates value that is formatted as [`formatc!`] or [`formatnc!`]
+/// depending on the color choice at the time when it is displayed (see
+/// [`LazyColored`]). The arguments are moved into the value.
+///
+/// # Examples
+/// ```
+/// use termal::*;
+///
+/// let msg = lazyc!("{'r}error{'_}: {}", "oops");
+/// let plain = with_color_choice(ColorChoice::Never, || msg.to_string());
+/// assert_eq!(plain, "error: oops");
+/// assert_eq!(msg.with_color(true), formatc!("{'r}error{'_}: oops"));
+/// ```
+#[macro_export]
+macro_rules! lazyc {
+    ($l:literal, else $el:literal $(,)?) => {
+        $crate::LazyColored::new(
+            move |c: bool, f: &mut std::fmt::Formatter<'_>| {
+                $crate::writemc!(f, c, $l, else $el)
+            },
+        )
+    };
+    ($l:literal, else $el:literal, $($e:expr),+ $(,)?) => {
+        $crate::LazyColored::new(
+            move |c: bool, f: &mut std::fmt::Formatter<'_>| {
+                $crate::writemc!(f, c, $l, else $el, $($e),+)
+            },
+        )
+    };
+    ($l:literal $(,)?) => {
+        $crate::LazyColored::new(
+            move |c: bool, f: &mut std::fmt::Formatter<'_>| {
+                $crate::writemc!(f, c, $l)
+            },
+        )
+    };
+    ($l:literal, $($e:expr),+ $(,)?) => {
+        $crate::LazyColored::new(
+            move |c: bool, f: &mut std::fmt::Formatter<'_>| {
+                $crate::writemc!(f, c, $l, $($e),+)
+            },
+        )
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -820,6 +1278,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_m_else() {
+        use std::fmt::Write as _;
+
+        let s = "Hello";
+        assert_eq!(formatmc!(false, "{'y}{s}{'_}", else "*{s}*"), "*Hello*");
+        assert_eq!(
+            formatmc!(true, "{'y}{}{'_}", else "*{}*", s),
+            formatc!("{'y}Hello{'_}")
+        );
+        let lazy = lazyc!("{'y}{}{'_}", else "*{}*", s);
+        assert_eq!(lazy.with_color(false), "*Hello*");
+        let r = with_color_choice(
+            ColorChoice::Never,
+            || formatac!("{'y}{s}{'_}", else "*{s}*"),
+        );
+        assert_eq!(r, "*Hello*");
+
+        let mut out = String::new();
+        writemcln!(out, false, "{'y}{}{'_}", else "*{}*", s).unwrap();
+        assert_eq!(out, "*Hello*\n");
+        printmc!(false, "{'y}{'_}", else "");
+        eprintac!("{'y}{}{'_}", else "{}", "");
+    }
+
     #[cfg(feature = "term_text")]
     #[test]
     fn term_text() {
@@ -0,0 +1,31 @@
+//! Curated set of the most commonly used items, so that applications can
+//! start with single import:
+//! ```rust
+//! use termal::prelude::*;
+//!
+//! printcln!("{'green}hello{'reset}");
+//! ```
+//!
+//! Items in the prelude are considered the stable surface of termal. Items
+//! are added to it only if they are useful to most applications.
+
+pub use crate::{
+    eprintac, eprintacln, eprintc, eprintcln, eprintmc, eprintmcln, eprintnc,
+    eprintncln, formatac, formatc, formatmc, formatnc, gradient, printac,
+    printacln, printc, printcln, printmc, printmcln, printnc, printncln,
+    register_reset_on_panic, reset_terminal, set_color_choice, writec,
+    writecln, writemc, writemcln, writenc, writencln, Attrs, Color,
+    ColorChoice, Rgb, Style,
+};
+
+#[cfg(feature = "raw")]
+pub use crate::raw::{
+    disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, raw_mode_guard,
+    term_size, Encoding, MouseCapture, MouseMode, RawModeGuard, Terminal,
+    TuiSession,
+};
+
+#[cfg(feature = "events")]
+pub use crate::raw::events::{
+    Event, Key, KeyBinding, KeyCode, KeyMap, Modifiers,
+};
@@ -0,0 +1,50 @@
+//! Standard base64 (with padding) used by the codes and events for selection
+//! data. It is implemented here so that the codes don't need any dependency.
+
+const CHARS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Appends the data encoded with standard base64 (with padding).
+pub(crate) fn encode_to(data: &[u8], res: &mut String) {
+    res.reserve(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = |i: usize| chunk.get(i).copied().unwrap_or_default() as u32;
+        let n = (b(0) << 16) | (b(1) << 8) | b(2);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                res.push(CHARS[(n >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                res.push('=');
+            }
+        }
+    }
+}
+
+/// Encodes the data with standard base64 (with padding).
+#[cfg(feature = "events")]
+pub(crate) fn encode(data: &[u8]) -> String {
+    let mut res = String::new();
+    encode_to(data, &mut res);
+    res
+}
+
+/// Decodes standard base64. The padding is optional. Returns [`None`] if the
+/// data are not valid base64.
+#[cfg(feature = "events")]
+pub(crate) fn decode(s: &str) -> Option<Vec<u8>> {
+    let data = s.trim_end_matches('=').as_bytes();
+    if data.len() % 4 == 1 || s.len() - data.len() > 2 {
+        return None;
+    }
+
+    let mut res = Vec::with_capacity(data.len() * 3 / 4);
+    for chunk in data.chunks(4) {
+        let mut n = 0;
+        for (i, c) in chunk.iter().enumerate() {
+            let v = CHARS.iter().position(|b| b == c)? as u32;
+            n |= v << (18 - 6 * i);
+        }
+        res.extend_from_slice(&n.to_be_bytes()[1..chunk.len()]);
+    }
+    Some(res)
+}
@@ -19,11 +19,15 @@
 //! - **String codes:** these codes are just strings that can be just printed
 //!   to terminal to do what they say they do. This is the majority of the
 //!   codes.
+//! - **Write codes:** functions such as [`write_move_to`] and macros such as
+//!   [`crate::write_csi`] write the codes with parameters directly to
+//!   [`std::fmt::Write`] (e.g. reused [`String`]) without allocating. They
+//!   are useful in hot render loops.
 
-use std::fmt::Display;
-
-use base64::Engine;
-use place_macro::place;
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+};
 
 /// Creates the given sequence, this is used internally, you should use
 /// the macro [`csi`]
@@ -43,6 +47,25 @@ macro_rules! seq {
     }
 }
 
+/// Writes the given sequence to [`std::fmt::Write`] without allocation. The
+/// first argument must be mutable reference to the writer. This is used
+/// internally, you should use the macro [`crate::write_csi`].
+#[macro_export]
+macro_rules! write_seq {
+    ($w:expr, $sq:literal, $i:literal) => {
+        ::std::fmt::Write::write_str($w, concat!($sq, $i))
+    };
+    ($w:expr, $sq:literal, $i:literal, $f:expr $(,$a:expr)*) => {
+        $crate::write_seq!($w, $sq, $i, $f, $(";{}"; $a),*)
+    };
+    ($w:expr, $sq:literal, $i:literal, $f:expr, $($l:literal; $e:expr),*) => {
+        ::std::fmt::Write::write_fmt(
+            $w,
+            format_args!(concat!($sq, "{}" $(,$l)*, $i), $f $(,$e)*),
+        )
+    };
+}
+
 // Sequences:
 
 /// The escape character
@@ -57,6 +80,129 @@ pub const OSC: &str = "\x1b]";
 pub const ST: &str = "\x1b\\";
 /// Single shift three
 pub const SS3: &str = "\x1bO";
+/// Application Program Command: Start of APC sequence (e.g. kitty graphics)
+pub const APC: &str = "\x1b_";
+
+/// 8-bit version of [`CSI`]. See [`to_8bit`].
+pub const CSI8: u8 = 0x9B;
+/// 8-bit version of [`DCS`]. See [`to_8bit`].
+pub const DCS8: u8 = 0x90;
+/// 8-bit version of [`OSC`]. See [`to_8bit`].
+pub const OSC8: u8 = 0x9D;
+/// 8-bit version of [`ST`]. See [`to_8bit`].
+pub const ST8: u8 = 0x9C;
+/// 8-bit version of [`SS3`]. See [`to_8bit`].
+pub const SS3_8: u8 = 0x8F;
+
+/// Tells the terminal to send its responses with 8-bit control introducers
+/// (e.g. [`CSI8`] instead of [`CSI`]).
+pub const ENABLE_8BIT_RESPONSES: &str = "\x1b G";
+/// Tells the terminal to send its responses with 7-bit control introducers.
+/// This is the default.
+pub const DISABLE_8BIT_RESPONSES: &str = "\x1b F";
+
+/// Converts the 7-bit escape sequence introducers (e.g. [`CSI`], [`OSC`],
+/// [`DCS`], [`ST`]) in the code to their single byte 8-bit versions (e.g.
+/// [`CSI8`]).
+///
+/// The result is not valid UTF-8, so it is useful only with terminals that
+/// don't expect UTF-8 (e.g. legacy hardware terminals) or when the
+/// bandwidth matters.
+pub fn to_8bit(code: &str) -> Vec<u8> {
+    let mut res = Vec::with_capacity(code.len());
+    let mut bytes = code.bytes().peekable();
+    while let Some(b) = bytes.next() {
+        match bytes.peek().copied().and_then(c1_of) {
+            Some(c1) if b == 0x1b => {
+                res.push(c1);
+                bytes.next();
+            }
+            _ => res.push(b),
+        }
+    }
+    res
+}
+
+/// Gets the 8-bit control for the byte that follows escape. Returns [`None`]
+/// if there is no such 8-bit control.
+pub const fn c1_of(b: u8) -> Option<u8> {
+    if b >= 0x40 && b <= 0x5F {
+        Some(b + 0x40)
+    } else {
+        None
+    }
+}
+
+/// Writes `payload` split into chunks of at most `max_len` bytes. Each chunk
+/// is wrapped in string sequence that starts with `intro` (e.g. [`DCS`] or
+/// [`APC`]) and ends with [`ST`]. The chunks are split only at character
+/// boundaries.
+///
+/// `header` is called for each chunk with the index of the chunk and
+/// whether it is the last chunk. Its result is written before the payload
+/// of the chunk (e.g. the control data of kitty graphics). Control
+/// characters are removed from the payload, so that it can't end the
+/// sequence early. Empty payload is written as single empty chunk.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::codes;
+///
+/// let mut res = String::new();
+/// codes::write_chunked(&mut res, codes::APC, "abcde", 2, |i, last| {
+///     format!("G{}m={};", if i == 0 { "a=T," } else { "" }, !last as u8)
+/// })?;
+/// assert_eq!(
+///     res,
+///     "\x1b_Ga=T,m=1;ab\x1b\\\x1b_Gm=1;cd\x1b\\\x1b_Gm=0;e\x1b\\"
+/// );
+/// # Ok::<_, std::fmt::Error>(())
+/// ```
+pub fn write_chunked<D: Display>(
+    w: &mut impl fmt::Write,
+    intro: &str,
+    payload: &str,
+    max_len: usize,
+    mut header: impl FnMut(usize, bool) -> D,
+) -> fmt::Result {
+    let mut rest = payload;
+    let mut i = 0;
+    loop {
+        let mut len = max_len.min(rest.len());
+        while !rest.is_char_boundary(len) {
+            len -= 1;
+        }
+        if len == 0 {
+            len = rest.chars().next().map_or(0, char::len_utf8);
+        }
+
+        let (chunk, r) = rest.split_at(len);
+        rest = r;
+        write!(w, "{intro}{}", header(i, rest.is_empty()))?;
+        for c in chunk.chars().filter(|c| !c.is_control()) {
+            w.write_char(c)?;
+        }
+        w.write_str(ST)?;
+
+        if rest.is_empty() {
+            return Ok(());
+        }
+        i += 1;
+    }
+}
+
+/// Splits `payload` into chunks wrapped in string sequences. See
+/// [`write_chunked`].
+pub fn chunked<D: Display>(
+    intro: &str,
+    payload: &str,
+    max_len: usize,
+    header: impl FnMut(usize, bool) -> D,
+) -> String {
+    let mut res = String::new();
+    _ = write_chunked(&mut res, intro, payload, max_len, header);
+    res
+}
 
 /// Creates control escape sequence, the first literal is the end of the
 /// sequence, the other arguments are the values in the sequence
@@ -67,6 +213,37 @@ macro_rules! csi {
     };
 }
 
+/// Writes control escape sequence to [`std::fmt::Write`] without allocation.
+/// The first argument must be mutable reference to the writer, the second is
+/// the end of the sequence and the others are the values in the sequence.
+///
+/// # Example
+/// ```rust
+/// use termal_core::codes;
+///
+/// let mut buf = String::new();
+/// let n = 5;
+/// termal_core::write_csi!(&mut buf, 'L', n)?;
+/// assert_eq!(buf, codes::insert_lines!(5));
+/// # Ok::<_, std::fmt::Error>(())
+/// ```
+#[macro_export]
+macro_rules! write_csi {
+    ($w:expr, $i:literal $(,$a:expr)* $(,)?) => {
+        $crate::write_seq!($w, "\x1b[", $i $(, $a)*)
+    };
+}
+
+/// Writes control escape sequence for graphic mode to [`std::fmt::Write`]
+/// without allocation. The first argument must be mutable reference to the
+/// writer.
+#[macro_export]
+macro_rules! write_graphic {
+    ($w:expr $(,$a:expr)* $(,)?) => {
+        $crate::write_csi!($w, 'm' $(, $a)*)
+    };
+}
+
 /// Creates control escape sequence for graphic mode.
 #[macro_export]
 macro_rules! graphic {
@@ -216,43 +393,37 @@ pub const DELETE: char = '\x7f';
 // both cases
 
 macro_rules! code_macro {
-    ($code:ident $(
-        $name:ident
-        $(, $($nam:ident)? $($lit:literal)?)+ ;
-        $($i:literal)? $(?$doc:literal)?),+ $(,)?
-    ) => {
-        place! {$(
-            $(#[doc = __repnl__($doc, " ")])?
-            #[macro_export]
-            macro_rules! $name {
-                (__start__($($(__s__ $nam:expr,)?)+) __s__ (,)?) => {
-                    __s__ crate::$code!($($i,)? $($(__s__ $nam)? $($lit)?),+)
+    ($d:tt $code:ident != $ex:literal => $(
+        $name:ident, $nam:ident; $i:literal $(? $doc:literal)?
+    ),+ $(,)?) => {$(
+        $(#[doc = $doc])?
+        #[macro_export]
+        macro_rules! $name {
+            ($d $nam:expr) => {{
+                let v = $d $nam;
+                if v == $ex {
+                    "".into()
+                } else {
+                    $d crate::$code!($i, v)
                 }
+            }}
+        }
+        pub use $name;
+    )+};
+    ($d:tt $code:ident $(
+        $name:ident($($arg:ident),*)
+        $(, $($nam:ident)? $($lit:literal)?)+ ;
+        $(? $doc:literal)?
+    ),+ $(,)?) => {$(
+        $(#[doc = $doc])?
+        #[macro_export]
+        macro_rules! $name {
+            ($($d $arg:expr),* $d(,)?) => {
+                $d crate::$code!($($($d $nam)? $($lit)?),+)
             }
-            pub use $name;
-        )+}
-    };
-    ($code:ident != $ex:literal => $(
-        $name:ident,
-        $nam:ident;
-        $($i:literal)? $(?$doc:literal)?),+ $(,)?
-    ) => {
-        place! {$(
-            $(#[doc = __repnl__($doc, " ")])?
-            #[macro_export]
-            macro_rules! $name {
-                (__start__(__s__ $nam:expr,)) => {{
-                    let v = __s__ $nam;
-                    if v == $ex {
-                        "".into()
-                    } else {
-                        __s__ crate::$code!($($i,)? v)
-                    }
-                }}
-            }
-            pub use $name;
-        )+}
-    };
+        }
+        pub use $name;
+    )+};
 }
 
 /// Moves cursor to the given position. Position of the top left conrner is
@@ -270,7 +441,7 @@ macro_rules! code_macro {
 ///
 /// let txt = "centered";
 /// let size = term_size()?;
-/// let x = (size.char_width - txt.len() + 1) / 2;
+/// let x = (size.char_width - txt.len()).div_ceil(2);
 /// let y = size.char_height / 2;
 /// // If one of arguments is not literal, produces string.
 /// let center: String = codes::move_to!(x, y);
@@ -301,9 +472,72 @@ macro_rules! move_to {
 
 pub use move_to;
 
-use crate::Rgb;
+/// Writes the code of [`move_to!`] without allocation. Position of the top
+/// left corner is (1, 1).
+///
+/// # Example
+/// ```rust
+/// use termal_core::codes;
+///
+/// // Reuse the buffer for each frame.
+/// let mut buf = String::new();
+/// for y in 1..=3 {
+///     codes::write_move_to(&mut buf, 5, y)?;
+///     buf += "*";
+/// }
+/// assert_eq!(buf, "\x1b[1;5H*\x1b[2;5H*\x1b[3;5H*");
+/// # Ok::<_, std::fmt::Error>(())
+/// ```
+pub fn write_move_to(
+    w: &mut impl fmt::Write,
+    x: usize,
+    y: usize,
+) -> fmt::Result {
+    write_csi!(w, 'H', y, x)
+}
+
+/// Writes the code of [`move_up!`] without allocation. Nothing is written
+/// if `n` is `0`.
+pub fn write_move_up(w: &mut impl fmt::Write, n: usize) -> fmt::Result {
+    write_move(w, n, 'A')
+}
+
+/// Writes the code of [`move_down!`] without allocation. Nothing is written
+/// if `n` is `0`.
+pub fn write_move_down(w: &mut impl fmt::Write, n: usize) -> fmt::Result {
+    write_move(w, n, 'B')
+}
+
+/// Writes the code of [`move_right!`] without allocation. Nothing is written
+/// if `n` is `0`.
+pub fn write_move_right(w: &mut impl fmt::Write, n: usize) -> fmt::Result {
+    write_move(w, n, 'C')
+}
+
+/// Writes the code of [`move_left!`] without allocation. Nothing is written
+/// if `n` is `0`.
+pub fn write_move_left(w: &mut impl fmt::Write, n: usize) -> fmt::Result {
+    write_move(w, n, 'D')
+}
+
+/// Writes the code of [`column!`] without allocation.
+pub fn write_column(w: &mut impl fmt::Write, n: usize) -> fmt::Result {
+    write_csi!(w, 'G', n)
+}
+
+fn write_move(w: &mut impl fmt::Write, n: usize, dir: char) -> fmt::Result {
+    if n == 0 {
+        return Ok(());
+    }
+    write!(w, "\x1b[{n}{dir}")
+}
+
+use crate::{
+    error::{Error, Result},
+    Rgb,
+};
 
-code_macro!(csi != 0 =>
+code_macro!($ csi != 0 =>
     move_up, n; 'A' ? "Moves cursor up by N positions",
     move_down, n; 'B' ? "Moves cursor down by N positions",
     move_right, n; 'C' ? "Moves cursor right by N positions",
@@ -317,21 +551,75 @@ code_macro!(csi != 0 =>
     delete_columns, n; "'~" ? "Delete n columns, moving them from the right",
     set_down, n; 'E' ? "Moves cursor to the start of line N lines down",
     set_up, n; 'F' ? "Moves cursor to the start of line N lines up",
-    repeat_char, n; 'b' ? "Repeat the previous char n times."
+    repeat_char, n; 'b' ? "Repeat the previous char n times.",
+    scroll_up, n; 'S'
+        ? "Scrolls the content (of scroll region) up by n lines. New lines \
+           are added at the bottom.",
+    scroll_down, n; 'T'
+        ? "Scrolls the content (of scroll region) down by n lines. New lines \
+           are added at the top.",
+    cursor_forward_tab, n; 'I' ? "Moves cursor forward by n tab stops.",
 );
 
-code_macro!(csi
-    column, n; 'G' ? "Moves cursor to the given column",
+code_macro!($ csi
+    column(n), 'G', n; ? "Moves cursor to the given column",
+    row(n), 'd', n; ? "Moves cursor to the given row",
 );
 
 /// Moves cursor one line up, scrolling if needed
 pub const UP_SCRL: &str = "\x1bM";
+/// Sets horizontal tab stop at the cursor position.
+pub const SET_TAB_STOP: &str = "\x1bH";
+/// Clears horizontal tab stop at the cursor position.
+pub const CLEAR_TAB_STOP: &str = csi!('g');
+/// Clears all horizontal tab stops.
+pub const CLEAR_ALL_TAB_STOPS: &str = csi!('g', 3);
 /// Saves the cursor position (this is single save slot, not stack)
 pub const CUR_SAVE: &str = "\x1b7";
 /// Restores the cursor position to the last saved position (this is single
 /// save slot, not stack)
 pub const CUR_LOAD: &str = "\x1b8";
 
+/// Whether [`CUR_SAVE`] and [`CUR_LOAD`] are supported. `0` means that it is
+/// detected, `1` that they are supported and `2` that they are not.
+static CURSOR_SAVE: std::sync::atomic::AtomicU8 =
+    std::sync::atomic::AtomicU8::new(0);
+
+/// Sets whether the terminal supports saving the cursor position with
+/// [`CUR_SAVE`] and [`CUR_LOAD`]. [`None`] means that it is detected. See
+/// [`supports_cursor_save`].
+pub fn set_cursor_save_support(v: Option<bool>) {
+    let v = match v {
+        None => 0,
+        Some(true) => 1,
+        Some(false) => 2,
+    };
+    CURSOR_SAVE.store(v, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Checks whether the terminal supports saving the cursor position with
+/// [`CUR_SAVE`] and [`CUR_LOAD`]. Some terminals (e.g. some serial consoles)
+/// don't support it, so termal restores the cursor with absolute position
+/// instead (e.g. in [`crate::reset_terminal`] and
+/// [`crate::live::LiveRegion`]).
+///
+/// Unless it is set with [`set_cursor_save_support`], it is supported if
+/// the terminfo entry of the terminal has the capability `sc` or if the
+/// entry is not available (or the feature `terminfo` is disabled).
+pub fn supports_cursor_save() -> bool {
+    match CURSOR_SAVE.load(std::sync::atomic::Ordering::Relaxed) {
+        1 => true,
+        2 => false,
+        #[cfg(feature = "terminfo")]
+        _ => !matches!(
+            crate::terminfo::capabilities(),
+            Some(c) if c.get_str(crate::terminfo::StrCap::SaveCursor).is_none()
+        ),
+        #[cfg(not(feature = "terminfo"))]
+        _ => true,
+    }
+}
+
 // Erase codes
 
 /// Erases from the cursor to the end of the screen
@@ -379,12 +667,21 @@ pub const STRIKETROUGH: &str = graphic!(9);
 pub const DOUBLE_UNDERLINE: &str = graphic!(21);
 /// Set ouverline mode
 pub const OVERLINE: &str = graphic!(53);
+/// Set curly (wavy) underline mode. Terminals that don't support underline
+/// styles usually show it as [`UNDERLINE`].
+pub const CURLY_UNDERLINE: &str = graphic!("4:3");
+/// Set dotted underline mode. Terminals that don't support underline styles
+/// usually show it as [`UNDERLINE`].
+pub const DOTTED_UNDERLINE: &str = graphic!("4:4");
+/// Set dashed underline mode. Terminals that don't support underline styles
+/// usually show it as [`UNDERLINE`].
+pub const DASHED_UNDERLINE: &str = graphic!("4:5");
 
 /// Reset [`BOLD`] and [`FAINT`] mode
 pub const RESET_BOLD: &str = graphic!(22);
 /// Reset [`ITALIC`] mode
 pub const RESET_ITALIC: &str = graphic!(23);
-/// Reset [`UNDERLINE`] and [`DOUBLE_UNDERLINE`] mode
+/// Reset [`UNDERLINE`], [`DOUBLE_UNDERLINE`] and the other underline styles
 pub const RESET_UNDERLINE: &str = graphic!(24);
 /// Reset [`BLINKING`] mode
 pub const RESET_BLINKING: &str = graphic!(25);
@@ -473,28 +770,48 @@ pub const CYAN_DARK_BG: &str = graphic!(46);
 /// Reset the background color
 pub const RESET_BG: &str = graphic!(49);
 
-code_macro! { graphic
-    fg256, 38, 5, c;
+code_macro! { $ graphic
+    fg256(c), 38, 5, c;
         ? "creates a foreground color, color is value in range 0..256",
 
-    bg256, 48, 5, c;
+    bg256(c), 48, 5, c;
         ? "creates a background color, color is value in range 0..256",
 
-    underline256, 58, 5, c;
+    underline256(c), 58, 5, c;
         ? "Set underline color as 256 color.",
 
-    fg, 38, 2, r, g, b;
-        ? "creates a true rgb foreground color. R, G and B must be values in
+    fg(r, g, b), 38, 2, r, g, b;
+        ? "creates a true rgb foreground color. R, G and B must be values in \
            range 0..256",
 
-    bg, 48, 2, r, g, b;
-        ? "creates a true rgb background color. R, G and B must be values in
+    bg(r, g, b), 48, 2, r, g, b;
+        ? "creates a true rgb background color. R, G and B must be values in \
            range 0..256",
 
-    underline_rgb, 58, 2, r, g, b;
+    underline_rgb(r, g, b), 58, 2, r, g, b;
         ? "Set underline color as rgb.",
 }
 
+/// Writes the code of [`fg!`] without allocation.
+pub fn write_fg(w: &mut impl fmt::Write, r: u8, g: u8, b: u8) -> fmt::Result {
+    write_graphic!(w, 38, 2, r, g, b)
+}
+
+/// Writes the code of [`bg!`] without allocation.
+pub fn write_bg(w: &mut impl fmt::Write, r: u8, g: u8, b: u8) -> fmt::Result {
+    write_graphic!(w, 48, 2, r, g, b)
+}
+
+/// Writes the code of [`fg256!`] without allocation.
+pub fn write_fg256(w: &mut impl fmt::Write, c: u8) -> fmt::Result {
+    write_graphic!(w, 38, 5, c)
+}
+
+/// Writes the code of [`bg256!`] without allocation.
+pub fn write_bg256(w: &mut impl fmt::Write, c: u8) -> fmt::Result {
+    write_graphic!(w, 48, 5, c)
+}
+
 /// Reset the underline color.
 pub const RESET_UNDERLINE_COLOR: &str = graphic!(59);
 
@@ -559,8 +876,69 @@ pub const REQUEST_TEXT_AREA_SIZE_PX: &str = csi!('t', 14);
 pub const REQUEST_CHAR_SIZE: &str = csi!('t', 16);
 /// Request size of the text area in characters.
 pub const REQUEST_TEXT_AREA_SIZE: &str = csi!('t', 18);
+/// Request the title of the window. The terminal responds with `OSC l title
+/// ST`. Many terminals don't respond for security reasons.
+pub const REQUEST_WINDOW_TITLE: &str = csi!('t', 21);
+/// Request the icon name of the window. The terminal responds with `OSC L
+/// name ST`. Many terminals don't respond for security reasons.
+pub const REQUEST_ICON_NAME: &str = csi!('t', 20);
+/// Request whether the window is minimized. The terminal responds with
+/// `CSI 1 t` if it isn't and with `CSI 2 t` if it is.
+pub const REQUEST_WINDOW_STATE: &str = csi!('t', 11);
+/// Request the position of the top left corner of the window on the screen
+/// in pixels.
+pub const REQUEST_WINDOW_POSITION: &str = csi!('t', 13);
+/// Request the size of the screen in pixels.
+pub const REQUEST_SCREEN_SIZE_PX: &str = csi!('t', 15);
+/// Request the size of the screen in characters.
+pub const REQUEST_SCREEN_SIZE: &str = csi!('t', 19);
 /// Request the number of sixel color registers.
 pub const REQUEST_SIXEL_COLORS: &str = "\x1b[?1;1;1S";
+/// Request the current graphic rendition (DECRQSS for SGR). The terminal
+/// responds with the code that sets the current text style.
+pub const REQUEST_SGR: &str = "\x1bP$qm\x1b\\";
+/// Request the current scroll region (DECRQSS for DECSTBM). The terminal
+/// responds with the code that sets the current scroll region.
+pub const REQUEST_SCROLL_REGION: &str = "\x1bP$qr\x1b\\";
+
+/// Request the state of the given private mode (DECRQM). The terminal
+/// responds with the mode and its state (`CSI ? mode ; state $ y`).
+///
+/// If used with literal, produces `&'static str`, otherwise produces
+/// [`String`].
+///
+/// # Example
+/// ```rust
+/// use termal_core::codes;
+///
+/// assert_eq!(codes::request_mode!(2004), "\x1b[?2004$p");
+/// ```
+#[macro_export]
+macro_rules! request_mode {
+    ($m:expr) => {
+        $crate::seq!("\x1b[?", "$p", $m)
+    };
+}
+pub use request_mode;
+
+/// Request the value of termcap/terminfo capability (XTGETTCAP). The name is
+/// sent hex encoded. The terminal responds with `DCS 1 + r name = value ST`
+/// where name and value are hex encoded, or with `DCS 0 + r name ST` if it
+/// doesn't know the capability.
+///
+/// # Example
+/// ```rust
+/// use termal_core::codes;
+///
+/// assert_eq!(codes::request_termcap("Co"), "\x1bP+q436F\x1b\\");
+/// ```
+pub fn request_termcap(name: &str) -> String {
+    let mut res = String::from("\x1bP+q");
+    for b in name.bytes() {
+        res += &format!("{b:02X}");
+    }
+    res + ST
+}
 
 /// Enables mouse tracking for X and Y coordinate on press.
 pub const ENABLE_MOUSE_XY_TRACKING: &str = enable!(9);
@@ -596,6 +974,12 @@ pub const ENABLE_MOUSE_XY_EXT: &str = enable!(1006);
 /// Disables extension to send mouse inputs in different format as position in
 /// characters.
 pub const DISABLE_MOUSE_XY_EXT: &str = disable!(1006);
+/// Enables alternate scroll mode. When alternate buffer is active and mouse
+/// tracking is disabled, the terminal will send mouse wheel as up and down
+/// arrow keys.
+pub const ENABLE_ALTERNATE_SCROLL: &str = enable!(1007);
+/// Disables alternate scroll mode.
+pub const DISABLE_ALTERNATE_SCROLL: &str = disable!(1007);
 /// Enables URXVT mouse extension. Not recommended, rather use
 /// [`ENABLE_MOUSE_XY_EXT`].
 pub const ENABLE_MOUSE_XY_URXVT_EXT: &str = enable!(1015);
@@ -608,14 +992,46 @@ pub const ENABLE_MOUSE_XY_PIX_EXT: &str = enable!(1016);
 /// pixels.
 pub const DISABLE_MOUSE_XY_PIX_EXT: &str = disable!(1016);
 
-code_macro! { csi
-    scroll_region, t, b; 'r'
-        ? "Set the scroll region in the terminal. Also moves the cursor to the
+code_macro! { $ csi
+    scroll_region(t, b), 'r', t, b;
+        ? "Set the scroll region in the terminal. Also moves the cursor to the \
            top left."
 }
 
 /// Reset the scroll region
 pub const RESET_SCROLL_REGION: &str = scroll_region!(0, 0);
+
+/// Creates code that sets the scroll region to the rows `top..=bottom`.
+/// Rows are numbered from 1. Fails if the region is empty or it doesn't fit
+/// into terminal with the given height.
+///
+/// Setting the scroll region also moves the cursor to the top left.
+pub fn checked_scroll_region(
+    top: usize,
+    bottom: usize,
+    height: usize,
+) -> Result<String> {
+    if top == 0 || top >= bottom || bottom > height {
+        Err(Error::InvalidScrollRegion {
+            top,
+            bottom,
+            height,
+        })
+    } else {
+        Ok(scroll_region!(top, bottom))
+    }
+}
+
+/// Creates code that sets the scroll region to the rows `top..=bottom`.
+/// Rows are numbered from 1. Fails if the region is empty or it doesn't fit
+/// into the terminal (see [`crate::raw::term_size`]).
+///
+/// Setting the scroll region also moves the cursor to the top left.
+#[cfg(feature = "raw")]
+pub fn set_scroll_region(top: usize, bottom: usize) -> Result<String> {
+    let height = crate::raw::term_size()?.char_height;
+    checked_scroll_region(top, bottom, height)
+}
 /// Don't limit the printing area.
 pub const DONT_LIMIT_PRINT_TO_SCROLL_REGION: &str = enable!(19);
 /// Limit printing area only to scroll region.
@@ -626,6 +1042,22 @@ pub const LIMIT_PRINT_TO_SCROLL_REGION: &str = disable!(19);
 pub const ENABLE_BRACKETED_PASTE_MODE: &str = enable!(2004);
 pub const DISABLE_BRACKETED_PASTE_MODE: &str = disable!(2004);
 
+/// Begins synchronized update. The terminal doesn't redraw the screen until
+/// [`END_SYNCHRONIZED_UPDATE`], so full screen redraws don't tear.
+/// Terminals that don't support it ignore it.
+pub const BEGIN_SYNCHRONIZED_UPDATE: &str = enable!(2026);
+/// Ends synchronized update started with [`BEGIN_SYNCHRONIZED_UPDATE`].
+pub const END_SYNCHRONIZED_UPDATE: &str = disable!(2026);
+/// Request whether the terminal supports synchronized updates (DECRQM for
+/// mode 2026).
+pub const REQUEST_SYNCHRONIZED_UPDATE: &str = request_mode!(2026);
+
+/// Request the current cursor style (DECRQSS for DECSCUSR). The terminal
+/// responds with the code that sets the current cursor style.
+pub const REQUEST_CURSOR_STYLE: &str = "\x1bP$q q\x1b\\";
+/// Resets the cursor style to the default of the terminal.
+pub const RESET_CURSOR_STYLE: &str = csi!(" q", 0);
+
 #[derive(Clone, Debug, Copy, Eq, PartialEq)]
 pub enum CursorStyle {
     /// Set cursor to block.
@@ -643,6 +1075,37 @@ pub enum CursorStyle {
     Bar(bool),
 }
 
+impl CursorStyle {
+    /// Gets the cursor style from the number used in the code that sets the
+    /// cursor style (DECSCUSR). This is the inverse of [`Self::code`].
+    pub const fn from_code(code: u32) -> Option<Self> {
+        match code {
+            0 => Some(Self::Block(Some(true))),
+            1 => Some(Self::Block(None)),
+            2 => Some(Self::Block(Some(false))),
+            3 => Some(Self::Underline(true)),
+            4 => Some(Self::Underline(false)),
+            5 => Some(Self::Bar(true)),
+            6 => Some(Self::Bar(false)),
+            _ => None,
+        }
+    }
+
+    /// Gets the number used in the code that sets the cursor style
+    /// (DECSCUSR).
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::Block(Some(true)) => 0,
+            Self::Block(None) => 1,
+            Self::Block(Some(false)) => 2,
+            Self::Underline(true) => 3,
+            Self::Underline(false) => 4,
+            Self::Bar(true) => 5,
+            Self::Bar(false) => 6,
+        }
+    }
+}
+
 pub fn set_cursor(style: CursorStyle) -> &'static str {
     match style {
         CursorStyle::Block(Some(true)) => csi!(" q", 0),
@@ -657,11 +1120,11 @@ pub fn set_cursor(style: CursorStyle) -> &'static str {
 
 // OSC sequences
 
-code_macro! {osc
-    request_color_code, 4, code, "?";
+code_macro! { $ osc
+    request_color_code(code), 4, code, "?";
         ? "Requests the current color assigned to the given color code.",
 
-    reset_color_code, 104, code;
+    reset_color_code(code), 104, code;
         ? "Resets the color definition for the given color code.",
 }
 
@@ -697,6 +1160,135 @@ where
     osc!(12, color.into())
 }
 
+/// Sets the title of the window. Control characters are removed from the
+/// title, so that it can't end the sequence.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::codes;
+///
+/// assert_eq!(codes::set_window_title("vim"), "\x1b]2;vim\x1b\\");
+/// assert_eq!(codes::set_window_title("a\x07b"), "\x1b]2;ab\x1b\\");
+/// ```
+pub fn set_window_title(title: impl Display) -> String {
+    osc!(2, strip_control_chars(title))
+}
+
+/// Sets the icon name of the window (the title of minimized window or of
+/// tab on some terminals). Control characters are removed from the name.
+pub fn set_icon_name(name: impl Display) -> String {
+    osc!(1, strip_control_chars(name))
+}
+
+/// Sets both the title and the icon name of the window. Control characters
+/// are removed from the title.
+pub fn set_window_title_and_icon(title: impl Display) -> String {
+    osc!(0, strip_control_chars(title))
+}
+
+/// Asks the terminal to get the users attention (e.g. by bouncing the dock
+/// icon or by marking the window as urgent). This is supported only by some
+/// terminals (e.g. iTerm2 or WezTerm), other terminals ignore it.
+pub const REQUEST_ATTENTION: &str = osc!(1337, "RequestAttention=yes");
+
+/// Sends desktop notification through the terminal. This emits both OSC 9
+/// (iTerm2, WezTerm, kitty, ...) and OSC 777 (urxvt, foot, ...), terminals
+/// ignore the one they don't support. Support may be guessed with
+/// `diagnostics::Report::notifications`.
+///
+/// Control characters are removed from the title and the body, so that they
+/// can't end the sequences. `;` is also removed from the title because it
+/// separates the title from the body in OSC 777.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::codes;
+///
+/// assert_eq!(
+///     codes::notify("Build", "done"),
+///     "\x1b]9;Build: done\x1b\\\x1b]777;notify;Build;done\x1b\\"
+/// );
+/// ```
+pub fn notify(title: impl Display, body: impl Display) -> String {
+    let title = strip_control_chars(title).replace(';', "");
+    let body = strip_control_chars(body);
+    let msg = if title.is_empty() {
+        body.clone()
+    } else {
+        format!("{title}: {body}")
+    };
+    osc!(9, msg) + &osc!(777, "notify", title, body)
+}
+
+/// Marks the start of prompt (OSC 133 A). Terminals with shell integration
+/// (FinalTerm semantic prompts) use the marks to jump between prompts. See
+/// also [`crate::PromptMarker`].
+pub const PROMPT_START: &str = osc!(133, 'A');
+/// Marks the end of prompt and the start of the command input (OSC 133 B).
+pub const PROMPT_END: &str = osc!(133, 'B');
+/// Marks the end of the command input and the start of the command output
+/// (OSC 133 C).
+pub const COMMAND_OUTPUT_START: &str = osc!(133, 'C');
+
+/// Marks the end of the command output (OSC 133 D). `exit` is the exit code
+/// of the command if it is known.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::codes;
+///
+/// assert_eq!(codes::command_finished(None), "\x1b]133;D\x1b\\");
+/// assert_eq!(codes::command_finished(Some(1)), "\x1b]133;D;1\x1b\\");
+/// ```
+pub fn command_finished(exit: Option<i32>) -> String {
+    match exit {
+        Some(e) => osc!(133, 'D', e),
+        None => osc!(133, 'D').to_string(),
+    }
+}
+
+fn strip_control_chars(s: impl Display) -> String {
+    s.to_string().chars().filter(|c| !c.is_control()).collect()
+}
+
+/// Saves the title of the window on stack (XTWINOPS). It can be restored
+/// with [`POP_WINDOW_TITLE`].
+pub const PUSH_WINDOW_TITLE: &str = csi!('t', 22, 2);
+/// Restores the title of the window saved with [`PUSH_WINDOW_TITLE`].
+pub const POP_WINDOW_TITLE: &str = csi!('t', 23, 2);
+/// Saves the icon name of the window on stack (XTWINOPS). It can be
+/// restored with [`POP_ICON_NAME`].
+pub const PUSH_ICON_NAME: &str = csi!('t', 22, 1);
+/// Restores the icon name of the window saved with [`PUSH_ICON_NAME`].
+pub const POP_ICON_NAME: &str = csi!('t', 23, 1);
+
+// Window operations (XTWINOPS). Many terminals ignore these or allow them
+// only when configured to.
+
+/// Restores the window if it is minimized.
+pub const RESTORE_WINDOW: &str = csi!('t', 1);
+/// Minimizes the window.
+pub const MINIMIZE_WINDOW: &str = csi!('t', 2);
+/// Maximizes the window.
+pub const MAXIMIZE_WINDOW: &str = csi!('t', 9, 1);
+/// Restores the window if it is maximized.
+pub const UNMAXIMIZE_WINDOW: &str = csi!('t', 9, 0);
+/// Makes the window fullscreen.
+pub const ENABLE_FULLSCREEN: &str = csi!('t', 10, 1);
+/// Makes the window not fullscreen.
+pub const DISABLE_FULLSCREEN: &str = csi!('t', 10, 0);
+/// Toggles fullscreen of the window.
+pub const TOGGLE_FULLSCREEN: &str = csi!('t', 10, 2);
+
+code_macro!($ csi
+    move_window(x, y), 't', 3, x, y;
+        ? "Moves the window to the given position on screen in pixels.",
+    resize_window(w, h), 't', 8, h, w;
+        ? "Resizes the text area to the given size in chars (0 keeps size).",
+    resize_window_px(w, h), 't', 4, h, w;
+        ? "Resizes the text area to the given size in pixels (0 keeps size).",
+);
+
 /// Resets all the color codes to their default colors.
 pub const RESET_ALL_COLOR_CODES: &str = osc!(104);
 /// Resets the default foreground color.
@@ -769,14 +1361,149 @@ pub fn request_selectoin(sel: impl IntoIterator<Item = Selection>) -> String {
 }
 
 /// Sets the given selection buffers. If empty sets the default selection
-/// buffers.
+/// buffers. The code is wrapped with [`passthrough`] if the output goes
+/// through terminal multiplexer.
 pub fn set_selection(
     sel: impl IntoIterator<Item = Selection>,
     data: impl AsRef<[u8]>,
 ) -> String {
     let mut res = prepare_selection(sel);
-    base64::prelude::BASE64_STANDARD.encode_string(data, &mut res);
-    res + "\x1b\\"
+    crate::base64::encode_to(data.as_ref(), &mut res);
+    res += "\x1b\\";
+    passthrough(&res).into_owned()
+}
+
+// Multiplexer passthrough
+
+/// Maximum length of single passthrough string in screen.
+const SCREEN_CHUNK: usize = 768;
+
+/// Terminal multiplexer that the output goes through. Multiplexers swallow
+/// sequences that they don't understand (e.g. sixel or kitty graphics)
+/// unless they are wrapped in passthrough. See [`passthrough`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Multiplexer {
+    /// No multiplexer.
+    #[default]
+    None,
+    /// tmux. It passes the sequences through only if the option
+    /// `allow-passthrough` is enabled.
+    Tmux,
+    /// GNU screen.
+    Screen,
+}
+
+impl Multiplexer {
+    /// Detects the multiplexer from the environment variables `TMUX` and
+    /// `STY`.
+    pub fn detect() -> Self {
+        let var = |name| std::env::var_os(name).is_some_and(|v| !v.is_empty());
+        if var("TMUX") {
+            Self::Tmux
+        } else if var("STY") {
+            Self::Screen
+        } else {
+            Self::None
+        }
+    }
+
+    /// Wraps the sequence so that it passes through the multiplexer. See
+    /// [`tmux_passthrough`] and [`screen_passthrough`].
+    pub fn wrap<'a>(&self, seq: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::None => seq.into(),
+            Self::Tmux => tmux_passthrough(seq).into(),
+            Self::Screen => screen_passthrough(seq).into(),
+        }
+    }
+
+    fn id(v: Option<Self>) -> u8 {
+        match v {
+            None => 0,
+            Some(Self::None) => 1,
+            Some(Self::Tmux) => 2,
+            Some(Self::Screen) => 3,
+        }
+    }
+}
+
+/// Multiplexer set with [`set_multiplexer`]. `0` means that it is detected.
+static MULTIPLEXER: std::sync::atomic::AtomicU8 =
+    std::sync::atomic::AtomicU8::new(0);
+
+/// Sets the multiplexer that is used by [`passthrough`]. [`None`] means
+/// that it is detected (see [`Multiplexer::detect`]).
+pub fn set_multiplexer(v: Option<Multiplexer>) {
+    MULTIPLEXER
+        .store(Multiplexer::id(v), std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Gets the multiplexer that is used by [`passthrough`]. Unless it is set
+/// with [`set_multiplexer`], it is detected from the environment.
+pub fn multiplexer() -> Multiplexer {
+    match MULTIPLEXER.load(std::sync::atomic::Ordering::Relaxed) {
+        1 => Multiplexer::None,
+        2 => Multiplexer::Tmux,
+        3 => Multiplexer::Screen,
+        _ => Multiplexer::detect(),
+    }
+}
+
+/// Wraps the sequence so that it passes through the multiplexer if the
+/// output goes through one (see [`multiplexer`]). Otherwise the sequence is
+/// returned unchanged. Termal uses this for OSC 52 ([`set_selection`]) and
+/// sixel images.
+pub fn passthrough(seq: &str) -> Cow<'_, str> {
+    multiplexer().wrap(seq)
+}
+
+/// Wraps the sequence in the tmux passthrough envelope. Escape characters
+/// in the sequence are doubled.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::codes;
+///
+/// assert_eq!(
+///     codes::tmux_passthrough("\x1b]52;c;YQ==\x1b\\"),
+///     "\x1bPtmux;\x1b\x1b]52;c;YQ==\x1b\x1b\\\x1b\\"
+/// );
+/// ```
+pub fn tmux_passthrough(seq: &str) -> String {
+    format!("\x1bPtmux;{}\x1b\\", seq.replace('\x1b', "\x1b\x1b"))
+}
+
+/// Wraps the sequence in the screen passthrough envelope (DCS). Screen
+/// limits the length of the passthrough string, so long sequences are split
+/// into multiple envelopes. String terminator in the sequence is split
+/// between two envelopes, so that it doesn't end the envelope.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::codes;
+///
+/// assert_eq!(
+///     codes::screen_passthrough("\x1b]2;a\x1b\\"),
+///     "\x1bP\x1b]2;a\x1b\x1b\\\x1bP\\\x1b\\"
+/// );
+/// ```
+pub fn screen_passthrough(seq: &str) -> String {
+    let mut res = String::new();
+    let mut rest = seq;
+    while !rest.is_empty() {
+        let mut len = rest.len().min(SCREEN_CHUNK);
+        while !rest.is_char_boundary(len) {
+            len -= 1;
+        }
+        if let Some(st) = rest[..len].find(ST) {
+            len = st + 1;
+        }
+        res += DCS;
+        res += &rest[..len];
+        res += ST;
+        rest = &rest[len..];
+    }
+    res
 }
 
 // TODO: Kitty extensions
@@ -788,6 +1515,143 @@ pub const BRACKETED_PASTE_START: &str = "\x1b[200~";
 /// Input code for bracketed paste end. Used internally.
 pub const BRACKETED_PASTE_END: &str = "\x1b[201~";
 
+/// Codes that terminals send for common keys.
+///
+/// Terminals don't agree on the codes of some keys, so there may be more
+/// codes for the same key. The codes are named after the terminals that
+/// send them (xterm, rxvt or the linux console). Codes with the prefix
+/// `APP_` are sent by xterm in the application cursor mode.
+///
+/// All the codes are recognized by the parser of events. Codes of keys with
+/// modifiers are not listed, because the modifiers are encoded in the
+/// argument of the codes (e.g. `\x1b[1;5A` is control + up arrow).
+///
+/// # Examples
+/// ```rust
+/// use termal_core::codes::input;
+///
+/// fn is_home(code: &str) -> bool {
+///     matches!(
+///         code,
+///         input::HOME
+///             | input::APP_HOME
+///             | input::VT_HOME
+///             | input::RXVT_HOME
+///     )
+/// }
+///
+/// assert!(is_home("\x1b[7~"));
+/// assert!(!is_home(input::END));
+/// ```
+pub mod input {
+    /// Up arrow.
+    pub const UP: &str = "\x1b[A";
+    /// Down arrow.
+    pub const DOWN: &str = "\x1b[B";
+    /// Right arrow.
+    pub const RIGHT: &str = "\x1b[C";
+    /// Left arrow.
+    pub const LEFT: &str = "\x1b[D";
+    /// Up arrow in the application cursor mode.
+    pub const APP_UP: &str = "\x1bOA";
+    /// Down arrow in the application cursor mode.
+    pub const APP_DOWN: &str = "\x1bOB";
+    /// Right arrow in the application cursor mode.
+    pub const APP_RIGHT: &str = "\x1bOC";
+    /// Left arrow in the application cursor mode.
+    pub const APP_LEFT: &str = "\x1bOD";
+
+    /// Shift + up arrow in rxvt.
+    pub const RXVT_SHIFT_UP: &str = "\x1b[a";
+    /// Shift + down arrow in rxvt.
+    pub const RXVT_SHIFT_DOWN: &str = "\x1b[b";
+    /// Shift + right arrow in rxvt.
+    pub const RXVT_SHIFT_RIGHT: &str = "\x1b[c";
+    /// Shift + left arrow in rxvt.
+    pub const RXVT_SHIFT_LEFT: &str = "\x1b[d";
+    /// Control + up arrow in rxvt.
+    pub const RXVT_CTRL_UP: &str = "\x1bOa";
+    /// Control + down arrow in rxvt.
+    pub const RXVT_CTRL_DOWN: &str = "\x1bOb";
+    /// Control + right arrow in rxvt.
+    pub const RXVT_CTRL_RIGHT: &str = "\x1bOc";
+    /// Control + left arrow in rxvt.
+    pub const RXVT_CTRL_LEFT: &str = "\x1bOd";
+
+    /// Home in xterm.
+    pub const HOME: &str = "\x1b[H";
+    /// End in xterm.
+    pub const END: &str = "\x1b[F";
+    /// Home in xterm in the application cursor mode.
+    pub const APP_HOME: &str = "\x1bOH";
+    /// End in xterm in the application cursor mode.
+    pub const APP_END: &str = "\x1bOF";
+    /// Home in the linux console and vt220 like terminals (e.g. tmux).
+    pub const VT_HOME: &str = "\x1b[1~";
+    /// End in the linux console and vt220 like terminals (e.g. tmux).
+    pub const VT_END: &str = "\x1b[4~";
+    /// Home in rxvt.
+    pub const RXVT_HOME: &str = "\x1b[7~";
+    /// End in rxvt.
+    pub const RXVT_END: &str = "\x1b[8~";
+
+    /// Insert.
+    pub const INSERT: &str = "\x1b[2~";
+    /// Delete.
+    pub const DELETE: &str = "\x1b[3~";
+    /// Page up.
+    pub const PAGE_UP: &str = "\x1b[5~";
+    /// Page down.
+    pub const PAGE_DOWN: &str = "\x1b[6~";
+    /// Shift + tab.
+    pub const BACK_TAB: &str = "\x1b[Z";
+
+    /// F1 in xterm.
+    pub const F1: &str = "\x1bOP";
+    /// F2 in xterm.
+    pub const F2: &str = "\x1bOQ";
+    /// F3 in xterm.
+    pub const F3: &str = "\x1bOR";
+    /// F4 in xterm.
+    pub const F4: &str = "\x1bOS";
+    /// F5.
+    pub const F5: &str = "\x1b[15~";
+    /// F6.
+    pub const F6: &str = "\x1b[17~";
+    /// F7.
+    pub const F7: &str = "\x1b[18~";
+    /// F8.
+    pub const F8: &str = "\x1b[19~";
+    /// F9.
+    pub const F9: &str = "\x1b[20~";
+    /// F10.
+    pub const F10: &str = "\x1b[21~";
+    /// F11.
+    pub const F11: &str = "\x1b[23~";
+    /// F12.
+    pub const F12: &str = "\x1b[24~";
+
+    /// F1 in rxvt and vt220 like terminals.
+    pub const VT_F1: &str = "\x1b[11~";
+    /// F2 in rxvt and vt220 like terminals.
+    pub const VT_F2: &str = "\x1b[12~";
+    /// F3 in rxvt and vt220 like terminals.
+    pub const VT_F3: &str = "\x1b[13~";
+    /// F4 in rxvt and vt220 like terminals.
+    pub const VT_F4: &str = "\x1b[14~";
+
+    /// F1 in the linux console.
+    pub const LINUX_F1: &str = "\x1b[[A";
+    /// F2 in the linux console.
+    pub const LINUX_F2: &str = "\x1b[[B";
+    /// F3 in the linux console.
+    pub const LINUX_F3: &str = "\x1b[[C";
+    /// F4 in the linux console.
+    pub const LINUX_F4: &str = "\x1b[[D";
+    /// F5 in the linux console.
+    pub const LINUX_F5: &str = "\x1b[[E";
+}
+
 /// Trait for getting string from &str and String
 pub trait GetString {
     /// If [`self`] is `&str` uses `.to_owned()`, if [`self`] is [`String`] returns
@@ -816,6 +1680,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::identity_op)]
     fn test_macros() {
         assert_eq!(csi!('a', 1, 2, 3, 4, 5), "\x1b[1;2;3;4;5a");
         assert_eq!(csi!('a', 1 + 0, 2, 3, 4, 5), "\x1b[1;2;3;4;5a");
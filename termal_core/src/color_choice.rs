@@ -0,0 +1,219 @@
+use std::{
+    cell::Cell,
+    fmt::{self, Display},
+    io::IsTerminal,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// Determines whether colors (and other terminal codes) should be used in
+/// the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorChoice {
+    /// Always use colors.
+    Always,
+    /// Use colors only if the output is terminal and the environment
+    /// variables allow it (see [`env_color_choice`]).
+    #[default]
+    Auto,
+    /// Never use colors.
+    Never,
+}
+
+/// Global color choice set with [`set_color_choice`].
+static GLOBAL_CHOICE: AtomicU8 = AtomicU8::new(ColorChoice::Auto as u8);
+
+thread_local! {
+    static SCOPED_CHOICE: Cell<Option<ColorChoice>> = const { Cell::new(None) };
+}
+
+/// Value that is formatted with or without colors based on the color choice
+/// at the time when it is displayed (not when it is created). Usually
+/// created with the `lazyc!` macro.
+///
+/// This allows libraries to return colored messages that respect the color
+/// choice of the app (e.g. `--color=never`) without passing it around.
+///
+/// The [`Display`] implementation decides with [`use_color`] for stdout. Use
+/// [`LazyColored::with_color`] for other outputs.
+///
+/// # Examples
+/// ```rust
+/// use std::fmt::Write;
+/// use termal_core::{with_color_choice, ColorChoice, LazyColored};
+///
+/// let msg = LazyColored::new(|color, f| {
+///     if color {
+///         f.write_str("\x1b[31merror\x1b[0m")
+///     } else {
+///         f.write_str("error")
+///     }
+/// });
+///
+/// with_color_choice(ColorChoice::Never, || {
+///     assert_eq!(msg.to_string(), "error");
+/// });
+/// assert_eq!(msg.with_color(true), "\x1b[31merror\x1b[0m");
+/// ```
+#[derive(Clone, Copy)]
+pub struct LazyColored<F>(F);
+
+/// Restores the previous scoped color choice when dropped (also on panic).
+struct ScopeGuard(Option<ColorChoice>);
+
+impl ColorChoice {
+    /// Checks whether colors should be used for output. `is_terminal` tells
+    /// whether the output is terminal.
+    pub fn use_color(&self, is_terminal: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Auto => match env_color_choice() {
+                Self::Auto => is_terminal,
+                c => c.use_color(is_terminal),
+            },
+            Self::Never => false,
+        }
+    }
+
+    fn from_u8(v: u8) -> Self {
+        match v {
+            0 => Self::Always,
+            2 => Self::Never,
+            _ => Self::Auto,
+        }
+    }
+}
+
+/// Sets the color choice for the whole app (e.g. from `--color` flag). The
+/// choice may be overriden for single thread with [`with_color_choice`].
+///
+/// # Examples
+/// ```rust
+/// use termal_core::{color_choice, set_color_choice, ColorChoice};
+///
+/// set_color_choice(ColorChoice::Never);
+/// assert_eq!(color_choice(), ColorChoice::Never);
+/// # set_color_choice(ColorChoice::Auto);
+/// ```
+pub fn set_color_choice(choice: ColorChoice) {
+    GLOBAL_CHOICE.store(choice as u8, Ordering::Relaxed);
+}
+
+/// Gets the color choice set with [`set_color_choice`].
+pub fn global_color_choice() -> ColorChoice {
+    ColorChoice::from_u8(GLOBAL_CHOICE.load(Ordering::Relaxed))
+}
+
+/// Gets the color choice given by the environment variables:
+/// - [`ColorChoice::Always`] if `CLICOLOR_FORCE` is set and is not `0`.
+/// - [`ColorChoice::Never`] if `NO_COLOR` is set and is not empty.
+/// - [`ColorChoice::Never`] if `CLICOLOR` is `0`.
+/// - [`ColorChoice::Auto`] otherwise.
+///
+/// The variables are checked in this order.
+pub fn env_color_choice() -> ColorChoice {
+    let var = |name| std::env::var_os(name).filter(|v| !v.is_empty());
+    if var("CLICOLOR_FORCE").is_some_and(|v| v != "0") {
+        ColorChoice::Always
+    } else if var("NO_COLOR").is_some()
+        || var("CLICOLOR").is_some_and(|v| v == "0")
+    {
+        ColorChoice::Never
+    } else {
+        ColorChoice::Auto
+    }
+}
+
+/// Overrides the color choice for the current thread while `f` runs. The
+/// previous choice is restored afterwards. The overrides may be nested.
+///
+/// This is useful in library code that needs to force or strip colors for
+/// a section (e.g. when writing to capture buffer) without changing the
+/// setting used by the rest of the app. The auto color macros (e.g.
+/// `printacln!`) respect the override.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::{color_choice, with_color_choice, ColorChoice};
+///
+/// with_color_choice(ColorChoice::Never, || {
+///     assert_eq!(color_choice(), ColorChoice::Never);
+///     assert!(!color_choice().use_color(true));
+/// });
+/// assert_eq!(color_choice(), ColorChoice::Auto);
+/// ```
+pub fn with_color_choice<R>(choice: ColorChoice, f: impl FnOnce() -> R) -> R {
+    let _guard = ScopeGuard(SCOPED_CHOICE.replace(Some(choice)));
+    f()
+}
+
+/// Gets the color choice overriden for the current thread with
+/// [`with_color_choice`].
+pub fn scoped_color_choice() -> Option<ColorChoice> {
+    SCOPED_CHOICE.get()
+}
+
+/// Gets the color choice that should be used on the current thread. This is
+/// the override from [`with_color_choice`] or the choice set with
+/// [`set_color_choice`].
+pub fn color_choice() -> ColorChoice {
+    scoped_color_choice().unwrap_or_else(global_color_choice)
+}
+
+/// Checks whether colors should be used for output based on
+/// [`color_choice`]. `is_terminal` tells whether the output is terminal.
+pub fn use_color(is_terminal: bool) -> bool {
+    color_choice().use_color(is_terminal)
+}
+
+impl<F> LazyColored<F>
+where
+    F: Fn(bool, &mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    /// Creates value that is formatted with `f`. The first argument of `f`
+    /// tells whether colors should be used.
+    pub fn new(f: F) -> Self {
+        Self(f)
+    }
+
+    /// Formats the value with or without colors.
+    pub fn with_color(&self, color: bool) -> String {
+        struct Fmt<'a, F>(&'a F, bool);
+
+        impl<F> Display for Fmt<'_, F>
+        where
+            F: Fn(bool, &mut fmt::Formatter<'_>) -> fmt::Result,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                (self.0)(self.1, f)
+            }
+        }
+
+        Fmt(&self.0, color).to_string()
+    }
+}
+
+impl<F> Display for LazyColored<F>
+where
+    F: Fn(bool, &mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(use_color(std::io::stdout().is_terminal()), f)
+    }
+}
+
+impl<F> fmt::Debug for LazyColored<F>
+where
+    F: Fn(bool, &mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LazyColored")
+            .field(&self.with_color(false))
+            .finish()
+    }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPED_CHOICE.set(self.0);
+    }
+}
@@ -0,0 +1,334 @@
+//! Printing of aligned columns for streaming tabular data (e.g. `ps` like
+//! listings).
+//!
+//! [`ColumnPrinter`] collects rows and when flushed, it outputs them aligned
+//! to the widest cell in each column. The widths are kept between flushes,
+//! so the following batches stay aligned with the previous ones. Widths are
+//! in terminal columns and cells may contain control sequences.
+//!
+//! # Examples
+//! ```rust
+//! use termal_core::columns::{ColumnPrinter, ColumnSpec};
+//!
+//! let mut cols = ColumnPrinter::new(&[
+//!     ColumnSpec::right().header("PID"),
+//!     ColumnSpec::left().header("CMD"),
+//! ]);
+//! cols.push_row(["1", "init"]);
+//! cols.push_row(["1234", "bash"]);
+//!
+//! assert_eq!(cols.flush(), " PID  CMD\n   1  init\n1234  bash\n");
+//! ```
+
+use std::borrow::Cow;
+
+use crate::{
+    codes,
+    fill::char_width,
+    glyphs::{self, GlyphSet},
+    term_text::TermTextSpans,
+    theme::{self, StyleSet},
+    wrap::visible_width,
+    ResetPolicy, Style,
+};
+
+/// Alignment of text in column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// Specification of single column of [`ColumnPrinter`].
+#[derive(Debug, Clone, Default)]
+pub struct ColumnSpec<'a> {
+    /// Header of the column. It is printed with the first batch.
+    pub header: Option<Cow<'a, str>>,
+    /// Alignment of the cells.
+    pub align: Align,
+    /// Minimum width of the column.
+    pub min_width: usize,
+    /// Maximum width of the column. Longer cells are truncated with
+    /// [`GlyphSet::ellipsis`] (e.g. `…`).
+    pub max_width: Option<usize>,
+    /// Style of the cells.
+    pub style: Style,
+}
+
+/// Prints rows aligned in columns. See the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct ColumnPrinter<'a> {
+    specs: Vec<ColumnSpec<'a>>,
+    rows: Vec<Vec<String>>,
+    widths: Vec<usize>,
+    separator: Cow<'a, str>,
+    header_printed: bool,
+    reset: ResetPolicy,
+    styles: Option<StyleSet>,
+    glyphs: Option<GlyphSet>,
+}
+
+impl<'a> ColumnSpec<'a> {
+    /// Creates left aligned column.
+    pub fn left() -> Self {
+        Self::default()
+    }
+
+    /// Creates right aligned column.
+    pub fn right() -> Self {
+        Self::left().align(Align::Right)
+    }
+
+    /// Creates centered column.
+    pub fn center() -> Self {
+        Self::left().align(Align::Center)
+    }
+
+    /// Sets the header of the column.
+    pub fn header(mut self, s: impl Into<Cow<'a, str>>) -> Self {
+        self.header = Some(s.into());
+        self
+    }
+
+    /// Sets the alignment of the cells.
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets the minimum width of the column.
+    pub fn min_width(mut self, w: usize) -> Self {
+        self.min_width = w;
+        self
+    }
+
+    /// Sets the maximum width of the column.
+    pub fn max_width(mut self, w: usize) -> Self {
+        self.max_width = Some(w);
+        self
+    }
+
+    /// Sets the style of the cells.
+    pub fn style(mut self, style: impl Into<Style>) -> Self {
+        self.style = style.into();
+        self
+    }
+}
+
+impl<'a> ColumnPrinter<'a> {
+    /// Creates printer with the given columns. Columns are separated with
+    /// two spaces.
+    pub fn new(specs: &[ColumnSpec<'a>]) -> Self {
+        Self {
+            widths: specs.iter().map(|s| s.min_width).collect(),
+            specs: specs.to_vec(),
+            rows: vec![],
+            separator: "  ".into(),
+            header_printed: false,
+            reset: ResetPolicy::default(),
+            styles: None,
+            glyphs: None,
+        }
+    }
+
+    /// Sets styles that override the theme styles for this printer (see
+    /// [`theme::style`]). The header has the style `table.header`.
+    pub fn styles(mut self, styles: StyleSet) -> Self {
+        self.styles = Some(styles);
+        self
+    }
+
+    /// Sets the glyphs used by this printer. The default is the current
+    /// glyph set ([`glyphs::glyphs`]).
+    pub fn glyphs(mut self, glyphs: GlyphSet) -> Self {
+        self.glyphs = Some(glyphs);
+        self
+    }
+
+    /// Sets how styles are handled at the ends of the rows. Styled cells
+    /// are always reset at their end, except for the last cell when the
+    /// styles are kept. It is then reset after the newline.
+    pub fn reset_policy(mut self, policy: ResetPolicy) -> Self {
+        self.reset = policy;
+        self
+    }
+
+    /// Sets the separator of the columns. It may contain control sequences.
+    pub fn separator(mut self, s: impl Into<Cow<'a, str>>) -> Self {
+        self.separator = s.into();
+        self
+    }
+
+    /// Adds row. Missing cells are empty and extra cells are ignored.
+    pub fn push_row<I>(&mut self, row: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let row: Vec<String> = row
+            .into_iter()
+            .take(self.specs.len())
+            .map(Into::into)
+            .collect();
+        self.update_widths(&row);
+        self.rows.push(row);
+    }
+
+    /// Gets the number of rows that are waiting to be flushed.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Checks if there are no rows waiting to be flushed.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Gets the current widths of the columns.
+    pub fn widths(&self) -> &[usize] {
+        &self.widths
+    }
+
+    /// Resets the widths of the columns to their minimums. The next batch
+    /// is aligned independently of the previous batches.
+    pub fn reset_widths(&mut self) {
+        self.widths = self.specs.iter().map(|s| s.min_width).collect();
+        for row in std::mem::take(&mut self.rows) {
+            self.update_widths(&row);
+            self.rows.push(row);
+        }
+    }
+
+    /// Appends the waiting rows aligned to `res`. Each row ends with
+    /// newline. The header is appended before the first batch.
+    pub fn flush_to(&mut self, res: &mut String) {
+        if !self.header_printed
+            && self.specs.iter().any(|s| s.header.is_some())
+        {
+            let header: Vec<_> = self
+                .specs
+                .iter()
+                .map(|s| s.header.as_deref().unwrap_or_default().to_string())
+                .collect();
+            self.update_widths(&header);
+            self.write_row(res, &header, true);
+        }
+        self.header_printed = true;
+
+        for row in std::mem::take(&mut self.rows) {
+            self.write_row(res, &row, false);
+        }
+    }
+
+    /// Gets the waiting rows aligned. See [`ColumnPrinter::flush_to`].
+    pub fn flush(&mut self) -> String {
+        let mut res = String::new();
+        self.flush_to(&mut res);
+        res
+    }
+
+    fn update_widths(&mut self, row: &[String]) {
+        for ((w, spec), cell) in
+            self.widths.iter_mut().zip(&self.specs).zip(row)
+        {
+            let cw = visible_width(cell);
+            *w = (*w).max(spec.max_width.map_or(cw, |m| cw.min(m)));
+        }
+    }
+
+    fn write_row(&self, res: &mut String, row: &[String], header: bool) {
+        let start = res.len();
+        let ellipsis = self.glyphs.unwrap_or_else(glyphs::glyphs).ellipsis;
+        let last = self.specs.len().saturating_sub(1);
+        let mut reset_after = false;
+        let header_style = if header {
+            theme::style("table.header", self.styles.as_ref())
+                .unwrap_or_default()
+        } else {
+            Style::DEFAULT
+        };
+        for (i, (spec, width)) in
+            self.specs.iter().zip(&self.widths).enumerate()
+        {
+            if i != 0 {
+                *res += &self.separator;
+            }
+
+            let cell = row.get(i).map(|c| c.as_str()).unwrap_or_default();
+            let cell = truncate(cell, *width, ellipsis);
+            let pad = width - visible_width(&cell);
+            let (left, right) = match spec.align {
+                Align::Left => (0, pad),
+                Align::Right => (pad, 0),
+                Align::Center => (pad / 2, pad - pad / 2),
+            };
+
+            *res += &" ".repeat(left);
+            let style = if header { header_style } else { spec.style };
+            let styled = !style.is_default() || cell.contains('\x1b');
+            *res += &style.code();
+            *res += &cell;
+            if styled && i == last && self.reset == ResetPolicy::Keep {
+                reset_after = true;
+            } else if styled {
+                *res += codes::RESET;
+            }
+            // Don't pad the last column with trailing spaces.
+            if i != last {
+                *res += &" ".repeat(right);
+            }
+        }
+        // Don't leave trailing spaces after empty cells.
+        res.truncate(start + res[start..].trim_end_matches(' ').len());
+        res.push('\n');
+        if reset_after {
+            *res += codes::RESET;
+        }
+    }
+}
+
+/// Truncates the text so that it fits into the width. Truncated text ends
+/// with the ellipsis if it fits. Control sequences are kept.
+fn truncate<'s>(s: &'s str, width: usize, ellipsis: &str) -> Cow<'s, str> {
+    if visible_width(s) <= width {
+        return s.into();
+    }
+    if width == 0 {
+        return "".into();
+    }
+
+    // Just cut the text if the ellipsis doesn't fit.
+    let mut ew = visible_width(ellipsis);
+    let ellipsis = if ew > width {
+        ew = 0;
+        ""
+    } else {
+        ellipsis
+    };
+    let mut res = String::new();
+    let mut w = 0;
+    let mut full = false;
+    for span in TermTextSpans::new(s) {
+        if span.is_control() {
+            res += span.text();
+            continue;
+        }
+        if full {
+            continue;
+        }
+        for c in span.text().chars() {
+            let cw = char_width(c);
+            // Leave space for the ellipsis.
+            if w + cw + ew > width {
+                res += ellipsis;
+                full = true;
+                break;
+            }
+            res.push(c);
+            w += cw;
+        }
+    }
+    res.into()
+}
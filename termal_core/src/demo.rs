@@ -0,0 +1,223 @@
+//! Diagnostic screens that show what the terminal supports. They are
+//! generated from the codes in [`crate::codes`], so they show how termal
+//! output looks in the terminal.
+//!
+//! Run `cargo run --example demo` to see the screens.
+//!
+//! # Examples
+//! ```no_run
+//! use termal_core::demo;
+//!
+//! for page in demo::color_chart() {
+//!     print!("{page}");
+//! }
+//! print!("{}", demo::style_matrix());
+//! ```
+
+use std::fmt::Write;
+
+use crate::codes;
+
+#[cfg(feature = "events")]
+use std::io::Write as _;
+
+#[cfg(feature = "events")]
+use crate::{
+    error::Result,
+    raw::{
+        events::{AnyEvent, Event, KeyBinding, KeyCode, Modifiers},
+        IoProvider, Terminal,
+    },
+};
+
+/// Width of the color bars in cells.
+const BAR_WIDTH: usize = 72;
+
+/// Styles shown in [`style_matrix`].
+const STYLES: &[(&str, &str)] = &[
+    ("normal", ""),
+    ("bold", codes::BOLD),
+    ("faint", codes::FAINT),
+    ("italic", codes::ITALIC),
+    ("underline", codes::UNDERLINE),
+    ("double underline", codes::DOUBLE_UNDERLINE),
+    ("blinking", codes::BLINKING),
+    ("inverse", codes::INVERSE),
+    ("invisible", codes::INVISIBLE),
+    ("striketrough", codes::STRIKETROUGH),
+    ("overline", codes::OVERLINE),
+];
+
+/// Colors shown in [`style_matrix`].
+const COLORS: &[(&str, &str)] = &[
+    ("default", ""),
+    ("red", codes::RED_DARK_FG),
+    ("green", codes::GREEN_DARK_FG),
+    ("yellow", codes::YELLOW_DARK_FG),
+    ("blue", codes::BLUE_DARK_FG),
+    ("magenta", codes::MAGENTA_DARK_FG),
+    ("cyan", codes::CYAN_DARK_FG),
+];
+
+/// Generates pages with color charts. The first page shows the 256 color
+/// palette and the second page shows RGB gradients. Each page fits into
+/// 80x24 terminal.
+///
+/// If the RGB gradients have visible bands, the terminal doesn't support
+/// true color and quantizes the colors.
+pub fn color_chart() -> Vec<String> {
+    vec![palette_page(), rgb_page()]
+}
+
+/// Generates table that shows all the text styles combined with the basic
+/// colors.
+pub fn style_matrix() -> String {
+    const NAME_WIDTH: usize = 18;
+
+    let mut res = String::new();
+    _ = write!(res, "{:NAME_WIDTH$}", "");
+    for (name, _) in COLORS {
+        _ = write!(res, "{name:8}");
+    }
+    res.push('\n');
+
+    for (style, code) in STYLES {
+        _ = write!(res, "{style:NAME_WIDTH$}");
+        for (_, color) in COLORS {
+            res += code;
+            res += color;
+            res += "Text";
+            res += codes::RESET;
+            res += "    ";
+        }
+        res.push('\n');
+    }
+
+    res
+}
+
+/// Shows the events received by the terminal until `ctrl+c` is pressed. This
+/// is useful to check which keys and modifiers the terminal sends. The
+/// terminal should be in raw mode.
+#[cfg(feature = "events")]
+pub fn key_tester<T: IoProvider>(term: &mut Terminal<T>) -> Result<()> {
+    let exit = KeyBinding::new(KeyCode::Char('c'), Modifiers::CONTROL);
+
+    term.println(
+        "Press keys to see how they are parsed. Press ctrl+c to exit.",
+    )?;
+    term.flush()?;
+
+    loop {
+        let evt = term.read_ambigous()?;
+        let line = match &evt.event {
+            AnyEvent::Known(Event::KeyPress(k)) if exit.matches(k) => {
+                return Ok(());
+            }
+            AnyEvent::Known(Event::KeyPress(k)) => {
+                format!("{}: {k:?}", KeyBinding::from(k))
+            }
+            AnyEvent::Known(e) => format!("{e:?}"),
+            AnyEvent::Unknown(u) => format!("unknown: {u:?}"),
+        };
+        term.println(line)?;
+        for other in &evt.other {
+            term.println(format!("  or {other:?}"))?;
+        }
+        term.flush()?;
+    }
+}
+
+fn palette_page() -> String {
+    let mut res = String::new();
+
+    res += "Basic colors (0..16):\n";
+    for row in 0..2 {
+        for c in row * 8..row * 8 + 8 {
+            palette_cell(&mut res, c, 9);
+        }
+        res += codes::RESET;
+        res.push('\n');
+    }
+
+    res += "\nColor cube (16..232):\n";
+    for row in 0..6 {
+        for c in 16 + row * 36..16 + row * 36 + 36 {
+            res += &codes::bg256!(c);
+            res += "  ";
+        }
+        res += codes::RESET;
+        res.push('\n');
+    }
+
+    res += "\nGrayscale (232..256):\n";
+    for c in 232..256 {
+        res += &codes::bg256!(c);
+        res += "   ";
+    }
+    res += codes::RESET;
+    res.push('\n');
+
+    res
+}
+
+fn palette_cell(res: &mut String, c: usize, width: usize) {
+    // Use readable text color.
+    let fg = if matches!(c, 0 | 4 | 8) {
+        codes::WHITE_FG
+    } else {
+        codes::BLACK_FG
+    };
+    *res += &codes::bg256!(c);
+    *res += fg;
+    _ = write!(res, "{c:^width$}");
+}
+
+fn rgb_page() -> String {
+    let mut res = String::new();
+    let t = |i: usize| (i * 255 / (BAR_WIDTH - 1)) as u8;
+
+    res += "RGB gradients (there should be no visible bands):\n";
+    // Which channels are in the gradient.
+    let bars = [
+        ("red", (1, 0, 0)),
+        ("green", (0, 1, 0)),
+        ("blue", (0, 0, 1)),
+        ("gray", (1, 1, 1)),
+    ];
+    for (name, (r, g, b)) in bars {
+        _ = writeln!(res, "{name}:");
+        for i in 0..BAR_WIDTH {
+            let (r, g, b) = (t(i) * r, t(i) * g, t(i) * b);
+            res += &codes::bg!(r, g, b);
+            res.push(' ');
+        }
+        res += codes::RESET;
+        res.push('\n');
+    }
+
+    res += "\nHue:\n";
+    for row in 0..6 {
+        // Rows go from light to dark.
+        let light = 1. - row as f32 / 6.;
+        for i in 0..BAR_WIDTH {
+            let (r, g, b) = hue(i as f32 / BAR_WIDTH as f32, light);
+            res += &codes::bg!(r, g, b);
+            res.push(' ');
+        }
+        res += codes::RESET;
+        res.push('\n');
+    }
+
+    res
+}
+
+/// Gets color with the given hue (`0..1`) and lightness (`0..=1`) with full
+/// saturation.
+fn hue(h: f32, light: f32) -> (u8, u8, u8) {
+    let channel = |offset: f32| {
+        let d = ((h + offset).fract() * 6. - 3.).abs();
+        ((d - 1.).clamp(0., 1.) * light * 255.) as u8
+    };
+    (channel(0.), channel(2. / 3.), channel(1. / 3.))
+}
@@ -0,0 +1,234 @@
+//! Report about the terminal environment. Useful for bug reports of apps
+//! built with termal.
+//!
+//! # Examples
+//! ```no_run
+//! // Print the report so that the user can paste it into bug report.
+//! println!("{}", termal_core::diagnostics::report());
+//! ```
+
+use std::{env, fmt::Display};
+
+use crate::{
+    codes,
+    raw::{
+        events::{Status, StatusCode, TermAttr, TermFeatures, TermName},
+        raw_mode_guard,
+        request::verify_truecolor_on,
+        term_size, IoProvider, TermSize, Terminal,
+    },
+};
+
+/// Report about the terminal environment.
+///
+/// The report can be printed with [`Display`] or serialized to any format
+/// with [`Report::entries`].
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// Version of termal.
+    pub version: &'static str,
+    /// Operating system.
+    pub os: &'static str,
+    /// Value of the environment variable `TERM`.
+    pub term: Option<String>,
+    /// Value of the environment variable `COLORTERM`.
+    pub colorterm: Option<String>,
+    /// Value of the environment variable `TERM_PROGRAM`.
+    pub term_program: Option<String>,
+    /// Whether stdin is terminal.
+    pub in_terminal: bool,
+    /// Whether stdout is terminal.
+    pub out_terminal: bool,
+    /// Whether raw mode can be enabled.
+    pub raw_mode: bool,
+    /// Size of the terminal.
+    pub size: Option<TermSize>,
+    /// Name of the terminal reported by the terminal.
+    pub term_name: Option<TermName>,
+    /// Attributes reported by the terminal.
+    pub attributes: Option<TermAttr>,
+    /// Number of sixel color registers reported by the terminal.
+    pub sixel_colors: Option<usize>,
+    /// Whether the terminal kept RGB color when it was read back. See
+    /// [`crate::raw::request::verify_truecolor`].
+    pub truecolor_verified: Option<bool>,
+}
+
+/// Gathers report about the terminal environment. If stdin and stdout are
+/// terminals, this will enable raw mode and probe the terminal.
+pub fn report() -> Report {
+    let mut term = Terminal::stdio();
+    let mut res = Report::from_env();
+    res.in_terminal = term.is_in_terminal();
+    res.out_terminal = term.is_out_terminal();
+    res.size = term_size().ok();
+
+    let Ok(_guard) = raw_mode_guard() else {
+        return res;
+    };
+    res.raw_mode = true;
+
+    if res.in_terminal && res.out_terminal {
+        res.probe(&mut term);
+    }
+
+    res
+}
+
+impl Report {
+    /// Creates report with information that doesn't need to query the
+    /// terminal.
+    pub fn from_env() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            os: env::consts::OS,
+            term: env::var("TERM").ok(),
+            colorterm: env::var("COLORTERM").ok(),
+            term_program: env::var("TERM_PROGRAM").ok(),
+            ..Default::default()
+        }
+    }
+
+    /// Queries the terminal for information. The terminal should be in raw
+    /// mode.
+    pub fn probe<T: IoProvider>(&mut self, term: &mut Terminal<T>) {
+        let status = |term: &mut Terminal<T>, code, kind| {
+            term.request_status(code, kind).ok().flatten()
+        };
+
+        if let Some(Status::Attributes(a)) = status(
+            term,
+            codes::REQUEST_DEVICE_ATTRIBUTES,
+            StatusCode::Attributes,
+        ) {
+            self.attributes = Some(a);
+        }
+
+        if let Some(Status::TerminalName(n)) = status(
+            term,
+            codes::REQUEST_TERMINAL_NAME,
+            StatusCode::TerminalName,
+        ) {
+            self.term_name = Some(n);
+        }
+
+        let timeout = term.request_policy().timeout();
+        self.truecolor_verified =
+            verify_truecolor_on(term, timeout).ok().flatten();
+
+        if self.sixel() == Some(true) {
+            if let Some(Status::SixelColors(c)) = status(
+                term,
+                codes::REQUEST_SIXEL_COLORS,
+                StatusCode::SixelColors,
+            ) {
+                self.sixel_colors = Some(c);
+            }
+        }
+    }
+
+    /// Checks whether the terminal supports sixel graphics. `None` if the
+    /// terminal didn't report its attributes.
+    pub fn sixel(&self) -> Option<bool> {
+        self.attributes
+            .map(|a| a.features.contains(TermFeatures::SIXEL_GRAPHICS))
+    }
+
+    /// Checks whether the terminal looks like kitty (and so supports the
+    /// kitty protocols). This is based on `TERM` and the reported terminal
+    /// name.
+    pub fn kitty(&self) -> bool {
+        self.term.as_deref() == Some("xterm-kitty")
+            || self
+                .term_name
+                .as_ref()
+                .is_some_and(|n| n.name.eq_ignore_ascii_case("kitty"))
+    }
+
+    /// Checks whether the terminal looks like it supports desktop
+    /// notifications with [`codes::notify`]. This is based on `TERM`,
+    /// `TERM_PROGRAM` and the reported terminal name.
+    pub fn notifications(&self) -> bool {
+        const TERMS: &[&str] =
+            &["rxvt", "foot", "xterm-kitty", "xterm-ghostty"];
+        const NAMES: &[&str] =
+            &["iterm2", "wezterm", "kitty", "foot", "ghostty"];
+        const PROGRAMS: &[&str] = &["iTerm.app", "WezTerm", "ghostty"];
+
+        self.term
+            .as_deref()
+            .is_some_and(|t| TERMS.iter().any(|n| t.starts_with(n)))
+            || self
+                .term_program
+                .as_deref()
+                .is_some_and(|p| PROGRAMS.contains(&p))
+            || self.term_name.as_ref().is_some_and(|n| {
+                NAMES.iter().any(|t| n.name.eq_ignore_ascii_case(t))
+            })
+    }
+
+    /// Checks whether the terminal supports true color. This is based on
+    /// [`Report::truecolor_verified`] if known, otherwise on `COLORTERM`.
+    pub fn truecolor(&self) -> bool {
+        self.truecolor_verified.unwrap_or(matches!(
+            self.colorterm.as_deref(),
+            Some("truecolor" | "24bit")
+        ))
+    }
+
+    /// Gets the report as list of key value pairs. Missing values are
+    /// `unknown`.
+    pub fn entries(&self) -> Vec<(&'static str, String)> {
+        fn opt(v: Option<impl ToString>) -> String {
+            v.map_or_else(|| "unknown".into(), |v| v.to_string())
+        }
+
+        vec![
+            ("termal", self.version.into()),
+            ("os", self.os.into()),
+            ("TERM", opt(self.term.as_ref())),
+            ("COLORTERM", opt(self.colorterm.as_ref())),
+            ("TERM_PROGRAM", opt(self.term_program.as_ref())),
+            ("stdin terminal", self.in_terminal.to_string()),
+            ("stdout terminal", self.out_terminal.to_string()),
+            ("raw mode", self.raw_mode.to_string()),
+            (
+                "size",
+                opt(self.size.as_ref().map(|s| {
+                    format!(
+                        "{}x{} chars, {}x{} px",
+                        s.char_width,
+                        s.char_height,
+                        s.pixel_width,
+                        s.pixel_height
+                    )
+                })),
+            ),
+            ("terminal name", opt(self.term_name.as_ref())),
+            (
+                "attributes",
+                opt(self.attributes.map(|a| {
+                    format!(
+                        "{:?} with features {:#x}",
+                        a.typ,
+                        a.features.bits()
+                    )
+                })),
+            ),
+            ("truecolor", self.truecolor().to_string()),
+            ("sixel", opt(self.sixel())),
+            ("sixel colors", opt(self.sixel_colors)),
+            ("kitty", self.kitty().to_string()),
+            ("notifications", self.notifications().to_string()),
+        ]
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (k, v) in self.entries() {
+            writeln!(f, "{k}: {v}")?;
+        }
+        Ok(())
+    }
+}
@@ -1,24 +1,116 @@
-use thiserror::Error;
+use std::fmt::Display;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Error type of termal.
-#[derive(Debug, Error)]
+#[derive(Debug)]
 pub enum Error {
     /// Eof was reached on stdin.
-    #[error("End of file was reached on stdin")]
     StdInEof,
     /// The given action is not supported on this platform.
-    #[error("{0} is not supported on this platform.")]
     NotSupportedOnPlatform(&'static str),
     /// Failed to wait for stdin (on windows).
-    #[error("Failed to wait for stdin.")]
     WaitAbandoned,
-    #[error("Failed to parse rgb.")]
     InvalidRgbFormat,
+    /// The scroll region is empty or doesn't fit into the terminal.
+    InvalidScrollRegion {
+        top: usize,
+        bottom: usize,
+        height: usize,
+    },
+    /// Failed to parse key binding.
+    InvalidKeyBinding(String),
+    /// Terminfo entry for the terminal was not found.
+    TerminfoNotFound(String),
+    /// The terminfo entry has invalid format.
+    InvalidTerminfo,
+    /// The asciicast recording has invalid format.
+    InvalidCast,
+    /// Failed to parse color template. Contains the error message.
+    InvalidTemplate(String),
+    /// The operation requires the terminal to be in raw mode, but it isn't.
+    RawModeRequired,
+    /// The user interrupted the input (e.g. with `ctrl+c`).
+    Interrupted,
+    /// The user ended the input without entering anything (e.g. with
+    /// `ctrl+d`).
+    Eof,
+    /// The value has no equivalent in the type it is converted to (e.g.
+    /// when converting events to crossterm).
+    NoEquivalent,
+    /// There are no items to select from.
+    NothingToSelect,
     /// Any IO error.
-    #[error(transparent)]
-    Io(#[from] std::io::Error),
-    #[error(transparent)]
-    ParseInt(#[from] std::num::ParseIntError),
+    Io(std::io::Error),
+    ParseInt(std::num::ParseIntError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::StdInEof => f.write_str("End of file was reached on stdin"),
+            Error::NotSupportedOnPlatform(a) => {
+                write!(f, "{a} is not supported on this platform.")
+            }
+            Error::WaitAbandoned => f.write_str("Failed to wait for stdin."),
+            Error::InvalidRgbFormat => f.write_str("Failed to parse rgb."),
+            Error::InvalidScrollRegion {
+                top,
+                bottom,
+                height,
+            } => write!(
+                f,
+                "Invalid scroll region {top}..={bottom} in terminal with \
+                height {height}."
+            ),
+            Error::InvalidKeyBinding(b) => {
+                write!(f, "Invalid key binding `{b}`.")
+            }
+            Error::TerminfoNotFound(t) => {
+                write!(f, "Terminfo entry for terminal `{t}` was not found.")
+            }
+            Error::InvalidTerminfo => {
+                f.write_str("Terminfo entry has invalid format.")
+            }
+            Error::InvalidCast => {
+                f.write_str("The asciicast recording has invalid format.")
+            }
+            Error::InvalidTemplate(msg) => f.write_str(msg),
+            Error::RawModeRequired => {
+                f.write_str("The terminal must be in raw mode.")
+            }
+            Error::Interrupted => f.write_str("The input was interrupted."),
+            Error::Eof => f.write_str("The input was ended."),
+            Error::NoEquivalent => {
+                f.write_str("The value has no equivalent in the target type.")
+            }
+            Error::NothingToSelect => {
+                f.write_str("There are no items to select from.")
+            }
+            Error::Io(e) => e.fmt(f),
+            Error::ParseInt(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => e.source(),
+            Error::ParseInt(e) => e.source(),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<std::num::ParseIntError> for Error {
+    fn from(value: std::num::ParseIntError) -> Self {
+        Self::ParseInt(value)
+    }
 }
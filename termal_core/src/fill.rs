@@ -0,0 +1,159 @@
+//! Helpers for repeating text to the given width (e.g. for separators and
+//! borders).
+//!
+//! Widths are in terminal columns, so wide characters (e.g. CJK) take two
+//! columns. When the output is terminal, repetition of single character is
+//! done with [`crate::codes::repeat_char`] to minimize the number of bytes.
+//!
+//! # Examples
+//! ```rust
+//! use termal_core::fill::{write_fill_line, write_repeat_to_width};
+//!
+//! let mut s = String::new();
+//! write_fill_line(&mut s, "-", 5, false);
+//! assert_eq!(s, "-----");
+//!
+//! s.clear();
+//! write_fill_line(&mut s, "-", 20, true);
+//! assert_eq!(s, "-\x1b[19b");
+//!
+//! s.clear();
+//! write_fill_line(&mut s, "界", 5, false);
+//! assert_eq!(s, "界界 ");
+//!
+//! s.clear();
+//! write_repeat_to_width(&mut s, "ab", 5, true);
+//! assert_eq!(s, "ababa");
+//! ```
+
+use std::{
+    fmt::Display,
+    io::{stdout, IsTerminal},
+};
+
+use crate::codes;
+
+/// Gets the number of columns that the character takes in terminal.
+/// Control characters, combining characters and bidi control characters
+/// have zero width and wide
+/// characters (e.g. CJK and emoji) have width 2.
+pub fn char_width(c: char) -> usize {
+    match c as u32 {
+        0..=0x1f
+        | 0x7f..=0x9f
+        | 0x300..=0x36f
+        | 0x591..=0x5bd
+        | 0x5bf
+        | 0x5c1..=0x5c2
+        | 0x5c4..=0x5c5
+        | 0x5c7
+        | 0x610..=0x61a
+        | 0x64b..=0x65f
+        | 0x670
+        | 0x200b..=0x200f
+        | 0x202a..=0x202e
+        | 0x2066..=0x2069
+        | 0x20d0..=0x20ff
+        | 0xfe00..=0xfe0f => 0,
+        0x1100..=0x115f
+        | 0x2e80..=0x303e
+        | 0x3041..=0x33ff
+        | 0x3400..=0x4dbf
+        | 0x4e00..=0x9fff
+        | 0xa000..=0xa4cf
+        | 0xac00..=0xd7a3
+        | 0xf900..=0xfaff
+        | 0xfe30..=0xfe4f
+        | 0xff00..=0xff60
+        | 0xffe0..=0xffe6
+        | 0x1f300..=0x1f64f
+        | 0x1f900..=0x1f9ff
+        | 0x20000..=0x2fffd
+        | 0x30000..=0x3fffd => 2,
+        _ => 1,
+    }
+}
+
+/// Gets the number of columns that the string takes in terminal. The string
+/// shouldn't contain control sequences.
+pub fn str_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Appends `s` repeated so that it takes at most `width` columns. The last
+/// repetition may be partial. Returns the number of columns of the appended
+/// text which may be less than `width` if wide character doesn't fit.
+///
+/// If `rep` is `true`, [`crate::codes::repeat_char`] is used to repeat
+/// single character if it is shorter.
+pub fn write_repeat_to_width(
+    res: &mut String,
+    s: &str,
+    width: usize,
+    rep: bool,
+) -> usize {
+    let s_width = str_width(s);
+    if s_width == 0 || width == 0 {
+        return 0;
+    }
+
+    let cnt = width / s_width;
+    let mut chars = s.chars();
+    if rep
+        && cnt > 1
+        && matches!((chars.next(), chars.next()), (Some(_), None))
+    {
+        let code = codes::repeat_char!(cnt - 1);
+        if code.len() < s.len() * (cnt - 1) {
+            *res += s;
+            *res += &code;
+        } else {
+            *res += &s.repeat(cnt);
+        }
+    } else {
+        *res += &s.repeat(cnt);
+    }
+
+    let mut written = cnt * s_width;
+    for c in s.chars() {
+        let w = char_width(c);
+        if written + w > width {
+            break;
+        }
+        res.push(c);
+        written += w;
+    }
+
+    written
+}
+
+/// Appends `fill` repeated so that it takes exactly `width` columns. If wide
+/// character doesn't fit, the rest is filled with spaces.
+///
+/// If `rep` is `true`, [`crate::codes::repeat_char`] is used to repeat
+/// single character if it is shorter.
+pub fn write_fill_line(res: &mut String, fill: &str, width: usize, rep: bool) {
+    let written = write_repeat_to_width(res, fill, width, rep);
+    write_repeat_to_width(res, " ", width - written, rep);
+}
+
+/// Repeats `s` so that it takes at most `width` columns. The last repetition
+/// may be partial. Repeat code is used only if stdout is terminal.
+pub fn repeat_to_width(s: impl AsRef<str>, width: usize) -> String {
+    let mut res = String::new();
+    write_repeat_to_width(&mut res, s.as_ref(), width, stdout().is_terminal());
+    res
+}
+
+/// Repeats `fill` (char or string) so that it takes exactly `width`
+/// columns. Repeat code is used only if stdout is terminal.
+pub fn fill_line(fill: impl Display, width: usize) -> String {
+    let mut res = String::new();
+    write_fill_line(
+        &mut res,
+        &fill.to_string(),
+        width,
+        stdout().is_terminal(),
+    );
+    res
+}
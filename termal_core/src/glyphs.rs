@@ -0,0 +1,212 @@
+//! Sets of glyphs for drawing (e.g. box lines, bars and arrows) with
+//! fallbacks for terminals without Unicode.
+//!
+//! Components (e.g. `Select` and `ColumnPrinter`) get their glyphs from
+//! [`GlyphSet`]. The current set ([`glyphs`]) is selected by the locale and
+//! `TERM` when it is first used (see [`detect_glyphs`]), so that the output
+//! degrades to the [`GlyphSet::DEC`] line drawing characters or to plain
+//! [`GlyphSet::ASCII`] instead of printing mojibake. Each component may also
+//! have its own set.
+//!
+//! # Examples
+//! ```rust
+//! use termal_core::glyphs::{self, GlyphSet};
+//!
+//! glyphs::set_glyphs(GlyphSet::ASCII);
+//! let g = glyphs::glyphs();
+//! assert_eq!(
+//!     format!("{}{}{}", g.top_left, g.horizontal.repeat(3), g.top_right),
+//!     "+---+",
+//! );
+//! ```
+
+use std::sync::RwLock;
+
+/// The current glyph set. [`None`] if it wasn't detected or set yet.
+static GLYPHS: RwLock<Option<GlyphSet>> = RwLock::new(None);
+
+/// Wraps the character in the codes that switch to the DEC special graphics
+/// character set and back to ASCII.
+macro_rules! dec {
+    ($c:literal) => {
+        concat!("\x1b(0", $c, "\x1b(B")
+    };
+}
+
+/// Glyphs used by components for drawing. Each glyph except
+/// [`GlyphSet::ellipsis`] is single column wide, but it may contain control
+/// sequences (see [`GlyphSet::DEC`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlyphSet {
+    /// Horizontal line (e.g. `─`).
+    pub horizontal: &'static str,
+    /// Vertical line (e.g. `│`).
+    pub vertical: &'static str,
+    /// Top left corner (e.g. `┌`).
+    pub top_left: &'static str,
+    /// Top right corner (e.g. `┐`).
+    pub top_right: &'static str,
+    /// Bottom left corner (e.g. `└`).
+    pub bottom_left: &'static str,
+    /// Bottom right corner (e.g. `┘`).
+    pub bottom_right: &'static str,
+    /// Vertical line with branch to the right (e.g. `├`). Used also by
+    /// trees.
+    pub left_tee: &'static str,
+    /// Vertical line with branch to the left (e.g. `┤`).
+    pub right_tee: &'static str,
+    /// Horizontal line with branch down (e.g. `┬`).
+    pub top_tee: &'static str,
+    /// Horizontal line with branch up (e.g. `┴`).
+    pub bottom_tee: &'static str,
+    /// Crossing lines (e.g. `┼`).
+    pub cross: &'static str,
+    /// Filled part of progress bars and scrollbar thumb (e.g. `█`).
+    pub bar_filled: &'static str,
+    /// Empty part of progress bars and scrollbar track (e.g. `░`).
+    pub bar_empty: &'static str,
+    /// Frames of spinner.
+    pub spinner: &'static [&'static str],
+    /// Mark of truncated text (e.g. `…`). It may be wider than single
+    /// column (e.g. `...`).
+    pub ellipsis: &'static str,
+    /// Arrow up (e.g. `↑`).
+    pub arrow_up: &'static str,
+    /// Arrow down (e.g. `↓`).
+    pub arrow_down: &'static str,
+    /// Bullet of list items (e.g. `•`).
+    pub bullet: &'static str,
+}
+
+impl GlyphSet {
+    /// Unicode glyphs. This needs UTF-8 terminal.
+    pub const UNICODE: Self = Self {
+        horizontal: "─",
+        vertical: "│",
+        top_left: "┌",
+        top_right: "┐",
+        bottom_left: "└",
+        bottom_right: "┘",
+        left_tee: "├",
+        right_tee: "┤",
+        top_tee: "┬",
+        bottom_tee: "┴",
+        cross: "┼",
+        bar_filled: "█",
+        bar_empty: "░",
+        spinner: &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+        ellipsis: "…",
+        arrow_up: "↑",
+        arrow_down: "↓",
+        bullet: "•",
+    };
+
+    /// Plain ASCII glyphs (e.g. `+--+`). This works on any terminal.
+    pub const ASCII: Self = Self {
+        horizontal: "-",
+        vertical: "|",
+        top_left: "+",
+        top_right: "+",
+        bottom_left: "+",
+        bottom_right: "+",
+        left_tee: "+",
+        right_tee: "+",
+        top_tee: "+",
+        bottom_tee: "+",
+        cross: "+",
+        bar_filled: "#",
+        bar_empty: "-",
+        spinner: &["|", "/", "-", "\\"],
+        ellipsis: "...",
+        arrow_up: "^",
+        arrow_down: "v",
+        bullet: "*",
+    };
+
+    /// Line drawing characters from the DEC special graphics character set.
+    /// Each glyph switches to the character set and back to ASCII. It works
+    /// on VT100 compatible terminals even without UTF-8. Glyphs missing from
+    /// the character set are the same as in [`GlyphSet::ASCII`].
+    pub const DEC: Self = Self {
+        horizontal: dec!("q"),
+        vertical: dec!("x"),
+        top_left: dec!("l"),
+        top_right: dec!("k"),
+        bottom_left: dec!("m"),
+        bottom_right: dec!("j"),
+        left_tee: dec!("t"),
+        right_tee: dec!("u"),
+        top_tee: dec!("w"),
+        bottom_tee: dec!("v"),
+        cross: dec!("n"),
+        bar_filled: dec!("a"),
+        bar_empty: dec!("q"),
+        bullet: dec!("~"),
+        ..Self::ASCII
+    };
+
+    /// Selects the glyph set for terminal with the given locale (e.g. value
+    /// of `LANG`) and `TERM`:
+    /// - [`GlyphSet::UNICODE`] if the locale uses UTF-8.
+    /// - [`GlyphSet::DEC`] for known VT100 compatible terminals (e.g.
+    ///   `xterm`, `linux` or `screen`).
+    /// - [`GlyphSet::ASCII`] otherwise.
+    pub fn for_terminal(locale: Option<&str>, term: Option<&str>) -> Self {
+        if locale.is_some_and(is_utf8_locale) {
+            return Self::UNICODE;
+        }
+
+        const DEC_TERMS: [&str; 7] =
+            ["vt1", "vt2", "xterm", "linux", "screen", "tmux", "rxvt"];
+        match term {
+            Some(t) if DEC_TERMS.iter().any(|p| t.starts_with(p)) => Self::DEC,
+            _ => Self::ASCII,
+        }
+    }
+}
+
+impl Default for GlyphSet {
+    fn default() -> Self {
+        Self::UNICODE
+    }
+}
+
+/// Gets the current glyph set. It is detected with [`detect_glyphs`] if it
+/// wasn't set with [`set_glyphs`].
+pub fn glyphs() -> GlyphSet {
+    if let Some(g) = GLYPHS.read().ok().and_then(|g| *g) {
+        return g;
+    }
+    let g = detect_glyphs();
+    set_glyphs(g);
+    g
+}
+
+/// Sets the current glyph set.
+pub fn set_glyphs(glyphs: GlyphSet) {
+    if let Ok(mut g) = GLYPHS.write() {
+        *g = Some(glyphs);
+    }
+}
+
+/// Selects glyph set by the environment. The locale is taken from the first
+/// set of `LC_ALL`, `LC_CTYPE` and `LANG`. See [`GlyphSet::for_terminal`].
+///
+/// On windows, this is always [`GlyphSet::UNICODE`].
+pub fn detect_glyphs() -> GlyphSet {
+    if cfg!(windows) {
+        return GlyphSet::UNICODE;
+    }
+
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|v| std::env::var(v).ok())
+        .find(|v| !v.is_empty());
+    let term = std::env::var("TERM").ok();
+    GlyphSet::for_terminal(locale.as_deref(), term.as_deref())
+}
+
+fn is_utf8_locale(locale: &str) -> bool {
+    let locale = locale.to_ascii_lowercase();
+    locale.contains("utf-8") || locale.contains("utf8")
+}
@@ -0,0 +1,79 @@
+use crate::raw::TermSize;
+
+/// Size of character cell in pixels that is used if the terminal doesn't
+/// report its size in pixels.
+pub(crate) const DEFAULT_CELL_SIZE: (usize, usize) = (8, 16);
+
+/// How to fit image into the terminal. See [`fit_to_cells`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FitMode {
+    /// The image is as large as possible while it fits into the terminal.
+    #[default]
+    Contain,
+    /// Same as [`FitMode::Contain`], but images that already fit into the
+    /// terminal keep their size.
+    Shrink,
+    /// The image fills the width of the terminal.
+    Width,
+    /// The image fills the height of the terminal.
+    Height,
+}
+
+/// Gets the size in character cells `(width, height)` in which the image
+/// with the given size in pixels should be drawn so that it fits into the
+/// terminal with the given size. The aspect ratio of the image is preserved
+/// based on the size of the cells in pixels. If the terminal doesn't report
+/// its size in pixels (see [`TermSize::with_cell_size`]), cells are expected
+/// to be 8x16 pixels.
+///
+/// The result is at least `1x1` unless the image is empty.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::{image::{fit_to_cells, FitMode}, raw::TermSize};
+///
+/// let size = TermSize {
+///     char_width: 80,
+///     char_height: 24,
+///     pixel_width: 800,
+///     pixel_height: 480,
+/// };
+/// assert_eq!(fit_to_cells(1000, 1000, size, FitMode::Contain), (48, 24));
+/// assert_eq!(fit_to_cells(1000, 1000, size, FitMode::Width), (80, 40));
+/// assert_eq!(fit_to_cells(100, 100, size, FitMode::Shrink), (10, 5));
+/// ```
+pub fn fit_to_cells(
+    img_w_px: usize,
+    img_h_px: usize,
+    size: TermSize,
+    mode: FitMode,
+) -> (usize, usize) {
+    if img_w_px == 0 || img_h_px == 0 {
+        return (0, 0);
+    }
+
+    let (cw, ch) = size.cell_size().unwrap_or(DEFAULT_CELL_SIZE);
+    // Size of the image in cells if it is not scaled.
+    let w = img_w_px as f64 / cw.max(1) as f64;
+    let h = img_h_px as f64 / ch.max(1) as f64;
+    let tw = size.char_width as f64;
+    let th = size.char_height as f64;
+
+    let scale = match mode {
+        FitMode::Contain => (tw / w).min(th / h),
+        FitMode::Shrink => (tw / w).min(th / h).min(1.),
+        FitMode::Width => tw / w,
+        FitMode::Height => th / h,
+    };
+
+    let fw = ((w * scale).round() as usize).max(1);
+    let fh = ((h * scale).round() as usize).max(1);
+    match mode {
+        FitMode::Contain | FitMode::Shrink => (
+            fw.min(size.char_width.max(1)),
+            fh.min(size.char_height.max(1)),
+        ),
+        FitMode::Width => (size.char_width.max(1), fh),
+        FitMode::Height => (fw, size.char_height.max(1)),
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "raw")]
+mod fit;
 mod img_nearest;
 mod mat;
 mod raw_img;
@@ -5,8 +7,14 @@ mod rect;
 mod sixel;
 mod texel;
 
+use std::ops::Range;
+
 use crate::Rgb;
 
+#[cfg(all(feature = "raw", feature = "events"))]
+pub(crate) use self::fit::DEFAULT_CELL_SIZE;
+#[cfg(feature = "raw")]
+pub use self::fit::*;
 pub use self::{
     img_nearest::*, mat::*, raw_img::*, rect::*, sixel::*, texel::*,
 };
@@ -40,6 +48,39 @@ pub trait Image {
     }
 }
 
+/// Appends rows generated by `f` to `out` in order. `f` appends the row with
+/// the given index to the string.
+fn push_rows(
+    out: &mut String,
+    rows: Range<usize>,
+    f: impl Fn(usize, &mut String),
+) {
+    for y in rows {
+        f(y, out);
+    }
+}
+
+/// Appends rows generated by `f` to `out` in order. `f` appends the row with
+/// the given index to the string. The rows are generated in parallel.
+#[cfg(feature = "rayon")]
+fn push_rows_par(
+    out: &mut String,
+    rows: Range<usize>,
+    f: impl Fn(usize, &mut String) + Sync,
+) {
+    use rayon::prelude::*;
+
+    let rows: Vec<_> = rows
+        .into_par_iter()
+        .map(|y| {
+            let mut row = String::new();
+            f(y, &mut row);
+            row
+        })
+        .collect();
+    out.extend(rows);
+}
+
 #[cfg(feature = "image")]
 impl<T: image::GenericImage> Image for T
 where
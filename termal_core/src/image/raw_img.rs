@@ -1,23 +1,130 @@
 use super::{Image, Rgb};
 
-/// Image with owned raw RGB data.
-pub struct RawImg {
-    data: Vec<u8>,
+/// Layout of the channels of single pixel in [`RawImg`]. Each channel is one
+/// byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PixelFormat {
+    /// Red, green and blue.
+    #[default]
+    Rgb,
+    /// Red, green, blue and alpha. Alpha is ignored.
+    Rgba,
+    /// Blue, green and red.
+    Bgr,
+    /// Blue, green, red and alpha. Alpha is ignored.
+    Bgra,
+    /// Single grayscale channel.
+    Gray,
+}
+
+/// Image with raw pixel data. The data may be owned (e.g. `Vec<u8>`) or
+/// borrowed (e.g. slice of framebuffer, see [`RawImg::new`] and
+/// [`RawImg::with_stride`]). The channels are mapped to RGB when reading the
+/// pixels, so the data is never converted or copied.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::{image::{Image, PixelFormat, RawImg}, Rgb};
+///
+/// // 2x2 BGRA image where each row is padded to 12 bytes.
+/// let data = [
+///     1, 2, 3, 255, 4, 5, 6, 255, 0, 0, 0, 0,
+///     7, 8, 9, 255, 10, 11, 12, 255,
+/// ];
+/// let img = RawImg::with_stride(&data[..], 2, 2, 12, PixelFormat::Bgra);
+/// assert_eq!(img.get_pixel(1, 0), Rgb::new(6, 5, 4));
+/// assert_eq!(img.get_pixel(0, 1), Rgb::new(9, 8, 7));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RawImg<D = Vec<u8>> {
+    data: D,
     width: usize,
     height: usize,
+    stride: usize,
+    format: PixelFormat,
 }
 
-impl RawImg {
-    /// Create raw image from owned raw rgb data.
+impl PixelFormat {
+    /// Gets the number of bytes of single pixel.
+    pub fn bytes_per_pixel(&self) -> usize {
+        match self {
+            Self::Rgb | Self::Bgr => 3,
+            Self::Rgba | Self::Bgra => 4,
+            Self::Gray => 1,
+        }
+    }
+
+    /// Reads the color of pixel from its data.
+    fn get_rgb(&self, p: &[u8]) -> Rgb {
+        match self {
+            Self::Rgb | Self::Rgba => Rgb::new(p[0], p[1], p[2]),
+            Self::Bgr | Self::Bgra => Rgb::new(p[2], p[1], p[0]),
+            Self::Gray => Rgb::new(p[0], p[0], p[0]),
+        }
+    }
+}
+
+impl<D: AsRef<[u8]>> RawImg<D> {
+    /// Create raw image from tightly packed data with the given pixel
+    /// format.
     ///
     /// # Panic
     /// - If the data size doesn't match the width and size.
-    pub fn from_rgb(data: Vec<u8>, width: usize, height: usize) -> Self {
-        if width * height * 3 != data.len() {
+    pub fn new(
+        data: D,
+        width: usize,
+        height: usize,
+        format: PixelFormat,
+    ) -> Self {
+        let len = width * height * format.bytes_per_pixel();
+        if len != data.as_ref().len() {
             panic!(
                 "Invalid raw image data length of {} for \
-                [{width}, {height}]({})",
-                data.len(),
+                [{width}, {height}]({}) with {format:?}",
+                data.as_ref().len(),
+                width * height
+            );
+        }
+        Self::with_stride(
+            data,
+            width,
+            height,
+            width * format.bytes_per_pixel(),
+            format,
+        )
+    }
+
+    /// Create raw image from data where each row starts `stride` bytes after
+    /// the start of the previous row (the rows may be padded). The padding
+    /// after the last row may be missing.
+    ///
+    /// # Panic
+    /// - If the stride is smaller than the row.
+    /// - If the data is too short for the width, height and stride.
+    pub fn with_stride(
+        data: D,
+        width: usize,
+        height: usize,
+        stride: usize,
+        format: PixelFormat,
+    ) -> Self {
+        let row = width * format.bytes_per_pixel();
+        if stride < row {
+            panic!(
+                "Raw image stride {stride} is smaller than row of {row} \
+                bytes"
+            );
+        }
+        let len = if height == 0 {
+            0
+        } else {
+            stride * (height - 1) + row
+        };
+        if data.as_ref().len() < len {
+            panic!(
+                "Invalid raw image data length of {} for \
+                [{width}, {height}]({}) with stride {stride}",
+                data.as_ref().len(),
                 width * height
             );
         }
@@ -25,11 +132,75 @@ impl RawImg {
             data,
             width,
             height,
+            stride,
+            format,
         }
     }
+
+    /// Gets the pixel format of the data.
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    /// Gets the number of bytes between starts of two rows.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Gets the raw data of the image.
+    pub fn data(&self) -> &D {
+        &self.data
+    }
+
+    /// Gets the raw data of the image.
+    pub fn into_data(self) -> D {
+        self.data
+    }
+}
+
+impl RawImg {
+    /// Create raw image from raw rgb data.
+    ///
+    /// # Panic
+    /// - If the data size doesn't match the width and size.
+    pub fn from_rgb(data: Vec<u8>, width: usize, height: usize) -> Self {
+        Self::new(data, width, height, PixelFormat::Rgb)
+    }
+
+    /// Create raw image from raw rgba data. Alpha is ignored.
+    ///
+    /// # Panic
+    /// - If the data size doesn't match the width and size.
+    pub fn from_rgba(data: Vec<u8>, width: usize, height: usize) -> Self {
+        Self::new(data, width, height, PixelFormat::Rgba)
+    }
+
+    /// Create raw image from raw bgr data.
+    ///
+    /// # Panic
+    /// - If the data size doesn't match the width and size.
+    pub fn from_bgr(data: Vec<u8>, width: usize, height: usize) -> Self {
+        Self::new(data, width, height, PixelFormat::Bgr)
+    }
+
+    /// Create raw image from raw bgra data. Alpha is ignored.
+    ///
+    /// # Panic
+    /// - If the data size doesn't match the width and size.
+    pub fn from_bgra(data: Vec<u8>, width: usize, height: usize) -> Self {
+        Self::new(data, width, height, PixelFormat::Bgra)
+    }
+
+    /// Create raw image from raw grayscale data with one byte per pixel.
+    ///
+    /// # Panic
+    /// - If the data size doesn't match the width and size.
+    pub fn from_gray(data: Vec<u8>, width: usize, height: usize) -> Self {
+        Self::new(data, width, height, PixelFormat::Gray)
+    }
 }
 
-impl Image for RawImg {
+impl<D: AsRef<[u8]>> Image for RawImg<D> {
     fn width(&self) -> usize {
         self.width
     }
@@ -39,7 +210,7 @@ impl Image for RawImg {
     }
 
     fn get_pixel(&self, x: usize, y: usize) -> Rgb {
-        let pos = (self.width * y + x) * 3;
-        (self.data[pos], self.data[pos + 1], self.data[pos + 2]).into()
+        let pos = self.stride * y + x * self.format.bytes_per_pixel();
+        self.format.get_rgb(&self.data.as_ref()[pos..])
     }
 }
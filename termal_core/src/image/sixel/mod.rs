@@ -2,6 +2,8 @@ mod sixel_state;
 
 use sixel_state::SixelState;
 
+use crate::codes;
+
 use super::Image;
 
 #[derive(Default)]
@@ -31,7 +33,29 @@ impl Sixel {
 }
 
 /// Generate sixel image and append it to the string `out`.
+///
+/// Not all terminals support sixel. Use
+/// [`crate::raw::request::supports_sixel`] to detect the support. The image
+/// is wrapped with [`codes::passthrough`] if the output goes through
+/// terminal multiplexer.
 pub fn push_sixel(out: &mut String, img: &impl Image) {
-    let mut state = SixelState::new(img, out);
-    state.encode();
+    push_passthrough(out, |out| SixelState::new(img, out).encode());
+}
+
+/// Same as [`push_sixel`], but the lines of the image are encoded in
+/// parallel.
+#[cfg(feature = "rayon")]
+pub fn push_sixel_par(out: &mut String, img: &(impl Image + Sync)) {
+    push_passthrough(out, |out| SixelState::new(img, out).encode_par());
+}
+
+/// Appends the output of `f` to `out` and wraps it with
+/// [`codes::passthrough`] if the output goes through terminal multiplexer.
+fn push_passthrough(out: &mut String, f: impl FnOnce(&mut String)) {
+    let start = out.len();
+    f(out);
+    if codes::multiplexer() != codes::Multiplexer::None {
+        let img = out.split_off(start);
+        *out += &codes::passthrough(&img);
+    }
 }
@@ -1,6 +1,12 @@
 use std::collections::BTreeSet;
 
-use crate::{image::Image, Rgb};
+use crate::{
+    image::{push_rows, Image},
+    Rgb,
+};
+
+#[cfg(feature = "rayon")]
+use crate::image::push_rows_par;
 
 use super::Sixel;
 
@@ -9,7 +15,6 @@ pub(super) struct SixelState<'a, I>
 where
     I: Image,
 {
-    line: Vec<Sixel>,
     img: &'a I,
     out: &'a mut String,
 }
@@ -21,32 +26,28 @@ where
     /// Create new sixel state. Output will be appended to `out`. To actually
     /// generate the sixel data, call `encode`.
     pub fn new(img: &'a I, out: &'a mut String) -> Self {
-        Self {
-            line: Vec::with_capacity(img.width()),
-            img,
-            out,
-        }
+        Self { img, out }
     }
 
     /// Generate the sixel data and append it to the output.
     pub fn encode(&mut self) {
-        *self.out += "\x1bPq";
+        self.begin();
 
-        self.define_colors();
+        let img = self.img;
+        push_rows(self.out, 0..img.height() / 6, |y, out| {
+            draw_line(&get_line(img, y), out)
+        });
 
-        for y in 0..(self.img.height() / 6) {
-            self.get_line(y);
-            self.draw_line();
-        }
+        self.end();
+    }
 
-        *self.out += "\x1b\\";
+    fn begin(&mut self) {
+        *self.out += "\x1bPq";
+        self.define_colors();
     }
 
-    fn get_line(&mut self, y: usize) {
-        self.line.clear();
-        for x in 0..self.img.width() {
-            self.line.push(Sixel::from_img(self.img, (x, y * 6)));
-        }
+    fn end(&mut self) {
+        *self.out += "\x1b\\";
     }
 
     fn define_colors(&mut self) {
@@ -55,21 +56,47 @@ where
             *self.out += &format!("#{i};2;{r};{g};{b}");
         }
     }
+}
 
-    fn draw_line(&mut self) {
-        let mut line_colors = BTreeSet::new();
-        for sx in &self.line {
-            line_colors.extend(sx.0);
-        }
+#[cfg(feature = "rayon")]
+impl<I> SixelState<'_, I>
+where
+    I: Image + Sync,
+{
+    /// Generate the sixel data and append it to the output. The lines are
+    /// encoded in parallel.
+    pub fn encode_par(&mut self) {
+        self.begin();
 
-        for c in line_colors {
-            *self.out += &format!("#{c}");
-            for sx in &self.line {
-                self.out.push(sx.color_char(c));
-            }
-            self.out.push('$');
-        }
+        let img = self.img;
+        push_rows_par(self.out, 0..img.height() / 6, |y, out| {
+            draw_line(&get_line(img, y), out)
+        });
+
+        self.end();
+    }
+}
 
-        self.out.push('-');
+/// Gets the sixels of line of the image. `y` is index of the line.
+fn get_line(img: &impl Image, y: usize) -> Vec<Sixel> {
+    (0..img.width())
+        .map(|x| Sixel::from_img(img, (x, y * 6)))
+        .collect()
+}
+
+fn draw_line(line: &[Sixel], out: &mut String) {
+    let mut line_colors = BTreeSet::new();
+    for sx in line {
+        line_colors.extend(sx.0);
+    }
+
+    for c in line_colors {
+        *out += &format!("#{c}");
+        for sx in line {
+            out.push(sx.color_char(c));
+        }
+        out.push('$');
     }
+
+    out.push('-');
 }
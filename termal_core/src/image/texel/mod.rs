@@ -1,7 +1,7 @@
 use texel_state::TexelState;
 
 use crate::{
-    codes::{bg, fg},
+    codes::{self, bg, fg},
     Rgb,
 };
 
@@ -30,7 +30,8 @@ impl Texel {
 /// is specified, it is as if `w` was `80`.
 ///
 /// When calculating `w` or `h` it is expected that each character is twice as
-/// tall as wide.
+/// tall as wide. Use [`super::fit_to_cells`] to get the size based on the
+/// actual size of the terminal.
 pub fn push_texel_half(
     img: &impl Image,
     res: &mut String,
@@ -39,8 +40,20 @@ pub fn push_texel_half(
     h: Option<usize>,
 ) {
     let (w, h) = get_wh(img, w, h);
-    let mut state = TexelState::new(img, w, h);
-    state.append_half(res, nl);
+    TexelState::new(img, w, h).append(res, nl, TexelState::get_half_texel);
+}
+
+/// Same as [`push_texel_half`], but the lines are encoded in parallel.
+#[cfg(feature = "rayon")]
+pub fn push_texel_half_par(
+    img: &(impl Image + Sync),
+    res: &mut String,
+    nl: &str,
+    w: Option<usize>,
+    h: Option<usize>,
+) {
+    let (w, h) = get_wh(img, w, h);
+    TexelState::new(img, w, h).append_par(res, nl, TexelState::get_half_texel);
 }
 
 /// Append image `img` from quater block characters (`▄`, `▖`, `▗`, `▘`, `▝`,
@@ -50,7 +63,8 @@ pub fn push_texel_half(
 /// `80`.
 ///
 /// When calculating `w` or `h` it is expected that each character is twice as
-/// tall as wide.
+/// tall as wide. Use [`super::fit_to_cells`] to get the size based on the
+/// actual size of the terminal.
 pub fn push_texel_quater(
     img: &impl Image,
     res: &mut String,
@@ -59,8 +73,103 @@ pub fn push_texel_quater(
     h: Option<usize>,
 ) {
     let (w, h) = get_wh(img, w, h);
-    let mut state = TexelState::new(img, w, h);
-    state.append_quater(res, nl);
+    TexelState::new(img, w, h).append(res, nl, TexelState::get_quater_texel);
+}
+
+/// Same as [`push_texel_quater`], but the lines are encoded in parallel.
+#[cfg(feature = "rayon")]
+pub fn push_texel_quater_par(
+    img: &(impl Image + Sync),
+    res: &mut String,
+    nl: &str,
+    w: Option<usize>,
+    h: Option<usize>,
+) {
+    let (w, h) = get_wh(img, w, h);
+    TexelState::new(img, w, h).append_par(
+        res,
+        nl,
+        TexelState::get_quater_texel,
+    );
+}
+
+/// Append image `img` as thumbnail from half block characters (`▄`) to the
+/// buffer `res`. The thumbnail fits into box of `w` x `h` characters with
+/// the top left corner at `x`, `y` (as in [`crate::codes::move_to`]). The
+/// image keeps its aspect ratio and it is centered in the box.
+///
+/// Each line of the thumbnail is positioned with absolute cursor movement,
+/// so it may be drawn beside other content (e.g. as preview of item in
+/// menu). Nothing is appended if the image or the box is empty.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::{codes, image::{push_texel_thumbnail, RawImg}};
+///
+/// let img = RawImg::from_rgb(vec![255; 2 * 2 * 3], 2, 2);
+/// let mut res = String::new();
+/// push_texel_thumbnail(&img, &mut res, 10, 5, 4, 1);
+///
+/// assert!(res.starts_with(codes::move_to!(11, 5)));
+/// assert_eq!(res.matches('▄').count(), 2);
+/// assert!(res.ends_with(codes::RESET));
+/// ```
+pub fn push_texel_thumbnail(
+    img: &impl Image,
+    res: &mut String,
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize,
+) {
+    push_thumbnail(img, res, (x, y, w, h), |state, res| {
+        state.append(res, "\n", TexelState::get_half_texel)
+    });
+}
+
+/// Same as [`push_texel_thumbnail`], but the lines are encoded in parallel.
+#[cfg(feature = "rayon")]
+pub fn push_texel_thumbnail_par(
+    img: &(impl Image + Sync),
+    res: &mut String,
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize,
+) {
+    push_thumbnail(img, res, (x, y, w, h), |state, res| {
+        state.append_par(res, "\n", TexelState::get_half_texel)
+    });
+}
+
+/// Appends thumbnail of the image to `res`. The lines of the image are
+/// generated with `draw`.
+fn push_thumbnail<I: Image>(
+    img: &I,
+    res: &mut String,
+    (x, y, w, h): (usize, usize, usize, usize),
+    draw: impl FnOnce(&TexelState<'_, I>, &mut String),
+) {
+    if img.width() == 0 || img.height() == 0 || w == 0 || h == 0 {
+        return;
+    }
+
+    let (mut tw, mut th) = get_wh(img, Some(w), None);
+    if th > h {
+        (tw, th) = get_wh(img, None, Some(h));
+    }
+    let (tw, th) = (tw.clamp(1, w), th.clamp(1, h));
+
+    let mut img_str = String::new();
+    draw(&TexelState::new(img, tw, th), &mut img_str);
+
+    let x = x + (w - tw) / 2;
+    let y = y + (h - th) / 2;
+    for (i, line) in img_str.split('\n').enumerate() {
+        *res += &codes::move_to!(x, y + i);
+        *res += line;
+    }
+    *res += codes::RESET;
 }
 
 fn get_wh(
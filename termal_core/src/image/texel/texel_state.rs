@@ -1,9 +1,12 @@
 use crate::{
     codes,
-    image::{Image, Rect},
+    image::{push_rows, Image, Rect},
     Rgb,
 };
 
+#[cfg(feature = "rayon")]
+use crate::image::push_rows_par;
+
 use super::Texel;
 
 /// State when generating texel image.
@@ -35,36 +38,38 @@ where
         }
     }
 
-    /// Append texel image with half chars to the string `res`.
-    pub fn append_half(&mut self, res: &mut String, nl: &str) {
-        self.append(res, nl, Self::get_half_texel);
-    }
-
-    /// Append texel image with quater chars to the string `res`.
-    pub fn append_quater(&mut self, res: &mut String, nl: &str) {
-        self.append(res, nl, Self::get_quater_texel);
+    /// Append texel image to the string `res`. Texels are generated with
+    /// `get_texel` (e.g. [`Self::get_half_texel`]).
+    pub fn append(
+        &self,
+        res: &mut String,
+        nl: &str,
+        get_texel: fn(&Self, usize, usize) -> Texel,
+    ) {
+        push_rows(res, 0..self.h, |y, res| {
+            self.append_row(res, nl, y, get_texel)
+        });
     }
 
-    fn append(
-        &mut self,
+    /// Append single row of the texel image to the string `res`.
+    fn append_row(
+        &self,
         res: &mut String,
         nl: &str,
-        get_texel: impl Fn(&Self, usize, usize) -> Texel,
+        y: usize,
+        get_texel: fn(&Self, usize, usize) -> Texel,
     ) {
-        for y in 0..self.h - 1 {
-            for x in 0..self.w {
-                get_texel(self, x, y).append_to(res);
-            }
+        for x in 0..self.w {
+            get_texel(self, x, y).append_to(res);
+        }
+        if y + 1 != self.h {
             *res += codes::RESET;
             *res += nl;
         }
-
-        for x in 0..self.w {
-            get_texel(self, x, self.h - 1).append_to(res);
-        }
     }
 
-    fn get_half_texel(&self, x: usize, y: usize) -> Texel {
+    /// Gets texel from half block characters.
+    pub fn get_half_texel(&self, x: usize, y: usize) -> Texel {
         let x = x as f32 * self.texw;
         let y = y as f32 * self.texh;
         let half = self.texh / 2.;
@@ -82,7 +87,8 @@ where
         }
     }
 
-    fn get_quater_texel(&self, x: usize, y: usize) -> Texel {
+    /// Gets texel from quater block characters.
+    pub fn get_quater_texel(&self, x: usize, y: usize) -> Texel {
         let chrs = [
             ('▄', [0, 0, 1, 1]),
             ('▖', [0, 0, 1, 0]),
@@ -146,3 +152,21 @@ where
         )
     }
 }
+
+#[cfg(feature = "rayon")]
+impl<I> TexelState<'_, I>
+where
+    I: Image + Sync,
+{
+    /// Same as [`Self::append`], but the rows are generated in parallel.
+    pub fn append_par(
+        &self,
+        res: &mut String,
+        nl: &str,
+        get_texel: fn(&Self, usize, usize) -> Texel,
+    ) {
+        push_rows_par(res, 0..self.h, |y, res| {
+            self.append_row(res, nl, y, get_texel)
+        });
+    }
+}
@@ -1,23 +1,54 @@
 //! Core library of termal, contains the implementation.
+mod base64;
+mod color_choice;
+mod output;
+mod prompt_marker;
 mod rgb;
+mod style;
+mod styled_writer;
+mod template;
 
 use std::{
     io::{self, Write},
     panic,
 };
 
-pub use rgb::*;
+pub use self::{
+    color_choice::*, output::*, prompt_marker::*, rgb::*, style::*,
+    styled_writer::*, template::*,
+};
 
 pub mod codes;
+#[cfg(feature = "term_text")]
+pub mod columns;
+pub mod demo;
+#[cfg(feature = "events")]
+pub mod diagnostics;
 pub mod error;
+pub mod fill;
+pub mod glyphs;
 #[cfg(feature = "term_image")]
 pub mod image;
+pub mod live;
 #[cfg(feature = "proc")]
 pub mod proc;
 #[cfg(feature = "raw")]
 pub mod raw;
+pub mod redraw;
 #[cfg(feature = "term_text")]
 pub mod term_text;
+#[cfg(feature = "term_text")]
+pub use term_text::{sanitize, strip_unsafe, Sanitized};
+#[cfg(feature = "terminfo")]
+pub mod terminfo;
+pub mod theme;
+#[cfg(feature = "term_text")]
+pub mod wrap;
+
+/// Reexport of crossterm with the version that termal converts events to and
+/// from.
+#[cfg(feature = "crossterm")]
+pub use crossterm;
 
 /// Appends linear gradient to the given string
 pub fn write_gradient(
@@ -66,6 +97,11 @@ pub fn reset_terminal() {
     if raw::is_raw_mode_enabled() {
         _ = raw::disable_raw_mode();
     }
+    let scroll = if codes::supports_cursor_save() {
+        [codes::CUR_SAVE, codes::RESET_SCROLL_REGION, codes::CUR_LOAD].concat()
+    } else {
+        reset_scroll_region_no_save()
+    };
     let s = [
         codes::RESET,
         codes::SHOW_CURSOR,
@@ -78,9 +114,8 @@ pub fn reset_terminal() {
         codes::DISABLE_MOUSE_XY_DRAG_TRACKING,
         codes::DISABLE_MOUSE_XY_ALL_TRACKING,
         codes::DISABLE_FOCUS_EVENT,
-        codes::CUR_SAVE,
-        codes::RESET_SCROLL_REGION,
-        codes::CUR_LOAD,
+        codes::DISABLE_ALTERNATE_SCROLL,
+        &scroll,
         codes::DISABLE_ALTERNATIVE_BUFFER,
         codes::DISABLE_REVERSE_COLOR,
         codes::DISABLE_BRACKETED_PASTE_MODE,
@@ -88,10 +123,28 @@ pub fn reset_terminal() {
         codes::RESET_DEFAULT_FG_COLOR,
         codes::RESET_DEFAULT_BG_COLOR,
         codes::RESET_CURSOR_COLOR,
+        codes::RESET_CURSOR_STYLE,
+        codes::DISABLE_8BIT_RESPONSES,
     ]
     .concat();
     print!("{}", s);
     _ = io::stdout().flush();
+    #[cfg(feature = "raw")]
+    raw::track_modes(&s);
+}
+
+/// Resets the scroll region on terminal that doesn't support cursor save.
+/// Resetting the scroll region moves the cursor to the top left and its
+/// position can't be restored, so the cursor is moved to the last line
+/// instead. If the terminal size is not known, the scroll region is not
+/// reset.
+fn reset_scroll_region_no_save() -> String {
+    #[cfg(feature = "raw")]
+    if let Ok(size) = raw::term_size() {
+        return codes::RESET_SCROLL_REGION.to_string()
+            + &codes::move_to!(1, size.char_height);
+    }
+    String::new()
 }
 
 /// Registers panic hook that will prepend terminal reset before the current
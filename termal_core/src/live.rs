@@ -0,0 +1,194 @@
+//! Dynamic content at the bottom of the terminal (e.g. progress bars and
+//! status) below scrolling log lines.
+//!
+//! [`LiveRegion`] reserves the bottom lines of the terminal. The rest of the
+//! terminal is set as scroll region, so the lines logged with
+//! [`LiveRegion::log`] scroll above the live lines and don't overwrite them.
+//! The live lines are drawn at fixed positions with the cursor saved and
+//! restored around them, so the logging continues where it was. On terminals
+//! without cursor save, the cursor is moved back to the last logged line
+//! (see [`LiveRegion::set_cursor_save`]).
+//!
+//! The live lines are not wrapped, so text that doesn't fit is cut at the
+//! edge of the terminal. When the region is finished (or dropped), the
+//! scroll region is reset and the cursor is moved below the live lines.
+//!
+//! # Examples
+//! ```rust
+//! use termal_core::live::LiveRegion;
+//!
+//! let mut out = vec![];
+//! let mut live = LiveRegion::new(&mut out, 1, 24)?;
+//! for i in 1..=3 {
+//!     live.log(format!("compiled file {i}"))?;
+//!     live.set_line(0, format!("compiled {i}/3"));
+//!     live.draw()?;
+//! }
+//! live.finish()?;
+//! # Ok::<_, termal_core::error::Error>(())
+//! ```
+
+use std::io::Write;
+
+use crate::{codes, error::Result};
+
+/// Disables automatic wrapping at the end of line (DECAWM).
+const DISABLE_AUTOWRAP: &str = crate::disable!(7);
+/// Enables automatic wrapping at the end of line (DECAWM).
+const ENABLE_AUTOWRAP: &str = crate::enable!(7);
+
+/// Lines at the bottom of the terminal with dynamic content. See the
+/// [module documentation](self).
+#[derive(Debug)]
+pub struct LiveRegion<W: Write> {
+    out: W,
+    lines: Vec<String>,
+    height: usize,
+    dirty: bool,
+    finished: bool,
+    cursor_save: bool,
+}
+
+impl<W: Write> LiveRegion<W> {
+    /// Reserves `lines` lines at the bottom of terminal with the given
+    /// `height` for the live content. Logged lines start at the bottom of the
+    /// rest of the terminal.
+    ///
+    /// Fails if there would be no space for the logged lines.
+    pub fn new(mut out: W, lines: usize, height: usize) -> Result<Self> {
+        let bottom = height.saturating_sub(lines);
+        let scroll = codes::checked_scroll_region(1, bottom, height)?;
+        // Make space for the live lines, so that the current content isn't
+        // overwritten.
+        out.write_all("\n".repeat(lines).as_bytes())?;
+        out.write_all(scroll.as_bytes())?;
+        out.write_all(codes::move_to!(1, bottom).as_bytes())?;
+        out.flush()?;
+        Ok(Self {
+            out,
+            lines: vec![String::new(); lines],
+            height,
+            dirty: true,
+            finished: false,
+            cursor_save: codes::supports_cursor_save(),
+        })
+    }
+
+    /// Gets the number of the live lines.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Checks if there are no live lines.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Gets the content of the live line. Returns [`None`] if there is no
+    /// such line.
+    pub fn line(&self, idx: usize) -> Option<&str> {
+        self.lines.get(idx).map(|l| l.as_str())
+    }
+
+    /// Sets the content of the live line. The change is shown with
+    /// [`LiveRegion::draw`]. Lines out of the region are ignored.
+    pub fn set_line(&mut self, idx: usize, s: impl Into<String>) {
+        if let Some(l) = self.lines.get_mut(idx) {
+            *l = s.into();
+            self.dirty = true;
+        }
+    }
+
+    /// Sets whether the cursor is saved with [`codes::CUR_SAVE`] when the
+    /// live lines are drawn. If not, the cursor is moved back to the start of
+    /// the last logged line. That is enough because logged lines start with
+    /// newline. The default is [`codes::supports_cursor_save`].
+    pub fn set_cursor_save(&mut self, v: bool) {
+        self.cursor_save = v;
+    }
+
+    /// Checks whether the live lines changed since they were last drawn.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Prints line above the live lines. It scrolls together with the
+    /// previous logged lines. The line shouldn't contain newlines.
+    pub fn log(&mut self, s: impl AsRef<str>) -> Result<()> {
+        self.out.write_all(b"\r\n")?;
+        self.out.write_all(s.as_ref().as_bytes())?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    /// Draws the live lines.
+    pub fn draw(&mut self) -> Result<()> {
+        let mut buf = String::new();
+        if self.cursor_save {
+            buf += codes::CUR_SAVE;
+        }
+        buf += DISABLE_AUTOWRAP;
+        let top = self.height - self.lines.len() + 1;
+        for (i, l) in self.lines.iter().enumerate() {
+            buf += &codes::move_to!(1, top + i);
+            buf += codes::ERASE_LINE;
+            buf += l;
+            buf += codes::RESET;
+        }
+        buf += ENABLE_AUTOWRAP;
+        if self.cursor_save {
+            buf += codes::CUR_LOAD;
+        } else {
+            buf += &codes::move_to!(1, top - 1);
+        }
+        self.out.write_all(buf.as_bytes())?;
+        self.out.flush()?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Draws the live lines only if they changed since they were last
+    /// drawn.
+    pub fn draw_if_dirty(&mut self) -> Result<()> {
+        if self.dirty {
+            self.draw()?;
+        }
+        Ok(())
+    }
+
+    /// Resets the scroll region and moves the cursor below the live lines.
+    /// The live lines stay visible.
+    pub fn finish(mut self) -> Result<()> {
+        self.reset()
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+        // Resetting the scroll region moves the cursor to the top left.
+        self.out.write_all(codes::RESET_SCROLL_REGION.as_bytes())?;
+        self.out
+            .write_all(codes::move_to!(1, self.height).as_bytes())?;
+        self.out.write_all(b"\r\n")?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "raw")]
+impl LiveRegion<std::io::Stdout> {
+    /// Reserves `lines` lines at the bottom of the terminal on stdout. See
+    /// [`LiveRegion::new`].
+    pub fn stdout(lines: usize) -> Result<Self> {
+        let height = crate::raw::term_size()?.char_height;
+        Self::new(std::io::stdout(), lines, height)
+    }
+}
+
+impl<W: Write> Drop for LiveRegion<W> {
+    fn drop(&mut self) {
+        _ = self.reset();
+    }
+}
@@ -0,0 +1,85 @@
+use std::io::{self, IsTerminal, StdoutLock, Write};
+
+/// Handle to the standard output that can be shared between threads. See
+/// [`stdout`].
+///
+/// Output written while the handle is locked is not interleaved with output
+/// from other threads. The lock is the same as the lock used by [`print!`]
+/// and it is reentrant, so the print macros (e.g. `printc!`) may be used
+/// while the handle is locked.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Output(());
+
+/// Gets handle to the standard output that can be locked, so that escape
+/// codes written from multiple threads are not interleaved. See [`Output`].
+///
+/// # Examples
+/// ```rust
+/// use std::{io::Write, thread};
+/// use termal_core::{codes, stdout};
+///
+/// let threads: Vec<_> = (0..4)
+///     .map(|i| {
+///         thread::spawn(move || {
+///             stdout().with_lock(|w| {
+///                 w.write_all(codes::BOLD.as_bytes())?;
+///                 write!(w, "thread {i} is not interleaved")?;
+///                 writeln!(w, "{}", codes::RESET)
+///             })
+///         })
+///     })
+///     .collect();
+/// for t in threads {
+///     t.join().unwrap()?;
+/// }
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn stdout() -> Output {
+    Output(())
+}
+
+impl Output {
+    /// Locks the output. Other threads can't write to the output while the
+    /// lock is alive.
+    pub fn lock(&self) -> StdoutLock<'static> {
+        io::stdout().lock()
+    }
+
+    /// Locks the output for the duration of `f`. The output is flushed
+    /// after `f` returns, even if it fails. Error from `f` takes precedence
+    /// over error from the flush.
+    pub fn with_lock<R>(
+        &self,
+        f: impl FnOnce(&mut StdoutLock<'static>) -> io::Result<R>,
+    ) -> io::Result<R> {
+        let mut lock = self.lock();
+        let res = f(&mut lock);
+        let flushed = lock.flush();
+        let res = res?;
+        flushed?;
+        Ok(res)
+    }
+
+    /// Checks whether the output is terminal.
+    pub fn is_terminal(&self) -> bool {
+        io::stdout().is_terminal()
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.lock().write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.lock().write_all(buf)
+    }
+
+    fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> io::Result<()> {
+        self.lock().write_fmt(args)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.lock().flush()
+    }
+}
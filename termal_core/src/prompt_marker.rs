@@ -0,0 +1,86 @@
+use std::fmt::Display;
+
+use crate::codes;
+
+/// Emits shell integration marks (OSC 133) around prompts and command
+/// output, so that terminals can jump between prompts and select the output
+/// of commands. Terminals without shell integration ignore the marks.
+///
+/// The marker may be disabled (e.g. when the output is not terminal), in
+/// which case it emits no marks.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::PromptMarker;
+///
+/// let marker = PromptMarker::new();
+/// assert_eq!(
+///     marker.prompt("$ "),
+///     "\x1b]133;A\x1b\\$ \x1b]133;B\x1b\\"
+/// );
+/// assert_eq!(marker.output_start(), "\x1b]133;C\x1b\\");
+/// assert_eq!(marker.output_end(Some(0)), "\x1b]133;D;0\x1b\\");
+///
+/// assert_eq!(PromptMarker::disabled().prompt("$ "), "$ ");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PromptMarker {
+    enabled: bool,
+}
+
+impl PromptMarker {
+    /// Creates marker that emits the marks.
+    pub fn new() -> Self {
+        Self { enabled: true }
+    }
+
+    /// Creates marker that doesn't emit any marks.
+    pub fn disabled() -> Self {
+        Self { enabled: false }
+    }
+
+    /// Enables or disables the marks.
+    pub fn set_enabled(&mut self, v: bool) {
+        self.enabled = v;
+    }
+
+    /// Checks whether the marks are emitted.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Wraps the prompt with the marks of start and end of prompt.
+    pub fn prompt(&self, prompt: impl Display) -> String {
+        if self.enabled {
+            format!("{}{prompt}{}", codes::PROMPT_START, codes::PROMPT_END)
+        } else {
+            prompt.to_string()
+        }
+    }
+
+    /// Gets the mark that should be printed after the command is entered,
+    /// before its output.
+    pub fn output_start(&self) -> &'static str {
+        if self.enabled {
+            codes::COMMAND_OUTPUT_START
+        } else {
+            ""
+        }
+    }
+
+    /// Gets the mark that should be printed after the output of command.
+    /// `exit` is the exit code of the command if it is known.
+    pub fn output_end(&self, exit: Option<i32>) -> String {
+        if self.enabled {
+            codes::command_finished(exit)
+        } else {
+            String::new()
+        }
+    }
+}
+
+impl Default for PromptMarker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,78 @@
+use std::io::{self, Write};
+
+use crate::{
+    codes::{self, CursorStyle},
+    error::Result,
+};
+
+/// Guard that sets the cursor style and restores the original style when
+/// dropped.
+///
+/// The codes are written to stdout. If the original style is not known, the
+/// style is reset to the default of the terminal. If the guard is leaked
+/// (e.g. due to panic), the style is reset by [`crate::reset_terminal`].
+#[derive(Debug)]
+pub struct CursorStyleGuard {
+    original: Option<CursorStyle>,
+}
+
+impl CursorStyleGuard {
+    /// Sets the cursor style to `style`. The cursor style is restored to
+    /// `original` when the returned guard is dropped. If `original` is
+    /// [`None`], the style is reset to the default of the terminal.
+    pub fn new(
+        style: CursorStyle,
+        original: Option<CursorStyle>,
+    ) -> Result<Self> {
+        let mut out = io::stdout().lock();
+        out.write_all(codes::set_cursor(style).as_bytes())?;
+        out.flush()?;
+        Ok(Self { original })
+    }
+
+    /// Asks the terminal for the current cursor style and sets the cursor
+    /// style to `style`. The original style is restored when the returned
+    /// guard is dropped. The request waits at most `timeout`. See
+    /// [`super::request::cursor_style`].
+    #[cfg(feature = "events")]
+    pub fn detect(
+        style: CursorStyle,
+        timeout: std::time::Duration,
+    ) -> Result<Self> {
+        let original = super::request::cursor_style(timeout)?;
+        Self::new(style, original)
+    }
+
+    /// Gets the style that will be restored.
+    pub fn original(&self) -> Option<CursorStyle> {
+        self.original
+    }
+
+    /// Gets the code that restores the original cursor style.
+    pub fn restore_code(&self) -> &'static str {
+        self.original
+            .map(codes::set_cursor)
+            .unwrap_or(codes::RESET_CURSOR_STYLE)
+    }
+
+    /// Restores the original cursor style. Unlike drop, this will report
+    /// errors.
+    pub fn restore(self) -> Result<()> {
+        let res = self.write_restore();
+        std::mem::forget(self);
+        res
+    }
+
+    fn write_restore(&self) -> Result<()> {
+        let mut out = io::stdout().lock();
+        out.write_all(self.restore_code().as_bytes())?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for CursorStyleGuard {
+    fn drop(&mut self) {
+        _ = self.write_restore();
+    }
+}
@@ -0,0 +1,91 @@
+//! Utilities for debugging the input from terminal.
+//!
+//! [`key_inspector`] shows the raw bytes of each event and all the possible
+//! interpretations of the bytes. This is useful to find out what the
+//! terminal sends and how termal parses it.
+//!
+//! # Examples
+//! ```no_run
+//! termal_core::raw::debug::key_inspector()?;
+//! # Ok::<_, termal_core::error::Error>(())
+//! ```
+
+use std::{fmt::Write as _, io::Write};
+
+use crate::error::Result;
+
+use super::{
+    events::{AmbigousEvent, AnyEvent, Event, KeyBinding, KeyCode, Modifiers},
+    raw_mode_guard, IoProvider, Terminal,
+};
+
+/// Formats the bytes so that they are all visible. Control characters are
+/// escaped and the bytes are also shown as hex.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::raw::debug::pretty_bytes;
+///
+/// assert_eq!(pretty_bytes(b"\x1b[A"), r#""\x1b[A" (1b 5b 41)"#);
+/// ```
+pub fn pretty_bytes(b: &[u8]) -> String {
+    let mut res = format!("\"{}\" (", b.escape_ascii());
+    for (i, b) in b.iter().enumerate() {
+        if i != 0 {
+            res.push(' ');
+        }
+        _ = write!(res, "{b:02x}");
+    }
+    res.push(')');
+    res
+}
+
+/// Formats the event with its raw bytes and all its interpretations. The
+/// first interpretation is the one that is chosen by termal.
+pub fn pretty_event(evt: &AmbigousEvent, raw: &[u8]) -> String {
+    let mut res = pretty_bytes(raw);
+    match &evt.event {
+        AnyEvent::Known(e) => _ = write!(res, "\n  chosen: {e:?}"),
+        AnyEvent::Unknown(_) => res += "\n  chosen: unknown",
+    }
+    for o in &evt.other {
+        _ = write!(res, "\n  or: {o:?}");
+    }
+    res
+}
+
+/// Prints each event received from stdin with its raw bytes and all its
+/// interpretations until `ctrl+c` is pressed. Raw mode is enabled while the
+/// inspector runs.
+pub fn key_inspector() -> Result<()> {
+    let _guard = raw_mode_guard()?;
+    key_inspector_on(&mut Terminal::stdio())
+}
+
+/// Prints each event received by the terminal with its raw bytes and all its
+/// interpretations until `ctrl+c` is pressed. The terminal should be in raw
+/// mode. See [`key_inspector`].
+pub fn key_inspector_on<T: IoProvider>(term: &mut Terminal<T>) -> Result<()> {
+    let exit = KeyBinding::new(KeyCode::Char('c'), Modifiers::CONTROL);
+    let recording = term.is_recording_input();
+    term.set_record_input(true);
+
+    let res = (|| {
+        term.println("Press keys to inspect them. Press ctrl+c to exit.")?;
+        term.flush()?;
+
+        loop {
+            let evt = term.read_ambigous()?;
+            let raw = term.take_recorded_input();
+            if matches!(&evt.event, AnyEvent::Known(Event::KeyPress(k)) if exit.matches(k))
+            {
+                return Ok(());
+            }
+            term.println(pretty_event(&evt, &raw))?;
+            term.flush()?;
+        }
+    })();
+
+    term.set_record_input(recording);
+    res
+}
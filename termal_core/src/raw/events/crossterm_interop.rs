@@ -0,0 +1,286 @@
+use ::crossterm::event as ct;
+
+use crate::error::Error;
+
+use super::{
+    mouse::{self, Mouse},
+    Event, Key, KeyCode, Modifiers,
+};
+
+impl From<ct::KeyModifiers> for Modifiers {
+    fn from(value: ct::KeyModifiers) -> Self {
+        let mut res = Modifiers::NONE;
+        res.set(Self::SHIFT, value.contains(ct::KeyModifiers::SHIFT));
+        res.set(Self::CONTROL, value.contains(ct::KeyModifiers::CONTROL));
+        res.set(Self::ALT, value.contains(ct::KeyModifiers::ALT));
+        res.set(
+            Self::META,
+            value.intersects(ct::KeyModifiers::SUPER | ct::KeyModifiers::META),
+        );
+        res
+    }
+}
+
+impl From<Modifiers> for ct::KeyModifiers {
+    fn from(value: Modifiers) -> Self {
+        let mut res = Self::NONE;
+        res.set(Self::SHIFT, value.contains(Modifiers::SHIFT));
+        res.set(Self::CONTROL, value.contains(Modifiers::CONTROL));
+        res.set(Self::ALT, value.contains(Modifiers::ALT));
+        // Termal uses meta for the windows key which is super in crossterm.
+        res.set(Self::SUPER, value.contains(Modifiers::META));
+        res
+    }
+}
+
+impl TryFrom<ct::KeyCode> for KeyCode {
+    type Error = Error;
+
+    fn try_from(value: ct::KeyCode) -> Result<Self, Error> {
+        Ok(match value {
+            ct::KeyCode::Backspace => Self::Backspace,
+            ct::KeyCode::Enter => Self::Enter,
+            ct::KeyCode::Left => Self::Left,
+            ct::KeyCode::Right => Self::Right,
+            ct::KeyCode::Up => Self::Up,
+            ct::KeyCode::Down => Self::Down,
+            ct::KeyCode::Home => Self::Home,
+            ct::KeyCode::End => Self::End,
+            ct::KeyCode::PageUp => Self::PgUp,
+            ct::KeyCode::PageDown => Self::PgDown,
+            ct::KeyCode::Tab | ct::KeyCode::BackTab => Self::Tab,
+            ct::KeyCode::Delete => Self::Delete,
+            ct::KeyCode::Insert => Self::Insert,
+            ct::KeyCode::Esc => Self::Esc,
+            ct::KeyCode::Char(' ') => Self::Space,
+            ct::KeyCode::Char(c) => Self::Char(c.to_ascii_lowercase()),
+            ct::KeyCode::F(n) => f_key(n).ok_or(Error::NoEquivalent)?,
+            _ => return Err(Error::NoEquivalent),
+        })
+    }
+}
+
+impl From<KeyCode> for ct::KeyCode {
+    fn from(value: KeyCode) -> Self {
+        match value {
+            KeyCode::Up => Self::Up,
+            KeyCode::Down => Self::Down,
+            KeyCode::Right => Self::Right,
+            KeyCode::Left => Self::Left,
+            KeyCode::Space => Self::Char(' '),
+            KeyCode::Tab => Self::Tab,
+            KeyCode::Enter => Self::Enter,
+            KeyCode::F0 => Self::F(0),
+            KeyCode::F1 => Self::F(1),
+            KeyCode::F2 => Self::F(2),
+            KeyCode::F3 => Self::F(3),
+            KeyCode::F4 => Self::F(4),
+            KeyCode::F5 => Self::F(5),
+            KeyCode::F6 => Self::F(6),
+            KeyCode::F7 => Self::F(7),
+            KeyCode::F8 => Self::F(8),
+            KeyCode::F9 => Self::F(9),
+            KeyCode::F10 => Self::F(10),
+            KeyCode::F11 => Self::F(11),
+            KeyCode::F12 => Self::F(12),
+            KeyCode::F13 => Self::F(13),
+            KeyCode::F14 => Self::F(14),
+            KeyCode::F15 => Self::F(15),
+            KeyCode::F16 => Self::F(16),
+            KeyCode::F17 => Self::F(17),
+            KeyCode::F18 => Self::F(18),
+            KeyCode::F19 => Self::F(19),
+            KeyCode::F20 => Self::F(20),
+            KeyCode::Delete => Self::Delete,
+            KeyCode::Insert => Self::Insert,
+            KeyCode::End => Self::End,
+            KeyCode::Home => Self::Home,
+            KeyCode::PgUp => Self::PageUp,
+            KeyCode::PgDown => Self::PageDown,
+            KeyCode::Backspace => Self::Backspace,
+            KeyCode::Esc => Self::Esc,
+            KeyCode::Char(c) => Self::Char(c),
+        }
+    }
+}
+
+/// Key release events have no equivalent in termal.
+impl TryFrom<ct::KeyEvent> for Key {
+    type Error = Error;
+
+    fn try_from(value: ct::KeyEvent) -> Result<Self, Error> {
+        if value.kind == ct::KeyEventKind::Release {
+            return Err(Error::NoEquivalent);
+        }
+
+        let modifiers: Modifiers = value.modifiers.into();
+        let mut key = Key::mcode(value.code.try_into()?, modifiers);
+        if let ct::KeyCode::Char(c) = value.code {
+            if (modifiers - Modifiers::SHIFT).is_empty() {
+                key.key_char = Some(c);
+            }
+        }
+        Ok(key)
+    }
+}
+
+impl From<Key> for ct::KeyEvent {
+    fn from(value: Key) -> Self {
+        let shift = value.modifiers.contains(Modifiers::SHIFT);
+        let code = match value.code {
+            KeyCode::Tab if shift => ct::KeyCode::BackTab,
+            KeyCode::Char(c) => {
+                ct::KeyCode::Char(value.key_char.unwrap_or(if shift {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }))
+            }
+            c => c.into(),
+        };
+        Self::new(code, value.modifiers.into())
+    }
+}
+
+impl TryFrom<ct::MouseEvent> for Mouse {
+    type Error = Error;
+
+    fn try_from(value: ct::MouseEvent) -> Result<Self, Error> {
+        let (button, event) = match value.kind {
+            ct::MouseEventKind::Down(b) => (b.into(), mouse::Event::Down),
+            ct::MouseEventKind::Up(b) => (b.into(), mouse::Event::Up),
+            ct::MouseEventKind::Drag(b) => (b.into(), mouse::Event::Move),
+            ct::MouseEventKind::Moved => {
+                (mouse::Button::None, mouse::Event::Move)
+            }
+            ct::MouseEventKind::ScrollUp => {
+                (mouse::Button::None, mouse::Event::ScrollUp)
+            }
+            ct::MouseEventKind::ScrollDown => {
+                (mouse::Button::None, mouse::Event::ScrollDown)
+            }
+            _ => return Err(Error::NoEquivalent),
+        };
+
+        // Crossterm positions start at 0 and termal positions start at 1.
+        Ok(Self {
+            button,
+            event,
+            modifiers: value.modifiers.into(),
+            x: value.column as usize + 1,
+            y: value.row as usize + 1,
+        })
+    }
+}
+
+impl TryFrom<Mouse> for ct::MouseEvent {
+    type Error = Error;
+
+    fn try_from(value: Mouse) -> Result<Self, Error> {
+        let button = match value.button {
+            mouse::Button::None => None,
+            mouse::Button::Left => Some(ct::MouseButton::Left),
+            mouse::Button::Middle => Some(ct::MouseButton::Middle),
+            mouse::Button::Right => Some(ct::MouseButton::Right),
+        };
+        let kind = match (value.event, button) {
+            (mouse::Event::Down, Some(b)) => ct::MouseEventKind::Down(b),
+            (mouse::Event::Up, Some(b)) => ct::MouseEventKind::Up(b),
+            (mouse::Event::Move, Some(b)) => ct::MouseEventKind::Drag(b),
+            (mouse::Event::Move, None) => ct::MouseEventKind::Moved,
+            (mouse::Event::ScrollUp, _) => ct::MouseEventKind::ScrollUp,
+            (mouse::Event::ScrollDown, _) => ct::MouseEventKind::ScrollDown,
+            _ => return Err(Error::NoEquivalent),
+        };
+        let pos = |p: usize| {
+            u16::try_from(p.saturating_sub(1)).map_err(|_| Error::NoEquivalent)
+        };
+
+        Ok(Self {
+            kind,
+            column: pos(value.x)?,
+            row: pos(value.y)?,
+            modifiers: value.modifiers.into(),
+        })
+    }
+}
+
+impl From<ct::MouseButton> for mouse::Button {
+    fn from(value: ct::MouseButton) -> Self {
+        match value {
+            ct::MouseButton::Left => Self::Left,
+            ct::MouseButton::Right => Self::Right,
+            ct::MouseButton::Middle => Self::Middle,
+        }
+    }
+}
+
+/// Only key presses, mouse events, focus changes and resizes can be
+/// converted.
+impl TryFrom<ct::Event> for Event {
+    type Error = Error;
+
+    fn try_from(value: ct::Event) -> Result<Self, Error> {
+        match value {
+            ct::Event::FocusGained => Ok(Self::Focus),
+            ct::Event::FocusLost => Ok(Self::FocusLost),
+            ct::Event::Key(k) => Ok(Self::KeyPress(k.try_into()?)),
+            ct::Event::Mouse(m) => Ok(Self::Mouse(m.try_into()?)),
+            ct::Event::Resize(w, h) => Ok(Self::Resize {
+                w: w as usize,
+                h: h as usize,
+            }),
+            // `Paste` exists only with the crossterm feature
+            // `bracketed-paste`.
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::NoEquivalent),
+        }
+    }
+}
+
+/// Only key presses, mouse events, focus changes and resizes can be
+/// converted.
+impl TryFrom<Event> for ct::Event {
+    type Error = Error;
+
+    fn try_from(value: Event) -> Result<Self, Error> {
+        match value {
+            Event::Focus => Ok(Self::FocusGained),
+            Event::FocusLost => Ok(Self::FocusLost),
+            Event::KeyPress(k) => Ok(Self::Key(k.into())),
+            Event::Mouse(m) => Ok(Self::Mouse(m.try_into()?)),
+            Event::Resize { w, h } => Ok(Self::Resize(
+                u16::try_from(w).map_err(|_| Error::NoEquivalent)?,
+                u16::try_from(h).map_err(|_| Error::NoEquivalent)?,
+            )),
+            _ => Err(Error::NoEquivalent),
+        }
+    }
+}
+
+fn f_key(n: u8) -> Option<KeyCode> {
+    const KEYS: [KeyCode; 21] = [
+        KeyCode::F0,
+        KeyCode::F1,
+        KeyCode::F2,
+        KeyCode::F3,
+        KeyCode::F4,
+        KeyCode::F5,
+        KeyCode::F6,
+        KeyCode::F7,
+        KeyCode::F8,
+        KeyCode::F9,
+        KeyCode::F10,
+        KeyCode::F11,
+        KeyCode::F12,
+        KeyCode::F13,
+        KeyCode::F14,
+        KeyCode::F15,
+        KeyCode::F16,
+        KeyCode::F17,
+        KeyCode::F18,
+        KeyCode::F19,
+        KeyCode::F20,
+    ];
+    KEYS.get(n as usize).copied()
+}
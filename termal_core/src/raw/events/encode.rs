@@ -0,0 +1,242 @@
+use crate::{base64, codes, Rgb};
+
+use super::{
+    mouse::{Button, Event as MouseEvent, Mouse},
+    Event, Key, KeyCode, Modifiers, StateChange, Status,
+};
+
+/// Encodes the event into the code that the terminal would send to produce
+/// the event. This is inverse to parsing events.
+///
+/// Mouse events are encoded with the SGR encoding
+/// ([`crate::raw::Encoding::Sgr`]). Some keys with modifiers cannot be
+/// encoded, they are encoded without the modifiers that cannot be encoded.
+/// [`Event::Resize`] is encoded as empty code.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::raw::events::{encode_event, Event, Key, KeyCode};
+///
+/// let evt = Event::KeyPress(Key::code(KeyCode::Up));
+/// assert_eq!(encode_event(&evt), b"\x1b[A");
+/// ```
+pub fn encode_event(evt: &Event) -> Vec<u8> {
+    let mut res = vec![];
+    match evt {
+        Event::KeyPress(k) => encode_key(k, &mut res),
+        Event::Mouse(m) => encode_mouse(m, &mut res),
+        Event::Status(s) => encode_status(s, &mut res),
+        Event::Focus => res.extend_from_slice(crate::csi!('I').as_bytes()),
+        Event::FocusLost => res.extend_from_slice(crate::csi!('O').as_bytes()),
+        Event::StateChange(StateChange::BracketedPasteStart) => {
+            res.extend_from_slice(crate::csi!('~', 200).as_bytes())
+        }
+        Event::StateChange(StateChange::BracketedPasteEnd) => {
+            res.extend_from_slice(crate::csi!('~', 201).as_bytes())
+        }
+        // The terminal doesn't send any code when it is resized.
+        Event::Resize { .. } => {}
+    }
+    res
+}
+
+impl Event {
+    /// Encodes the event into the code that the terminal would send to
+    /// produce the event. See [`encode_event`].
+    pub fn to_ansi(&self) -> Vec<u8> {
+        encode_event(self)
+    }
+}
+
+/// Appends the code that the terminal sends when the key is pressed.
+pub(crate) fn encode_key(key: &Key, res: &mut Vec<u8>) {
+    let mods = key.modifiers;
+    let mid = (!mods.is_empty()).then(|| mods.bits() + 1);
+    let (id, post) = match key.code {
+        KeyCode::Up => (1, 'A'),
+        KeyCode::Down => (1, 'B'),
+        KeyCode::Right => (1, 'C'),
+        KeyCode::Left => (1, 'D'),
+        KeyCode::End => (1, 'F'),
+        KeyCode::Home => (1, 'H'),
+        KeyCode::F1 if mid.is_none() => return ss3(res, 'P'),
+        KeyCode::F2 if mid.is_none() => return ss3(res, 'Q'),
+        KeyCode::F3 if mid.is_none() => return ss3(res, 'R'),
+        KeyCode::F4 if mid.is_none() => return ss3(res, 'S'),
+        KeyCode::F1 => (1, 'P'),
+        KeyCode::F2 => (1, 'Q'),
+        KeyCode::F3 => (1, 'R'),
+        KeyCode::F4 => (1, 'S'),
+        KeyCode::Insert => (2, '~'),
+        KeyCode::Delete => (3, '~'),
+        KeyCode::PgUp => (5, '~'),
+        KeyCode::PgDown => (6, '~'),
+        KeyCode::F0 => (10, '~'),
+        KeyCode::F5 => (15, '~'),
+        KeyCode::F6 => (17, '~'),
+        KeyCode::F7 => (18, '~'),
+        KeyCode::F8 => (19, '~'),
+        KeyCode::F9 => (20, '~'),
+        KeyCode::F10 => (21, '~'),
+        KeyCode::F11 => (23, '~'),
+        KeyCode::F12 => (24, '~'),
+        KeyCode::F13 => (25, '~'),
+        KeyCode::F14 => (26, '~'),
+        KeyCode::F15 => (28, '~'),
+        KeyCode::F16 => (29, '~'),
+        KeyCode::F17 => (31, '~'),
+        KeyCode::F18 => (32, '~'),
+        KeyCode::F19 => (33, '~'),
+        KeyCode::F20 => (34, '~'),
+        KeyCode::Tab if mods == Modifiers::SHIFT => {
+            return res.extend_from_slice(codes::input::BACK_TAB.as_bytes())
+        }
+        // Keys that are sent as single char (possibly prefixed with escape
+        // for alt).
+        _ => {
+            if mods.contains(Modifiers::ALT) {
+                res.push(0x1b);
+            }
+            let key = Key {
+                modifiers: mods - Modifiers::ALT,
+                ..*key
+            };
+            if let Some(c) = single_char(&key) {
+                push_char(res, c);
+            }
+            return;
+        }
+    };
+
+    res.extend_from_slice(codes::CSI.as_bytes());
+    match (mid, post) {
+        (None, '~') => res.extend_from_slice(id.to_string().as_bytes()),
+        (None, _) => {}
+        (Some(m), _) => res.extend_from_slice(format!("{id};{m}").as_bytes()),
+    }
+    push_char(res, post);
+}
+
+/// Appends the code that the terminal sends for the mouse event. SGR mouse
+/// encoding is used.
+pub(crate) fn encode_mouse(mouse: &Mouse, res: &mut Vec<u8>) {
+    let mut state = match mouse.button {
+        Button::Left => 0,
+        Button::Middle => 1,
+        Button::Right => 2,
+        Button::None => 3,
+    };
+    match mouse.event {
+        MouseEvent::ScrollUp => state = 0x40,
+        MouseEvent::ScrollDown => state = 0x41,
+        MouseEvent::Move => state |= 0x20,
+        MouseEvent::Down | MouseEvent::Up => {}
+    }
+    state |= (mouse.modifiers & !Modifiers::META).bits() << 2;
+
+    let post = if mouse.event == MouseEvent::Up {
+        'm'
+    } else {
+        'M'
+    };
+    res.extend_from_slice(
+        format!("{}<{state};{};{}{post}", codes::CSI, mouse.x, mouse.y)
+            .as_bytes(),
+    );
+}
+
+/// Gets the single char that represents the key with the given modifiers
+/// (except alt).
+fn single_char(key: &Key) -> Option<char> {
+    let ctrl = key.modifiers.contains(Modifiers::CONTROL);
+    let shift = key.modifiers.contains(Modifiers::SHIFT);
+    match key.code {
+        KeyCode::Space if ctrl => Some('\0'),
+        KeyCode::Space => Some(' '),
+        KeyCode::Tab if !ctrl && !shift => Some('\t'),
+        KeyCode::Enter if !ctrl && !shift => Some('\r'),
+        KeyCode::Backspace if ctrl => Some('\x08'),
+        KeyCode::Backspace if !shift => Some('\x7f'),
+        KeyCode::Esc if !ctrl && !shift => Some('\x1b'),
+        KeyCode::Char(c) if ctrl && c.is_ascii_lowercase() => {
+            Some((c as u8 - b'a' + 1) as char)
+        }
+        KeyCode::Char(_) if ctrl => None,
+        KeyCode::Char(c) if shift => {
+            Some(key.key_char.unwrap_or(c.to_ascii_uppercase()))
+        }
+        KeyCode::Char(c) => Some(key.key_char.unwrap_or(c)),
+        _ => None,
+    }
+}
+
+fn ss3(res: &mut Vec<u8>, c: char) {
+    res.extend_from_slice(codes::SS3.as_bytes());
+    push_char(res, c);
+}
+
+fn push_char(res: &mut Vec<u8>, c: char) {
+    let mut buf = [0; 4];
+    res.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+}
+
+/// Appends the code that the terminal sends as the status response.
+fn encode_status(status: &Status, res: &mut Vec<u8>) {
+    fn color(c: &Rgb<u16>) -> String {
+        format!("rgb:{:04x}/{:04x}/{:04x}", c.r, c.g, c.b)
+    }
+
+    fn hex(s: &str) -> String {
+        s.bytes().map(|b| format!("{b:02X}")).collect()
+    }
+
+    let code = match status {
+        Status::Attributes(a) => {
+            let ids: Vec<_> = a.ids().iter().map(|i| i.to_string()).collect();
+            format!("{}?{}c", codes::CSI, ids.join(";"))
+        }
+        Status::Ok => crate::csi!('n', 0).into(),
+        Status::CursorPosition { x, y } => crate::csi!('R', y, x),
+        Status::Mode { mode, state } => {
+            format!("{}?{mode};{}$y", codes::CSI, *state as u32)
+        }
+        Status::TerminalName(n) => match &n.version {
+            Some(v) => format!("{}>|{}({v}){}", codes::DCS, n.name, codes::ST),
+            None => format!("{}>|{}{}", codes::DCS, n.name, codes::ST),
+        },
+        Status::TextAreaSizePx { w, h } => crate::csi!('t', 4, h, w),
+        Status::CharSize { w, h } => crate::csi!('t', 6, h, w),
+        Status::TextAreaSize { w, h } => crate::csi!('t', 8, h, w),
+        Status::WindowMinimized(m) => crate::csi!('t', 1 + *m as u8),
+        Status::WindowPosition { x, y } => crate::csi!('t', 3, x, y),
+        Status::ScreenSizePx { w, h } => crate::csi!('t', 5, h, w),
+        Status::ScreenSize { w, h } => crate::csi!('t', 9, h, w),
+        Status::SixelColors(c) => format!("{}?1;0;{c}S", codes::CSI),
+        Status::SixelSize { w, h } => {
+            format!("{}?2;0;{w};{h}S", codes::CSI)
+        }
+        Status::ColorCodeColor { code, color: c } => {
+            crate::osc!(4, code, color(c))
+        }
+        Status::DefaultFgColor(c) => crate::osc!(10, color(c)),
+        Status::DefaultBgColor(c) => crate::osc!(11, color(c)),
+        Status::CursorColor(c) => crate::osc!(12, color(c)),
+        Status::WindowTitle(t) => format!("{}l{t}{}", codes::OSC, codes::ST),
+        Status::IconName(n) => format!("{}L{n}{}", codes::OSC, codes::ST),
+        Status::Setting(Some(s)) => {
+            format!("{}1$r{s}{}", codes::DCS, codes::ST)
+        }
+        Status::Setting(None) => format!("{}0$r{}", codes::DCS, codes::ST),
+        Status::TermCap {
+            name,
+            value: Some(v),
+        } => format!("{}1+r{}={}{}", codes::DCS, hex(name), hex(v), codes::ST),
+        Status::TermCap { name, value: None } => {
+            format!("{}0+r{}{}", codes::DCS, hex(name), codes::ST)
+        }
+        Status::SelectionData(d) => {
+            crate::osc!(52, 'c', base64::encode(d))
+        }
+    };
+    res.extend_from_slice(code.as_bytes());
+}
@@ -1,10 +1,8 @@
-use base64::Engine;
-
-use crate::{codes, raw::events::csi::Csi};
+use crate::{base64, codes, raw::events::csi::Csi};
 
 use super::{
     mouse::Mouse, osc::Osc, state_change::StateChange, Key, KeyCode,
-    Modifiers, Status, TermAttr,
+    ModeState, Modifiers, Status, TermAttr, TermName,
 };
 
 /// Possibly ambiguous terminal event.
@@ -36,12 +34,31 @@ pub enum Event {
     Mouse(Mouse),
     /// Received terminal attributes.
     Status(Status),
-    /// The terminal has gained focus.
+    /// The terminal has gained focus. Focus events are sent only if they are
+    /// enabled with [`codes::ENABLE_FOCUS_EVENT`].
     Focus,
-    /// The terminal has lost focus.
+    /// The terminal has lost focus. Focus events are sent only if they are
+    /// enabled with [`codes::ENABLE_FOCUS_EVENT`].
     FocusLost,
     /// The input state has changed.
     StateChange(StateChange),
+    /// The terminal was resized to the given size in characters. Resize
+    /// events are sent only if they are enabled with
+    /// [`crate::raw::Terminal::set_resize_events`].
+    Resize { w: usize, h: usize },
+}
+
+impl Event {
+    /// Gets the focus change of this event. Returns `Some(true)` if the
+    /// terminal gained focus, `Some(false)` if it lost focus and `None` if
+    /// this is not focus event.
+    pub fn focus(&self) -> Option<bool> {
+        match self {
+            Self::Focus => Some(true),
+            Self::FocusLost => Some(false),
+            _ => None,
+        }
+    }
 }
 
 impl AmbigousEvent {
@@ -83,6 +100,23 @@ impl AmbigousEvent {
         Self::event(Event::StateChange(state))
     }
 
+    /// Adds the alt modifier to the key press events. Returns `false` and
+    /// doesn't change the event if the main event isn't key press.
+    pub(crate) fn add_alt(&mut self) -> bool {
+        let AnyEvent::Known(Event::KeyPress(k)) = &mut self.event else {
+            return false;
+        };
+        k.modifiers |= Modifiers::ALT;
+        k.key_char = None;
+        for e in self.other.iter_mut() {
+            if let Event::KeyPress(k) = e {
+                k.modifiers |= Modifiers::ALT;
+                k.key_char = None;
+            }
+        }
+        true
+    }
+
     /// Parse single char event.
     pub fn from_char_code(code: char) -> Self {
         Self::char_key(code)
@@ -167,6 +201,10 @@ impl AmbigousEvent {
             return Some(res);
         }
 
+        if let Some(key) = Self::special_key(code) {
+            return Some(Self::key(key));
+        }
+
         // check for code type
         if let Some(code) = code.strip_prefix(codes::CSI) {
             Self::csi(code)
@@ -245,6 +283,27 @@ impl AmbigousEvent {
                 w: *w as usize,
                 h: *h as usize,
             })),
+            // Whether the window is minimized
+            ("", [s @ (1 | 2)], "t") => {
+                Some(Self::status(Status::WindowMinimized(*s == 2)))
+            }
+            // Position of the window
+            ("", [3, x, y], "t") => {
+                Some(Self::status(Status::WindowPosition {
+                    x: *x as usize,
+                    y: *y as usize,
+                }))
+            }
+            // Size of screen in pixels
+            ("", [5, h, w], "t") => Some(Self::status(Status::ScreenSizePx {
+                w: *w as usize,
+                h: *h as usize,
+            })),
+            // Size of screen in characters
+            ("", [9, h, w], "t") => Some(Self::status(Status::ScreenSize {
+                w: *w as usize,
+                h: *h as usize,
+            })),
             // Sixel color register count
             ("?", [1, 0, v], "S") => {
                 Some(Self::status(Status::SixelColors(*v as usize)))
@@ -256,6 +315,11 @@ impl AmbigousEvent {
                     h: *h as usize,
                 }))
             }
+            // State of private mode
+            ("?", [mode, state], "$y") => Some(Self::status(Status::Mode {
+                mode: *mode,
+                state: ModeState::from_id(*state),
+            })),
             ("", [200], "~") => {
                 Some(Self::state_change(StateChange::BracketedPasteStart))
             }
@@ -273,8 +337,39 @@ impl AmbigousEvent {
     fn dcs(code: &str) -> Option<Self> {
         let code = code.strip_suffix(codes::ST)?;
 
-        code.strip_prefix(">|")
-            .map(|name| Self::status(Status::TerminalName(name.into())))
+        if let Some(name) = code.strip_prefix(">|") {
+            return Some(Self::status(Status::TerminalName(TermName::parse(
+                name,
+            ))));
+        }
+
+        // Only the first capability is used if the terminal responds with
+        // more of them.
+        if let Some(cap) = code.strip_prefix("1+r") {
+            let cap = cap.split(';').next().unwrap_or_default();
+            let (name, value) = cap.split_once('=').unwrap_or((cap, ""));
+            return Some(Self::status(Status::TermCap {
+                name: decode_hex(name)?,
+                value: Some(decode_hex(value)?),
+            }));
+        }
+
+        if let Some(name) = code.strip_prefix("0+r") {
+            let name = name.split(';').next().unwrap_or_default();
+            return Some(Self::status(Status::TermCap {
+                name: decode_hex(name)?,
+                value: None,
+            }));
+        }
+
+        // Some terminals use `0` for valid requests, so only the data
+        // determines whether the request was valid.
+        let setting = code
+            .strip_prefix("1$r")
+            .or_else(|| code.strip_prefix("0$r"))?;
+        Some(Self::status(Status::Setting(
+            (!setting.is_empty()).then(|| setting.to_string()),
+        )))
     }
 
     fn osc(code: &str) -> Option<Self> {
@@ -298,17 +393,54 @@ impl AmbigousEvent {
                 Some(Self::status(Status::CursorColor(color.parse().ok()?)))
             }
             ([52], selection) => Some(Self::status(Status::SelectionData(
-                base64::prelude::BASE64_STANDARD
-                    .decode(selection.split_once(';')?.1)
-                    .ok()?,
+                base64::decode(selection.split_once(';')?.1)?,
             ))),
             ([52, _], selection) => Some(Self::status(Status::SelectionData(
-                base64::prelude::BASE64_STANDARD.decode(selection).ok()?,
+                base64::decode(selection)?,
             ))),
+            ([], data) => {
+                if let Some(title) = data.strip_prefix('l') {
+                    Some(Self::status(Status::WindowTitle(title.into())))
+                } else {
+                    data.strip_prefix('L')
+                        .map(|n| Self::status(Status::IconName(n.into())))
+                }
+            }
             _ => None,
         }
     }
 
+    /// Parses keys whose codes don't follow the xterm and vt conventions.
+    /// See [`codes::input`].
+    fn special_key(code: &str) -> Option<Key> {
+        use codes::input;
+
+        let (code, mods) = match code {
+            input::APP_UP => (KeyCode::Up, Modifiers::NONE),
+            input::APP_DOWN => (KeyCode::Down, Modifiers::NONE),
+            input::APP_RIGHT => (KeyCode::Right, Modifiers::NONE),
+            input::APP_LEFT => (KeyCode::Left, Modifiers::NONE),
+            input::APP_HOME => (KeyCode::Home, Modifiers::NONE),
+            input::APP_END => (KeyCode::End, Modifiers::NONE),
+            input::RXVT_SHIFT_UP => (KeyCode::Up, Modifiers::SHIFT),
+            input::RXVT_SHIFT_DOWN => (KeyCode::Down, Modifiers::SHIFT),
+            input::RXVT_SHIFT_RIGHT => (KeyCode::Right, Modifiers::SHIFT),
+            input::RXVT_SHIFT_LEFT => (KeyCode::Left, Modifiers::SHIFT),
+            input::RXVT_CTRL_UP => (KeyCode::Up, Modifiers::CONTROL),
+            input::RXVT_CTRL_DOWN => (KeyCode::Down, Modifiers::CONTROL),
+            input::RXVT_CTRL_RIGHT => (KeyCode::Right, Modifiers::CONTROL),
+            input::RXVT_CTRL_LEFT => (KeyCode::Left, Modifiers::CONTROL),
+            input::BACK_TAB => (KeyCode::Tab, Modifiers::SHIFT),
+            input::LINUX_F1 => (KeyCode::F1, Modifiers::NONE),
+            input::LINUX_F2 => (KeyCode::F2, Modifiers::NONE),
+            input::LINUX_F3 => (KeyCode::F3, Modifiers::NONE),
+            input::LINUX_F4 => (KeyCode::F4, Modifiers::NONE),
+            input::LINUX_F5 => (KeyCode::F5, Modifiers::NONE),
+            _ => return None,
+        };
+        Some(Key::mcode(code, mods))
+    }
+
     /// # Prerequisities
     /// - `csi.postfix.chars().count() == 1`
     fn csi_xterm(csi: Csi) -> Option<Self> {
@@ -388,3 +520,15 @@ impl AmbigousEvent {
         }
     }
 }
+
+/// Decodes string where each byte is encoded as two hex digits.
+fn decode_hex(s: &str) -> Option<String> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    let bytes = (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<_>>>()?;
+    String::from_utf8(bytes).ok()
+}
@@ -0,0 +1,296 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use crate::error::{Error, Result};
+
+use super::{AmbigousEvent, AnyEvent, Event, Key, KeyCode, Modifiers};
+
+/// Key with modifiers that can be bound to action. The key char of key press
+/// is ignored when matching.
+///
+/// Key binding can be parsed from string such as `"ctrl+shift+p"` or
+/// `"alt+enter"`:
+/// ```rust
+/// use termal_core::raw::events::{KeyBinding, KeyCode, Modifiers};
+///
+/// let binding: KeyBinding = "ctrl+shift+p".parse()?;
+/// assert_eq!(
+///     binding,
+///     KeyBinding::new(KeyCode::Char('p'), Modifiers::CONTROL | Modifiers::SHIFT)
+/// );
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    /// The key.
+    pub code: KeyCode,
+    /// Modifiers that must be pressed with the key.
+    pub modifiers: Modifiers,
+}
+
+/// Maps key bindings to actions.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::raw::events::{Event, Key, KeyCode, KeyMap, Modifiers};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Action {
+///     Quit,
+///     Palette,
+/// }
+///
+/// let mut map = KeyMap::new();
+/// map.bind_str("ctrl+q", Action::Quit)?;
+/// map.bind_str("ctrl+shift+p", Action::Palette)?;
+///
+/// let evt = Event::KeyPress(Key::mcode(
+///     KeyCode::Char('q'),
+///     Modifiers::CONTROL,
+/// ));
+/// assert_eq!(map.get_event(&evt), Some(&Action::Quit));
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyMap<T> {
+    map: HashMap<KeyBinding, T>,
+}
+
+impl KeyBinding {
+    /// Creates new key binding.
+    pub fn new(code: KeyCode, modifiers: Modifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Checks if the key matches this binding.
+    pub fn matches(&self, key: &Key) -> bool {
+        *self == Self::from(key)
+    }
+
+    fn parse_code(s: &str) -> Option<(KeyCode, Modifiers)> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            let m = if c.is_uppercase() {
+                Modifiers::SHIFT
+            } else {
+                Modifiers::NONE
+            };
+            let code = match c {
+                ' ' => KeyCode::Space,
+                c => KeyCode::Char(c.to_lowercase().next().unwrap_or(c)),
+            };
+            return Some((code, m));
+        }
+
+        let code = match s.to_ascii_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "right" => KeyCode::Right,
+            "left" => KeyCode::Left,
+            "space" => KeyCode::Space,
+            "tab" => KeyCode::Tab,
+            "enter" | "return" => KeyCode::Enter,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "end" => KeyCode::End,
+            "home" => KeyCode::Home,
+            "pgup" | "pageup" => KeyCode::PgUp,
+            "pgdown" | "pagedown" => KeyCode::PgDown,
+            "backspace" => KeyCode::Backspace,
+            "esc" | "escape" => KeyCode::Esc,
+            "plus" => KeyCode::Char('+'),
+            f => {
+                let n: u32 = f.strip_prefix('f')?.parse().ok()?;
+                function_key(n)?
+            }
+        };
+
+        Some((code, Modifiers::NONE))
+    }
+}
+
+fn function_key(n: u32) -> Option<KeyCode> {
+    const KEYS: [KeyCode; 21] = [
+        KeyCode::F0,
+        KeyCode::F1,
+        KeyCode::F2,
+        KeyCode::F3,
+        KeyCode::F4,
+        KeyCode::F5,
+        KeyCode::F6,
+        KeyCode::F7,
+        KeyCode::F8,
+        KeyCode::F9,
+        KeyCode::F10,
+        KeyCode::F11,
+        KeyCode::F12,
+        KeyCode::F13,
+        KeyCode::F14,
+        KeyCode::F15,
+        KeyCode::F16,
+        KeyCode::F17,
+        KeyCode::F18,
+        KeyCode::F19,
+        KeyCode::F20,
+    ];
+    KEYS.get(n as usize).copied()
+}
+
+impl From<Key> for KeyBinding {
+    fn from(value: Key) -> Self {
+        Self::new(value.code, value.modifiers)
+    }
+}
+
+impl From<&Key> for KeyBinding {
+    fn from(value: &Key) -> Self {
+        Self::new(value.code, value.modifiers)
+    }
+}
+
+impl From<KeyCode> for KeyBinding {
+    fn from(value: KeyCode) -> Self {
+        Self::new(value, Modifiers::NONE)
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = Error;
+
+    /// Parses key binding such as `ctrl+shift+p`. Modifiers and keys are
+    /// case insensitive, except for single character keys where uppercase
+    /// letter implies shift. The `+` key can be written as `plus` or as the
+    /// last `+` (e.g. `ctrl++`).
+    fn from_str(s: &str) -> Result<Self> {
+        let err = || Error::InvalidKeyBinding(s.into());
+
+        let (mods, key) = if let Some(m) = s.strip_suffix("++") {
+            (Some(m), "+")
+        } else if s == "+" {
+            (None, "+")
+        } else {
+            match s.rsplit_once('+') {
+                Some((m, k)) => (Some(m), k),
+                None => (None, s),
+            }
+        };
+
+        let (code, mut modifiers) =
+            Self::parse_code(key.trim()).ok_or_else(err)?;
+
+        for m in mods.into_iter().flat_map(|m| m.split('+')) {
+            modifiers |= match m.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => Modifiers::CONTROL,
+                "shift" => Modifiers::SHIFT,
+                "alt" | "option" | "opt" => Modifiers::ALT,
+                "meta" | "super" | "win" | "cmd" => Modifiers::META,
+                _ => return Err(err()),
+            };
+        }
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mods = [
+            (Modifiers::CONTROL, "ctrl+"),
+            (Modifiers::SHIFT, "shift+"),
+            (Modifiers::ALT, "alt+"),
+            (Modifiers::META, "meta+"),
+        ];
+        for (m, s) in mods {
+            if self.modifiers.contains(m) {
+                f.write_str(s)?;
+            }
+        }
+
+        match self.code {
+            KeyCode::Char('+') => f.write_str("plus"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            c => f.write_str(&format!("{c:?}").to_ascii_lowercase()),
+        }
+    }
+}
+
+impl<T> KeyMap<T> {
+    /// Creates empty key map.
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+        }
+    }
+
+    /// Binds the action to the key binding. Returns the action that was
+    /// previously bound to the key binding.
+    pub fn bind(
+        &mut self,
+        binding: impl Into<KeyBinding>,
+        action: T,
+    ) -> Option<T> {
+        self.map.insert(binding.into(), action)
+    }
+
+    /// Parses the key binding and binds the action to it. Returns the action
+    /// that was previously bound to the key binding.
+    pub fn bind_str(&mut self, binding: &str, action: T) -> Result<Option<T>> {
+        Ok(self.bind(binding.parse::<KeyBinding>()?, action))
+    }
+
+    /// Removes the binding. Returns the action that was bound to it.
+    pub fn unbind(&mut self, binding: impl Into<KeyBinding>) -> Option<T> {
+        self.map.remove(&binding.into())
+    }
+
+    /// Gets the action bound to the key.
+    pub fn get(&self, key: impl Into<KeyBinding>) -> Option<&T> {
+        self.map.get(&key.into())
+    }
+
+    /// Gets the action bound to the key press event.
+    pub fn get_event(&self, evt: &Event) -> Option<&T> {
+        match evt {
+            Event::KeyPress(k) => self.get(k),
+            _ => None,
+        }
+    }
+
+    /// Gets the action bound to the ambiguous event. If the main event is
+    /// not bound, the other possible events are checked.
+    pub fn get_ambigous(&self, evt: &AmbigousEvent) -> Option<&T> {
+        let main = match &evt.event {
+            AnyEvent::Known(e) => self.get_event(e),
+            AnyEvent::Unknown(_) => None,
+        };
+        main.or_else(|| evt.other.iter().find_map(|e| self.get_event(e)))
+    }
+
+    /// Iterates over all the bindings.
+    pub fn iter(&self) -> impl Iterator<Item = (&KeyBinding, &T)> {
+        self.map.iter()
+    }
+
+    /// Gets the number of bindings.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Checks if there are no bindings.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<T> Default for KeyMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(KeyBinding, T)> for KeyMap<T> {
+    fn from_iter<I: IntoIterator<Item = (KeyBinding, T)>>(iter: I) -> Self {
+        Self {
+            map: iter.into_iter().collect(),
+        }
+    }
+}
@@ -1,10 +1,18 @@
+#[cfg(feature = "crossterm")]
+mod crossterm_interop;
 mod csi;
+mod encode;
 mod event;
 mod key;
+mod key_map;
 pub mod mouse;
 mod osc;
+mod parser;
 mod state_change;
 mod status;
 mod term_attr;
 
-pub use self::{event::*, key::*, state_change::*, status::*, term_attr::*};
+pub use self::{
+    encode::*, event::*, key::*, key_map::*, parser::*, state_change::*,
+    status::*, term_attr::*,
+};
@@ -0,0 +1,268 @@
+use crate::codes;
+
+use super::{AmbigousEvent, StateChange};
+
+/// Incremental parser of terminal input. It doesn't do any I/O, so it can
+/// be used with custom event loops or with input from other sources (e.g.
+/// pty or ssh stream). When reading directly from the terminal,
+/// [`crate::raw::Terminal`] is simpler to use.
+///
+/// Incomplete sequence at the end of the input (including lone escape) is
+/// kept until more input is fed. If no more input comes within short
+/// timeout, it should be parsed as is with [`Parser::flush`].
+///
+/// # Examples
+/// ```rust
+/// use termal_core::raw::events::{AnyEvent, Event, Key, KeyCode, Parser};
+///
+/// let mut parser = Parser::new();
+/// assert_eq!(parser.feed(b"\x1b[").count(), 0);
+/// assert_eq!(
+///     parser.feed(b"A").next().unwrap().event,
+///     AnyEvent::Known(Event::KeyPress(Key::code(KeyCode::Up)))
+/// );
+///
+/// assert_eq!(parser.feed(b"\x1b").count(), 0);
+/// assert_eq!(
+///     parser.flush().unwrap().event,
+///     AnyEvent::Known(Event::KeyPress(Key::code(KeyCode::Esc)))
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Parser {
+    buffer: Vec<u8>,
+    c1: bool,
+    bracketed_paste_open: bool,
+}
+
+impl Parser {
+    /// Creates new parser.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the data to the input and parses all complete events in it.
+    ///
+    /// Events that are not consumed from the iterator are kept and they
+    /// will be returned on the next call.
+    pub fn feed(
+        &mut self,
+        data: &[u8],
+    ) -> impl Iterator<Item = AmbigousEvent> + '_ {
+        self.buffer.extend_from_slice(data);
+        std::iter::from_fn(|| self.next_event())
+    }
+
+    /// Parses the incomplete input as is. Lone escape is parsed as escape
+    /// key. Returns [`None`] if there is no incomplete input.
+    pub fn flush(&mut self) -> Option<AmbigousEvent> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let code = std::mem::take(&mut self.buffer);
+        if code == b"\x1b" {
+            Some(AmbigousEvent::from_char_code('\x1b'))
+        } else {
+            Some(AmbigousEvent::from_code(&code))
+        }
+    }
+
+    /// Gets the input that wasn't parsed yet.
+    pub fn pending(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Enables or disables 8-bit control introducers. See
+    /// [`crate::raw::Terminal::set_8bit_controls`].
+    pub fn set_8bit_controls(&mut self, v: bool) {
+        self.c1 = v;
+    }
+
+    /// Checks whether 8-bit control introducers are enabled.
+    pub fn is_8bit_controls(&self) -> bool {
+        self.c1
+    }
+
+    /// Checks if bracketed paste is open. See
+    /// [`crate::raw::Terminal::is_bracketed_paste_open`].
+    pub fn is_bracketed_paste_open(&self) -> bool {
+        self.bracketed_paste_open
+    }
+
+    fn next_event(&mut self) -> Option<AmbigousEvent> {
+        let (len, evt) = if self.bracketed_paste_open {
+            self.parse_bracketed()?
+        } else {
+            self.expand_c1();
+            self.parse()?
+        };
+        self.buffer.drain(..len);
+        Some(evt)
+    }
+
+    /// Replaces 8-bit control introducer at the start of the buffer with
+    /// its 7-bit version.
+    fn expand_c1(&mut self) {
+        let Some(&cur) = self.buffer.first() else {
+            return;
+        };
+        let expand = match cur {
+            codes::CSI8 | codes::SS3_8 => true,
+            codes::DCS8 | codes::OSC8 => self.c1,
+            _ => false,
+        };
+        if expand {
+            self.buffer[0] = cur - 0x40;
+            self.buffer.insert(0, 0x1b);
+        }
+    }
+
+    /// Parses the event at the start of the buffer. Returns the length of
+    /// the event and the event or [`None`] if the event is incomplete.
+    fn parse(&mut self) -> Option<(usize, AmbigousEvent)> {
+        let buf = &self.buffer[..];
+        match buf {
+            [] | [0x1b] | [0x1b, 0x1b] | [0x1b, 0x1b, b'[' | b'O'] => None,
+            [0x1b, b'[', ..] => self.parse_csi(),
+            [0x1b, b'O', ..] => {
+                let len = sequence_len(buf, 2)?;
+                Some((len, AmbigousEvent::from_code(&buf[..len])))
+            }
+            [0x1b, b'P' | b']', ..] => self.parse_string(),
+            // Some terminals send alt + key as escape followed by the code
+            // of the key (e.g. `ESC ESC [ A` for alt + up).
+            [0x1b, 0x1b, b'[' | b'O', _, ..] => {
+                self.buffer.remove(0);
+                let res = self.parse();
+                self.buffer.insert(0, 0x1b);
+                let (len, mut evt) = res?;
+                // If it is not key (e.g. response to request), the escape is
+                // dropped so that the response isn't lost.
+                evt.add_alt();
+                Some((len + 1, evt))
+            }
+            [0x1b, rest @ ..] => {
+                let (_, len) = utf8_char(rest)?;
+                Some((len + 1, AmbigousEvent::from_code(&buf[..=len])))
+            }
+            _ => {
+                let (chr, len) = utf8_char(buf)?;
+                Some((len, AmbigousEvent::from_char_code(chr)))
+            }
+        }
+    }
+
+    fn parse_csi(&mut self) -> Option<(usize, AmbigousEvent)> {
+        let buf = &self.buffer[..];
+        if buf.get(2) == Some(&b'M') {
+            // Special mouse event that actually doesn't conform to CSI
+            // sequence rules. The coordinates may be UTF-8 encoded.
+            let mut len = 3;
+            for _ in 0..3 {
+                match buf.get(len) {
+                    None => return None,
+                    Some(&b) if b < 32 => break,
+                    Some(&b) if b & 0xE0 == 0xC0 => len += 2,
+                    Some(_) => len += 1,
+                }
+            }
+            if len > buf.len() {
+                return None;
+            }
+            return Some((len, AmbigousEvent::from_code(&buf[..len])));
+        }
+
+        let len = csi_len(buf)?;
+        let code = &buf[..len];
+        if code == codes::BRACKETED_PASTE_START.as_bytes() {
+            self.bracketed_paste_open = true;
+            Some((
+                len,
+                AmbigousEvent::state_change(StateChange::BracketedPasteStart),
+            ))
+        } else {
+            Some((len, AmbigousEvent::from_code(code)))
+        }
+    }
+
+    /// Parses DCS or OSC terminated with string terminator.
+    fn parse_string(&mut self) -> Option<(usize, AmbigousEvent)> {
+        let osc = self.buffer[1] == b']';
+        for i in 2..self.buffer.len() {
+            let b = self.buffer[i];
+            if (b == b'\\' && self.buffer[i - 1] == 0x1b)
+                || (osc && b == codes::BELL as u8)
+            {
+                let evt = AmbigousEvent::from_code(&self.buffer[..=i]);
+                return Some((i + 1, evt));
+            }
+            if self.c1 && b == codes::ST8 {
+                let mut code = self.buffer[..i].to_vec();
+                code.extend_from_slice(codes::ST.as_bytes());
+                return Some((i + 1, AmbigousEvent::from_code(&code)));
+            }
+        }
+        None
+    }
+
+    fn parse_bracketed(&mut self) -> Option<(usize, AmbigousEvent)> {
+        let end = codes::BRACKETED_PASTE_END.as_bytes();
+        let buf = &self.buffer[..];
+        if buf.starts_with(end) {
+            self.bracketed_paste_open = false;
+            return Some((
+                end.len(),
+                AmbigousEvent::state_change(StateChange::BracketedPasteEnd),
+            ));
+        }
+        if end.starts_with(buf) {
+            return None;
+        }
+
+        match utf8_char(buf)? {
+            ('\r', len) => Some((len, AmbigousEvent::verbatim('\n'))),
+            (c, len) => Some((len, AmbigousEvent::verbatim(c))),
+        }
+    }
+}
+
+/// Gets the length of CSI sequence at the start of the buffer. The linux
+/// console sends F1 - F5 as `ESC [ [ A` - `ESC [ [ E`, so `[` right after
+/// the introducer is part of the sequence. Returns [`None`] if the sequence
+/// is incomplete.
+pub(crate) fn csi_len(buf: &[u8]) -> Option<usize> {
+    if buf.get(2) == Some(&b'[') {
+        return (buf.len() > 3).then_some(4);
+    }
+    sequence_len(buf, 2)
+}
+
+/// Gets the length of CSI or SS3 sequence whose introducer has the length
+/// `start`. Returns [`None`] if the sequence is incomplete.
+fn sequence_len(buf: &[u8], start: usize) -> Option<usize> {
+    let mut i = start;
+    while buf.get(i).is_some_and(|b| (0x30..=0x3F).contains(b)) {
+        i += 1;
+    }
+    while buf.get(i).is_some_and(|b| (0x20..=0x2F).contains(b)) {
+        i += 1;
+    }
+    (i < buf.len()).then_some(i + 1)
+}
+
+/// Decodes the first UTF-8 character in the buffer. Invalid bytes are
+/// decoded as single character. Returns [`None`] if the character is
+/// incomplete.
+fn utf8_char(buf: &[u8]) -> Option<(char, usize)> {
+    let buf = &buf[..buf.len().min(4)];
+    let valid = match std::str::from_utf8(buf) {
+        Ok(s) => s,
+        Err(e) if e.valid_up_to() != 0 => {
+            std::str::from_utf8(&buf[..e.valid_up_to()]).unwrap()
+        }
+        Err(e) if e.error_len().is_none() => return None,
+        Err(_) => return Some((buf[0] as char, 1)),
+    };
+    let c = valid.chars().next()?;
+    Some((c, c.len_utf8()))
+}
@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use crate::Rgb;
 
 use super::TermAttr;
@@ -12,7 +14,7 @@ pub enum Status {
     /// Cursor position report.
     CursorPosition { x: usize, y: usize },
     /// Terminal name report.
-    TerminalName(String),
+    TerminalName(TermName),
     /// Size of text area in pixels report.
     TextAreaSizePx { w: usize, h: usize },
     /// Size of text area in characters report.
@@ -33,4 +35,248 @@ pub enum Status {
     CursorColor(Rgb<u16>),
     /// Data from selection.
     SelectionData(Vec<u8>),
+    /// Response to request for setting (DECRQSS). It contains the code that
+    /// sets the setting (without the `DCS` and `ST`), or [`None`] if the
+    /// request was invalid.
+    Setting(Option<String>),
+    /// Response to request for the state of private mode (DECRQM).
+    Mode { mode: u32, state: ModeState },
+    /// Response to request for termcap/terminfo capability (XTGETTCAP). The
+    /// value is [`None`] if the terminal doesn't know the capability and
+    /// empty for boolean capabilities.
+    TermCap { name: String, value: Option<String> },
+    /// Title of the window.
+    WindowTitle(String),
+    /// Icon name of the window.
+    IconName(String),
+    /// Whether the window is minimized.
+    WindowMinimized(bool),
+    /// Position of the window on the screen in pixels.
+    WindowPosition { x: usize, y: usize },
+    /// Size of the screen in pixels.
+    ScreenSizePx { w: usize, h: usize },
+    /// Size of the screen in characters.
+    ScreenSize { w: usize, h: usize },
+}
+
+/// Identifier of the kind of [`Status`]. The numeric values are stable and
+/// will not change across versions of termal, so they can be used for
+/// logging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StatusCode {
+    Attributes = 1,
+    Ok = 2,
+    CursorPosition = 3,
+    TerminalName = 4,
+    TextAreaSizePx = 5,
+    TextAreaSize = 6,
+    CharSize = 7,
+    SixelColors = 8,
+    SixelSize = 9,
+    ColorCodeColor = 10,
+    DefaultFgColor = 11,
+    DefaultBgColor = 12,
+    CursorColor = 13,
+    SelectionData = 14,
+    Setting = 15,
+    Mode = 16,
+    WindowTitle = 17,
+    IconName = 18,
+    WindowMinimized = 19,
+    WindowPosition = 20,
+    ScreenSizePx = 21,
+    ScreenSize = 22,
+    TermCap = 23,
+}
+
+/// State of terminal mode as reported by the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModeState {
+    /// The terminal doesn't recognize the mode.
+    NotRecognized = 0,
+    /// The mode is enabled.
+    Set = 1,
+    /// The mode is disabled.
+    Reset = 2,
+    /// The mode is enabled and it can't be changed.
+    PermanentlySet = 3,
+    /// The mode is disabled and it can't be changed.
+    PermanentlyReset = 4,
+}
+
+/// Name and version of terminal as reported by the terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TermName {
+    /// Name of the terminal.
+    pub name: String,
+    /// Version of the terminal if it was reported.
+    pub version: Option<String>,
+}
+
+impl Status {
+    /// Gets the identifier of the kind of this status.
+    pub fn code(&self) -> StatusCode {
+        match self {
+            Self::Attributes(_) => StatusCode::Attributes,
+            Self::Ok => StatusCode::Ok,
+            Self::CursorPosition { .. } => StatusCode::CursorPosition,
+            Self::TerminalName(_) => StatusCode::TerminalName,
+            Self::TextAreaSizePx { .. } => StatusCode::TextAreaSizePx,
+            Self::TextAreaSize { .. } => StatusCode::TextAreaSize,
+            Self::CharSize { .. } => StatusCode::CharSize,
+            Self::SixelColors(_) => StatusCode::SixelColors,
+            Self::SixelSize { .. } => StatusCode::SixelSize,
+            Self::ColorCodeColor { .. } => StatusCode::ColorCodeColor,
+            Self::DefaultFgColor(_) => StatusCode::DefaultFgColor,
+            Self::DefaultBgColor(_) => StatusCode::DefaultBgColor,
+            Self::CursorColor(_) => StatusCode::CursorColor,
+            Self::SelectionData(_) => StatusCode::SelectionData,
+            Self::Setting(_) => StatusCode::Setting,
+            Self::Mode { .. } => StatusCode::Mode,
+            Self::WindowTitle(_) => StatusCode::WindowTitle,
+            Self::IconName(_) => StatusCode::IconName,
+            Self::WindowMinimized(_) => StatusCode::WindowMinimized,
+            Self::WindowPosition { .. } => StatusCode::WindowPosition,
+            Self::ScreenSizePx { .. } => StatusCode::ScreenSizePx,
+            Self::ScreenSize { .. } => StatusCode::ScreenSize,
+            Self::TermCap { .. } => StatusCode::TermCap,
+        }
+    }
+}
+
+impl ModeState {
+    /// Gets the mode state from its id in the DECRQM response. Unknown ids
+    /// are [`ModeState::NotRecognized`].
+    pub fn from_id(id: u32) -> Self {
+        match id {
+            1 => Self::Set,
+            2 => Self::Reset,
+            3 => Self::PermanentlySet,
+            4 => Self::PermanentlyReset,
+            _ => Self::NotRecognized,
+        }
+    }
+
+    /// Checks whether the mode is enabled. Returns [`None`] if the mode is
+    /// not recognized.
+    pub fn is_set(&self) -> Option<bool> {
+        match self {
+            Self::NotRecognized => None,
+            Self::Set | Self::PermanentlySet => Some(true),
+            Self::Reset | Self::PermanentlyReset => Some(false),
+        }
+    }
+}
+
+impl TermName {
+    /// Parses the terminal name as reported by the terminal. Recognizes the
+    /// forms `name(version)` and `name version`.
+    pub fn parse(s: &str) -> Self {
+        if let Some((name, ver)) =
+            s.strip_suffix(')').and_then(|s| s.split_once('('))
+        {
+            return Self {
+                name: name.trim_end().into(),
+                version: Some(ver.into()),
+            };
+        }
+
+        match s.rsplit_once(' ') {
+            Some((name, ver))
+                if ver.starts_with(|c: char| c.is_ascii_digit()) =>
+            {
+                Self {
+                    name: name.trim_end().into(),
+                    version: Some(ver.into()),
+                }
+            }
+            _ => Self {
+                name: s.into(),
+                version: None,
+            },
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Attributes(a) => write!(
+                f,
+                "terminal attributes: {:?} with features {:#x}",
+                a.typ,
+                a.features.bits()
+            ),
+            Self::Ok => write!(f, "status ok"),
+            Self::CursorPosition { x, y } => {
+                write!(f, "cursor position: {x}, {y}")
+            }
+            Self::TerminalName(n) => write!(f, "terminal name: {n}"),
+            Self::TextAreaSizePx { w, h } => {
+                write!(f, "text area size: {w}x{h} px")
+            }
+            Self::TextAreaSize { w, h } => {
+                write!(f, "text area size: {w}x{h} chars")
+            }
+            Self::CharSize { w, h } => write!(f, "char size: {w}x{h} px"),
+            Self::SixelColors(c) => write!(f, "sixel colors: {c}"),
+            Self::SixelSize { w, h } => {
+                write!(f, "max sixel size: {w}x{h} px")
+            }
+            Self::ColorCodeColor { code, color } => {
+                write!(f, "color of code {code}: {color}")
+            }
+            Self::DefaultFgColor(c) => write!(f, "default fg color: {c}"),
+            Self::DefaultBgColor(c) => write!(f, "default bg color: {c}"),
+            Self::CursorColor(c) => write!(f, "cursor color: {c}"),
+            Self::SelectionData(d) => {
+                write!(f, "selection data: {} bytes", d.len())
+            }
+            Self::Setting(Some(s)) => write!(f, "setting: {s:?}"),
+            Self::Setting(None) => write!(f, "invalid setting request"),
+            Self::Mode { mode, state } => write!(f, "mode {mode}: {state}"),
+            Self::WindowTitle(t) => write!(f, "window title: {t:?}"),
+            Self::IconName(n) => write!(f, "icon name: {n:?}"),
+            Self::WindowMinimized(m) => write!(f, "window minimized: {m}"),
+            Self::WindowPosition { x, y } => {
+                write!(f, "window position: {x}, {y} px")
+            }
+            Self::ScreenSizePx { w, h } => {
+                write!(f, "screen size: {w}x{h} px")
+            }
+            Self::ScreenSize { w, h } => {
+                write!(f, "screen size: {w}x{h} chars")
+            }
+            Self::TermCap {
+                name,
+                value: Some(v),
+            } => write!(f, "termcap {name}: {v:?}"),
+            Self::TermCap { name, value: None } => {
+                write!(f, "termcap {name}: unknown")
+            }
+        }
+    }
+}
+
+impl Display for ModeState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NotRecognized => "not recognized",
+            Self::Set => "set",
+            Self::Reset => "reset",
+            Self::PermanentlySet => "permanently set",
+            Self::PermanentlyReset => "permanently reset",
+        })
+    }
+}
+
+impl Display for TermName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(ver) = &self.version {
+            write!(f, "{} {ver}", self.name)
+        } else {
+            write!(f, "{}", self.name)
+        }
+    }
 }
@@ -29,27 +29,49 @@ pub enum TermType {
 }
 
 bitflags! {
-    #[doc = "Terminal features."]
+    #[doc = "Terminal features as reported in the device attributes."]
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct TermFeatures: u32 {
         /// No extra features.
         const NONE = 0x0;
+        /// Supports 132 columns (id 1).
         const COLUMNS132 = 0x1;
+        /// Has printer port (id 2).
         const PRINTER = 0x2;
+        /// Supports ReGIS graphics (id 3).
         const REGIS_GRAPHICS = 0x4;
-        /// Supports sixel graphics.
+        /// Supports sixel graphics (id 4).
         const SIXEL_GRAPHICS = 0x8;
+        /// Supports selective erase (id 6).
         const SELECTIVE_ERASE = 0x10;
+        /// Supports user defined keys (id 8).
         const USER_DEFINED_KEYS = 0x20;
+        /// Supports national replacement character sets (id 9).
         const NATIONAL_REPLACEMENT_CHARACTER_SETS = 0x40;
+        /// Supports technical characters (id 15).
         const TECHNICAL_CHARACTERS = 0x80;
+        /// Has locator port (id 16).
         const LOCATOR_PORT = 0x100;
+        /// Supports terminal state interrogation (id 17).
         const TERMINAL_STATE_INTERROGATION = 0x200;
+        /// Supports user windows (id 18).
         const USER_WINDOWS = 0x400;
+        /// Supports horizontal scrolling (id 21).
         const HORIZONTAL_SCROLLING = 0x800;
+        /// Supports ANSI color (id 22).
         const ANSI_COLOR = 0x1000;
+        /// Supports rectangular editing (id 28).
         const RECTANGULAR_EDITING = 0x2000;
+        /// Supports ANSI text locator (id 29).
         const ANSI_TEXT_LOCATOR = 0x4000;
+        /// Supports ISO Latin-2 character set (id 42).
+        const ISO_LATIN2 = 0x8000;
+        /// Supports PC term (id 44).
+        const PC_TERM = 0x10000;
+        /// Supports soft key map (id 45).
+        const SOFT_KEY_MAP = 0x20000;
+        /// Supports ASCII emulation (id 46).
+        const ASCII_EMULATION = 0x40000;
     }
 }
 
@@ -77,7 +99,39 @@ impl TermAttr {
     }
 }
 
+impl TermAttr {
+    /// Gets the arguments of the device attributes response that describes
+    /// this [`TermAttr`]. This is inverse to parsing.
+    pub fn ids(&self) -> Vec<u32> {
+        let mut res = match self.typ {
+            TermType::Vt100 => vec![1, 2],
+            TermType::Vt101 => vec![1, 0],
+            TermType::Vt132 => vec![4, 6],
+            t => t.id().into_iter().collect(),
+        };
+        res.extend(self.features.ids());
+        res
+    }
+}
+
 impl TermType {
+    /// Gets the id of the terminal type. Returns [`None`] for types that
+    /// don't have single id ([`TermType::Vt100`], [`TermType::Vt101`],
+    /// [`TermType::Vt132`] and unknown type without id).
+    pub fn id(&self) -> Option<u32> {
+        match self {
+            Self::Vt102 => Some(6),
+            Self::Vt131 => Some(7),
+            Self::Vt125 => Some(12),
+            Self::Vt220 => Some(62),
+            Self::Vt320 => Some(63),
+            Self::Vt420 => Some(64),
+            Self::Vt510 => Some(65),
+            Self::Other(i) => *i,
+            Self::Vt100 | Self::Vt101 | Self::Vt132 => None,
+        }
+    }
+
     /// Get terminal type from its id.
     pub fn from_id(id: u32) -> Self {
         match id {
@@ -99,6 +153,16 @@ impl TermFeatures {
         ids.iter().fold(Self::NONE, |r, f| r | Self::from_id(*f))
     }
 
+    /// Gets the ids of the features.
+    pub fn ids(&self) -> Vec<u32> {
+        (1..=46)
+            .filter(|i| {
+                let f = Self::from_id(*i);
+                !f.is_empty() && self.contains(f)
+            })
+            .collect()
+    }
+
     /// Get terminal feature from its id.
     pub fn from_id(id: u32) -> Self {
         match id {
@@ -117,6 +181,10 @@ impl TermFeatures {
             22 => Self::ANSI_COLOR,
             28 => Self::RECTANGULAR_EDITING,
             29 => Self::ANSI_TEXT_LOCATOR,
+            42 => Self::ISO_LATIN2,
+            44 => Self::PC_TERM,
+            45 => Self::SOFT_KEY_MAP,
+            46 => Self::ASCII_EMULATION,
             _ => Self::NONE,
         }
     }
@@ -3,7 +3,7 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-use super::WaitForIn;
+use super::{TermSize, WaitForIn};
 
 /// Represents mutable value that is either owned or borrowed.
 pub enum ValueOrMut<'a, T> {
@@ -36,6 +36,12 @@ pub trait IoProvider: WaitForIn {
     fn is_out_raw(&self) -> bool {
         false
     }
+
+    /// Gets the size of the terminal. Returns [`None`] if the size is not
+    /// known.
+    fn term_size(&self) -> Option<TermSize> {
+        None
+    }
 }
 
 impl<T> AsRef<T> for ValueOrMut<'_, T> {
@@ -0,0 +1,345 @@
+//! Translation of VT codes to operations of legacy consoles that can't
+//! process the codes (e.g. conhost on older windows).
+//!
+//! Only the core subset of the codes is translated: colors, cursor movement
+//! and erasing. Other codes are dropped so that they are not printed as
+//! garbage.
+//!
+//! On windows, [`crate::raw::StdioProvider`] uses this automatically if the
+//! console doesn't support VT codes, so [`crate::raw::Terminal`] works also
+//! on legacy consoles.
+//!
+//! # Examples
+//! ```rust
+//! use termal_core::raw::legacy::{LegacyOp, LegacyTranslator};
+//!
+//! let mut t = LegacyTranslator::new();
+//! assert_eq!(
+//!     t.feed(b"\x1b[31mhi\x1b[2;3H"),
+//!     [
+//!         LegacyOp::Fg(Some(1)),
+//!         LegacyOp::Text(b"hi".to_vec()),
+//!         LegacyOp::MoveTo { x: Some(2), y: Some(1) },
+//!     ]
+//! );
+//! ```
+
+/// Operation on legacy console.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LegacyOp {
+    /// Print the text.
+    Text(Vec<u8>),
+    /// Reset all the text attributes to the defaults.
+    Reset,
+    /// Set the foreground color. The color is index of one of the 16 basic
+    /// colors in the ANSI order. [`None`] is the default color.
+    Fg(Option<u8>),
+    /// Set the background color. The color is index of one of the 16 basic
+    /// colors in the ANSI order. [`None`] is the default color.
+    Bg(Option<u8>),
+    /// Enable or disable bold (intense foreground color).
+    Bold(bool),
+    /// Enable or disable swapping of foreground and background color.
+    Reverse(bool),
+    /// Move the cursor relative to its current position.
+    MoveBy { x: isize, y: isize },
+    /// Move the cursor to the given position. The position is 0 based and
+    /// relative to the visible window. [`None`] keeps the coordinate.
+    MoveTo { x: Option<usize>, y: Option<usize> },
+    /// Erase part of the screen.
+    Erase(Erase),
+}
+
+/// Part of the screen to erase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Erase {
+    /// The whole visible screen.
+    Screen,
+    /// From the cursor to the end of the screen.
+    ToScreenEnd,
+    /// From the start of the screen to the cursor.
+    ToScreenStart,
+    /// The whole line with the cursor.
+    Line,
+    /// From the cursor to the end of the line.
+    ToLineEnd,
+    /// From the start of the line to the cursor.
+    ToLineStart,
+}
+
+/// Translates VT codes to [`LegacyOp`]. The codes may be split between
+/// multiple calls to [`LegacyTranslator::feed`].
+#[derive(Debug, Clone, Default)]
+pub struct LegacyTranslator {
+    state: State,
+    params: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum State {
+    #[default]
+    Text,
+    Esc,
+    EscIntermediate,
+    Csi,
+    /// String sequence (OSC, DCS, APC, ...).
+    Str,
+    StrEsc,
+}
+
+/// Palette of legacy windows console in the ANSI order.
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (128, 0, 0),
+    (0, 128, 0),
+    (128, 128, 0),
+    (0, 0, 128),
+    (128, 0, 128),
+    (0, 128, 128),
+    (192, 192, 192),
+    (128, 128, 128),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (0, 0, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+impl LegacyTranslator {
+    /// Creates new translator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Translates the data to operations. Incomplete code at the end of the
+    /// data is kept and finished by the next call.
+    pub fn feed(&mut self, data: &[u8]) -> Vec<LegacyOp> {
+        let mut res = vec![];
+        let mut text = vec![];
+
+        for &b in data {
+            self.state = match self.state {
+                State::Text if b == 0x1b => {
+                    if !text.is_empty() {
+                        res.push(LegacyOp::Text(std::mem::take(&mut text)));
+                    }
+                    State::Esc
+                }
+                State::Text => {
+                    text.push(b);
+                    State::Text
+                }
+                State::Esc => match b {
+                    b'[' => {
+                        self.params.clear();
+                        State::Csi
+                    }
+                    b']' | b'P' | b'_' | b'^' | b'X' => State::Str,
+                    0x20..=0x2f => State::EscIntermediate,
+                    _ => State::Text,
+                },
+                State::EscIntermediate if (0x20..=0x2f).contains(&b) => {
+                    State::EscIntermediate
+                }
+                State::EscIntermediate => State::Text,
+                State::Csi if (0x40..=0x7e).contains(&b) => {
+                    self.csi(b, &mut res);
+                    State::Text
+                }
+                State::Csi => {
+                    self.params.push(b);
+                    State::Csi
+                }
+                State::Str => match b {
+                    0x07 => State::Text,
+                    0x1b => State::StrEsc,
+                    _ => State::Str,
+                },
+                State::StrEsc => match b {
+                    b'\\' => State::Text,
+                    0x1b => State::StrEsc,
+                    _ => State::Str,
+                },
+            };
+        }
+
+        if !text.is_empty() {
+            res.push(LegacyOp::Text(text));
+        }
+        res
+    }
+
+    fn csi(&self, cmd: u8, res: &mut Vec<LegacyOp>) {
+        // Private and unknown codes with intermediate bytes are not
+        // supported.
+        let Ok(params) = std::str::from_utf8(&self.params) else {
+            return;
+        };
+        if !params
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b == b';' || b == b':')
+        {
+            return;
+        }
+
+        if cmd == b'm' {
+            sgr(params, res);
+            return;
+        }
+
+        let args: Vec<_> =
+            params.split(';').map(|a| a.parse::<usize>().ok()).collect();
+        let arg = |i: usize| args.get(i).copied().flatten();
+        let n = arg(0).unwrap_or(1).max(1);
+        let pos = |i: usize| Some(arg(i).unwrap_or(1).max(1) - 1);
+
+        let op = match cmd {
+            b'A' => LegacyOp::MoveBy {
+                x: 0,
+                y: -(n as isize),
+            },
+            b'B' => LegacyOp::MoveBy {
+                x: 0,
+                y: n as isize,
+            },
+            b'C' => LegacyOp::MoveBy {
+                x: n as isize,
+                y: 0,
+            },
+            b'D' => LegacyOp::MoveBy {
+                x: -(n as isize),
+                y: 0,
+            },
+            b'E' | b'F' => {
+                let y = if cmd == b'E' {
+                    n as isize
+                } else {
+                    -(n as isize)
+                };
+                res.push(LegacyOp::MoveBy { x: 0, y });
+                LegacyOp::MoveTo {
+                    x: Some(0),
+                    y: None,
+                }
+            }
+            b'G' => LegacyOp::MoveTo { x: pos(0), y: None },
+            b'd' => LegacyOp::MoveTo { x: None, y: pos(0) },
+            b'H' | b'f' => LegacyOp::MoveTo {
+                x: pos(1),
+                y: pos(0),
+            },
+            b'J' => LegacyOp::Erase(match arg(0).unwrap_or_default() {
+                0 => Erase::ToScreenEnd,
+                1 => Erase::ToScreenStart,
+                _ => Erase::Screen,
+            }),
+            b'K' => LegacyOp::Erase(match arg(0).unwrap_or_default() {
+                0 => Erase::ToLineEnd,
+                1 => Erase::ToLineStart,
+                _ => Erase::Line,
+            }),
+            _ => return,
+        };
+        res.push(op);
+    }
+}
+
+/// Translates the arguments of SGR code.
+fn sgr(params: &str, res: &mut Vec<LegacyOp>) {
+    let groups: Vec<Vec<_>> = params
+        .split(';')
+        .map(|g| g.split(':').map(|a| a.parse::<usize>().ok()).collect())
+        .collect();
+
+    let mut i = 0;
+    while i < groups.len() {
+        let group = &groups[i];
+        i += 1;
+        let code = group[0].unwrap_or_default();
+        let op = match code {
+            0 => LegacyOp::Reset,
+            1 => LegacyOp::Bold(true),
+            22 => LegacyOp::Bold(false),
+            7 => LegacyOp::Reverse(true),
+            27 => LegacyOp::Reverse(false),
+            30..=37 => LegacyOp::Fg(Some(code as u8 - 30)),
+            90..=97 => LegacyOp::Fg(Some(code as u8 - 90 + 8)),
+            39 => LegacyOp::Fg(None),
+            40..=47 => LegacyOp::Bg(Some(code as u8 - 40)),
+            100..=107 => LegacyOp::Bg(Some(code as u8 - 100 + 8)),
+            49 => LegacyOp::Bg(None),
+            38 | 48 => {
+                let color = if group.len() > 1 {
+                    extended_color(&group[1..])
+                } else {
+                    // The arguments are separated with `;`.
+                    let len = match groups.get(i).map(|g| g[0]) {
+                        Some(Some(5)) => 2,
+                        Some(Some(2)) => 4,
+                        _ => 1,
+                    };
+                    let args: Vec<_> =
+                        groups[i..].iter().take(len).map(|g| g[0]).collect();
+                    i += len;
+                    extended_color(&args)
+                };
+                let Some(color) = color else {
+                    continue;
+                };
+                if code == 38 {
+                    LegacyOp::Fg(Some(color))
+                } else {
+                    LegacyOp::Bg(Some(color))
+                }
+            }
+            _ => continue,
+        };
+        res.push(op);
+    }
+}
+
+/// Gets the nearest basic color for the extended color arguments (after
+/// `38` or `48`).
+fn extended_color(args: &[Option<usize>]) -> Option<u8> {
+    match args {
+        [Some(5), Some(c), ..] => color_256(*c),
+        [Some(2), .., Some(r), Some(g), Some(b)] => {
+            Some(nearest_color(*r as u8, *g as u8, *b as u8))
+        }
+        _ => None,
+    }
+}
+
+/// Gets the nearest basic color for color from the 256 color palette.
+fn color_256(c: usize) -> Option<u8> {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match c {
+        0..=15 => Some(c as u8),
+        16..=231 => {
+            let c = c - 16;
+            Some(nearest_color(
+                LEVELS[c / 36],
+                LEVELS[c / 6 % 6],
+                LEVELS[c % 6],
+            ))
+        }
+        232..=255 => {
+            let g = 8 + 10 * (c - 232) as u8;
+            Some(nearest_color(g, g, g))
+        }
+        _ => None,
+    }
+}
+
+/// Gets the index of the nearest color in the legacy console palette.
+fn nearest_color(r: u8, g: u8, b: u8) -> u8 {
+    let dist = |(pr, pg, pb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, pr) + d(g, pg) + d(b, pb)
+    };
+    (0..16)
+        .min_by_key(|&i| dist(PALETTE[i as usize]))
+        .unwrap_or_default()
+}
@@ -1,14 +1,43 @@
+mod cursor_style_guard;
 mod io_provider;
+mod modes;
+mod mouse_capture;
+mod newline;
+mod raw_mode_guard;
+mod read_provider;
+mod split;
 mod stdio_provider;
 mod sys;
 mod terminal;
+mod tty_provider;
+mod tui_session;
 mod wait_for_in;
 
 pub use self::{
-    io_provider::*, stdio_provider::*, sys::*, terminal::*, wait_for_in::*,
+    cursor_style_guard::*, io_provider::*, modes::*, mouse_capture::*,
+    newline::*, raw_mode_guard::*, read_provider::*, split::*,
+    stdio_provider::*, sys::*, terminal::*, tty_provider::*, tui_session::*,
+    wait_for_in::*,
 };
 
+#[cfg(all(feature = "term_image", feature = "events"))]
+mod show_image;
+#[cfg(all(feature = "term_image", feature = "events"))]
+pub use show_image::*;
+
+#[cfg(feature = "events")]
+pub mod request;
+#[cfg(feature = "events")]
+pub use request::{RequestPolicy, RequestStats, Support};
+
+#[cfg(feature = "events")]
+pub mod debug;
 #[cfg(feature = "events")]
 pub mod events;
+pub mod legacy;
+#[cfg(feature = "pty")]
+pub mod pty;
 #[cfg(feature = "readers")]
 pub mod readers;
+pub mod recording;
+pub mod testing;
@@ -0,0 +1,244 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use crate::codes;
+
+use super::{Encoding, MouseMode};
+
+/// Private modes that are believed to be set in the terminal. They are
+/// shared by the whole process, so that libraries that use termal
+/// independently can see what the others have set.
+static MODES: Mutex<ActiveModes> = Mutex::new(ActiveModes::new());
+
+/// Private modes that are interesting to query with DECRQM.
+#[cfg(feature = "events")]
+pub(crate) const KNOWN_MODES: &[u32] = &[
+    25, 1000, 1002, 1003, 1004, 1005, 1006, 1007, 1015, 1016, 1049, 2004,
+];
+
+/// Private terminal modes that termal believes are set or unset.
+///
+/// The state of the modes is tracked from the codes that termal writes to
+/// the terminal (through [`super::Terminal`], [`super::MouseCapture`],
+/// [`super::TuiSession`] and [`crate::reset_terminal`]) and it may be
+/// verified by asking the terminal (see `Terminal::verify_modes`). Modes set
+/// by other means are not known unless they are verified.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActiveModes {
+    modes: BTreeMap<u32, ModeInfo>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ModeInfo {
+    enabled: bool,
+    verified: bool,
+}
+
+/// Change of the private modes found by [`ModeTracker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ModeChange {
+    /// Full reset, the state of all modes is unknown.
+    Reset,
+    /// The mode was set or reset.
+    Set(u32, bool),
+}
+
+/// Incremental scanner of the codes that change private modes. The codes
+/// may be split across multiple calls of [`ModeTracker::feed`], the state
+/// of the incomplete code is kept between the calls.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ModeTracker {
+    state: TrackState,
+    /// Modes of the current DECSET or DECRST.
+    modes: Vec<u32>,
+    /// The mode that is being parsed.
+    cur: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum TrackState {
+    #[default]
+    Text,
+    /// After escape.
+    Esc,
+    /// After `ESC [`.
+    Csi,
+    /// In the parameters after `ESC [ ?`.
+    Private,
+}
+
+impl ActiveModes {
+    /// Creates empty set of modes where no mode has known state.
+    pub const fn new() -> Self {
+        Self {
+            modes: BTreeMap::new(),
+        }
+    }
+
+    /// Gets the believed state of the mode. Returns [`None`] if the state is
+    /// not known.
+    pub fn get(&self, mode: u32) -> Option<bool> {
+        self.modes.get(&mode).map(|m| m.enabled)
+    }
+
+    /// Checks whether the mode is believed to be enabled.
+    pub fn is_enabled(&self, mode: u32) -> bool {
+        self.get(mode).unwrap_or_default()
+    }
+
+    /// Checks whether the state of the mode was verified by the terminal.
+    pub fn is_verified(&self, mode: u32) -> bool {
+        self.modes.get(&mode).is_some_and(|m| m.verified)
+    }
+
+    /// Iterates over the modes that are believed to be enabled.
+    pub fn enabled(&self) -> impl Iterator<Item = u32> + '_ {
+        self.modes
+            .iter()
+            .filter(|(_, m)| m.enabled)
+            .map(|(m, _)| *m)
+    }
+
+    /// Iterates over all the modes with known state.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, bool)> + '_ {
+        self.modes.iter().map(|(m, i)| (*m, i.enabled))
+    }
+
+    /// Gets the enabled mouse tracking mode. If more modes are enabled, the
+    /// one that reports the most events is returned, because that is what
+    /// the terminal will do.
+    pub fn mouse_mode(&self) -> Option<MouseMode> {
+        [MouseMode::All, MouseMode::Drag, MouseMode::Press]
+            .into_iter()
+            .find(|m| self.is_enabled(m.id()))
+    }
+
+    /// Gets the enabled encoding of mouse events.
+    pub fn mouse_encoding(&self) -> Encoding {
+        [
+            Encoding::SgrPixels,
+            Encoding::Sgr,
+            Encoding::Urxvt,
+            Encoding::Utf8,
+        ]
+        .into_iter()
+        .find(|e| e.id().is_some_and(|id| self.is_enabled(id)))
+        .unwrap_or(Encoding::Default)
+    }
+
+    /// Checks whether bracketed paste mode is enabled.
+    pub fn bracketed_paste(&self) -> bool {
+        self.is_enabled(2004)
+    }
+
+    /// Checks whether alternate scroll mode is enabled.
+    pub fn alternate_scroll(&self) -> bool {
+        self.is_enabled(1007)
+    }
+
+    /// Checks whether the alternate buffer is enabled.
+    pub fn alternate_buffer(&self) -> bool {
+        self.is_enabled(1049)
+    }
+
+    /// Checks whether focus events are enabled.
+    pub fn focus_events(&self) -> bool {
+        self.is_enabled(1004)
+    }
+
+    /// Sets the believed state of the mode.
+    pub fn set(&mut self, mode: u32, enabled: bool, verified: bool) {
+        self.modes.insert(mode, ModeInfo { enabled, verified });
+    }
+
+    /// Forgets the state of all the modes.
+    pub fn clear(&mut self) {
+        self.modes.clear();
+    }
+
+    /// Updates the modes from the codes in `s`. Codes that set and reset
+    /// private modes (DECSET and DECRST) are recognized. Full reset
+    /// ([`codes::FULL_RESET`]) forgets the state of all modes.
+    pub fn track(&mut self, s: &str) {
+        ModeTracker::default().feed(s.as_bytes(), |c| self.apply(c));
+    }
+
+    pub(crate) fn apply(&mut self, change: ModeChange) {
+        match change {
+            ModeChange::Reset => self.clear(),
+            ModeChange::Set(mode, enabled) => self.set(mode, enabled, false),
+        }
+    }
+}
+
+impl ModeTracker {
+    /// Checks whether the data fed so far ends inside escape sequence.
+    pub(crate) fn is_pending(&self) -> bool {
+        self.state != TrackState::Text
+    }
+
+    /// Scans the data and calls `f` for each change of modes.
+    pub(crate) fn feed(&mut self, data: &[u8], mut f: impl FnMut(ModeChange)) {
+        for &b in data {
+            self.state = match (self.state, b) {
+                (_, 0x1b) => TrackState::Esc,
+                (TrackState::Esc, b'c') => {
+                    f(ModeChange::Reset);
+                    TrackState::Text
+                }
+                (TrackState::Esc, b'[') => TrackState::Csi,
+                (TrackState::Csi, b'?') => {
+                    self.modes.clear();
+                    self.cur = None;
+                    TrackState::Private
+                }
+                (TrackState::Private, b'0'..=b'9') => {
+                    let d = (b - b'0') as u64;
+                    let cur = self.cur.unwrap_or_default();
+                    self.cur = Some(cur.saturating_mul(10).saturating_add(d));
+                    TrackState::Private
+                }
+                (TrackState::Private, b';') => {
+                    self.push_mode();
+                    TrackState::Private
+                }
+                (TrackState::Private, b'h' | b'l') => {
+                    self.push_mode();
+                    for &m in &self.modes {
+                        f(ModeChange::Set(m, b == b'h'));
+                    }
+                    TrackState::Text
+                }
+                _ => TrackState::Text,
+            };
+        }
+    }
+
+    fn push_mode(&mut self) {
+        if let Some(m) = self.cur.take().and_then(|m| m.try_into().ok()) {
+            self.modes.push(m);
+        }
+    }
+}
+
+/// Gets the private modes that termal believes are set in the terminal of
+/// this process. See [`ActiveModes`].
+pub fn active_modes() -> ActiveModes {
+    MODES.lock().map(|m| m.clone()).unwrap_or_default()
+}
+
+/// Updates the believed state of the private modes from the codes written
+/// to the terminal. This is done automatically for codes written by termal,
+/// but codes written by other means may be tracked with this.
+pub fn track_modes(s: &str) {
+    if !s.contains(codes::ESC) {
+        return;
+    }
+    modify_active_modes(|m| m.track(s));
+}
+
+/// Modifies the believed state of the private modes.
+pub fn modify_active_modes(f: impl FnOnce(&mut ActiveModes)) {
+    if let Ok(mut m) = MODES.lock() {
+        f(&mut m);
+    }
+}
@@ -0,0 +1,169 @@
+use std::io::{self, Write};
+
+use crate::{codes, error::Result};
+
+use super::track_modes;
+
+/// Determines which mouse events will be reported by the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseMode {
+    /// Report button presses and releases (including the mouse wheel).
+    Press,
+    /// Same as [`MouseMode::Press`], but also report mouse movement while
+    /// button is pressed.
+    Drag,
+    /// Report all mouse events including move without any button pressed.
+    All,
+}
+
+/// Determines how the terminal encodes the mouse events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Encoding {
+    /// The default encoding. Coordinates are limited to 223.
+    Default,
+    /// Coordinates are encoded as utf8 characters. Coordinates are limited
+    /// to 2015.
+    Utf8,
+    /// SGR encoding. Coordinates are not limited. This is the recommended
+    /// encoding.
+    #[default]
+    Sgr,
+    /// URXVT encoding. Not recommended, rather use [`Encoding::Sgr`].
+    Urxvt,
+    /// Same as [`Encoding::Sgr`] but the coordinates are in pixels.
+    SgrPixels,
+}
+
+/// Guard that enables mouse capture and disables it when dropped.
+///
+/// The codes are written to stdout. If the guard is leaked (e.g. due to
+/// panic), mouse capture will still be disabled by
+/// [`crate::reset_terminal`].
+#[derive(Debug)]
+pub struct MouseCapture {
+    mode: MouseMode,
+    encoding: Encoding,
+}
+
+impl MouseMode {
+    /// Gets the id of the private mode of this mouse mode.
+    pub const fn id(&self) -> u32 {
+        match self {
+            Self::Press => 1000,
+            Self::Drag => 1002,
+            Self::All => 1003,
+        }
+    }
+
+    /// Gets the code that enables this mouse mode.
+    pub const fn enable_code(&self) -> &'static str {
+        match self {
+            Self::Press => codes::ENABLE_MOUSE_XY_PR_TRACKING,
+            Self::Drag => codes::ENABLE_MOUSE_XY_DRAG_TRACKING,
+            Self::All => codes::ENABLE_MOUSE_XY_ALL_TRACKING,
+        }
+    }
+
+    /// Gets the code that disables this mouse mode.
+    pub const fn disable_code(&self) -> &'static str {
+        match self {
+            Self::Press => codes::DISABLE_MOUSE_XY_PR_TRACKING,
+            Self::Drag => codes::DISABLE_MOUSE_XY_DRAG_TRACKING,
+            Self::All => codes::DISABLE_MOUSE_XY_ALL_TRACKING,
+        }
+    }
+}
+
+impl Encoding {
+    /// Gets the id of the private mode of this encoding. The default
+    /// encoding doesn't have any mode.
+    pub const fn id(&self) -> Option<u32> {
+        match self {
+            Self::Default => None,
+            Self::Utf8 => Some(1005),
+            Self::Sgr => Some(1006),
+            Self::Urxvt => Some(1015),
+            Self::SgrPixels => Some(1016),
+        }
+    }
+
+    /// Gets the code that enables this encoding.
+    pub const fn enable_code(&self) -> &'static str {
+        match self {
+            Self::Default => "",
+            Self::Utf8 => codes::ENABLE_MOUSE_XY_UTF8_EXT,
+            Self::Sgr => codes::ENABLE_MOUSE_XY_EXT,
+            Self::Urxvt => codes::ENABLE_MOUSE_XY_URXVT_EXT,
+            Self::SgrPixels => codes::ENABLE_MOUSE_XY_PIX_EXT,
+        }
+    }
+
+    /// Gets the code that disables this encoding.
+    pub const fn disable_code(&self) -> &'static str {
+        match self {
+            Self::Default => "",
+            Self::Utf8 => codes::DISABLE_MOUSE_XY_UTF8_EXT,
+            Self::Sgr => codes::DISABLE_MOUSE_XY_EXT,
+            Self::Urxvt => codes::DISABLE_MOUSE_XY_URXVT_EXT,
+            Self::SgrPixels => codes::DISABLE_MOUSE_XY_PIX_EXT,
+        }
+    }
+}
+
+impl MouseCapture {
+    /// Enables mouse capture with the given mode and encoding. The mouse
+    /// capture is disabled when the returned guard is dropped.
+    pub fn enable(mode: MouseMode, encoding: Encoding) -> Result<Self> {
+        let res = Self { mode, encoding };
+        let mut out = io::stdout().lock();
+        let code = res.enable_code();
+        out.write_all(code.as_bytes())?;
+        track_modes(&code);
+        out.flush()?;
+        Ok(res)
+    }
+
+    /// Gets the mouse mode of this capture.
+    pub fn mode(&self) -> MouseMode {
+        self.mode
+    }
+
+    /// Gets the encoding of this capture.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Gets the code that enables mouse capture with the mode and encoding
+    /// of this capture.
+    pub fn enable_code(&self) -> String {
+        [self.mode.enable_code(), self.encoding.enable_code()].concat()
+    }
+
+    /// Gets the code that disables mouse capture with the mode and encoding
+    /// of this capture.
+    pub fn disable_code(&self) -> String {
+        [self.encoding.disable_code(), self.mode.disable_code()].concat()
+    }
+
+    /// Disables the mouse capture. Unlike drop, this will report errors.
+    pub fn disable(self) -> Result<()> {
+        let res = self.write_disable();
+        std::mem::forget(self);
+        res
+    }
+
+    fn write_disable(&self) -> Result<()> {
+        let mut out = io::stdout().lock();
+        let code = self.disable_code();
+        out.write_all(code.as_bytes())?;
+        track_modes(&code);
+        out.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for MouseCapture {
+    fn drop(&mut self) {
+        _ = self.write_disable();
+    }
+}
@@ -0,0 +1,51 @@
+use super::is_raw_mode_enabled;
+
+/// Determines how [`crate::raw::Terminal`] translates newlines (`\n`) when
+/// printing.
+///
+/// In raw mode, the terminal doesn't move the cursor to the start of the line
+/// on newline, so `\n` must be followed by `\r`. If the terminal still
+/// translates newlines (e.g. `ONLCR` is enabled), the translation should be
+/// disabled to avoid doubled carriage returns.
+/// The policy is checked at the time of writing, so the translation follows
+/// raw mode as it is enabled and disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NewlinePolicy {
+    /// Never translate newlines. Use this when piping data that must not be
+    /// modified.
+    Off,
+    /// Always translate `\n` to `\r\n`.
+    Crlf,
+    /// Translate `\n` to `\r\n` only if the output is terminal in raw mode.
+    #[default]
+    Platform,
+    /// Translate `\n` to `\r\n` only if raw mode is enabled by termal (see
+    /// [`crate::raw::is_raw_mode_enabled`]). Unlike
+    /// [`NewlinePolicy::Platform`], this doesn't depend on the io provider,
+    /// so it works also with custom outputs.
+    RawMode,
+}
+
+impl NewlinePolicy {
+    /// Checks whether newlines should be translated. `raw_terminal` tells
+    /// whether the output is terminal in raw mode. It is ignored by
+    /// [`NewlinePolicy::RawMode`] which checks raw mode on each call.
+    pub fn translates(&self, raw_terminal: bool) -> bool {
+        match self {
+            Self::Off => false,
+            Self::Crlf => true,
+            Self::Platform => raw_terminal,
+            Self::RawMode => is_raw_mode_enabled(),
+        }
+    }
+
+    /// Gets the string that should be printed for newline. `raw_terminal`
+    /// tells whether the output is terminal in raw mode.
+    pub fn newline(&self, raw_terminal: bool) -> &'static str {
+        if self.translates(raw_terminal) {
+            "\r\n"
+        } else {
+            "\n"
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Spawning processes in pseudo terminal.
+//!
+//! The child process sees the pseudo terminal as its terminal, so it behaves
+//! the same as if it was run in real terminal. Everything it writes can be
+//! read from [`Pty`] and everything written to [`Pty`] is received by the
+//! child as input. This can be used to build terminal recorders and
+//! wrappers.
+//!
+//! # Examples
+//! ```no_run
+//! use std::{io::Read, process::Command};
+//! use termal_core::raw::pty::Pty;
+//!
+//! let mut pty = Pty::spawn_sized(Command::new("ls"), 80, 24)?;
+//! let mut out = vec![];
+//! // Reading fails with error once the child exits on linux.
+//! _ = pty.read_to_end(&mut out);
+//! pty.wait()?;
+//! # Ok::<_, termal_core::error::Error>(())
+//! ```
+
+use std::{
+    fs::File,
+    io::{Read, Write},
+    process::{Child, Command, ExitStatus},
+};
+
+use crate::error::{Error, Result};
+
+use super::{Terminal, TtyProvider};
+
+/// Child process running in pseudo terminal.
+///
+/// [`Read`] reads the output of the child and [`Write`] sends input to the
+/// child. The child is not killed when this is dropped.
+///
+/// # Support
+/// - Unix (Linux)
+#[derive(Debug)]
+pub struct Pty {
+    master: File,
+    child: Child,
+}
+
+impl Pty {
+    /// Allocates new pseudo terminal and spawns the command in it. The
+    /// standard input, output and error of the command are set to the pseudo
+    /// terminal.
+    pub fn spawn(mut cmd: Command) -> Result<Self> {
+        #[cfg(unix)]
+        {
+            let (master, child) = super::sys::spawn_pty(&mut cmd)?;
+            return Ok(Self { master, child });
+        }
+
+        #[allow(unreachable_code)]
+        Err(Error::NotSupportedOnPlatform("pseudo terminal"))
+    }
+
+    /// Allocates new pseudo terminal with the given size in characters and
+    /// spawns the command in it. See [`Pty::spawn`].
+    pub fn spawn_sized(cmd: Command, w: usize, h: usize) -> Result<Self> {
+        let res = Self::spawn(cmd)?;
+        res.resize(w, h)?;
+        Ok(res)
+    }
+
+    /// Sets the size of the pseudo terminal in characters. The child is
+    /// notified with `SIGWINCH`.
+    pub fn resize(&self, w: usize, h: usize) -> Result<()> {
+        #[cfg(unix)]
+        return super::sys::resize_pty(&self.master, w, h);
+
+        #[allow(unreachable_code)]
+        Err(Error::NotSupportedOnPlatform("pseudo terminal"))
+    }
+
+    /// Gets the child process.
+    pub fn child(&self) -> &Child {
+        &self.child
+    }
+
+    /// Gets the child process.
+    pub fn child_mut(&mut self) -> &mut Child {
+        &mut self.child
+    }
+
+    /// Waits for the child to exit.
+    pub fn wait(&mut self) -> Result<ExitStatus> {
+        Ok(self.child.wait()?)
+    }
+
+    /// Checks if the child has exited without blocking.
+    pub fn try_wait(&mut self) -> Result<Option<ExitStatus>> {
+        Ok(self.child.try_wait()?)
+    }
+
+    /// Gets the master side of the pseudo terminal.
+    pub fn master(&self) -> &File {
+        &self.master
+    }
+
+    /// Creates io provider for the master side of the pseudo terminal. It
+    /// can be used from another thread.
+    pub fn provider(&self) -> Result<TtyProvider> {
+        TtyProvider::from_fd(self.master.try_clone()?)
+    }
+
+    /// Creates terminal for the master side of the pseudo terminal. Events
+    /// read from the terminal are parsed from the output of the child (e.g.
+    /// requests that the child sends to its terminal).
+    pub fn terminal(&self) -> Result<Terminal<TtyProvider>> {
+        Ok(Terminal::new(self.provider()?))
+    }
+}
+
+impl Read for Pty {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.master.read(buf)
+    }
+}
+
+impl Write for Pty {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.master.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.master.flush()
+    }
+}
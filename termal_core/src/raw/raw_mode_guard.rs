@@ -0,0 +1,73 @@
+use std::sync::{Mutex, MutexGuard};
+
+use crate::error::Result;
+
+use super::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled};
+
+struct GuardState {
+    /// Number of living guards.
+    count: usize,
+    /// Whether raw mode was enabled before the first guard was created.
+    was_enabled: bool,
+}
+
+static GUARD_STATE: Mutex<GuardState> = Mutex::new(GuardState {
+    count: 0,
+    was_enabled: false,
+});
+
+fn get_guard_state() -> MutexGuard<'static, GuardState> {
+    GUARD_STATE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Guard that keeps raw mode enabled while it is alive. See
+/// [`raw_mode_guard`].
+#[derive(Debug)]
+pub struct RawModeGuard {
+    _private: (),
+}
+
+/// Enables raw mode and returns guard that restores the previous state of raw
+/// mode when dropped.
+///
+/// The guards may be nested. Raw mode is restored only when the last of the
+/// guards is dropped. If raw mode was already enabled before the first guard
+/// was created, it is left enabled.
+///
+/// # Support
+/// - Unix (Linux)
+/// - Windows (not tested)
+pub fn raw_mode_guard() -> Result<RawModeGuard> {
+    RawModeGuard::new()
+}
+
+impl RawModeGuard {
+    /// Enables raw mode and returns guard that restores the previous state of
+    /// raw mode when dropped. Same as [`raw_mode_guard`].
+    pub fn new() -> Result<Self> {
+        let mut state = get_guard_state();
+        if state.count == 0 {
+            state.was_enabled = is_raw_mode_enabled();
+            if !state.was_enabled {
+                enable_raw_mode()?;
+            }
+        }
+        state.count += 1;
+        Ok(Self { _private: () })
+    }
+
+    /// Gets the number of currently living raw mode guards.
+    pub fn count() -> usize {
+        get_guard_state().count
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let mut state = get_guard_state();
+        state.count -= 1;
+        if state.count == 0 && !state.was_enabled {
+            _ = disable_raw_mode();
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::{
+    cell::{Cell, RefCell},
+    io::{self, BufRead, Read, Write},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use crate::error::Result;
+
+use super::{IoProvider, Terminal, ValueOrMut, WaitForIn};
+
+/// Size of the buffer used to read on the background thread.
+const READ_CHUNK: usize = 4096;
+
+/// IoProvider that reads from any reader (e.g. socket, serial port or child
+/// pty) and writes to any writer. The reader is read on background thread
+/// and the data is received through channel, so it is possible to wait for
+/// input even if the reader doesn't support it.
+///
+/// The background thread ends when the reader reaches end of input, fails
+/// or when it reads after the provider is dropped.
+#[derive(Debug)]
+pub struct ReadProvider<W: Write> {
+    input: ReadIn,
+    output: W,
+}
+
+/// Input of [`ReadProvider`].
+#[derive(Debug)]
+pub struct ReadIn {
+    rx: Receiver<io::Result<Vec<u8>>>,
+    /// Data received while waiting for input.
+    received: RefCell<Option<io::Result<Vec<u8>>>>,
+    buf: Vec<u8>,
+    pos: usize,
+    eof: Cell<bool>,
+}
+
+impl<W: Write> ReadProvider<W> {
+    /// Creates provider that reads from `input` on background thread and
+    /// writes to `output`.
+    pub fn new<R>(input: R, output: W) -> Self
+    where
+        R: Read + Send + 'static,
+    {
+        Self {
+            input: ReadIn::new(input),
+            output,
+        }
+    }
+}
+
+impl<W: Write> Terminal<ReadProvider<W>> {
+    /// Creates terminal that reads from `input` and writes to `output`. See
+    /// [`ReadProvider`].
+    pub fn from_read<R>(input: R, output: W) -> Self
+    where
+        R: Read + Send + 'static,
+    {
+        Self::new(ReadProvider::new(input, output))
+    }
+}
+
+impl ReadIn {
+    /// Starts reading `input` on background thread.
+    pub fn new<R>(mut input: R) -> Self
+    where
+        R: Read + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || loop {
+            let mut buf = vec![0; READ_CHUNK];
+            let res = match input.read(&mut buf) {
+                Ok(0) => return,
+                Ok(n) => {
+                    buf.truncate(n);
+                    Ok(buf)
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => Err(e),
+            };
+            let fail = res.is_err();
+            if tx.send(res).is_err() || fail {
+                return;
+            }
+        });
+
+        Self {
+            rx,
+            received: RefCell::new(None),
+            buf: vec![],
+            pos: 0,
+            eof: Cell::new(false),
+        }
+    }
+}
+
+impl<W: Write> WaitForIn for ReadProvider<W> {
+    fn wait_for_in(&self, timeout: Duration) -> Result<bool> {
+        self.input.wait_for_in(timeout)
+    }
+}
+
+impl<W: Write> IoProvider for ReadProvider<W> {
+    type Out = W;
+    type In = ReadIn;
+
+    fn get_out(&mut self) -> ValueOrMut<'_, Self::Out> {
+        ValueOrMut::Mut(&mut self.output)
+    }
+
+    fn get_in(&mut self) -> ValueOrMut<'_, Self::In> {
+        ValueOrMut::Mut(&mut self.input)
+    }
+}
+
+impl WaitForIn for ReadIn {
+    fn wait_for_in(&self, timeout: Duration) -> Result<bool> {
+        if self.pos < self.buf.len()
+            || self.eof.get()
+            || self.received.borrow().is_some()
+        {
+            return Ok(true);
+        }
+
+        match self.rx.recv_timeout(timeout) {
+            Ok(data) => {
+                *self.received.borrow_mut() = Some(data);
+                Ok(true)
+            }
+            Err(RecvTimeoutError::Timeout) => Ok(false),
+            Err(RecvTimeoutError::Disconnected) => {
+                // Reading will return end of input immediately.
+                self.eof.set(true);
+                Ok(true)
+            }
+        }
+    }
+}
+
+impl Read for ReadIn {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let data = self.fill_buf()?;
+        let len = buf.len().min(data.len());
+        buf[..len].copy_from_slice(&data[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl BufRead for ReadIn {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos >= self.buf.len() && !self.eof.get() {
+            let data = match self.received.take() {
+                Some(data) => Some(data),
+                None => self.rx.recv().ok(),
+            };
+            match data {
+                Some(data) => {
+                    self.buf = data?;
+                    self.pos = 0;
+                }
+                None => self.eof.set(true),
+            }
+        }
+        Ok(&self.buf[self.pos.min(self.buf.len())..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.buf.len());
+    }
+}
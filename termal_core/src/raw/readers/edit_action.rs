@@ -0,0 +1,203 @@
+use crate::raw::events::{KeyBinding, KeyCode, KeyMap, Modifiers};
+
+/// Editing action of [`super::TermRead`] that may be bound to key with
+/// [`super::TermRead::set_bindings`] or [`super::ReadConf::bindings`]. The
+/// keys of the normal mode of [`super::EditMode::Vi`] are bound with
+/// [`super::TermRead::set_vi_bindings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EditAction {
+    /// Moves the cursor one character left.
+    Left,
+    /// Moves the cursor one character right. At the end of the input, it
+    /// accepts the hint of the validator.
+    Right,
+    /// Moves the cursor to the start of the previous word.
+    WordLeft,
+    /// Moves the cursor to the end of the next word.
+    WordRight,
+    /// Moves the cursor to the start of the input.
+    Home,
+    /// Moves the cursor to the end of the input.
+    End,
+    /// Deletes the character before the cursor.
+    Backspace,
+    /// Deletes the character under the cursor.
+    Delete,
+    /// Cancels empty input with [`crate::error::Error::Eof`] if
+    /// [`super::CancelPolicy::eof`] is set. Otherwise it is the same as
+    /// [`EditAction::Delete`].
+    DeleteOrEof,
+    /// Deletes the input from the cursor to the end and stores it in the kill
+    /// ring.
+    KillLine,
+    /// Deletes the input from the start to the cursor and stores it in the
+    /// kill ring.
+    KillToStart,
+    /// Deletes the word before the cursor and stores it in the kill ring.
+    KillWordLeft,
+    /// Inserts the last killed text at the cursor. Consecutive kills are
+    /// joined into single text.
+    Yank,
+    /// Replaces the text inserted by [`EditAction::Yank`] with the previous
+    /// text in the kill ring. It does nothing if the previous action wasn't
+    /// yank.
+    YankPop,
+    /// Swaps the character before the cursor with the character under the
+    /// cursor and moves the cursor right. At the end of the input, it swaps
+    /// the last two characters.
+    Transpose,
+    /// Requests the selection from the terminal and inserts it.
+    Paste,
+    /// Ends the input as if the exit condition was met.
+    Accept,
+    /// Cancels the input with [`crate::error::Error::Interrupted`] if
+    /// [`super::CancelPolicy::interrupt`] is set.
+    Cancel,
+    /// Switches to the normal mode in [`super::EditMode::Vi`] and moves the
+    /// cursor one character left.
+    NormalMode,
+    /// Switches to the insert mode in [`super::EditMode::Vi`].
+    Insert,
+    /// Moves the cursor one character right and switches to the insert mode.
+    Append,
+    /// Moves the cursor to the start and switches to the insert mode.
+    InsertStart,
+    /// Moves the cursor to the end and switches to the insert mode.
+    AppendEnd,
+    /// Deletes the character under the cursor and switches to the insert
+    /// mode.
+    Substitute,
+    /// Deletes the input from the cursor to the end, stores it in the kill
+    /// ring and switches to the insert mode.
+    ChangeToEnd,
+    /// Deletes the whole input, stores it in the kill ring and switches to
+    /// the insert mode.
+    ChangeLine,
+    /// Action of the application. The callback set with
+    /// [`super::TermRead::set_custom_action`] is called with the id.
+    Custom(u32),
+}
+
+impl EditAction {
+    /// Gets the default key bindings:
+    ///
+    /// | Key                       | Action                        |
+    /// |---------------------------|-------------------------------|
+    /// | `left`, `ctrl+b`          | [`EditAction::Left`]          |
+    /// | `right`, `ctrl+f`         | [`EditAction::Right`]         |
+    /// | `ctrl+left`, `alt+b`      | [`EditAction::WordLeft`]      |
+    /// | `ctrl+right`, `alt+f`     | [`EditAction::WordRight`]     |
+    /// | `home`, `ctrl+a`          | [`EditAction::Home`]          |
+    /// | `end`, `ctrl+e`           | [`EditAction::End`]           |
+    /// | `backspace`               | [`EditAction::Backspace`]     |
+    /// | `delete`                  | [`EditAction::Delete`]        |
+    /// | `ctrl+d`                  | [`EditAction::DeleteOrEof`]   |
+    /// | `ctrl+k`                  | [`EditAction::KillLine`]      |
+    /// | `ctrl+u`                  | [`EditAction::KillToStart`]   |
+    /// | `ctrl+w`                  | [`EditAction::KillWordLeft`]  |
+    /// | `ctrl+y`                  | [`EditAction::Yank`]          |
+    /// | `alt+y`                   | [`EditAction::YankPop`]       |
+    /// | `ctrl+t`                  | [`EditAction::Transpose`]     |
+    /// | `ctrl+v`                  | [`EditAction::Paste`]         |
+    /// | `ctrl+c`                  | [`EditAction::Cancel`]        |
+    pub fn default_bindings() -> KeyMap<Self> {
+        let ctrl = |c| KeyBinding::new(KeyCode::Char(c), Modifiers::CONTROL);
+        let alt = |c| KeyBinding::new(KeyCode::Char(c), Modifiers::ALT);
+        let none = |k| KeyBinding::new(k, Modifiers::NONE);
+        let ctrl_key = |k| KeyBinding::new(k, Modifiers::CONTROL);
+        [
+            (none(KeyCode::Left), Self::Left),
+            (ctrl('b'), Self::Left),
+            (none(KeyCode::Right), Self::Right),
+            (ctrl('f'), Self::Right),
+            (ctrl_key(KeyCode::Left), Self::WordLeft),
+            (alt('b'), Self::WordLeft),
+            (ctrl_key(KeyCode::Right), Self::WordRight),
+            (alt('f'), Self::WordRight),
+            (none(KeyCode::Home), Self::Home),
+            (ctrl('a'), Self::Home),
+            (none(KeyCode::End), Self::End),
+            (ctrl('e'), Self::End),
+            (none(KeyCode::Backspace), Self::Backspace),
+            (none(KeyCode::Delete), Self::Delete),
+            (ctrl('d'), Self::DeleteOrEof),
+            (ctrl('k'), Self::KillLine),
+            (ctrl('u'), Self::KillToStart),
+            (ctrl('w'), Self::KillWordLeft),
+            (ctrl('y'), Self::Yank),
+            (alt('y'), Self::YankPop),
+            (ctrl('t'), Self::Transpose),
+            (ctrl('v'), Self::Paste),
+            (ctrl('c'), Self::Cancel),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    /// Gets the default key bindings of the normal mode in
+    /// [`super::EditMode::Vi`]. Keys that are not bound are ignored in the
+    /// normal mode.
+    ///
+    /// | Key                           | Action                        |
+    /// |-------------------------------|-------------------------------|
+    /// | `h`, `left`, `backspace`      | [`EditAction::Left`]          |
+    /// | `l`, `right`, `space`         | [`EditAction::Right`]         |
+    /// | `b`, `ctrl+left`              | [`EditAction::WordLeft`]      |
+    /// | `w`, `e`, `ctrl+right`        | [`EditAction::WordRight`]     |
+    /// | `0`, `^`, `home`              | [`EditAction::Home`]          |
+    /// | `$`, `end`                    | [`EditAction::End`]           |
+    /// | `x`, `delete`                 | [`EditAction::Delete`]        |
+    /// | `X`                           | [`EditAction::Backspace`]     |
+    /// | `D`                           | [`EditAction::KillLine`]      |
+    /// | `P`                           | [`EditAction::Yank`]          |
+    /// | `i`, `insert`                 | [`EditAction::Insert`]        |
+    /// | `a`                           | [`EditAction::Append`]        |
+    /// | `I`                           | [`EditAction::InsertStart`]   |
+    /// | `A`                           | [`EditAction::AppendEnd`]     |
+    /// | `s`                           | [`EditAction::Substitute`]    |
+    /// | `C`                           | [`EditAction::ChangeToEnd`]   |
+    /// | `S`                           | [`EditAction::ChangeLine`]    |
+    /// | `ctrl+d`                      | [`EditAction::DeleteOrEof`]   |
+    /// | `ctrl+c`                      | [`EditAction::Cancel`]        |
+    pub fn vi_normal_bindings() -> KeyMap<Self> {
+        let key = |c| KeyBinding::new(KeyCode::Char(c), Modifiers::NONE);
+        let shift = |c| KeyBinding::new(KeyCode::Char(c), Modifiers::SHIFT);
+        let ctrl = |c| KeyBinding::new(KeyCode::Char(c), Modifiers::CONTROL);
+        let ctrl_key = |k| KeyBinding::new(k, Modifiers::CONTROL);
+        [
+            (key('h'), Self::Left),
+            (KeyCode::Left.into(), Self::Left),
+            (KeyCode::Backspace.into(), Self::Left),
+            (key('l'), Self::Right),
+            (KeyCode::Right.into(), Self::Right),
+            (KeyCode::Space.into(), Self::Right),
+            (key('b'), Self::WordLeft),
+            (ctrl_key(KeyCode::Left), Self::WordLeft),
+            (key('w'), Self::WordRight),
+            (key('e'), Self::WordRight),
+            (ctrl_key(KeyCode::Right), Self::WordRight),
+            (key('0'), Self::Home),
+            (key('^'), Self::Home),
+            (KeyCode::Home.into(), Self::Home),
+            (key('$'), Self::End),
+            (KeyCode::End.into(), Self::End),
+            (key('x'), Self::Delete),
+            (KeyCode::Delete.into(), Self::Delete),
+            (shift('x'), Self::Backspace),
+            (shift('d'), Self::KillLine),
+            (shift('p'), Self::Yank),
+            (key('i'), Self::Insert),
+            (KeyCode::Insert.into(), Self::Insert),
+            (key('a'), Self::Append),
+            (shift('i'), Self::InsertStart),
+            (shift('a'), Self::AppendEnd),
+            (key('s'), Self::Substitute),
+            (shift('c'), Self::ChangeToEnd),
+            (shift('s'), Self::ChangeLine),
+            (ctrl('d'), Self::DeleteOrEof),
+            (ctrl('c'), Self::Cancel),
+        ]
+        .into_iter()
+        .collect()
+    }
+}
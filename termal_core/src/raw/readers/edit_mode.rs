@@ -0,0 +1,38 @@
+use crate::term_text::TermText;
+
+/// Editing mode of [`super::TermRead`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EditMode {
+    /// Keys insert text and editing actions are bound to keys with
+    /// modifiers (see [`super::EditAction::default_bindings`]).
+    #[default]
+    Emacs,
+    /// The input starts in insert mode that works as [`EditMode::Emacs`].
+    /// `esc` switches to normal mode where keys are commands (see
+    /// [`super::EditAction::vi_normal_bindings`]).
+    Vi,
+}
+
+/// Text shown before the prompt in [`EditMode::Vi`] that shows the current
+/// mode (e.g. `[I] ` and `[N] `).
+#[derive(Debug, Clone, Default)]
+pub struct ModeIndicator<'a> {
+    /// Shown in insert mode.
+    pub insert: TermText<'a>,
+    /// Shown in normal mode.
+    pub normal: TermText<'a>,
+}
+
+impl<'a> ModeIndicator<'a> {
+    /// Creates mode indicator with the given texts for insert and normal
+    /// mode.
+    pub fn new(
+        insert: impl Into<TermText<'a>>,
+        normal: impl Into<TermText<'a>>,
+    ) -> Self {
+        Self {
+            insert: insert.into(),
+            normal: normal.into(),
+        }
+    }
+}
@@ -1,15 +1,25 @@
+mod edit_action;
+mod edit_mode;
 mod predicate;
 mod read_conf;
+mod select;
 mod term_read;
+mod validation;
 mod vec2;
 
 use std::io::{self, Write};
 
-use crate::error::Result;
+#[cfg(any(windows, unix))]
+use std::{fmt::Display, str::FromStr};
+
+use crate::error::{Error, Result};
 
 pub(crate) use self::vec2::*;
 
-pub use self::{predicate::*, read_conf::*, term_read::*};
+pub use self::{
+    edit_action::*, edit_mode::*, predicate::*, read_conf::*, select::*,
+    term_read::*, validation::*,
+};
 
 /// Read one line from standard input. This will use custom readline if
 /// supported. Otherwise it will fallback to the default readline function.
@@ -32,10 +42,15 @@ pub fn prompt(prompt: impl AsRef<str>) -> Result<String> {
 }
 
 /// Prompt the user with better read line capabilities.
+///
+/// If the user cancels the input, this returns [`Error::Interrupted`] or
+/// [`Error::Eof`]. See [`CancelPolicy`].
 #[cfg(any(windows, unix))]
 pub fn prompt_to(res: &mut String, prompt: impl AsRef<str>) -> Result<()> {
-    prompt_to_inner(res, prompt.as_ref())
-        .or_else(|_| prompt_to_fallback(res, prompt))
+    prompt_to_inner(res, prompt.as_ref()).or_else(|e| match e {
+        Error::Interrupted | Error::Eof => Err(e),
+        _ => prompt_to_fallback(res, prompt),
+    })
 }
 
 /// Prompt the user. Better readline is not supported on this platform, so this
@@ -45,28 +60,88 @@ pub fn prompt_to(res: &mut String, prompt: impl AsRef<str>) -> Result<()> {
     prompt_to_fallback(res, prompt)
 }
 
+/// Asks the user yes or no question. The `default` answer is used when the
+/// user enters nothing. See [`super::Terminal::prompt_confirm`].
+///
+/// If the user cancels the input, this returns [`Error::Interrupted`] or
+/// [`Error::Eof`]. See [`CancelPolicy`].
+#[cfg(any(windows, unix))]
+pub fn prompt_confirm(prompt: impl AsRef<str>, default: bool) -> Result<bool> {
+    with_stdio(|term| term.prompt_confirm(prompt, default))
+}
+
+/// Prompts the user until the entered text can be parsed. See
+/// [`super::Terminal::prompt_parse`].
+///
+/// If the user cancels the input, this returns [`Error::Interrupted`] or
+/// [`Error::Eof`]. See [`CancelPolicy`].
+#[cfg(any(windows, unix))]
+pub fn prompt_parse<T>(prompt: impl AsRef<str>) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    with_stdio(|term| term.prompt_parse(prompt))
+}
+
+/// Lets the user select one of the items with the arrows and returns its
+/// index. See [`Select`].
+///
+/// If the user cancels the input, this returns [`Error::Interrupted`] or
+/// [`Error::Eof`]. See [`CancelPolicy`].
+#[cfg(any(windows, unix))]
+pub fn select<I>(prompt: impl AsRef<str>, items: I) -> Result<usize>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    with_stdio(|term| Select::new(prompt, items).read(term))
+}
+
+/// Lets the user check any number of the items and returns indexes of the
+/// checked items. See [`Select`].
+///
+/// If the user cancels the input, this returns [`Error::Interrupted`] or
+/// [`Error::Eof`]. See [`CancelPolicy`].
+#[cfg(any(windows, unix))]
+pub fn multi_select<I>(prompt: impl AsRef<str>, items: I) -> Result<Vec<usize>>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    with_stdio(|term| Select::new(prompt, items).read_multi(term))
+}
+
 #[cfg(any(windows, unix))]
 fn prompt_to_inner(res: &mut String, prompt: impl AsRef<str>) -> Result<()> {
+    with_stdio(|term| match prompt.as_ref().rsplit_once('\n') {
+        Some((lines, prompt)) => term
+            .println(lines)
+            .and_then(|_| term.prompt_to(res, prompt)),
+        None => term.prompt_to(res, prompt.as_ref()),
+    })
+}
+
+/// Runs `f` with terminal on stdio in raw mode.
+#[cfg(any(windows, unix))]
+fn with_stdio<R>(
+    f: impl FnOnce(&mut super::Terminal) -> Result<R>,
+) -> Result<R> {
     use super::{
-        disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, Terminal,
+        disable_raw_mode, enable_raw_mode, is_raw_mode_enabled,
+        is_terminal_raw, Terminal,
     };
 
-    let raw = is_raw_mode_enabled();
+    // Don't toggle raw mode enabled by other library.
+    let external = !is_raw_mode_enabled() && is_terminal_raw();
+    let raw = is_raw_mode_enabled() || external;
     if !raw {
         enable_raw_mode()?;
     }
 
-    let mut i = prompt.as_ref().split('\n').peekable();
-    let mut prompt = "";
-    while let Some(s) = i.next() {
-        if i.peek().is_none() {
-            prompt = s;
-            break;
-        }
-        println!("{s}\r");
-    }
-
-    let r = Terminal::stdio().prompt_to(res, prompt);
+    let mut term = Terminal::stdio();
+    term.set_external_raw_mode(external);
+    let r = f(&mut term);
 
     if !raw {
         _ = disable_raw_mode();
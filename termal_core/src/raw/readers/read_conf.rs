@@ -1,4 +1,6 @@
-use crate::term_text::TermText;
+use crate::{raw::events::KeyMap, term_text::TermText};
+
+use super::{EditAction, EditMode, ModeIndicator};
 
 /// Configuration for terminal reader.
 #[derive(Debug, Clone, Default)]
@@ -9,4 +11,85 @@ pub struct ReadConf<'a> {
     pub edit_pos: Option<usize>,
     /// Prompt for the input. Empty by default.
     pub prompt: TermText<'a>,
+    /// How the input may be cancelled. See [`CancelPolicy`].
+    pub cancel: CancelPolicy,
+    /// Key bindings of the editing actions. [`EditAction::default_bindings`]
+    /// if [`None`] (the default).
+    pub bindings: Option<KeyMap<EditAction>>,
+    /// The editing mode. [`EditMode::Emacs`] by default.
+    pub edit_mode: EditMode,
+    /// Text before the prompt that shows the mode in [`EditMode::Vi`]. No
+    /// indicator by default.
+    pub mode_indicator: Option<ModeIndicator<'a>>,
+    /// If `true`, the prompt is wrapped in shell integration marks and the
+    /// start of output is marked when the input is accepted (see
+    /// [`crate::PromptMarker`]). `false` by default.
+    pub prompt_marks: bool,
+}
+
+impl<'a> ReadConf<'a> {
+    /// Sets the editing mode.
+    pub fn edit_mode(mut self, mode: EditMode) -> Self {
+        self.edit_mode = mode;
+        self
+    }
+
+    /// Sets the text before the prompt that shows the mode in
+    /// [`EditMode::Vi`].
+    pub fn mode_indicator(mut self, indicator: ModeIndicator<'a>) -> Self {
+        self.mode_indicator = Some(indicator);
+        self
+    }
+
+    /// Sets whether the prompt is wrapped in shell integration marks.
+    pub fn prompt_marks(mut self, v: bool) -> Self {
+        self.prompt_marks = v;
+        self
+    }
+}
+
+/// Determines how the user may cancel input of terminal reader.
+///
+/// When the input is cancelled, the cursor is moved to the end of the input
+/// and the reader returns the error. The cancelled input is still available
+/// with [`super::TermRead::get_input`] and the reader may be reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CancelPolicy {
+    /// If `true`, [`EditAction::Cancel`] (`ctrl+c` by default) cancels the
+    /// input with [`crate::error::Error::Interrupted`]. `true` by default.
+    pub interrupt: bool,
+    /// If `true`, [`EditAction::DeleteOrEof`] (`ctrl+d` by default) on empty
+    /// input cancels it with [`crate::error::Error::Eof`]. On non empty input,
+    /// it deletes the character under the cursor. `true` by default.
+    pub eof: bool,
+    /// If `true`, end of stdin with empty input cancels it with
+    /// [`crate::error::Error::Eof`]. Otherwise the empty input is returned.
+    /// Non empty input is always returned. `false` by default.
+    pub stdin_eof: bool,
+}
+
+impl CancelPolicy {
+    /// Input can't be cancelled.
+    pub const NONE: Self = Self {
+        interrupt: false,
+        eof: false,
+        stdin_eof: false,
+    };
+
+    /// Input can be cancelled in all the ways.
+    pub const ALL: Self = Self {
+        interrupt: true,
+        eof: true,
+        stdin_eof: true,
+    };
+}
+
+impl Default for CancelPolicy {
+    fn default() -> Self {
+        Self {
+            interrupt: true,
+            eof: true,
+            stdin_eof: false,
+        }
+    }
 }
@@ -0,0 +1,466 @@
+use std::{fmt::Debug, io::Write};
+
+use crate::{
+    codes,
+    error::{Error, Result},
+    glyphs::{self, GlyphSet},
+    raw::{
+        events::{Event, Key, KeyCode, Modifiers},
+        IoProvider, Terminal,
+    },
+    theme::{self, StyleSet},
+    Style,
+};
+
+use super::CancelPolicy;
+
+#[cfg(feature = "term_image")]
+use crate::fill::str_width;
+
+/// Disables automatic wrapping at the end of line (DECAWM).
+const DISABLE_AUTOWRAP: &str = crate::disable!(7);
+/// Enables automatic wrapping at the end of line (DECAWM).
+const ENABLE_AUTOWRAP: &str = crate::enable!(7);
+
+/// Draws preview of the item with the given index to the buffer in the given
+/// area `(x, y, w, h)`.
+#[cfg(feature = "term_image")]
+type Preview<'p> =
+    Box<dyn FnMut(usize, &mut String, (usize, usize, usize, usize)) + 'p>;
+
+/// Prompt where the user selects one or more items from list.
+///
+/// The items are below the prompt and the highlighted item is moved with the
+/// arrows (or `j` and `k`), `home`, `end`, `page up` and `page down`. `enter`
+/// confirms the selection. In multi select, `space` checks or unchecks the
+/// highlighted item. Only [`Select::height`] items are shown at once and the
+/// list scrolls with the highlighted item.
+///
+/// The styles may be customized with theme styles (see [`theme::style`]):
+/// - `select.prompt`: the prompt.
+/// - `select.highlight`: the highlighted item.
+/// - `select.checked`: the checked items in multi select.
+/// - `select.more`: the marks that there are more items above or below.
+///
+/// The marks are arrows from the current glyph set (see [`glyphs::glyphs`])
+/// or from the set given with [`Select::glyphs`].
+///
+/// When the selection is confirmed, the list is erased and the selected
+/// items are shown after the prompt.
+///
+/// With [`Select::preview`], image of the highlighted item is shown next to
+/// the list.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::raw::{
+///     events::{Key, KeyCode},
+///     readers::Select,
+///     testing::ScriptedInput,
+/// };
+///
+/// let mut term = ScriptedInput::new()
+///     .key(Key::code(KeyCode::Down))
+///     .key(Key::code(KeyCode::Enter))
+///     .terminal();
+///
+/// let mut select = Select::new("Pick fruit:", ["apple", "pear", "plum"]);
+/// assert_eq!(select.read(&mut term)?, 1);
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub struct Select<'p> {
+    prompt: String,
+    items: Vec<String>,
+    checked: Vec<bool>,
+    selected: usize,
+    top: usize,
+    height: usize,
+    cancel: CancelPolicy,
+    styles: Option<StyleSet>,
+    glyphs: Option<GlyphSet>,
+    #[cfg(feature = "term_image")]
+    preview: Option<Preview<'p>>,
+    /// Row of the last line of the list if the preview is shown.
+    #[cfg(feature = "term_image")]
+    preview_row: Option<usize>,
+    #[cfg(not(feature = "term_image"))]
+    _preview: std::marker::PhantomData<&'p ()>,
+}
+
+impl<'p> Select<'p> {
+    /// Creates select prompt with the given items. It shows at most 10 items
+    /// at once.
+    pub fn new<I>(prompt: impl AsRef<str>, items: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let items: Vec<_> =
+            items.into_iter().map(|i| i.as_ref().to_string()).collect();
+        Self {
+            prompt: prompt.as_ref().to_string(),
+            checked: vec![false; items.len()],
+            items,
+            selected: 0,
+            top: 0,
+            height: 10,
+            cancel: CancelPolicy::default(),
+            styles: None,
+            glyphs: None,
+            #[cfg(feature = "term_image")]
+            preview: None,
+            #[cfg(feature = "term_image")]
+            preview_row: None,
+            #[cfg(not(feature = "term_image"))]
+            _preview: std::marker::PhantomData,
+        }
+    }
+
+    /// Sets the maximum number of items shown at once. The minimum is 1.
+    pub fn height(mut self, height: usize) -> Self {
+        self.height = height.max(1);
+        self
+    }
+
+    /// Sets the item that is highlighted at the start.
+    pub fn selected(mut self, idx: usize) -> Self {
+        self.selected = idx.min(self.items.len().saturating_sub(1));
+        self
+    }
+
+    /// Sets the items that are checked at the start of multi select.
+    pub fn checked(mut self, idxs: impl IntoIterator<Item = usize>) -> Self {
+        for i in idxs {
+            if let Some(c) = self.checked.get_mut(i) {
+                *c = true;
+            }
+        }
+        self
+    }
+
+    /// Sets how the prompt may be cancelled. `ctrl+d` cancels with
+    /// [`Error::Eof`] if [`CancelPolicy::eof`] is set.
+    pub fn cancel(mut self, cancel: CancelPolicy) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Sets styles that override the theme styles for this prompt. See the
+    /// struct documentation for the keys.
+    pub fn styles(mut self, styles: StyleSet) -> Self {
+        self.styles = Some(styles);
+        self
+    }
+
+    /// Sets the glyphs used by this prompt. The default is the current glyph
+    /// set ([`glyphs::glyphs`]).
+    pub fn glyphs(mut self, glyphs: GlyphSet) -> Self {
+        self.glyphs = Some(glyphs);
+        self
+    }
+
+    /// Shows image of the highlighted item next to the list. `get` returns
+    /// the image for the item with the given index or [`None`] if the item
+    /// has no preview. The image is drawn with
+    /// [`crate::image::push_texel_thumbnail`] and it is as high as the list.
+    ///
+    /// The position of the list is requested from the terminal when the
+    /// prompt is shown. If the terminal doesn't respond, there is no
+    /// preview.
+    #[cfg(feature = "term_image")]
+    pub fn preview<F, I>(mut self, mut get: F) -> Self
+    where
+        F: FnMut(usize) -> Option<I> + 'p,
+        I: crate::image::Image,
+    {
+        self.preview = Some(Box::new(move |idx, res, (x, y, w, h)| {
+            if let Some(img) = get(idx) {
+                crate::image::push_texel_thumbnail(&img, res, x, y, w, h);
+            }
+        }));
+        self
+    }
+
+    /// Gets the index of the highlighted item.
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Gets the indexes of the checked items.
+    pub fn get_checked(&self) -> Vec<usize> {
+        (0..self.items.len()).filter(|i| self.checked[*i]).collect()
+    }
+
+    /// Lets the user select single item and returns its index. Fails with
+    /// [`Error::NothingToSelect`] if there are no items.
+    pub fn read<T: IoProvider>(
+        &mut self,
+        term: &mut Terminal<T>,
+    ) -> Result<usize> {
+        self.run(term, false)?;
+        Ok(self.selected)
+    }
+
+    /// Lets the user check any number of items and returns indexes of the
+    /// checked items. Fails with [`Error::NothingToSelect`] if there are no
+    /// items.
+    pub fn read_multi<T: IoProvider>(
+        &mut self,
+        term: &mut Terminal<T>,
+    ) -> Result<Vec<usize>> {
+        self.run(term, true)?;
+        Ok(self.get_checked())
+    }
+
+    fn run<T: IoProvider>(
+        &mut self,
+        term: &mut Terminal<T>,
+        multi: bool,
+    ) -> Result<()> {
+        if self.items.is_empty() {
+            return Err(Error::NothingToSelect);
+        }
+
+        self.scroll_to_selected();
+        let mut buf = codes::HIDE_CURSOR.to_string();
+        self.draw(&mut buf, multi, term.newline());
+        commit(term, &mut buf)?;
+        self.locate_preview(term)?;
+        self.draw_preview(&mut buf, multi);
+        commit(term, &mut buf)?;
+
+        let res = loop {
+            let key = match term.read() {
+                Ok(Event::KeyPress(key)) => key,
+                Ok(_) => continue,
+                Err(Error::StdInEof) if self.cancel.stdin_eof => {
+                    break Err(Error::Eof);
+                }
+                Err(Error::StdInEof) => break Ok(()),
+                Err(e) => break Err(e),
+            };
+
+            if self.cancel.interrupt && is_ctrl(&key, 'c') {
+                break Err(Error::Interrupted);
+            }
+            if self.cancel.eof && is_ctrl(&key, 'd') {
+                break Err(Error::Eof);
+            }
+            if key.code == KeyCode::Enter {
+                break Ok(());
+            }
+
+            if self.handle_key(&key, multi) {
+                self.erase(&mut buf);
+                self.draw(&mut buf, multi, term.newline());
+                self.draw_preview(&mut buf, multi);
+                commit(term, &mut buf)?;
+            }
+        };
+
+        self.erase(&mut buf);
+        buf += &self.style("select.prompt").code();
+        buf += &self.prompt;
+        buf += codes::RESET;
+        if res.is_ok() {
+            buf.push(' ');
+            if multi {
+                let checked: Vec<_> = self
+                    .get_checked()
+                    .into_iter()
+                    .map(|i| self.items[i].as_str())
+                    .collect();
+                buf += &checked.join(", ");
+            } else {
+                buf += &self.items[self.selected];
+            }
+            buf += codes::RESET;
+        }
+        buf += term.newline();
+        buf += codes::SHOW_CURSOR;
+        commit(term, &mut buf)?;
+        res
+    }
+
+    /// Handles the key. Returns `true` if the list should be redrawn.
+    fn handle_key(&mut self, key: &Key, multi: bool) -> bool {
+        let last = self.items.len() - 1;
+        let old = self.selected;
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k')
+                if !key.modifiers.contains(Modifiers::CONTROL) =>
+            {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j')
+                if !key.modifiers.contains(Modifiers::CONTROL) =>
+            {
+                self.selected = (self.selected + 1).min(last);
+            }
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = last,
+            KeyCode::PgUp => {
+                self.selected = self.selected.saturating_sub(self.height);
+            }
+            KeyCode::PgDown => {
+                self.selected = (self.selected + self.height).min(last);
+            }
+            KeyCode::Space if multi => {
+                self.checked[self.selected] = !self.checked[self.selected];
+                return true;
+            }
+            _ => {}
+        }
+        self.scroll_to_selected();
+        old != self.selected
+    }
+
+    fn scroll_to_selected(&mut self) {
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if self.selected >= self.top + self.height {
+            self.top = self.selected + 1 - self.height;
+        }
+    }
+
+    /// Number of lines of the list below the prompt.
+    fn rows(&self) -> usize {
+        self.items.len().min(self.height)
+    }
+
+    fn style(&self, key: &str) -> Style {
+        theme::style(key, self.styles.as_ref()).unwrap_or_else(|| match key {
+            "select.highlight" => Style::new().fg(theme::palette().primary),
+            "select.more" => Style::new().fg(theme::palette().dim),
+            _ => Style::DEFAULT,
+        })
+    }
+
+    /// Gets the row of the last line of the list from the terminal if there
+    /// is preview. The list has always the same number of lines, so the
+    /// position doesn't change while the prompt is shown.
+    fn locate_preview<T: IoProvider>(
+        &mut self,
+        term: &mut Terminal<T>,
+    ) -> Result<()> {
+        #[cfg(feature = "term_image")]
+        if self.preview.is_some() && term.is_out_terminal() {
+            self.preview_row = term.cursor_position()?.map(|(_, y)| y);
+        }
+        #[cfg(not(feature = "term_image"))]
+        let _ = term;
+        Ok(())
+    }
+
+    /// Draws preview of the highlighted item to the right of the list. The
+    /// cursor is moved back to the last line of the list, so the preview is
+    /// erased together with the list.
+    fn draw_preview(&mut self, buf: &mut String, multi: bool) {
+        #[cfg(feature = "term_image")]
+        {
+            let Some(bottom) = self.preview_row else {
+                return;
+            };
+            let rows = self.rows();
+            let prefix = if multi { 6 } else { 2 };
+            let width = self.items.iter().map(|i| str_width(i)).max();
+            let x = prefix + width.unwrap_or(0) + 2;
+            let y = (bottom + 1).saturating_sub(rows).max(1);
+            let Some(preview) = &mut self.preview else {
+                return;
+            };
+
+            *buf += DISABLE_AUTOWRAP;
+            preview(self.selected, buf, (x, y, rows * 4, rows));
+            *buf += &codes::move_to!(1, bottom);
+            *buf += ENABLE_AUTOWRAP;
+        }
+        #[cfg(not(feature = "term_image"))]
+        let _ = (buf, multi);
+    }
+
+    /// Moves the cursor to the start of the prompt and erases the prompt
+    /// with the list.
+    fn erase(&self, buf: &mut String) {
+        buf.push('\r');
+        *buf += &codes::move_up!(self.rows());
+        *buf += codes::ERASE_TO_END;
+    }
+
+    /// Draws the prompt with the list. The cursor is left at the end of the
+    /// last line.
+    fn draw(&self, buf: &mut String, multi: bool, newline: &str) {
+        let glyphs = self.glyphs.unwrap_or_else(glyphs::glyphs);
+        *buf += DISABLE_AUTOWRAP;
+        *buf += &self.style("select.prompt").code();
+        *buf += &self.prompt;
+        *buf += codes::RESET;
+
+        let end = self.top + self.rows();
+        for i in self.top..end {
+            *buf += newline;
+            let highlight = i == self.selected;
+            let more = (i == self.top && self.top != 0)
+                || (i + 1 == end && end != self.items.len());
+            if highlight {
+                *buf += &self.style("select.highlight").code();
+                *buf += "> ";
+            } else if more {
+                *buf += &self.style("select.more").code();
+                *buf += if i == self.top {
+                    glyphs.arrow_up
+                } else {
+                    glyphs.arrow_down
+                };
+                *buf += " ";
+                *buf += codes::RESET;
+            } else {
+                *buf += "  ";
+            }
+
+            if multi && self.checked[i] {
+                if !highlight {
+                    *buf += &self.style("select.checked").code();
+                }
+                *buf += "[x] ";
+            } else if multi {
+                *buf += "[ ] ";
+            }
+            *buf += &self.items[i];
+            *buf += codes::RESET;
+        }
+        *buf += ENABLE_AUTOWRAP;
+    }
+}
+
+impl Debug for Select<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Select")
+            .field("prompt", &self.prompt)
+            .field("items", &self.items)
+            .field("checked", &self.checked)
+            .field("selected", &self.selected)
+            .field("top", &self.top)
+            .field("height", &self.height)
+            .field("cancel", &self.cancel)
+            .field("styles", &self.styles)
+            .field("glyphs", &self.glyphs)
+            .finish_non_exhaustive()
+    }
+}
+
+fn is_ctrl(key: &Key, c: char) -> bool {
+    key.code == KeyCode::Char(c) && key.modifiers.contains(Modifiers::CONTROL)
+}
+
+fn commit<T: IoProvider>(
+    term: &mut Terminal<T>,
+    buf: &mut String,
+) -> Result<()> {
+    if term.is_out_terminal() {
+        term.write_all(buf.as_bytes())?;
+        term.flush()?;
+    }
+    buf.clear();
+    Ok(())
+}
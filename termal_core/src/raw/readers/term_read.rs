@@ -6,16 +6,31 @@ use crate::{
     codes,
     error::{Error, Result},
     raw::{
-        events::{Event, Key, KeyCode, Modifiers, Status},
-        term_size, IoProvider, StdioProvider, Terminal,
+        events::{Event, Key, KeyCode, KeyMap, StateChange, Status},
+        IoProvider, StdioProvider, Terminal,
     },
     term_text::TermText,
+    theme, PromptMarker, Style,
 };
 
-use super::{Predicate, ReadConf, Vec2};
+use super::{
+    CancelPolicy, EditAction, EditMode, ModeIndicator, Predicate, ReadConf,
+    Validation, Vec2,
+};
+
+/// Callback that validates the input of [`TermRead`].
+type Validator<'p> = Box<dyn FnMut(&[char]) -> Validation + 'p>;
+/// Callback of [`EditAction::Custom`].
+type CustomAction<'p> = Box<dyn FnMut(u32, &mut Vec<char>, &mut usize) + 'p>;
+
+/// Maximum number of texts in the kill ring.
+const KILL_RING_LEN: usize = 16;
 
 /// Terminal reader. Supports only single line. Newlines are skipped.
 ///
+/// Text pasted with bracketed paste mode enabled (see
+/// [`codes::ENABLE_BRACKETED_PASTE_MODE`]) is inserted verbatim at once.
+///
 /// ## Unstable API
 ///
 /// API of [`TermRead`] will likely change in the future.
@@ -30,12 +45,27 @@ where
     term: &'t mut Terminal<T>,
     exit: P,
     size: Vec2,
+    cancel: CancelPolicy,
     // TODO: use bitflags
-    // TODO: option to exit on ctrl+c
     finished: bool,
     paste: bool,
     last_event: Option<Event>,
     queue: VecDeque<Event>,
+    validator: Option<Validator<'p>>,
+    validation: Validation,
+    bindings: KeyMap<EditAction>,
+    custom_action: Option<CustomAction<'p>>,
+    edit_mode: EditMode,
+    vi_bindings: KeyMap<EditAction>,
+    vi_normal: bool,
+    mode_indicator: Option<ModeIndicator<'p>>,
+    kill_ring: VecDeque<String>,
+    /// Index of the last yanked text in the kill ring.
+    yank_idx: usize,
+    last_action: Option<EditAction>,
+    /// Text of bracketed paste that is in progress.
+    bracketed: Option<String>,
+    marker: PromptMarker,
 }
 
 impl<'t, T: IoProvider> TermRead<'t, '_, KeyCode, T> {
@@ -74,10 +104,26 @@ where
             exit,
             prompt: conf.prompt,
             size: (usize::MAX, usize::MAX).into(),
+            cancel: conf.cancel,
             finished: false,
             paste: false,
             last_event: None,
             queue: VecDeque::new(),
+            validator: None,
+            validation: Validation::default(),
+            bindings: conf
+                .bindings
+                .unwrap_or_else(EditAction::default_bindings),
+            custom_action: None,
+            edit_mode: conf.edit_mode,
+            vi_bindings: EditAction::vi_normal_bindings(),
+            vi_normal: false,
+            mode_indicator: conf.mode_indicator,
+            kill_ring: VecDeque::new(),
+            yank_idx: 0,
+            last_action: None,
+            bracketed: None,
+            marker: prompt_marker(conf.prompt_marks),
         }
     }
 
@@ -89,6 +135,8 @@ where
     ) -> Result<()> {
         self.set_edit(&s, pos);
         self.reshow()?;
+        // Keep the string unchanged if the input is cancelled.
+        self.get_all()?;
         s.clear();
         self.finish_to_str(s)
     }
@@ -114,10 +162,10 @@ where
         mem::swap(&mut self.buf, s);
         self.set_pos(pos);
         self.reshow()?;
-        self.get_all()?;
+        let res = self.get_all();
         mem::swap(&mut self.buf, s);
         self.clear();
-        Ok(())
+        res
     }
 
     /// Appends readed text to a string.
@@ -171,15 +219,18 @@ where
         self.pos = pos.unwrap_or(self.buf.len()).min(self.buf.len());
     }
 
-    /// Reset the buffer.
+    /// Reset the buffer. In [`EditMode::Vi`], this also switches to the
+    /// insert mode.
     pub fn clear(&mut self) {
         self.pos = 0;
         self.buf.clear();
         self.finished = false;
+        self.vi_normal = false;
     }
 
     /// Refresh the view.
     pub fn reshow(&mut self) -> Result<()> {
+        self.validate();
         self.reprint_all();
         self.commit()
     }
@@ -193,6 +244,18 @@ where
     pub fn configure(&mut self, conf: ReadConf<'p>) {
         self.set_buf(conf.edit, conf.edit_pos);
         self.set_prompt(conf.prompt);
+        self.cancel = conf.cancel;
+        if let Some(b) = conf.bindings {
+            self.bindings = b;
+        }
+        self.set_edit_mode(conf.edit_mode);
+        self.mode_indicator = conf.mode_indicator;
+        self.marker = prompt_marker(conf.prompt_marks);
+    }
+
+    /// Sets how the input may be cancelled. See [`CancelPolicy`].
+    pub fn set_cancel_policy(&mut self, cancel: CancelPolicy) {
+        self.cancel = cancel;
     }
 
     /// Set the read buffer. It is filtered for non control characters.
@@ -245,6 +308,165 @@ where
         self.last_event.as_ref()
     }
 
+    /// Sets function that is called with the input on each edit. The
+    /// returned [`Validation`] determines the style of the input and the
+    /// hint shown after it (e.g. suggestion of the rest of the input). The
+    /// hint has the theme style `input.hint` (see [`theme::style`]) and the
+    /// user may accept it with the right arrow at the end of the input.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use termal_core::raw::{
+    ///     readers::{TermRead, Validation},
+    ///     testing::ScriptedInput,
+    /// };
+    ///
+    /// let mut term = ScriptedInput::new().text("he").text("\x1b[C\r").terminal();
+    /// let mut reader = TermRead::lines(&mut term);
+    /// reader.set_validator(|input| {
+    ///     let input: String = input.iter().collect();
+    ///     match "hello".strip_prefix(&input) {
+    ///         Some(rest) => Validation::new().hint(rest),
+    ///         None => Validation::invalid(),
+    ///     }
+    /// });
+    /// assert_eq!(reader.read_str()?, "hello");
+    /// # Ok::<_, termal_core::error::Error>(())
+    /// ```
+    pub fn set_validator(
+        &mut self,
+        f: impl FnMut(&[char]) -> Validation + 'p,
+    ) {
+        self.validator = Some(Box::new(f));
+    }
+
+    /// Removes the validator set with [`TermRead::set_validator`].
+    pub fn clear_validator(&mut self) {
+        self.validator = None;
+        self.validation = Validation::default();
+    }
+
+    /// Sets the key bindings of the editing actions. Keys that are not bound
+    /// insert their key char. The default is
+    /// [`EditAction::default_bindings`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use termal_core::raw::{
+    ///     events::{KeyCode, Modifiers},
+    ///     readers::{EditAction, TermRead},
+    ///     testing::ScriptedInput,
+    /// };
+    ///
+    /// // `ctrl+y` swaps the case of the whole input.
+    /// let mut term = ScriptedInput::new().text("Hello\x19\r").terminal();
+    /// let mut reader = TermRead::lines(&mut term);
+    /// reader.bindings_mut().bind_str("ctrl+y", EditAction::Custom(0))?;
+    /// reader.set_custom_action(|_, input, _| {
+    ///     for c in input {
+    ///         *c = if c.is_uppercase() {
+    ///             c.to_ascii_lowercase()
+    ///         } else {
+    ///             c.to_ascii_uppercase()
+    ///         };
+    ///     }
+    /// });
+    /// assert_eq!(reader.read_str()?, "hELLO");
+    /// # Ok::<_, termal_core::error::Error>(())
+    /// ```
+    pub fn set_bindings(&mut self, bindings: KeyMap<EditAction>) {
+        self.bindings = bindings;
+    }
+
+    /// Gets the key bindings of the editing actions.
+    pub fn bindings(&self) -> &KeyMap<EditAction> {
+        &self.bindings
+    }
+
+    /// Gets mutable reference to the key bindings of the editing actions.
+    pub fn bindings_mut(&mut self) -> &mut KeyMap<EditAction> {
+        &mut self.bindings
+    }
+
+    /// Sets the editing mode. The input is in insert mode after switching to
+    /// [`EditMode::Vi`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use termal_core::raw::{
+    ///     events::{Key, KeyCode},
+    ///     readers::{EditMode, ReadConf, TermRead},
+    ///     testing::ScriptedInput,
+    /// };
+    ///
+    /// // Type `hello`, switch to normal mode, delete to the start with `0D`
+    /// // and append `!` with `A`.
+    /// let mut term = ScriptedInput::new()
+    ///     .text("hello")
+    ///     .key(Key::code(KeyCode::Esc))
+    ///     .text("0DA!\r")
+    ///     .terminal();
+    /// let mut reader = TermRead::from_config(
+    ///     &mut term,
+    ///     KeyCode::Enter,
+    ///     ReadConf::default().edit_mode(EditMode::Vi),
+    /// );
+    /// assert_eq!(reader.read_str()?, "!");
+    /// # Ok::<_, termal_core::error::Error>(())
+    /// ```
+    pub fn set_edit_mode(&mut self, mode: EditMode) {
+        self.edit_mode = mode;
+        self.vi_normal = false;
+    }
+
+    /// Gets the editing mode.
+    pub fn get_edit_mode(&self) -> EditMode {
+        self.edit_mode
+    }
+
+    /// Checks whether the input is in the normal mode of [`EditMode::Vi`].
+    pub fn is_vi_normal(&self) -> bool {
+        self.vi_normal
+    }
+
+    /// Sets the key bindings of the normal mode of [`EditMode::Vi`]. The
+    /// default is [`EditAction::vi_normal_bindings`].
+    pub fn set_vi_bindings(&mut self, bindings: KeyMap<EditAction>) {
+        self.vi_bindings = bindings;
+    }
+
+    /// Gets mutable reference to the key bindings of the normal mode of
+    /// [`EditMode::Vi`].
+    pub fn vi_bindings_mut(&mut self) -> &mut KeyMap<EditAction> {
+        &mut self.vi_bindings
+    }
+
+    /// Sets the text before the prompt that shows the mode in
+    /// [`EditMode::Vi`]. The change is shown with [`TermRead::reshow`].
+    pub fn set_mode_indicator(
+        &mut self,
+        indicator: Option<ModeIndicator<'p>>,
+    ) {
+        self.mode_indicator = indicator;
+    }
+
+    /// Sets whether the prompt is wrapped in shell integration marks. See
+    /// [`ReadConf::prompt_marks`].
+    pub fn set_prompt_marks(&mut self, v: bool) {
+        self.marker.set_enabled(v);
+    }
+
+    /// Sets function that is called when key bound to
+    /// [`EditAction::Custom`] is pressed. It gets the id of the action, the
+    /// input and the position of the cursor within the input. The input is
+    /// reprinted after the call and control characters are removed from it.
+    pub fn set_custom_action(
+        &mut self,
+        f: impl FnMut(u32, &mut Vec<char>, &mut usize) + 'p,
+    ) {
+        self.custom_action = Some(Box::new(f));
+    }
+
     /// Queue event to the reader.
     pub fn queue(&mut self, evt: impl IntoIterator<Item = Event>) {
         self.queue.extend(evt);
@@ -277,29 +499,27 @@ where
         Ok(())
     }
 
+    /// Checks the size of the terminal and reprints the input if it has
+    /// changed.
     fn resize(&mut self) {
-        let Ok(size) =
-            term_size().map(|s| Vec2::new(s.char_width, s.char_height))
-        else {
-            return;
-        };
-        self.size.map(|a| if a == 0 { usize::MAX } else { a });
+        if let Some(size) = self.term.io().term_size() {
+            self.resize_to(Vec2::new(size.char_width, size.char_height));
+        }
+    }
+
+    /// Reprints the prompt and the input for the new size of the terminal.
+    fn resize_to(&mut self, size: Vec2) {
+        let size = size.map(|a| if a == 0 { usize::MAX } else { a });
         if self.size == size {
             return;
         }
-        let pos = self.cur_pos();
-        if pos.x == 0 && pos.y != 0 && self.pos == self.buf.len() {
-            if size.x > self.size.x {
-                self.pbuf += &codes::move_up!(pos.y);
-            } else {
-                self.pbuf += &codes::move_up!(
-                    self.pos / size.x + (self.pos % size.y > 0) as usize
-                );
-            }
-        }
-        self.pbuf += &codes::move_left!(pos.x);
+
+        // Terminals reflow the wrapped lines, so the cursor stays after the
+        // same character. Its row is the row within the new width.
         self.size = size;
         let pos = self.pos;
+        self.pbuf.push('\r');
+        self.pbuf += &codes::move_up!(self.cur_pos().y);
         self.reprint_with_prompt_dont_move();
         self.move_to_pos(pos);
     }
@@ -312,6 +532,9 @@ where
         let evt = match self.term.read() {
             Ok(e) => e,
             Err(Error::StdInEof) => {
+                if self.cancel.stdin_eof && self.buf.is_empty() {
+                    return self.cancel_with(Error::Eof);
+                }
                 self.end();
                 self.commit()?;
                 return Ok(true);
@@ -323,11 +546,17 @@ where
     }
 
     fn handle_event(&mut self, evt: Event) -> Result<bool> {
+        // Pasted text is inserted verbatim at once when the paste ends.
+        if let Some(paste) = &mut self.bracketed {
+            if let Event::KeyPress(Key { key_char, .. }) = evt {
+                paste.extend(key_char.filter(|c| !c.is_ascii_control()));
+                return Ok(false);
+            }
+        }
+
         if self.exit.matches(&evt) {
             self.last_event = Some(evt);
-            self.end();
-            self.commit()?;
-            return Ok(true);
+            return self.accept();
         }
 
         match evt {
@@ -343,6 +572,29 @@ where
                 if let Ok(s) = std::str::from_utf8(&data) {
                     self.insert(s);
                 }
+                self.last_action = None;
+                self.revalidate();
+                self.commit()?;
+                Ok(false)
+            }
+            Event::Resize { w, h } => {
+                self.last_event = Some(evt);
+                self.resize_to(Vec2::new(w, h));
+                self.commit()?;
+                Ok(false)
+            }
+            Event::StateChange(StateChange::BracketedPasteStart) => {
+                self.bracketed = Some(String::new());
+                Ok(false)
+            }
+            Event::StateChange(StateChange::BracketedPasteEnd) => {
+                let Some(s) = self.bracketed.take() else {
+                    return Ok(false);
+                };
+                self.insert(&s);
+                self.last_action = None;
+                self.revalidate();
+                self.commit()?;
                 Ok(false)
             }
             _ => {
@@ -352,8 +604,48 @@ where
         }
     }
 
+    /// Ends the input. The cursor is moved to the end of the input.
+    fn accept(&mut self) -> Result<bool> {
+        self.end();
+        self.erase_hint();
+        self.pbuf += self.marker.output_start();
+        self.commit()?;
+        Ok(true)
+    }
+
+    /// Cancels the input with the given error. The cursor is moved to the end
+    /// of the input so that the following output doesn't overwrite it.
+    fn cancel_with<R>(&mut self, err: Error) -> Result<R> {
+        self.end();
+        self.erase_hint();
+        self.commit()?;
+        self.paste = false;
+        self.bracketed = None;
+        Err(err)
+    }
+
     fn handle_key_press(&mut self, key: Key) -> Result<bool> {
+        let bindings = if self.vi_normal {
+            &self.vi_bindings
+        } else {
+            &self.bindings
+        };
+        if let Some(action) = bindings.get(key).copied() {
+            return self.run_action(action);
+        }
+
+        if self.edit_mode == EditMode::Vi {
+            if self.vi_normal {
+                // Unbound keys don't insert anything in the normal mode.
+                return Ok(false);
+            }
+            if key.code == KeyCode::Esc && key.key_char.is_none() {
+                return self.run_action(EditAction::NormalMode);
+            }
+        }
+
         if let Some(chr) = key.key_char {
+            self.last_action = None;
             self.buf.insert(self.pos, chr);
 
             if self.pos + 1 < self.buf.len() {
@@ -363,47 +655,122 @@ where
                 self.print_from_dont_move(self.pos);
                 self.pos += 1;
                 if self.cur_pos().x == 0 {
-                    self.pbuf += "\r\n";
+                    self.pbuf += self.term.newline();
                 }
             }
 
+            self.revalidate();
             self.commit()?;
-            return Ok(false);
         }
 
-        match key.code {
-            KeyCode::Left => {
-                if key.modifiers.contains(Modifiers::CONTROL) {
-                    self.move_word_left();
-                } else {
-                    self.move_left()
-                }
+        Ok(false)
+    }
+
+    fn run_action(&mut self, action: EditAction) -> Result<bool> {
+        match action {
+            EditAction::Accept => return self.accept(),
+            EditAction::Cancel if self.cancel.interrupt => {
+                return self.cancel_with(Error::Interrupted);
             }
-            KeyCode::Right => {
-                if key.modifiers.contains(Modifiers::CONTROL) {
-                    self.move_word_right();
+            EditAction::DeleteOrEof
+                if self.cancel.eof && self.buf.is_empty() =>
+            {
+                return self.cancel_with(Error::Eof);
+            }
+            EditAction::Cancel => {}
+            EditAction::Left => self.move_left(),
+            EditAction::Right => {
+                if self.pos == self.buf.len()
+                    && !self.validation.hint.is_empty()
+                {
+                    let hint = mem::take(&mut self.validation.hint);
+                    self.insert(&hint);
                 } else {
                     self.move_right()
                 }
             }
-            KeyCode::Backspace => self.backspace(),
-            KeyCode::Delete => self.delete(),
-            KeyCode::Home => self.home(),
-            KeyCode::End => self.end(),
-            KeyCode::Char('v') => {
-                if key.modifiers.contains(Modifiers::CONTROL) {
-                    self.paste = true;
-                    self.pbuf += codes::REQUEST_SELECTION;
+            EditAction::WordLeft => self.move_word_left(),
+            EditAction::WordRight => self.move_word_right(),
+            EditAction::Home => self.home(),
+            EditAction::End => self.end(),
+            EditAction::Backspace => self.backspace(),
+            EditAction::Delete | EditAction::DeleteOrEof => self.delete(),
+            EditAction::KillLine => self.kill(self.pos, self.buf.len()),
+            EditAction::KillToStart => self.kill(0, self.pos),
+            EditAction::KillWordLeft => {
+                self.kill(self.word_left_pos(), self.pos)
+            }
+            EditAction::Yank => self.yank(),
+            EditAction::YankPop => self.yank_pop(),
+            EditAction::Transpose => self.transpose(),
+            EditAction::Paste => {
+                self.paste = true;
+                self.pbuf += codes::REQUEST_SELECTION;
+            }
+            EditAction::Custom(id) => self.custom(id),
+            EditAction::NormalMode => {
+                if self.edit_mode == EditMode::Vi && !self.vi_normal {
+                    self.set_vi_normal(true);
+                    self.move_left();
                 }
             }
-            _ => {}
+            EditAction::Insert => self.set_vi_normal(false),
+            EditAction::Append => {
+                self.move_right();
+                self.set_vi_normal(false);
+            }
+            EditAction::InsertStart => {
+                self.home();
+                self.set_vi_normal(false);
+            }
+            EditAction::AppendEnd => {
+                self.end();
+                self.set_vi_normal(false);
+            }
+            EditAction::Substitute => {
+                self.delete();
+                self.set_vi_normal(false);
+            }
+            EditAction::ChangeToEnd => {
+                self.kill(self.pos, self.buf.len());
+                self.set_vi_normal(false);
+            }
+            EditAction::ChangeLine => {
+                self.kill(0, self.buf.len());
+                self.set_vi_normal(false);
+            }
         }
 
+        self.last_action = Some(action);
+        self.revalidate();
         self.commit()?;
 
         Ok(false)
     }
 
+    /// Gets the validation of the current input from the validator.
+    fn validate(&mut self) {
+        if let Some(v) = &mut self.validator {
+            self.validation = v(&self.buf);
+        }
+    }
+
+    /// Validates the input and reprints it with the new style and hint.
+    fn revalidate(&mut self) {
+        if self.validator.is_some() {
+            self.validate();
+            self.reprint_from(0);
+        }
+    }
+
+    /// Erases the hint after the input. The cursor must be at the end of the
+    /// input.
+    fn erase_hint(&mut self) {
+        if !self.validation.hint.is_empty() {
+            self.pbuf += codes::ERASE_TO_END;
+        }
+    }
+
     fn insert(&mut self, s: &str) {
         let len = self.buf.len();
         self.buf.splice(self.pos..self.pos, s.chars());
@@ -423,6 +790,11 @@ where
     }
 
     fn move_word_left(&mut self) {
+        self.move_to_pos(self.word_left_pos());
+    }
+
+    /// Gets the position of the start of the word before the cursor.
+    fn word_left_pos(&self) -> usize {
         let mut pos = self.pos;
         pos = pos.saturating_sub(1);
         while pos > 0 && self.buf[pos].is_ascii_whitespace() {
@@ -434,12 +806,132 @@ where
         if pos < self.buf.len() && self.buf[pos].is_ascii_whitespace() {
             pos += 1;
         }
+        pos
+    }
+
+    /// Deletes the characters in the range, stores them in the kill ring and
+    /// moves the cursor to its start. Text of consecutive kills is joined.
+    fn kill(&mut self, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+
+        let text: String = self.buf[start..end].iter().collect();
+        match self.kill_ring.front_mut() {
+            Some(last) if is_kill(self.last_action) => {
+                if end <= self.pos {
+                    last.insert_str(0, &text);
+                } else {
+                    last.push_str(&text);
+                }
+            }
+            _ => {
+                self.kill_ring.push_front(text);
+                self.kill_ring.truncate(KILL_RING_LEN);
+            }
+        }
+
+        self.remove(start, end);
+    }
+
+    /// Deletes the characters in the range and moves the cursor to its
+    /// start.
+    fn remove(&mut self, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+        self.move_to_pos(start);
+        self.buf.drain(start..end);
+        self.reprint_pos();
+    }
+
+    fn yank(&mut self) {
+        self.yank_idx = 0;
+        if let Some(text) = self.kill_ring.front().cloned() {
+            self.insert(&text);
+        }
+    }
+
+    /// Replaces the yanked text before the cursor with the previous text in
+    /// the kill ring.
+    fn yank_pop(&mut self) {
+        if !matches!(
+            self.last_action,
+            Some(EditAction::Yank | EditAction::YankPop)
+        ) || self.kill_ring.is_empty()
+        {
+            return;
+        }
+
+        let len = self.kill_ring[self.yank_idx].chars().count();
+        self.remove(self.pos - len, self.pos);
+        self.yank_idx = (self.yank_idx + 1) % self.kill_ring.len();
+        let text = self.kill_ring[self.yank_idx].clone();
+        self.insert(&text);
+    }
+
+    fn transpose(&mut self) {
+        if self.buf.len() < 2 || self.pos == 0 {
+            return;
+        }
+        let pos = self.pos.min(self.buf.len() - 1);
+        self.buf.swap(pos - 1, pos);
+        self.reprint_from_move_to(pos - 1, pos + 1);
+    }
+
+    fn custom(&mut self, id: u32) {
+        if self.custom_action.is_none() {
+            return;
+        }
+        let mut pos = self.pos;
+        // The callback may change the whole input.
+        self.move_to_pos(0);
+        if let Some(f) = &mut self.custom_action {
+            f(id, &mut self.buf, &mut pos);
+        }
+        self.buf.retain(|c| !c.is_ascii_control());
+        self.reprint_from_move_to(0, pos.min(self.buf.len()));
+    }
+
+    /// Switches between the normal and insert mode of [`EditMode::Vi`].
+    /// The prompt is reprinted if there is mode indicator.
+    fn set_vi_normal(&mut self, normal: bool) {
+        if self.edit_mode != EditMode::Vi || self.vi_normal == normal {
+            return;
+        }
+        if self.mode_indicator.is_none() {
+            self.vi_normal = normal;
+            return;
+        }
+
+        // The length of the indicator may change, so move to the start
+        // before the change.
+        let pos = self.pos;
+        self.move_rd_dif((0, 0).into(), self.cur_pos());
+        self.vi_normal = normal;
+        self.reprint_with_prompt_dont_move();
         self.move_to_pos(pos);
     }
 
+    /// Gets the mode indicator that is shown before the prompt.
+    fn indicator(&self) -> Option<&TermText<'p>> {
+        let ind = self.mode_indicator.as_ref()?;
+        match self.edit_mode {
+            EditMode::Vi if self.vi_normal => Some(&ind.normal),
+            EditMode::Vi => Some(&ind.insert),
+            EditMode::Emacs => None,
+        }
+    }
+
+    /// Gets the length of the prompt with the mode indicator.
+    fn prompt_len(&self) -> usize {
+        self.prompt.display_char_cnt()
+            + self.indicator().map_or(0, |i| i.display_char_cnt())
+    }
+
     /// Gets the position + prompt lentgth
     fn len(&self) -> usize {
-        self.pos + self.prompt.display_char_cnt()
+        self.pos + self.prompt_len()
     }
 
     fn cur_pos(&self) -> Vec2 {
@@ -447,7 +939,7 @@ where
     }
 
     fn start_pos(&self) -> Vec2 {
-        self.size.pos_of_idx(self.prompt.display_char_cnt())
+        self.size.pos_of_idx(self.prompt_len())
     }
 
     fn move_start(&mut self) {
@@ -545,13 +1037,24 @@ where
 
     fn reprint_with_prompt_dont_move(&mut self) {
         self.pbuf += codes::ERASE_TO_END;
+        if self.marker.is_enabled() {
+            self.pbuf += codes::PROMPT_START;
+        }
+        if let Some(ind) = self.indicator() {
+            let ind = ind.as_str().to_string();
+            self.pbuf += &ind;
+        }
         self.pbuf += self.prompt.as_str();
+        if self.marker.is_enabled() {
+            self.pbuf += codes::PROMPT_END;
+        }
         self.print_from_dont_move(0);
 
         self.pos = self.buf.len();
         if self.cur_pos().x == 0 && !self.buf.is_empty() {
-            self.pbuf += "\r\n";
+            self.pbuf += self.term.newline();
         }
+        self.print_hint();
     }
 
     fn reprint_dont_move(&mut self, pos: usize) {
@@ -560,13 +1063,43 @@ where
 
         self.pos = self.buf.len();
         if self.cur_pos().x == 0 && !self.buf.is_empty() {
-            self.pbuf += "\r\n";
+            self.pbuf += self.term.newline();
         }
+        self.print_hint();
     }
 
     fn print_from_dont_move(&mut self, pos: usize) {
+        let style = self.validation.style;
+        if !style.is_default() {
+            self.pbuf += &style.code();
+        }
         self.pbuf
             .extend(self.buf[pos..].iter().copied().map(get_printable));
+        if !style.is_default() {
+            self.pbuf += codes::RESET;
+        }
+    }
+
+    /// Prints the hint after the end of the input and moves the cursor back
+    /// to the end of the input.
+    fn print_hint(&mut self) {
+        if self.validation.hint.is_empty() {
+            return;
+        }
+
+        let style = theme::style("input.hint", None)
+            .unwrap_or_else(|| Style::new().fg(theme::palette().dim));
+        self.pbuf += &style.code();
+        let hint = self.validation.hint.chars().map(get_printable);
+        let len = hint.clone().count();
+        self.pbuf.extend(hint);
+        self.pbuf += codes::RESET;
+
+        let end = self.size.pos_of_idx(self.len() + len);
+        if end.x == 0 {
+            self.pbuf += self.term.newline();
+        }
+        self.move_rd_dif(self.cur_pos(), end);
     }
 
     fn commit(&mut self) -> Result<()> {
@@ -579,6 +1112,27 @@ where
     }
 }
 
+/// Creates prompt marker that is enabled or disabled.
+fn prompt_marker(enabled: bool) -> PromptMarker {
+    let mut res = PromptMarker::new();
+    res.set_enabled(enabled);
+    res
+}
+
+/// Checks whether the action stores text in the kill ring.
+fn is_kill(action: Option<EditAction>) -> bool {
+    matches!(
+        action,
+        Some(
+            EditAction::KillLine
+                | EditAction::KillToStart
+                | EditAction::KillWordLeft
+                | EditAction::ChangeToEnd
+                | EditAction::ChangeLine
+        )
+    )
+}
+
 /// Get printable `non-control` character.
 pub fn get_printable(c: char) -> char {
     if !c.is_ascii_control() {
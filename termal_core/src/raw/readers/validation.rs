@@ -0,0 +1,40 @@
+use crate::{theme, Style};
+
+/// How the input of [`super::TermRead`] is shown. It is returned by the
+/// validator set with [`super::TermRead::set_validator`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validation {
+    /// Style of the input text.
+    pub style: Style,
+    /// Text shown after the input (e.g. suggestion of the rest of the
+    /// input). It is not part of the input, but the user may accept it with
+    /// the right arrow at the end of the input.
+    pub hint: String,
+}
+
+impl Validation {
+    /// Input without style and hint.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Input with the theme style `input.invalid` (see [`theme::style`]).
+    /// The default is the error color of the palette.
+    pub fn invalid() -> Self {
+        let style = theme::style("input.invalid", None)
+            .unwrap_or_else(|| Style::new().fg(theme::palette().error));
+        Self::new().style(style)
+    }
+
+    /// Sets the style of the input text.
+    pub fn style(mut self, style: impl Into<Style>) -> Self {
+        self.style = style.into();
+        self
+    }
+
+    /// Sets the text shown after the input.
+    pub fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = hint.into();
+        self
+    }
+}
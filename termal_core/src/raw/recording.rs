@@ -0,0 +1,633 @@
+//! Recording of terminal sessions in the asciicast v2 format used by
+//! [asciinema](https://asciinema.org).
+//!
+//! [`Recorder`] wraps anything that can be written to or read from (e.g.
+//! [`crate::raw::Terminal`] or [`crate::raw::pty::Pty`]) and logs the data
+//! with timestamps to [`CastWriter`]. Recorded sessions can be replayed with
+//! [`play`].
+//!
+//! # Examples
+//! ```rust
+//! use std::io::Write;
+//! use termal_core::raw::recording::{
+//!     read_cast, CastEventKind, CastHeader, CastWriter, Recorder,
+//! };
+//!
+//! let cast = CastWriter::new(vec![], &CastHeader::new(80, 24))?;
+//! let mut rec = Recorder::new(vec![], cast);
+//! write!(rec, "hello")?;
+//!
+//! let (_, cast) = rec.into_parts();
+//! let data = cast.into_inner()?;
+//! let (header, events) = read_cast(data.as_slice())?;
+//! assert_eq!((header.width, header.height), (80, 24));
+//! assert_eq!(events[0].kind, CastEventKind::Output);
+//! assert_eq!(events[0].data, "hello");
+//! # Ok::<_, termal_core::error::Error>(())
+//! ```
+
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{BufRead, BufReader, Read, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::error::{Error, Result};
+
+/// Header of asciicast recording.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CastHeader {
+    /// Width of the terminal in characters.
+    pub width: usize,
+    /// Height of the terminal in characters.
+    pub height: usize,
+    /// Unix timestamp of the start of the recording.
+    pub timestamp: Option<u64>,
+    /// Maximum time between events in seconds. Longer pauses are shortened
+    /// when replaying.
+    pub idle_time_limit: Option<f64>,
+    /// Title of the recording.
+    pub title: Option<String>,
+    /// Environment variables (e.g. `SHELL` and `TERM`).
+    pub env: Vec<(String, String)>,
+}
+
+/// Kind of event in asciicast recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CastEventKind {
+    /// Data written to the terminal (`o`).
+    Output,
+    /// Data read from the terminal (`i`).
+    Input,
+    /// The terminal was resized (`r`). The data has the format
+    /// `{width}x{height}`.
+    Resize,
+    /// Marker (`m`). The data is label of the marker.
+    Marker,
+}
+
+/// Single event in asciicast recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastEvent {
+    /// Time since the start of the recording in seconds.
+    pub time: f64,
+    /// Kind of the event.
+    pub kind: CastEventKind,
+    /// Data of the event.
+    pub data: String,
+}
+
+/// Writes asciicast recording. The times of events are measured from the
+/// creation of the writer.
+#[derive(Debug)]
+pub struct CastWriter<W: Write> {
+    out: W,
+    start: Instant,
+    // Incomplete UTF-8 sequences at the end of the last output and input.
+    pending_out: Vec<u8>,
+    pending_in: Vec<u8>,
+}
+
+/// Wraps `T` and records all the data written to it and read from it to
+/// [`CastWriter`].
+///
+/// By default written data is recorded as output and read data as input.
+/// This is correct for [`crate::raw::Terminal`]. For
+/// [`crate::raw::pty::Pty`] use [`Recorder::pty`], because reading from pty
+/// gives the output of the child.
+#[derive(Debug)]
+pub struct Recorder<T, W: Write> {
+    inner: T,
+    cast: CastWriter<W>,
+    reads_are_output: bool,
+}
+
+impl CastHeader {
+    /// Creates header for terminal with the given size in characters. The
+    /// timestamp is set to the current time.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs()),
+            ..Default::default()
+        }
+    }
+
+    /// Creates header with the size of the current terminal and with the
+    /// `SHELL` and `TERM` environment variables.
+    pub fn current() -> Result<Self> {
+        let size = super::term_size()?;
+        let mut res = Self::new(size.char_width, size.char_height);
+        res.env = ["SHELL", "TERM"]
+            .into_iter()
+            .filter_map(|k| Some((k.to_string(), std::env::var(k).ok()?)))
+            .collect();
+        Ok(res)
+    }
+
+    fn to_json(&self) -> String {
+        let mut res = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}",
+            self.width, self.height
+        );
+        if let Some(t) = self.timestamp {
+            _ = write!(res, ", \"timestamp\": {t}");
+        }
+        if let Some(l) = self.idle_time_limit {
+            _ = write!(res, ", \"idle_time_limit\": {l}");
+        }
+        if let Some(t) = &self.title {
+            res += ", \"title\": ";
+            write_json_str(&mut res, t);
+        }
+        if !self.env.is_empty() {
+            res += ", \"env\": {";
+            for (i, (k, v)) in self.env.iter().enumerate() {
+                if i != 0 {
+                    res += ", ";
+                }
+                write_json_str(&mut res, k);
+                res += ": ";
+                write_json_str(&mut res, v);
+            }
+            res.push('}');
+        }
+        res.push('}');
+        res
+    }
+
+    fn from_json(v: Json) -> Result<Self> {
+        let Json::Obj(fields) = v else {
+            return Err(Error::InvalidCast);
+        };
+        let mut res = Self::default();
+        let mut version = None;
+        for (k, v) in fields {
+            match (k.as_str(), v) {
+                ("version", Json::Num(n)) => version = Some(n),
+                ("width", Json::Num(n)) => res.width = n as usize,
+                ("height", Json::Num(n)) => res.height = n as usize,
+                ("timestamp", Json::Num(n)) => res.timestamp = Some(n as u64),
+                ("idle_time_limit", Json::Num(n)) => {
+                    res.idle_time_limit = Some(n)
+                }
+                ("title", Json::Str(s)) => res.title = Some(s),
+                ("env", Json::Obj(env)) => {
+                    res.env = env
+                        .into_iter()
+                        .filter_map(|(k, v)| match v {
+                            Json::Str(v) => Some((k, v)),
+                            _ => None,
+                        })
+                        .collect()
+                }
+                _ => {}
+            }
+        }
+        if version != Some(2.) {
+            return Err(Error::InvalidCast);
+        }
+        Ok(res)
+    }
+}
+
+impl CastEventKind {
+    /// Gets the code of the event kind in asciicast.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Output => "o",
+            Self::Input => "i",
+            Self::Resize => "r",
+            Self::Marker => "m",
+        }
+    }
+
+    /// Gets the event kind from its code in asciicast.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "o" => Some(Self::Output),
+            "i" => Some(Self::Input),
+            "r" => Some(Self::Resize),
+            "m" => Some(Self::Marker),
+            _ => None,
+        }
+    }
+}
+
+impl CastEvent {
+    fn to_json(&self) -> String {
+        let mut res = format!("[{:.6}, \"{}\", ", self.time, self.kind.code());
+        write_json_str(&mut res, &self.data);
+        res.push(']');
+        res
+    }
+
+    fn from_json(v: Json) -> Result<Self> {
+        let Json::Arr(v) = v else {
+            return Err(Error::InvalidCast);
+        };
+        let [Json::Num(time), Json::Str(kind), Json::Str(data)] = &v[..]
+        else {
+            return Err(Error::InvalidCast);
+        };
+        Ok(Self {
+            time: *time,
+            kind: CastEventKind::from_code(kind).ok_or(Error::InvalidCast)?,
+            data: data.clone(),
+        })
+    }
+}
+
+impl<W: Write> CastWriter<W> {
+    /// Creates new writer and writes the header.
+    pub fn new(mut out: W, header: &CastHeader) -> Result<Self> {
+        writeln!(out, "{}", header.to_json())?;
+        Ok(Self {
+            out,
+            start: Instant::now(),
+            pending_out: vec![],
+            pending_in: vec![],
+        })
+    }
+
+    /// Gets the time since the start of the recording.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Writes event with the given data at the current time.
+    pub fn event(&mut self, kind: CastEventKind, data: &str) -> Result<()> {
+        let evt = CastEvent {
+            time: self.elapsed().as_secs_f64(),
+            kind,
+            data: data.to_string(),
+        };
+        self.write_event(&evt)
+    }
+
+    /// Writes the event. The time of the event is not changed.
+    pub fn write_event(&mut self, evt: &CastEvent) -> Result<()> {
+        writeln!(self.out, "{}", evt.to_json())?;
+        Ok(())
+    }
+
+    /// Records output. Incomplete UTF-8 sequence at the end of the data is
+    /// recorded together with the next output.
+    pub fn output(&mut self, data: &[u8]) -> Result<()> {
+        let s = take_utf8(&mut self.pending_out, data);
+        if s.is_empty() {
+            return Ok(());
+        }
+        self.event(CastEventKind::Output, &s)
+    }
+
+    /// Records input. Incomplete UTF-8 sequence at the end of the data is
+    /// recorded together with the next input.
+    pub fn input(&mut self, data: &[u8]) -> Result<()> {
+        let s = take_utf8(&mut self.pending_in, data);
+        if s.is_empty() {
+            return Ok(());
+        }
+        self.event(CastEventKind::Input, &s)
+    }
+
+    /// Records resize of the terminal.
+    pub fn resize(&mut self, width: usize, height: usize) -> Result<()> {
+        self.event(CastEventKind::Resize, &format!("{width}x{height}"))
+    }
+
+    /// Records marker with the given label.
+    pub fn marker(&mut self, label: &str) -> Result<()> {
+        self.event(CastEventKind::Marker, label)
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        Ok(self.out.flush()?)
+    }
+
+    /// Flushes and gets the underlying writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.flush()?;
+        Ok(self.out)
+    }
+}
+
+impl CastWriter<File> {
+    /// Creates new recording file with the given header.
+    pub fn create(
+        path: impl AsRef<Path>,
+        header: &CastHeader,
+    ) -> Result<Self> {
+        Self::new(File::create(path)?, header)
+    }
+}
+
+impl<T, W: Write> Recorder<T, W> {
+    /// Creates recorder that records data written to `inner` as output and
+    /// data read from `inner` as input.
+    pub fn new(inner: T, cast: CastWriter<W>) -> Self {
+        Self {
+            inner,
+            cast,
+            reads_are_output: false,
+        }
+    }
+
+    /// Creates recorder for pseudo terminal. Data read from `inner` is
+    /// recorded as output and data written to `inner` is recorded as input.
+    pub fn pty(inner: T, cast: CastWriter<W>) -> Self {
+        Self {
+            inner,
+            cast,
+            reads_are_output: true,
+        }
+    }
+
+    /// Gets the wrapped value.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets the wrapped value. Data written directly to it is not recorded.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Gets the recording.
+    pub fn cast(&self) -> &CastWriter<W> {
+        &self.cast
+    }
+
+    /// Gets the recording (e.g. to record resize or marker).
+    pub fn cast_mut(&mut self) -> &mut CastWriter<W> {
+        &mut self.cast
+    }
+
+    /// Gets the wrapped value and the recording.
+    pub fn into_parts(self) -> (T, CastWriter<W>) {
+        (self.inner, self.cast)
+    }
+}
+
+impl<T: Write, W: Write> Write for Recorder<T, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        let data = &buf[..len];
+        if self.reads_are_output {
+            self.cast.input(data)
+        } else {
+            self.cast.output(data)
+        }
+        .map_err(std::io::Error::other)?;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()?;
+        self.cast.flush().map_err(std::io::Error::other)
+    }
+}
+
+impl<T: Read, W: Write> Read for Recorder<T, W> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        let data = &buf[..len];
+        if self.reads_are_output {
+            self.cast.output(data)
+        } else {
+            self.cast.input(data)
+        }
+        .map_err(std::io::Error::other)?;
+        Ok(len)
+    }
+}
+
+/// Reads the whole asciicast v2 recording.
+pub fn read_cast(r: impl BufRead) -> Result<(CastHeader, Vec<CastEvent>)> {
+    let mut lines = r.lines();
+    let header = lines.next().ok_or(Error::InvalidCast)??;
+    let header = CastHeader::from_json(parse_json(&header)?)?;
+
+    let mut events = vec![];
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        events.push(CastEvent::from_json(parse_json(&line)?)?);
+    }
+
+    Ok((header, events))
+}
+
+/// Replays the asciicast recording file to stdout with the original timing.
+pub fn play_file(path: impl AsRef<Path>) -> Result<()> {
+    let cast = BufReader::new(File::open(path)?);
+    play(cast, &mut std::io::stdout(), 1.)
+}
+
+/// Replays the asciicast recording to `out`. The output events are written
+/// at their times divided by `speed`. Pauses are shortened to the idle time
+/// limit of the recording.
+pub fn play(
+    cast: impl BufRead,
+    out: &mut impl Write,
+    speed: f64,
+) -> Result<()> {
+    let (header, events) = read_cast(cast)?;
+    let limit = header.idle_time_limit.unwrap_or(f64::INFINITY);
+
+    let start = Instant::now();
+    let mut last = 0.;
+    let mut time = 0.;
+    for evt in events {
+        if evt.kind != CastEventKind::Output {
+            continue;
+        }
+        time += (evt.time - last).clamp(0., limit);
+        last = evt.time;
+
+        let target = Duration::from_secs_f64(time / speed);
+        if let Some(d) = target.checked_sub(start.elapsed()) {
+            out.flush()?;
+            std::thread::sleep(d);
+        }
+        out.write_all(evt.data.as_bytes())?;
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// Appends `data` to `pending` and takes the longest valid UTF-8 prefix.
+/// Invalid bytes are replaced.
+fn take_utf8(pending: &mut Vec<u8>, data: &[u8]) -> String {
+    pending.extend_from_slice(data);
+    let valid = match std::str::from_utf8(pending) {
+        Ok(_) => pending.len(),
+        // Incomplete sequence at the end.
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(_) => pending.len(),
+    };
+    let res = String::from_utf8_lossy(&pending[..valid]).into_owned();
+    pending.drain(..valid);
+    res
+}
+
+fn write_json_str(res: &mut String, s: &str) {
+    res.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => *res += "\\\"",
+            '\\' => *res += "\\\\",
+            '\n' => *res += "\\n",
+            '\r' => *res += "\\r",
+            '\t' => *res += "\\t",
+            c if c.is_control() => _ = write!(res, "\\u{:04x}", c as u32),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+}
+
+/// Minimal JSON value for parsing the recordings.
+#[derive(Debug)]
+enum Json {
+    /// `null`, `true` or `false`.
+    Other,
+    Num(f64),
+    Str(String),
+    Arr(Vec<Json>),
+    Obj(Vec<(String, Json)>),
+}
+
+fn parse_json(s: &str) -> Result<Json> {
+    let mut chars = s.chars().peekable();
+    let res = parse_value(&mut chars)?;
+    skip_ws(&mut chars);
+    if chars.next().is_some() {
+        return Err(Error::InvalidCast);
+    }
+    Ok(res)
+}
+
+type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+fn skip_ws(chars: &mut Chars) {
+    while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+}
+
+fn expect(chars: &mut Chars, c: char) -> Result<()> {
+    skip_ws(chars);
+    if chars.next() == Some(c) {
+        Ok(())
+    } else {
+        Err(Error::InvalidCast)
+    }
+}
+
+fn parse_value(chars: &mut Chars) -> Result<Json> {
+    skip_ws(chars);
+    match chars.peek().ok_or(Error::InvalidCast)? {
+        '"' => Ok(Json::Str(parse_str(chars)?)),
+        '[' => {
+            chars.next();
+            let mut res = vec![];
+            skip_ws(chars);
+            if chars.next_if_eq(&']').is_some() {
+                return Ok(Json::Arr(res));
+            }
+            loop {
+                res.push(parse_value(chars)?);
+                skip_ws(chars);
+                match chars.next() {
+                    Some(',') => {}
+                    Some(']') => return Ok(Json::Arr(res)),
+                    _ => return Err(Error::InvalidCast),
+                }
+            }
+        }
+        '{' => {
+            chars.next();
+            let mut res = vec![];
+            skip_ws(chars);
+            if chars.next_if_eq(&'}').is_some() {
+                return Ok(Json::Obj(res));
+            }
+            loop {
+                skip_ws(chars);
+                let key = parse_str(chars)?;
+                expect(chars, ':')?;
+                res.push((key, parse_value(chars)?));
+                skip_ws(chars);
+                match chars.next() {
+                    Some(',') => {}
+                    Some('}') => return Ok(Json::Obj(res)),
+                    _ => return Err(Error::InvalidCast),
+                }
+            }
+        }
+        _ => {
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|c| {
+                c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.')
+            }) {
+                word.push(c);
+            }
+            match word.as_str() {
+                "null" | "true" | "false" => Ok(Json::Other),
+                n => n.parse().map(Json::Num).map_err(|_| Error::InvalidCast),
+            }
+        }
+    }
+}
+
+fn parse_str(chars: &mut Chars) -> Result<String> {
+    expect(chars, '"')?;
+    let mut res = String::new();
+    loop {
+        match chars.next().ok_or(Error::InvalidCast)? {
+            '"' => return Ok(res),
+            '\\' => {
+                let c = match chars.next().ok_or(Error::InvalidCast)? {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'b' => '\x08',
+                    'f' => '\x0c',
+                    'u' => parse_unicode_escape(chars)?,
+                    c => c,
+                };
+                res.push(c);
+            }
+            c => res.push(c),
+        }
+    }
+}
+
+/// Parses the code after `\u`. Surrogate pairs are also supported.
+fn parse_unicode_escape(chars: &mut Chars) -> Result<char> {
+    fn hex(chars: &mut Chars) -> Result<u32> {
+        let s: String = chars.take(4).collect();
+        u32::from_str_radix(&s, 16).map_err(|_| Error::InvalidCast)
+    }
+
+    let hi = hex(chars)?;
+    if !(0xd800..0xdc00).contains(&hi) {
+        return Ok(char::from_u32(hi).unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+    if chars.next() != Some('\\') || chars.next() != Some('u') {
+        return Ok(char::REPLACEMENT_CHARACTER);
+    }
+    let lo = hex(chars)?;
+    let c =
+        0x10000 + ((hi - 0xd800) << 10) + (lo.wrapping_sub(0xdc00) & 0x3ff);
+    Ok(char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER))
+}
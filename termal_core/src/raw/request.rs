@@ -0,0 +1,684 @@
+//! Requests to the terminal and related utilities.
+
+use std::{io::Write, ops::RangeInclusive, time::Duration};
+
+use crate::{
+    codes::{self, CursorStyle},
+    error::{Error, Result},
+    Style,
+};
+
+use super::{
+    events::{Event, Status, StatusCode, TermAttr, TermFeatures},
+    is_terminal_raw, raw_mode_guard, IoProvider, Terminal,
+};
+
+/// Color used to verify true color support. It isn't in the 256 color
+/// palette so the terminal can't represent it exactly when it quantizes.
+const PROBE_COLOR: (u8, u8, u8) = (17, 34, 51);
+
+/// Whether the terminal supports a feature as detected by the requests in
+/// this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Support {
+    /// The feature is supported.
+    Yes,
+    /// The feature is not supported.
+    No,
+    /// Support of the feature couldn't be detected.
+    #[default]
+    Unknown,
+}
+
+impl Support {
+    /// Converts to [`Option`] where [`Support::Unknown`] is [`None`].
+    pub fn known(self) -> Option<bool> {
+        match self {
+            Self::Yes => Some(true),
+            Self::No => Some(false),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl From<Option<bool>> for Support {
+    fn from(value: Option<bool>) -> Self {
+        match value {
+            Some(true) => Self::Yes,
+            Some(false) => Self::No,
+            None => Self::Unknown,
+        }
+    }
+}
+
+/// Setting that can be requested with DECRQSS. See [`query_setting`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingKind {
+    /// The current text style (SGR).
+    Sgr,
+    /// The current scroll region (DECSTBM).
+    ScrollRegion,
+    /// The current cursor style (DECSCUSR).
+    CursorStyle,
+}
+
+impl SettingKind {
+    /// Gets the code that requests this setting.
+    pub const fn request_code(&self) -> &'static str {
+        match self {
+            Self::Sgr => codes::REQUEST_SGR,
+            Self::ScrollRegion => codes::REQUEST_SCROLL_REGION,
+            Self::CursorStyle => codes::REQUEST_CURSOR_STYLE,
+        }
+    }
+}
+
+/// Value of setting reported by the terminal. See [`query_setting`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Setting {
+    /// The current text style.
+    Sgr(Style),
+    /// The rows of the current scroll region (numbered from 1).
+    ScrollRegion(RangeInclusive<usize>),
+    /// The current cursor style.
+    CursorStyle(CursorStyle),
+}
+
+impl Setting {
+    /// Parses the setting of the given kind from the data of DECRQSS
+    /// response (e.g. `1;24r` for scroll region). Returns `None` if the data
+    /// is not valid setting of the kind.
+    pub fn parse(kind: SettingKind, data: &str) -> Option<Self> {
+        match kind {
+            SettingKind::Sgr => {
+                let mut style = Style::DEFAULT;
+                style
+                    .apply_sgr(&format!("{}{data}", codes::CSI))
+                    .then_some(Self::Sgr(style))
+            }
+            SettingKind::ScrollRegion => {
+                let (top, bottom) = data.strip_suffix('r')?.split_once(';')?;
+                Some(Self::ScrollRegion(
+                    top.parse().ok()?..=bottom.parse().ok()?,
+                ))
+            }
+            SettingKind::CursorStyle => {
+                let n = data.strip_suffix(" q")?;
+                let n = if n.is_empty() { 0 } else { n.parse().ok()? };
+                CursorStyle::from_code(n).map(Self::CursorStyle)
+            }
+        }
+    }
+
+    /// Gets the kind of this setting.
+    pub fn kind(&self) -> SettingKind {
+        match self {
+            Self::Sgr(_) => SettingKind::Sgr,
+            Self::ScrollRegion(_) => SettingKind::ScrollRegion,
+            Self::CursorStyle(_) => SettingKind::CursorStyle,
+        }
+    }
+}
+
+/// Determines how long [`crate::raw::Terminal`] waits for response to
+/// request.
+///
+/// The timeout adapts to the latency of the terminal. Until the first
+/// response is received, [`RequestPolicy::initial_timeout`] is used. After
+/// that, the timeout is the measured round-trip time multiplied by
+/// [`RequestPolicy::factor`] and clamped to the range
+/// [`RequestPolicy::min_timeout`]..=[`RequestPolicy::max_timeout`].
+///
+/// This way requests to terminal that doesn't support them don't stall for
+/// long on local terminal, and they don't time out prematurely over high
+/// latency connections (e.g. ssh).
+///
+/// Some terminals drop requests that come right after mode change. Request
+/// that times out may be retried [`RequestPolicy::retries`] times, if the
+/// terminal responded to the same request before (so it is known to support
+/// it). The delay before the first retry is [`RequestPolicy::retry_delay`]
+/// and it doubles with each retry. Requests are not retried by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestPolicy {
+    /// Timeout used before the latency is known.
+    pub initial_timeout: Duration,
+    /// Minimum timeout after the latency is known.
+    pub min_timeout: Duration,
+    /// Maximum timeout after the latency is known.
+    pub max_timeout: Duration,
+    /// How many times the measured round-trip time should the timeout be.
+    pub factor: u32,
+    /// Maximum number of retries of request that timed out.
+    pub retries: u32,
+    /// Delay before the first retry.
+    pub retry_delay: Duration,
+    rtt: Option<Duration>,
+}
+
+/// Counts of requests made by [`crate::raw::Terminal`]. Useful for
+/// debugging of unreliable terminals.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestStats {
+    /// Number of requests (retries are not counted).
+    pub requests: u64,
+    /// Number of requests that received response.
+    pub responses: u64,
+    /// Number of times that the response didn't come within the timeout
+    /// (including timeouts of retries).
+    pub timeouts: u64,
+    /// Number of retries.
+    pub retries: u64,
+    /// Number of requests that received response after retry.
+    pub retry_successes: u64,
+}
+
+impl RequestPolicy {
+    /// Creates policy that doesn't adapt and always uses the given timeout.
+    pub fn fixed(timeout: Duration) -> Self {
+        Self {
+            initial_timeout: timeout,
+            min_timeout: timeout,
+            max_timeout: timeout,
+            factor: 1,
+            retries: 0,
+            retry_delay: Duration::from_millis(10),
+            rtt: None,
+        }
+    }
+
+    /// Gets the timeout for the next request.
+    pub fn timeout(&self) -> Duration {
+        match self.rtt {
+            Some(rtt) => (rtt * self.factor).clamp(
+                self.min_timeout,
+                self.max_timeout.max(self.min_timeout),
+            ),
+            None => self.initial_timeout,
+        }
+    }
+
+    /// Gets the measured round-trip time. This is smoothed value over all the
+    /// successful requests.
+    pub fn rtt(&self) -> Option<Duration> {
+        self.rtt
+    }
+
+    /// Records round-trip time of successful request.
+    pub fn record(&mut self, rtt: Duration) {
+        self.rtt = Some(match self.rtt {
+            Some(old) => (old * 3 + rtt) / 4,
+            None => rtt,
+        });
+    }
+
+    /// Forget the measured round-trip time.
+    pub fn reset(&mut self) {
+        self.rtt = None;
+    }
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self {
+            initial_timeout: Duration::from_secs(1),
+            min_timeout: Duration::from_millis(20),
+            max_timeout: Duration::from_secs(2),
+            factor: 4,
+            retries: 0,
+            retry_delay: Duration::from_millis(10),
+            rtt: None,
+        }
+    }
+}
+
+/// Sends the request code to stdout and waits for the status response at
+/// most `timeout`. Returns `None` if no status response came within the
+/// timeout.
+///
+/// Unlike other request helpers, this never enables or disables raw mode,
+/// so it can be used when the terminal is already in raw mode enabled by
+/// other library. The terminal must already be in raw mode (see
+/// [`is_terminal_raw`]), otherwise [`Error::RawModeRequired`] is returned.
+///
+/// Other input received while waiting for the response is discarded. Use
+/// [`Terminal::request`] to keep it.
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use termal_core::{
+///     codes,
+///     error::Error,
+///     raw::request::try_request_no_raw,
+/// };
+///
+/// let timeout = Duration::from_millis(100);
+/// match try_request_no_raw(codes::REQUEST_TERMINAL_NAME, timeout) {
+///     Ok(Some(status)) => println!("{status}"),
+///     Ok(None) => println!("no response"),
+///     Err(Error::RawModeRequired) => println!("enable raw mode first"),
+///     Err(e) => return Err(e),
+/// }
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub fn try_request_no_raw(
+    code: impl AsRef<str>,
+    timeout: Duration,
+) -> Result<Option<Status>> {
+    if !is_terminal_raw() {
+        return Err(Error::RawModeRequired);
+    }
+
+    let mut term = Terminal::stdio();
+    term.set_request_policy(RequestPolicy::fixed(timeout));
+    term.request(code, |e| match e {
+        Event::Status(s) => Some(s.clone()),
+        _ => None,
+    })
+}
+
+/// Checks whether the terminal really supports true color. This is the only
+/// reliable way to check it because `COLORTERM` may be missing or wrong.
+///
+/// RGB foreground color is set and read back with DECRQSS
+/// ([`codes::REQUEST_SGR`]). If the terminal reports the same color, it
+/// supports true color. If it reports different color, it quantized the
+/// color to its palette. Returns `None` if the terminal didn't respond
+/// within `timeout` (it doesn't support DECRQSS).
+///
+/// This enables raw mode for the duration of the request. The foreground
+/// color is reset to default afterwards. See also [`verify_truecolor_on`].
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use termal_core::raw::request::verify_truecolor;
+///
+/// match verify_truecolor(Duration::from_millis(100))? {
+///     Some(true) => println!("true color is supported"),
+///     Some(false) => println!("colors are quantized"),
+///     None => println!("unknown"),
+/// }
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub fn verify_truecolor(timeout: Duration) -> Result<Option<bool>> {
+    let _guard = raw_mode_guard()?;
+    verify_truecolor_on(&mut Terminal::stdio(), timeout)
+}
+
+/// Checks whether the terminal really supports true color. The terminal
+/// should be in raw mode. See [`verify_truecolor`].
+pub fn verify_truecolor_on<T: IoProvider>(
+    term: &mut Terminal<T>,
+    timeout: Duration,
+) -> Result<Option<bool>> {
+    let (r, g, b) = PROBE_COLOR;
+    let res = with_timeout(term, timeout, request_probe_sgr);
+    Ok(match res? {
+        Some(Status::Setting(Some(s))) => Some(has_rgb_fg(&s, r, g, b)),
+        _ => None,
+    })
+}
+
+/// Sets the foreground to [`PROBE_COLOR`], asks for the SGR and resets the
+/// foreground.
+fn request_probe_sgr<T: IoProvider>(
+    term: &mut Terminal<T>,
+) -> Result<Option<Status>> {
+    let (r, g, b) = PROBE_COLOR;
+    term.write_all(codes::fg!(r, g, b).as_bytes())?;
+    let res = term.request_status(codes::REQUEST_SGR, StatusCode::Setting);
+    term.write_all(codes::RESET_FG.as_bytes())?;
+    term.flush()?;
+    res
+}
+
+/// Asks the terminal for the title of the window. Returns `None` if the
+/// terminal didn't respond within `timeout`. Many terminals don't report the
+/// title for security reasons, so it is good idea to use
+/// [`codes::PUSH_WINDOW_TITLE`] and [`codes::POP_WINDOW_TITLE`] to restore
+/// the title instead.
+///
+/// This enables raw mode for the duration of the request. See also
+/// [`window_title_on`].
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use termal_core::raw::request::window_title;
+///
+/// match window_title(Duration::from_millis(100))? {
+///     Some(t) => println!("title: {t}"),
+///     None => println!("the terminal didn't report the title"),
+/// }
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub fn window_title(timeout: Duration) -> Result<Option<String>> {
+    let _guard = raw_mode_guard()?;
+    window_title_on(&mut Terminal::stdio(), timeout)
+}
+
+/// Asks the terminal for the title of the window. The terminal should be in
+/// raw mode. See [`window_title`].
+pub fn window_title_on<T: IoProvider>(
+    term: &mut Terminal<T>,
+    timeout: Duration,
+) -> Result<Option<String>> {
+    let res = with_timeout(term, timeout, |t| {
+        t.request_status(codes::REQUEST_WINDOW_TITLE, StatusCode::WindowTitle)
+    });
+    Ok(match res? {
+        Some(Status::WindowTitle(t)) => Some(t),
+        _ => None,
+    })
+}
+
+/// Asks the terminal for the size of single character cell in pixels with
+/// [`codes::REQUEST_CHAR_SIZE`]. Returns `(width, height)` or `None` if the
+/// terminal didn't respond within `timeout`. This is useful if
+/// [`super::term_size`] doesn't report the size in pixels.
+///
+/// This enables raw mode for the duration of the request. See also
+/// [`char_size_on`].
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use termal_core::raw::{request::char_size, term_size};
+///
+/// let mut size = term_size()?;
+/// if size.cell_size().is_none() {
+///     if let Some((w, h)) = char_size(Duration::from_millis(100))? {
+///         size = size.with_cell_size(w, h);
+///     }
+/// }
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub fn char_size(timeout: Duration) -> Result<Option<(usize, usize)>> {
+    let _guard = raw_mode_guard()?;
+    char_size_on(&mut Terminal::stdio(), timeout)
+}
+
+/// Asks the terminal for the size of single character cell in pixels. The
+/// terminal should be in raw mode. See [`char_size`].
+pub fn char_size_on<T: IoProvider>(
+    term: &mut Terminal<T>,
+    timeout: Duration,
+) -> Result<Option<(usize, usize)>> {
+    with_timeout(term, timeout, |t| t.char_size())
+}
+
+/// Asks the terminal for the position of the cursor. Returns `(x, y)`
+/// where the top left corner is `(1, 1)`, or `None` if the terminal didn't
+/// respond within `timeout`.
+///
+/// The unambiguous request [`codes::REQUEST_CURSOR_POSITION2`] is sent
+/// together with [`codes::REQUEST_STATUS_REPORT`]. If the terminal responds
+/// only to the status report, it doesn't support the unambiguous request and
+/// the widely supported [`codes::REQUEST_CURSOR_POSITION`] is sent right
+/// away. Its response may look like F3 key press with modifiers, but it is
+/// not reported as key press.
+///
+/// This enables raw mode for the duration of the request. See also
+/// [`cursor_position_on`].
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use termal_core::raw::request::cursor_position;
+///
+/// if let Some((x, y)) = cursor_position(Duration::from_millis(100))? {
+///     println!("cursor is at {x}, {y}");
+/// }
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub fn cursor_position(timeout: Duration) -> Result<Option<(usize, usize)>> {
+    let _guard = raw_mode_guard()?;
+    cursor_position_on(&mut Terminal::stdio(), timeout)
+}
+
+/// Asks the terminal for the position of the cursor. The terminal should be
+/// in raw mode. See [`cursor_position`].
+pub fn cursor_position_on<T: IoProvider>(
+    term: &mut Terminal<T>,
+    timeout: Duration,
+) -> Result<Option<(usize, usize)>> {
+    with_timeout(term, timeout, |t| t.cursor_position())
+}
+
+/// Asks the terminal for its device attributes (DA1) with
+/// [`codes::REQUEST_DEVICE_ATTRIBUTES`]. The response contains the type of
+/// the terminal and its features (e.g. sixel graphics or ANSI color).
+/// Returns `None` if the terminal didn't respond within `timeout`.
+///
+/// This enables raw mode for the duration of the request. See also
+/// [`device_attributes_on`].
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use termal_core::raw::{events::TermFeatures, request::device_attributes};
+///
+/// let attr = device_attributes(Duration::from_millis(100))?;
+/// if attr.is_some_and(|a| a.features.contains(TermFeatures::SIXEL_GRAPHICS))
+/// {
+///     println!("sixel is supported");
+/// }
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub fn device_attributes(timeout: Duration) -> Result<Option<TermAttr>> {
+    let _guard = raw_mode_guard()?;
+    device_attributes_on(&mut Terminal::stdio(), timeout)
+}
+
+/// Asks the terminal for its device attributes. The terminal should be in
+/// raw mode. See [`device_attributes`].
+pub fn device_attributes_on<T: IoProvider>(
+    term: &mut Terminal<T>,
+    timeout: Duration,
+) -> Result<Option<TermAttr>> {
+    let res = with_timeout(term, timeout, |t| {
+        t.request_status(
+            codes::REQUEST_DEVICE_ATTRIBUTES,
+            StatusCode::Attributes,
+        )
+    });
+    Ok(match res? {
+        Some(Status::Attributes(a)) => Some(a),
+        _ => None,
+    })
+}
+
+/// Asks the terminal for the current value of the given setting with
+/// DECRQSS. Returns `None` if the terminal didn't respond within `timeout` or
+/// the response is not valid setting of the kind.
+///
+/// This enables raw mode for the duration of the request. See also
+/// [`query_setting_on`].
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use termal_core::raw::request::{query_setting, Setting, SettingKind};
+///
+/// let timeout = Duration::from_millis(100);
+/// if let Some(Setting::ScrollRegion(rows)) =
+///     query_setting(SettingKind::ScrollRegion, timeout)?
+/// {
+///     println!("scroll region: {rows:?}");
+/// }
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub fn query_setting(
+    kind: SettingKind,
+    timeout: Duration,
+) -> Result<Option<Setting>> {
+    let _guard = raw_mode_guard()?;
+    query_setting_on(&mut Terminal::stdio(), kind, timeout)
+}
+
+/// Asks the terminal for the current value of the given setting. The
+/// terminal should be in raw mode. See [`query_setting`].
+pub fn query_setting_on<T: IoProvider>(
+    term: &mut Terminal<T>,
+    kind: SettingKind,
+    timeout: Duration,
+) -> Result<Option<Setting>> {
+    let res = with_timeout(term, timeout, |t| {
+        t.request_status(kind.request_code(), StatusCode::Setting)
+    });
+    Ok(match res? {
+        Some(Status::Setting(Some(s))) => Setting::parse(kind, &s),
+        _ => None,
+    })
+}
+
+/// Asks the terminal for the current cursor style with DECRQSS
+/// ([`codes::REQUEST_CURSOR_STYLE`]). Returns `None` if the terminal didn't
+/// respond within `timeout` or the response is not valid cursor style.
+///
+/// This enables raw mode for the duration of the request. See also
+/// [`cursor_style_on`].
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use termal_core::raw::request::cursor_style;
+///
+/// if let Some(style) = cursor_style(Duration::from_millis(100))? {
+///     println!("cursor style: {style:?}");
+/// }
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub fn cursor_style(timeout: Duration) -> Result<Option<CursorStyle>> {
+    let _guard = raw_mode_guard()?;
+    cursor_style_on(&mut Terminal::stdio(), timeout)
+}
+
+/// Asks the terminal for the current cursor style. The terminal should be in
+/// raw mode. See [`cursor_style`].
+pub fn cursor_style_on<T: IoProvider>(
+    term: &mut Terminal<T>,
+    timeout: Duration,
+) -> Result<Option<CursorStyle>> {
+    let res = query_setting_on(term, SettingKind::CursorStyle, timeout)?;
+    Ok(match res {
+        Some(Setting::CursorStyle(s)) => Some(s),
+        _ => None,
+    })
+}
+
+/// Detects whether the terminal supports sixel graphics.
+///
+/// The terminal is asked for its device attributes (see
+/// [`device_attributes`]). If it reports sixel graphics, it is supported.
+/// Otherwise the terminal is asked for the number of sixel color registers
+/// ([`codes::REQUEST_SIXEL_COLORS`]) because some terminals support sixel
+/// without reporting it. If the terminal doesn't respond to device
+/// attributes, the support is guessed from the variables `TERM` and
+/// `TERM_PROGRAM`.
+///
+/// Each request waits at most `timeout`. This enables raw mode for the
+/// duration of the requests. See also [`supports_sixel_on`].
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use termal_core::raw::request::{supports_sixel, Support};
+///
+/// match supports_sixel(Duration::from_millis(100))? {
+///     Support::Yes => println!("sixel is supported"),
+///     Support::No => println!("sixel is not supported"),
+///     Support::Unknown => println!("sixel may be supported"),
+/// }
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub fn supports_sixel(timeout: Duration) -> Result<Support> {
+    let _guard = raw_mode_guard()?;
+    supports_sixel_on(&mut Terminal::stdio(), timeout)
+}
+
+/// Detects whether the terminal supports sixel graphics. The terminal should
+/// be in raw mode. See [`supports_sixel`].
+pub fn supports_sixel_on<T: IoProvider>(
+    term: &mut Terminal<T>,
+    timeout: Duration,
+) -> Result<Support> {
+    let Some(attr) = device_attributes_on(term, timeout)? else {
+        return Ok(sixel_from_env());
+    };
+    if attr.features.contains(TermFeatures::SIXEL_GRAPHICS) {
+        return Ok(Support::Yes);
+    }
+
+    let res = with_timeout(term, timeout, |t| {
+        t.request_status(codes::REQUEST_SIXEL_COLORS, StatusCode::SixelColors)
+    });
+    Ok(match res? {
+        Some(Status::SixelColors(c)) if c > 0 => Support::Yes,
+        _ => Support::No,
+    })
+}
+
+/// Runs `f` with request policy of `term` that has fixed `timeout`. The
+/// rest of the policy (retries) still applies. The original timeouts are
+/// restored afterwards, but the round-trip time measured by the requests is
+/// kept.
+fn with_timeout<T: IoProvider, R>(
+    term: &mut Terminal<T>,
+    timeout: Duration,
+    f: impl FnOnce(&mut Terminal<T>) -> R,
+) -> R {
+    let mut policy = *term.request_policy();
+    term.set_request_policy(RequestPolicy {
+        initial_timeout: timeout,
+        min_timeout: timeout,
+        max_timeout: timeout,
+        ..policy
+    });
+    let res = f(term);
+    policy.rtt = term.request_policy().rtt;
+    term.set_request_policy(policy);
+    res
+}
+
+/// Guesses sixel support from the environment variables.
+fn sixel_from_env() -> Support {
+    const SIXEL_TERMS: &[&str] = &["foot", "mlterm", "yaft", "contour"];
+    const SIXEL_PROGRAMS: &[&str] = &["WezTerm", "mintty"];
+
+    let term = std::env::var("TERM").unwrap_or_default();
+    let program = std::env::var("TERM_PROGRAM").unwrap_or_default();
+    if SIXEL_TERMS.iter().any(|t| term.starts_with(t))
+        || SIXEL_PROGRAMS.contains(&program.as_str())
+    {
+        Support::Yes
+    } else if term == "linux"
+        || term == "xterm-kitty"
+        || program == "Apple_Terminal"
+    {
+        Support::No
+    } else {
+        Support::Unknown
+    }
+}
+
+/// Checks whether the SGR setting response sets the given RGB foreground
+/// color. Both `;` and `:` separators are supported.
+fn has_rgb_fg(sgr: &str, r: u8, g: u8, b: u8) -> bool {
+    let args: Vec<_> = sgr
+        .trim_end_matches('m')
+        .split([';', ':'])
+        .map(|a| a.parse::<u32>().ok())
+        .collect();
+    let rgb = [Some(r.into()), Some(g.into()), Some(b.into())];
+
+    args.windows(2)
+        .enumerate()
+        .filter(|(_, w)| w == &[Some(38), Some(2)])
+        .any(|(i, _)| {
+            let rest = &args[i + 2..];
+            // The color may be preceded with (possibly empty) color space id.
+            rest.starts_with(&rgb) || rest.get(1..4) == Some(&rgb[..])
+        })
+}
@@ -0,0 +1,220 @@
+use crate::{
+    codes,
+    error::Result,
+    image::{
+        fit_to_cells, push_sixel, push_texel_half, push_texel_quater, FitMode,
+        Image, Mat, Rect, DEFAULT_CELL_SIZE,
+    },
+    Rgb,
+};
+
+use super::{
+    request::{supports_sixel_on, Support},
+    IoProvider, TermSize, Terminal,
+};
+
+/// Size of the terminal that is used if the terminal doesn't report its
+/// size.
+const DEFAULT_TERM_SIZE: (usize, usize) = (80, 24);
+
+/// Protocol used to draw images by [`Terminal::show_image`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ImageProtocol {
+    /// Use sixel if the terminal supports it, otherwise use
+    /// [`ImageProtocol::TexelHalf`].
+    #[default]
+    Auto,
+    /// Sixel graphics. See [`push_sixel`].
+    Sixel,
+    /// Half block characters. See [`push_texel_half`].
+    TexelHalf,
+    /// Quater block characters. See [`push_texel_quater`].
+    TexelQuater,
+}
+
+/// Options for [`Terminal::show_image`].
+///
+/// # Examples
+/// ```rust
+/// use termal_core::{
+///     image::FitMode,
+///     raw::{ImageOptions, ImageProtocol},
+/// };
+///
+/// let opts = ImageOptions::default()
+///     .protocol(ImageProtocol::TexelHalf)
+///     .fit(FitMode::Shrink)
+///     .rect(1, 1, 40, 20);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ImageOptions {
+    protocol: ImageProtocol,
+    fit: FitMode,
+    rect: Option<(usize, usize, usize, usize)>,
+}
+
+impl ImageOptions {
+    /// Sets the protocol used to draw the image. The default is
+    /// [`ImageProtocol::Auto`].
+    pub fn protocol(mut self, protocol: ImageProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Sets how the image is fitted into the terminal or into the rectangle.
+    /// The default is [`FitMode::Contain`].
+    pub fn fit(mut self, fit: FitMode) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Draw the image centered in the rectangle of `w` x `h` cells with the
+    /// top left corner at `x`, `y` (as in [`codes::move_to`]). By default,
+    /// the image is drawn at the cursor.
+    pub fn rect(mut self, x: usize, y: usize, w: usize, h: usize) -> Self {
+        self.rect = Some((x, y, w, h));
+        self
+    }
+
+    /// Draw the image at the cursor. This is the default.
+    pub fn at_cursor(mut self) -> Self {
+        self.rect = None;
+        self
+    }
+}
+
+impl<T: IoProvider> Terminal<T> {
+    /// Draws the image and flushes the output. The image is fitted into the
+    /// terminal (or into the rectangle given by [`ImageOptions::rect`]) with
+    /// the size from [`IoProvider::term_size`].
+    ///
+    /// When the image is drawn at the cursor, the cursor ends on the line
+    /// below the image. When it is drawn into rectangle, the cursor doesn't
+    /// move.
+    ///
+    /// With [`ImageProtocol::Auto`], sixel support is detected with requests
+    /// (see [`supports_sixel_on`]). If the terminal doesn't report its size
+    /// in pixels, it is also requested when drawing sixel. The terminal
+    /// should be in raw mode for the requests.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use termal_core::{
+    ///     image::RawImg,
+    ///     raw::{raw_mode_guard, ImageOptions, Terminal},
+    /// };
+    ///
+    /// let img = RawImg::from_rgb(vec![255; 64 * 64 * 3], 64, 64);
+    /// let _guard = raw_mode_guard()?;
+    /// Terminal::stdio().show_image(&img, ImageOptions::default())?;
+    /// # Ok::<_, termal_core::error::Error>(())
+    /// ```
+    pub fn show_image(
+        &mut self,
+        img: &impl Image,
+        opts: ImageOptions,
+    ) -> Result<()> {
+        if img.width() == 0 || img.height() == 0 {
+            return Ok(());
+        }
+
+        let protocol = match opts.protocol {
+            ImageProtocol::Auto => {
+                let timeout = self.request_policy().timeout();
+                match supports_sixel_on(self, timeout)? {
+                    Support::Yes => ImageProtocol::Sixel,
+                    _ => ImageProtocol::TexelHalf,
+                }
+            }
+            p => p,
+        };
+
+        let mut size = self.io().term_size().unwrap_or_else(|| {
+            let (w, h) = DEFAULT_TERM_SIZE;
+            TermSize {
+                char_width: w,
+                char_height: h,
+                ..Default::default()
+            }
+        });
+        if protocol == ImageProtocol::Sixel && size.cell_size().is_none() {
+            let (w, h) = self.char_size()?.unwrap_or(DEFAULT_CELL_SIZE);
+            size = size.with_cell_size(w, h);
+        }
+        let cell = size.cell_size().unwrap_or(DEFAULT_CELL_SIZE);
+
+        // Size of the box that the image is fitted into.
+        let (bw, bh) = match opts.rect {
+            Some((_, _, w, h)) => (w, h),
+            // Keep space for the line below the image.
+            None => (size.char_width, size.char_height.saturating_sub(1)),
+        };
+        if bw == 0 || bh == 0 {
+            return Ok(());
+        }
+        let fit_size = TermSize {
+            char_width: bw,
+            char_height: bh,
+            pixel_width: bw * cell.0,
+            pixel_height: bh * cell.1,
+        };
+        let (w, h) =
+            fit_to_cells(img.width(), img.height(), fit_size, opts.fit);
+
+        let mut out = String::new();
+        match protocol {
+            ImageProtocol::Sixel => {
+                let scaled = scale(img, w * cell.0, h * cell.1);
+                push_sixel(&mut out, &scaled);
+            }
+            ImageProtocol::TexelQuater => {
+                push_texel_quater(img, &mut out, "\n", Some(w), Some(h));
+                out += codes::RESET;
+            }
+            _ => {
+                push_texel_half(img, &mut out, "\n", Some(w), Some(h));
+                out += codes::RESET;
+            }
+        }
+
+        match opts.rect {
+            Some((x, y, rw, rh)) => {
+                let x = x + rw.saturating_sub(w) / 2;
+                let y = y + rh.saturating_sub(h) / 2;
+                let mut res = codes::CUR_SAVE.to_string();
+                if protocol == ImageProtocol::Sixel {
+                    res += &codes::move_to!(x, y);
+                    res += &out;
+                } else {
+                    for (i, line) in out.split('\n').enumerate() {
+                        res += &codes::move_to!(x, y + i);
+                        res += line;
+                    }
+                }
+                res += codes::CUR_LOAD;
+                self.print(res)?;
+            }
+            None => {
+                if protocol != ImageProtocol::Sixel {
+                    out.push('\n');
+                }
+                self.print(out)?;
+            }
+        }
+        self.flush_queued()
+    }
+}
+
+/// Scales the image to the given size in pixels.
+fn scale(img: &impl Image, w: usize, h: usize) -> Mat<Rgb> {
+    let pw = img.width() as f32 / w as f32;
+    let ph = img.height() as f32 / h as f32;
+    let mut res = Mat::new(w, h);
+    for y in 0..h {
+        for x in 0..w {
+            let rect = Rect::new(x as f32 * pw, y as f32 * ph, pw, ph);
+            res[(x, y)] = img.get_avg(rect).as_u8();
+        }
+    }
+    res
+}
@@ -0,0 +1,144 @@
+use std::{
+    io::Write,
+    ops::{Deref, DerefMut},
+};
+
+use crate::error::Result;
+
+use super::{
+    testing::RecordingIoProvider, IoProvider, StdioProvider, Terminal,
+    TtyProvider,
+};
+
+/// IoProvider that can create another provider that writes to the same
+/// output. See [`Terminal::split`].
+pub trait SplitIo: IoProvider {
+    /// Provider used by [`TermWriter`].
+    type Writer: IoProvider;
+
+    /// Creates provider that writes to the same output as this provider.
+    fn split_writer(&self) -> Result<Self::Writer>;
+}
+
+/// Reading half of terminal. It dereferences to the [`Terminal`], so it can
+/// still write (e.g. requests). See [`Terminal::split`].
+#[derive(Debug)]
+pub struct TermReader<T: IoProvider>(Terminal<T>);
+
+/// Writing half of terminal. See [`Terminal::split`].
+#[derive(Debug)]
+pub struct TermWriter<T: IoProvider>(Terminal<T>);
+
+impl<T: SplitIo> Terminal<T> {
+    /// Splits the terminal into reading and writing half, so that one thread
+    /// can block on reading while other thread writes the output.
+    ///
+    /// The writer has the same newline policy and 8-bit control setting as
+    /// this terminal. Queued output is flushed before the split.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use termal_core::raw::Terminal;
+    ///
+    /// let (mut reader, mut writer) = Terminal::stdio().split()?;
+    /// std::thread::spawn(move || writer.println("rendering"));
+    /// let evt = reader.read()?;
+    /// # Ok::<_, termal_core::error::Error>(())
+    /// ```
+    pub fn split(mut self) -> Result<(TermReader<T>, TermWriter<T::Writer>)> {
+        self.flush_queued()?;
+        let mut writer = Terminal::new(self.io().split_writer()?);
+        writer.set_newline_policy(self.newline_policy());
+        writer.set_8bit_controls(self.is_8bit_controls());
+        Ok((TermReader(self), TermWriter(writer)))
+    }
+}
+
+impl<T: IoProvider> TermReader<T> {
+    /// Gets the terminal of this reader.
+    pub fn into_inner(self) -> Terminal<T> {
+        self.0
+    }
+}
+
+impl<T: IoProvider> Deref for TermReader<T> {
+    type Target = Terminal<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: IoProvider> DerefMut for TermReader<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: IoProvider> TermWriter<T> {
+    /// Prints to the output. See [`Terminal::print`].
+    pub fn print(&mut self, s: impl AsRef<str>) -> Result<()> {
+        self.0.print(s)
+    }
+
+    /// Prints to the output and appends newline. See [`Terminal::println`].
+    pub fn println(&mut self, s: impl AsRef<str>) -> Result<()> {
+        self.0.println(s)
+    }
+
+    /// Appends to the queued output. See [`Terminal::queue`].
+    pub fn queue(&mut self, s: impl AsRef<str>) {
+        self.0.queue(s)
+    }
+
+    /// Writes the queued output. See [`Terminal::flush_queued`].
+    pub fn flush_queued(&mut self) -> Result<()> {
+        self.0.flush_queued()
+    }
+
+    /// Gets the string that should be printed as newline. See
+    /// [`Terminal::newline`].
+    pub fn newline(&self) -> &'static str {
+        self.0.newline()
+    }
+
+    /// Gets the io provider of this writer.
+    pub fn io(&self) -> &T {
+        self.0.io()
+    }
+}
+
+impl<T: IoProvider> Write for TermWriter<T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl SplitIo for StdioProvider {
+    type Writer = Self;
+
+    fn split_writer(&self) -> Result<Self::Writer> {
+        Ok(*self)
+    }
+}
+
+impl SplitIo for TtyProvider {
+    type Writer = Self;
+
+    fn split_writer(&self) -> Result<Self::Writer> {
+        self.try_clone()
+    }
+}
+
+impl SplitIo for RecordingIoProvider {
+    type Writer = Self;
+
+    /// The writer has no input and it records its output separately.
+    fn split_writer(&self) -> Result<Self::Writer> {
+        Ok(self.without_io())
+    }
+}
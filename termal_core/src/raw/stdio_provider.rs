@@ -1,15 +1,25 @@
-use std::io::{stdin, stdout, IsTerminal, StdinLock, StdoutLock};
+use std::io::{self, stdin, stdout, IsTerminal, StdinLock, StdoutLock, Write};
 
 use crate::error::Result;
 
 use super::{
-    is_raw_mode_enabled, wait_for_stdin, IoProvider, ValueOrMut, WaitForIn,
+    is_raw_mode_enabled, term_size, wait_for_stdin, IoProvider, TermSize,
+    ValueOrMut, WaitForIn,
 };
 
 /// Zero size IoProvider with stdin and stdout.
+///
+/// On windows consoles that don't support VT codes, the codes written to the
+/// output are translated to the console api (see [`super::legacy`]).
 #[derive(Copy, Clone, Default, Debug)]
 pub struct StdioProvider();
 
+/// Locked stdout. If stdout doesn't support VT codes (see [`super::vt_supported`]),
+/// the codes are translated to the console api.
+pub struct StdioOut {
+    out: StdoutLock<'static>,
+}
+
 impl WaitForIn for StdioProvider {
     fn wait_for_in(&self, timeout: std::time::Duration) -> Result<bool> {
         wait_for_stdin(timeout)
@@ -17,11 +27,13 @@ impl WaitForIn for StdioProvider {
 }
 
 impl IoProvider for StdioProvider {
-    type Out = StdoutLock<'static>;
+    type Out = StdioOut;
     type In = StdinLock<'static>;
 
     fn get_out(&mut self) -> ValueOrMut<'_, Self::Out> {
-        ValueOrMut::Value(stdout().lock())
+        ValueOrMut::Value(StdioOut {
+            out: stdout().lock(),
+        })
     }
 
     fn get_in(&mut self) -> ValueOrMut<'_, Self::In> {
@@ -39,4 +51,28 @@ impl IoProvider for StdioProvider {
     fn is_out_raw(&self) -> bool {
         is_raw_mode_enabled()
     }
+    fn term_size(&self) -> Option<TermSize> {
+        term_size().ok()
+    }
+}
+
+impl Write for StdioOut {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        #[cfg(windows)]
+        if !super::vt_supported() {
+            super::sys::write_legacy(&mut self.out, buf).map_err(
+                |e| match e {
+                    crate::error::Error::Io(e) => e,
+                    e => io::Error::other(e),
+                },
+            )?;
+            return Ok(buf.len());
+        }
+
+        self.out.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
 }
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{fs::File, time::Duration};
 
 use crate::error::{Error, Result};
 
@@ -7,8 +7,15 @@ mod unix;
 #[cfg(windows)]
 mod windows;
 
-/// Size of terminal.
-#[derive(Clone, Debug)]
+#[cfg(windows)]
+pub(crate) use windows::write_legacy;
+
+#[cfg(all(unix, feature = "pty"))]
+pub(crate) use unix::{resize_pty, spawn_pty};
+
+/// Size of terminal. Some terminals don't report the size in pixels and it
+/// is `0`. See [`TermSize::with_cell_size`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub struct TermSize {
     /// Width in characters.
     pub char_width: usize,
@@ -20,6 +27,32 @@ pub struct TermSize {
     pub pixel_height: usize,
 }
 
+impl TermSize {
+    /// Gets the size of single character cell in pixels. Returns `None` if
+    /// the size in pixels is unknown.
+    pub fn cell_size(&self) -> Option<(usize, usize)> {
+        if self.char_width == 0
+            || self.char_height == 0
+            || self.pixel_width == 0
+            || self.pixel_height == 0
+        {
+            return None;
+        }
+        Some((
+            self.pixel_width / self.char_width,
+            self.pixel_height / self.char_height,
+        ))
+    }
+
+    /// Sets the size in pixels from the size of single character cell (e.g.
+    /// from [`super::request::char_size`]).
+    pub fn with_cell_size(mut self, w: usize, h: usize) -> Self {
+        self.pixel_width = self.char_width * w;
+        self.pixel_height = self.char_height * h;
+        self
+    }
+}
+
 /// Enables raw terminal.
 ///
 /// # Support
@@ -67,6 +100,24 @@ pub fn is_raw_mode_enabled() -> bool {
     false
 }
 
+/// Checks if the terminal is actually in raw mode. Unlike
+/// [`is_raw_mode_enabled`], this checks the state of the terminal, so it
+/// detects also raw mode enabled by other libraries.
+///
+/// # Support
+/// - Unix (Linux)
+/// - Windows (not tested)
+pub fn is_terminal_raw() -> bool {
+    #[cfg(unix)]
+    return unix::is_terminal_raw();
+
+    #[cfg(windows)]
+    return windows::is_raw_mode_enabled().unwrap_or_default();
+
+    #[allow(unreachable_code)]
+    false
+}
+
 /// Gets the terminal size.
 ///
 /// # Support
@@ -102,3 +153,73 @@ pub fn wait_for_stdin(timeout: Duration) -> Result<bool> {
     #[allow(unreachable_code)]
     Err(Error::NotSupportedOnPlatform("stdin timeout"))
 }
+
+/// Wait for input on the given file, but not longer than the timeout.
+///
+/// # Returns
+/// `true` if there is input. If this returns due to timeout or interrupt,
+/// returns `false`.
+pub(crate) fn wait_for_file(file: &File, timeout: Duration) -> Result<bool> {
+    #[cfg(unix)]
+    return unix::wait_for_fd(std::os::fd::AsRawFd::as_raw_fd(file), timeout);
+
+    #[cfg(windows)]
+    return windows::wait_for_handle(
+        std::os::windows::io::AsRawHandle::as_raw_handle(file) as _,
+        timeout,
+    );
+
+    #[allow(unreachable_code)]
+    Err(Error::NotSupportedOnPlatform("input timeout"))
+}
+
+/// Gets the size of the terminal of the given file. On windows, the file
+/// must be console output.
+pub(crate) fn file_term_size(file: &File) -> Result<TermSize> {
+    #[cfg(unix)]
+    return unix::fd_window_size(std::os::fd::AsRawFd::as_raw_fd(file));
+
+    #[cfg(windows)]
+    return windows::handle_term_size(
+        std::os::windows::io::AsRawHandle::as_raw_handle(file) as _,
+    );
+
+    #[allow(unreachable_code)]
+    Err(Error::NotSupportedOnPlatform("terminal size"))
+}
+
+/// Checks whether the terminal of the given file is in raw mode. Returns
+/// `false` if the file is not terminal. On windows, the file must be console
+/// input.
+pub(crate) fn is_file_raw(file: &File) -> bool {
+    #[cfg(unix)]
+    return unix::is_fd_raw(std::os::fd::AsRawFd::as_raw_fd(file));
+
+    #[cfg(windows)]
+    return windows::is_handle_raw(
+        std::os::windows::io::AsRawHandle::as_raw_handle(file) as _,
+    )
+    .unwrap_or_default();
+
+    #[allow(unreachable_code)]
+    false
+}
+
+/// Checks whether stdout supports VT codes. On windows, this tries to enable
+/// processing of VT codes once and the result is cached. If this returns
+/// `false`, [`super::StdioProvider`] translates the codes with
+/// [`super::legacy`].
+///
+/// # Support
+/// - Unix (Linux) (always `true`)
+/// - Windows (not tested)
+pub fn vt_supported() -> bool {
+    #[cfg(windows)]
+    {
+        static VT: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+        return *VT.get_or_init(windows::enable_vt_processing);
+    }
+
+    #[allow(unreachable_code)]
+    true
+}
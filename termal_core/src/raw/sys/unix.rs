@@ -7,7 +7,7 @@ use std::{
 
 use libc::{
     cfmakeraw, ioctl, poll, pollfd, tcgetattr, tcsetattr, termios as Termios,
-    winsize, EINTR, POLLIN, TCSANOW, TIOCGWINSZ,
+    winsize, ECHO, EINTR, ICANON, POLLIN, TCSANOW, TIOCGWINSZ,
 };
 
 use crate::{error::Result, raw::TermSize};
@@ -74,6 +74,21 @@ pub fn is_raw_mode_enabled() -> bool {
     get_original_terminal_mode().is_some()
 }
 
+/// Check if the terminal is actually in raw mode on linux (it may be
+/// enabled by other library).
+pub(crate) fn is_terminal_raw() -> bool {
+    let Ok(tty) = TtyFd::get() else {
+        return false;
+    };
+    is_fd_raw(tty.fd)
+}
+
+/// Check if the terminal of the given file descriptor is in raw mode on
+/// linux. Returns `false` if the file descriptor is not terminal.
+pub(crate) fn is_fd_raw(fd: RawFd) -> bool {
+    get_terminal_attr(fd).is_ok_and(|t| t.c_lflag & (ICANON | ECHO) == 0)
+}
+
 /// Enable raw mode on linux.
 pub(crate) fn enable_raw_mode() -> Result<()> {
     let mut orig_mode = get_original_terminal_mode();
@@ -111,6 +126,12 @@ pub(crate) fn disable_raw_mode() -> Result<()> {
 /// Get the window size on linux.
 pub(crate) fn window_size() -> Result<TermSize> {
     let tty = TtyFd::get()?;
+    fd_window_size(tty.fd)
+}
+
+/// Get the window size of the terminal of the given file descriptor on
+/// linux.
+pub(crate) fn fd_window_size(fd: RawFd) -> Result<TermSize> {
     let mut size = winsize {
         ws_col: 0,
         ws_row: 0,
@@ -118,17 +139,21 @@ pub(crate) fn window_size() -> Result<TermSize> {
         ws_ypixel: 0,
     };
 
-    Ok(
-        to_io_result(unsafe { ioctl(tty.fd, TIOCGWINSZ, &mut size) })
-            .map(|_| size.into())?,
-    )
+    Ok(to_io_result(unsafe { ioctl(fd, TIOCGWINSZ, &mut size) })
+        .map(|_| size.into())?)
 }
 
 /// Wait for stdin input on linux with the given timeout. If zero returns
 /// immidietly whether there is available input.
 pub(crate) fn wait_for_stdin(timeout: Duration) -> Result<bool> {
+    wait_for_fd(libc::STDIN_FILENO, timeout)
+}
+
+/// Wait for input on the given file descriptor on linux with the given
+/// timeout. If zero returns immidietly whether there is available input.
+pub(crate) fn wait_for_fd(fd: RawFd, timeout: Duration) -> Result<bool> {
     let mut pdfs = pollfd {
-        fd: libc::STDIN_FILENO,
+        fd,
         events: POLLIN,
         revents: 0,
     };
@@ -161,3 +186,88 @@ fn to_io_result(code: i32) -> io::Result<()> {
         Ok(())
     }
 }
+
+/// Opens new pseudo terminal and spawns the command in it. Returns the master
+/// side of the terminal and the child.
+#[cfg(feature = "pty")]
+pub(crate) fn spawn_pty(
+    cmd: &mut std::process::Command,
+) -> Result<(fs::File, std::process::Child)> {
+    use std::{
+        os::{
+            fd::FromRawFd, unix::fs::OpenOptionsExt, unix::process::CommandExt,
+        },
+        process::Stdio,
+    };
+
+    let fd = unsafe { libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY) };
+    to_io_result(fd)?;
+    let master = unsafe { fs::File::from_raw_fd(fd) };
+    // The child must not inherit the master side.
+    to_io_result(unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) })?;
+    to_io_result(unsafe { libc::grantpt(fd) })?;
+    to_io_result(unsafe { libc::unlockpt(fd) })?;
+
+    let slave = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NOCTTY)
+        .open(pty_name(fd)?)?;
+
+    cmd.stdin(Stdio::from(slave.try_clone()?))
+        .stdout(Stdio::from(slave.try_clone()?))
+        .stderr(Stdio::from(slave));
+    unsafe {
+        cmd.pre_exec(|| {
+            // Make the pseudo terminal the controlling terminal of the child.
+            to_io_result(libc::setsid())?;
+            to_io_result(ioctl(0, libc::TIOCSCTTY, 0))
+        })
+    };
+
+    Ok((master, cmd.spawn()?))
+}
+
+/// Sets the size of the pseudo terminal.
+#[cfg(feature = "pty")]
+pub(crate) fn resize_pty(master: &fs::File, w: usize, h: usize) -> Result<()> {
+    let size = winsize {
+        ws_col: w as u16,
+        ws_row: h as u16,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    to_io_result(unsafe {
+        ioctl(master.as_raw_fd(), libc::TIOCSWINSZ, &size)
+    })?;
+    Ok(())
+}
+
+/// Gets the path to the slave side of pseudo terminal.
+#[cfg(feature = "pty")]
+fn pty_name(master: RawFd) -> Result<String> {
+    use std::ffi::CStr;
+
+    #[cfg(target_os = "linux")]
+    {
+        let mut name = [0; 256];
+        let r =
+            unsafe { libc::ptsname_r(master, name.as_mut_ptr(), name.len()) };
+        if r != 0 {
+            return Err(io::Error::from_raw_os_error(r).into());
+        }
+        let name = unsafe { CStr::from_ptr(name.as_ptr()) };
+        Ok(name.to_string_lossy().into_owned())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        // `ptsname` is not thread safe, but `ptsname_r` is not available.
+        let name = unsafe { libc::ptsname(master) };
+        if name.is_null() {
+            return Err(io::Error::last_os_error().into());
+        }
+        let name = unsafe { CStr::from_ptr(name) };
+        Ok(name.to_string_lossy().into_owned())
+    }
+}
@@ -1,8 +1,14 @@
-use std::{io, mem::zeroed, ptr::null_mut, time::Duration};
+use std::{
+    io::{self, Write},
+    mem::{swap, zeroed},
+    ptr::null_mut,
+    sync::Mutex,
+    time::Duration,
+};
 
 use winapi::{
     shared::{
-        minwindef::{BOOL, DWORD},
+        minwindef::{BOOL, DWORD, WORD},
         winerror::WAIT_TIMEOUT,
     },
     um::{
@@ -11,16 +17,20 @@ use winapi::{
         handleapi::{CloseHandle, INVALID_HANDLE_VALUE},
         processenv::GetStdHandle,
         winbase::{
-            STD_INPUT_HANDLE, WAIT_ABANDONED, WAIT_IO_COMPLETION,
-            WAIT_OBJECT_0,
+            STD_INPUT_HANDLE, STD_OUTPUT_HANDLE, WAIT_ABANDONED,
+            WAIT_IO_COMPLETION, WAIT_OBJECT_0,
         },
         wincon::{
-            GetConsoleScreenBufferInfo, CONSOLE_SCREEN_BUFFER_INFO,
+            FillConsoleOutputAttribute, FillConsoleOutputCharacterW,
+            GetConsoleScreenBufferInfo, SetConsoleCursorPosition,
+            SetConsoleTextAttribute, CONSOLE_SCREEN_BUFFER_INFO, COORD,
             ENABLE_ECHO_INPUT, ENABLE_LINE_INPUT, ENABLE_PROCESSED_INPUT,
+            ENABLE_VIRTUAL_TERMINAL_PROCESSING, FOREGROUND_INTENSITY,
+            SMALL_RECT,
         },
         winnt::{
             FILE_SHARE_READ, FILE_SHARE_WRITE, GENERIC_READ, GENERIC_WRITE,
-            HANDLE,
+            HANDLE, SHORT, WCHAR,
         },
         winuser::{
             MsgWaitForMultipleObjectsEx, MWMO_INPUTAVAILABLE, QS_ALLINPUT,
@@ -30,17 +40,34 @@ use winapi::{
 
 use crate::{
     error::{Error, Result},
-    raw::TermSize,
+    raw::{
+        legacy::{Erase, LegacyOp, LegacyTranslator},
+        TermSize,
+    },
 };
 
 const NO_RAW_BITS: DWORD =
     ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT | ENABLE_PROCESSED_INPUT;
 
+/// State of the legacy console output. It is global because the console is
+/// global.
+static LEGACY: Mutex<Option<LegacyConsole>> = Mutex::new(None);
+
 struct Handle {
     handle: HANDLE,
     close: bool,
 }
 
+/// Console that doesn't support VT codes.
+struct LegacyConsole {
+    translator: LegacyTranslator,
+    default_attr: WORD,
+    fg: Option<u8>,
+    bg: Option<u8>,
+    bold: bool,
+    reverse: bool,
+}
+
 /// Enables raw mode on windows.
 pub fn enable_raw_mode() -> Result<()> {
     let in_buf = Handle::current_in_buf()?;
@@ -55,30 +82,40 @@ pub fn disable_raw_mode() -> Result<()> {
 
 /// Checks whether raw mode is enabled on windows.
 pub fn is_raw_mode_enabled() -> Result<bool> {
-    Handle::current_in_buf()?
-        .get_mode()
-        .map(|m| (m & NO_RAW_BITS) == 0)
+    Handle::current_in_buf()?.is_raw()
+}
+
+/// Checks whether the console input with the given handle is in raw mode on
+/// windows.
+pub fn is_handle_raw(handle: HANDLE) -> Result<bool> {
+    Handle::borrowed(handle)?.is_raw()
 }
 
 /// Get the terminal size on windows. The size in pixels is not supported.
 pub fn term_size() -> Result<TermSize> {
-    Handle::current_out_buf()?.get_info().map(|i| TermSize {
-        char_width: (i.srWindow.Right - i.srWindow.Left) as usize,
-        char_height: (i.srWindow.Bottom - i.srWindow.Top) as usize,
-        // Size in pixels is not supported
-        pixel_width: 0,
-        pixel_height: 0,
-    })
+    Handle::current_out_buf()?.term_size()
+}
+
+/// Get the size of the console with the given output handle on windows. The
+/// size in pixels is not supported.
+pub fn handle_term_size(handle: HANDLE) -> Result<TermSize> {
+    Handle::borrowed(handle)?.term_size()
 }
 
 /// Wait for stdin on windows with the given timeout. If timeout is zero
 /// returns immidietely whether there is data on stdin.
 pub fn wait_for_stdin(timeout: Duration) -> Result<bool> {
     let stdin = handle_result(unsafe { GetStdHandle(STD_INPUT_HANDLE) })?;
+    wait_for_handle(stdin, timeout)
+}
+
+/// Wait for input on the given handle on windows with the given timeout. If
+/// timeout is zero returns immidietely whether there is available input.
+pub fn wait_for_handle(handle: HANDLE, timeout: Duration) -> Result<bool> {
     let r = unsafe {
         MsgWaitForMultipleObjectsEx(
             1,
-            &stdin,
+            &handle,
             timeout.as_millis() as DWORD,
             QS_ALLINPUT,
             MWMO_INPUTAVAILABLE,
@@ -94,6 +131,37 @@ pub fn wait_for_stdin(timeout: Duration) -> Result<bool> {
     }
 }
 
+/// Tries to enable processing of VT codes on stdout. Returns `false` if the
+/// console doesn't support VT codes.
+pub fn enable_vt_processing() -> bool {
+    let Ok(out) = Handle::std_out() else {
+        return true;
+    };
+    let Ok(mode) = out.get_mode() else {
+        // Not console, the codes are written as they are.
+        return true;
+    };
+    mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+        || out
+            .set_mode(mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING)
+            .is_ok()
+}
+
+/// Writes to legacy console. The VT codes are translated to calls to the
+/// console api.
+pub fn write_legacy(out: &mut impl Write, data: &[u8]) -> Result<()> {
+    let mut legacy = LEGACY.lock().unwrap_or_else(|e| e.into_inner());
+    let con = match &mut *legacy {
+        Some(c) => c,
+        None => legacy.insert(LegacyConsole::new()?),
+    };
+
+    for op in con.translator.feed(data) {
+        con.apply(op, out)?;
+    }
+    Ok(())
+}
+
 fn result(val: BOOL) -> Result<()> {
     if val == 0 {
         Err(last_err())
@@ -122,6 +190,20 @@ impl Handle {
         })
     }
 
+    fn borrowed(handle: HANDLE) -> Result<Self> {
+        Ok(Self {
+            handle: handle_result(handle)?,
+            close: false,
+        })
+    }
+
+    fn std_out() -> Result<Self> {
+        Ok(Self {
+            handle: handle_result(unsafe { GetStdHandle(STD_OUTPUT_HANDLE) })?,
+            close: false,
+        })
+    }
+
     fn current_out_buf() -> Result<Self> {
         Self::file_handle("CONOUT$")
     }
@@ -136,6 +218,20 @@ impl Handle {
         Ok(res)
     }
 
+    fn term_size(&self) -> Result<TermSize> {
+        self.get_info().map(|i| TermSize {
+            char_width: (i.srWindow.Right - i.srWindow.Left) as usize,
+            char_height: (i.srWindow.Bottom - i.srWindow.Top) as usize,
+            // Size in pixels is not supported
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+    }
+
+    fn is_raw(&self) -> Result<bool> {
+        self.get_mode().map(|m| (m & NO_RAW_BITS) == 0)
+    }
+
     fn get_mode(&self) -> Result<DWORD> {
         let mut mode = 0;
         unsafe { result(GetConsoleMode(self.handle, &mut mode))? };
@@ -146,6 +242,71 @@ impl Handle {
         result(unsafe { SetConsoleMode(self.handle, mode) })
     }
 
+    fn set_attr(&self, attr: WORD) -> Result<()> {
+        result(unsafe { SetConsoleTextAttribute(self.handle, attr) })
+    }
+
+    /// Moves the cursor to the position given by `f` from the current
+    /// position and the visible window. The position is clamped to the
+    /// buffer.
+    fn move_cursor(
+        &self,
+        f: impl FnOnce(COORD, SMALL_RECT) -> (isize, isize),
+    ) -> Result<()> {
+        let info = self.get_info()?;
+        let (x, y) = f(info.dwCursorPosition, info.srWindow);
+        let pos = COORD {
+            X: x.clamp(0, info.dwSize.X as isize - 1) as SHORT,
+            Y: y.clamp(0, info.dwSize.Y as isize - 1) as SHORT,
+        };
+        result(unsafe { SetConsoleCursorPosition(self.handle, pos) })
+    }
+
+    fn erase(&self, erase: Erase, attr: WORD) -> Result<()> {
+        let info = self.get_info()?;
+        let w = info.dwSize.X as DWORD;
+        let cur = info.dwCursorPosition;
+        let win = info.srWindow;
+        let screen_start = COORD { X: 0, Y: win.Top };
+        let line_start = COORD { X: 0, Y: cur.Y };
+        let rows = |from: SHORT, to: SHORT| (to - from).max(0) as DWORD;
+
+        let (start, len) = match erase {
+            Erase::Screen => (screen_start, w * rows(win.Top, win.Bottom + 1)),
+            Erase::ToScreenEnd => (
+                cur,
+                (w * rows(cur.Y, win.Bottom + 1))
+                    .saturating_sub(cur.X as DWORD),
+            ),
+            Erase::ToScreenStart => {
+                (screen_start, w * rows(win.Top, cur.Y) + cur.X as DWORD + 1)
+            }
+            Erase::Line => (line_start, w),
+            Erase::ToLineEnd => (cur, w.saturating_sub(cur.X as DWORD)),
+            Erase::ToLineStart => (line_start, cur.X as DWORD + 1),
+        };
+
+        let mut written = 0;
+        result(unsafe {
+            FillConsoleOutputCharacterW(
+                self.handle,
+                b' ' as WCHAR,
+                len,
+                start,
+                &mut written,
+            )
+        })?;
+        result(unsafe {
+            FillConsoleOutputAttribute(
+                self.handle,
+                attr,
+                len,
+                start,
+                &mut written,
+            )
+        })
+    }
+
     fn file_handle(path: &str) -> Result<Self> {
         let path: Vec<_> = path.encode_utf16().chain([0]).collect();
         let path = path.as_ptr();
@@ -173,3 +334,80 @@ impl Drop for Handle {
         }
     }
 }
+
+impl LegacyConsole {
+    fn new() -> Result<Self> {
+        Ok(Self {
+            translator: LegacyTranslator::new(),
+            default_attr: Handle::std_out()?.get_info()?.wAttributes,
+            fg: None,
+            bg: None,
+            bold: false,
+            reverse: false,
+        })
+    }
+
+    fn apply(&mut self, op: LegacyOp, out: &mut impl Write) -> Result<()> {
+        if let LegacyOp::Text(t) = op {
+            out.write_all(&t)?;
+            return Ok(());
+        }
+
+        // The text must be written before the console state changes.
+        out.flush()?;
+        let con = Handle::std_out()?;
+        match op {
+            LegacyOp::Text(_) => unreachable!(),
+            LegacyOp::Reset => {
+                self.fg = None;
+                self.bg = None;
+                self.bold = false;
+                self.reverse = false;
+            }
+            LegacyOp::Fg(c) => self.fg = c,
+            LegacyOp::Bg(c) => self.bg = c,
+            LegacyOp::Bold(b) => self.bold = b,
+            LegacyOp::Reverse(r) => self.reverse = r,
+            LegacyOp::MoveBy { x, y } => {
+                return con
+                    .move_cursor(|p, _| (p.X as isize + x, p.Y as isize + y));
+            }
+            LegacyOp::MoveTo { x, y } => {
+                return con.move_cursor(|p, w| {
+                    (
+                        x.map_or(p.X as isize, |x| {
+                            w.Left as isize + x as isize
+                        }),
+                        y.map_or(p.Y as isize, |y| {
+                            w.Top as isize + y as isize
+                        }),
+                    )
+                });
+            }
+            LegacyOp::Erase(e) => return con.erase(e, self.attr()),
+        }
+
+        con.set_attr(self.attr())
+    }
+
+    /// Gets the console attribute for the current state.
+    fn attr(&self) -> WORD {
+        let def = self.default_attr;
+        let mut fg = self.fg.map_or(def & 0xf, win_color);
+        let mut bg = self.bg.map_or((def >> 4) & 0xf, win_color);
+        if self.bold {
+            fg |= FOREGROUND_INTENSITY;
+        }
+        if self.reverse {
+            swap(&mut fg, &mut bg);
+        }
+        (def & !0xff) | fg | (bg << 4)
+    }
+}
+
+/// Converts color in the ANSI order to windows console color (red and blue
+/// bits are swapped).
+fn win_color(c: u8) -> WORD {
+    let c = c as WORD;
+    (c & 0b1010) | ((c & 1) << 2) | ((c & 4) >> 2)
+}
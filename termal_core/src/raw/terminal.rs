@@ -1,20 +1,53 @@
 use std::{
     collections::VecDeque,
     io::{BufRead, Read, Write},
+    ops::RangeInclusive,
     time::{Duration, Instant},
 };
 
-use crate::error::{Error, Result};
+use crate::{
+    codes,
+    error::{Error, Result},
+};
 
-use super::{IoProvider, StdioProvider, WaitForIn};
+use super::{
+    active_modes, is_terminal_raw, modify_active_modes, ActiveModes,
+    IoProvider, ModeTracker, NewlinePolicy, StdioProvider, WaitForIn,
+};
 
 #[cfg(feature = "events")]
-use crate::{
-    codes,
-    raw::events::{AmbigousEvent, AnyEvent, Event, StateChange},
+use std::collections::HashSet;
+
+#[cfg(feature = "events")]
+use crate::raw::{
+    events::{
+        csi_len, mouse, AmbigousEvent, AnyEvent, Event, Key, KeyCode,
+        ModeState, StateChange, Status, StatusCode,
+    },
+    ModeChange, RequestPolicy, RequestStats, KNOWN_MODES,
 };
 #[cfg(feature = "readers")]
-use crate::{raw::readers::TermRead, term_text::TermText};
+use std::{fmt::Display, str::FromStr};
+
+#[cfg(feature = "readers")]
+use crate::{
+    raw::readers::{CancelPolicy, Select, TermRead},
+    term_text::TermText,
+    theme, Style,
+};
+
+/// How often the terminal size is checked while waiting for input when
+/// resize events are enabled.
+#[cfg(feature = "events")]
+const RESIZE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Result of waiting for input or resize.
+#[cfg(feature = "events")]
+enum Wait {
+    Input,
+    Timeout,
+    Resize(Event),
+}
 
 /// Terminal reader. Abstracts reading from terminal and parsing inputs. Works
 /// properly only if raw mode is enabled.
@@ -22,8 +55,45 @@ use crate::{raw::readers::TermRead, term_text::TermText};
 pub struct Terminal<T: IoProvider = StdioProvider> {
     buffer: VecDeque<u8>,
     io: T,
+    c1: bool,
+    pending_esc: bool,
+    /// Output that is written on the next write or flush.
+    queued: String,
+    newline: NewlinePolicy,
+    recorded: Option<Vec<u8>>,
+    external_raw: bool,
+    /// Tracks the modes in the written data.
+    mode_tracker: ModeTracker,
     #[cfg(feature = "events")]
     bracketed_paste_open: bool,
+    #[cfg(feature = "events")]
+    wheel_to_arrows: bool,
+    /// Translate wheel to arrows only in alternate buffer.
+    #[cfg(feature = "events")]
+    alternate_scroll: bool,
+    /// State of the alternate buffer written through this terminal. If it is
+    /// not known, the state shared by the process is used.
+    #[cfg(feature = "events")]
+    alternate_buffer: Option<bool>,
+    #[cfg(feature = "events")]
+    pending: VecDeque<AmbigousEvent>,
+    #[cfg(feature = "events")]
+    request_policy: RequestPolicy,
+    #[cfg(feature = "events")]
+    cursor_stack: Vec<Option<(usize, usize)>>,
+    /// Requests that the terminal responded to.
+    #[cfg(feature = "events")]
+    supported_requests: HashSet<String>,
+    #[cfg(feature = "events")]
+    request_stats: RequestStats,
+    #[cfg(feature = "events")]
+    resize_events: bool,
+    /// The last known size of the terminal for resize events.
+    #[cfg(feature = "events")]
+    size: Option<(usize, usize)>,
+    /// How long to wait for the rest of escape sequence after lone escape.
+    #[cfg(feature = "events")]
+    esc_timeout: Duration,
 }
 
 impl Terminal<StdioProvider> {
@@ -38,11 +108,91 @@ impl<T: IoProvider> Terminal<T> {
         Terminal {
             buffer: VecDeque::new(),
             io,
+            c1: false,
+            pending_esc: false,
+            queued: String::new(),
+            newline: NewlinePolicy::default(),
+            recorded: None,
+            external_raw: false,
+            mode_tracker: ModeTracker::default(),
             #[cfg(feature = "events")]
             bracketed_paste_open: false,
+            #[cfg(feature = "events")]
+            wheel_to_arrows: false,
+            #[cfg(feature = "events")]
+            alternate_scroll: false,
+            #[cfg(feature = "events")]
+            alternate_buffer: None,
+            #[cfg(feature = "events")]
+            pending: VecDeque::new(),
+            #[cfg(feature = "events")]
+            request_policy: RequestPolicy::default(),
+            #[cfg(feature = "events")]
+            cursor_stack: vec![],
+            #[cfg(feature = "events")]
+            supported_requests: HashSet::new(),
+            #[cfg(feature = "events")]
+            request_stats: RequestStats::default(),
+            #[cfg(feature = "events")]
+            resize_events: false,
+            #[cfg(feature = "events")]
+            size: None,
+            #[cfg(feature = "events")]
+            esc_timeout: Duration::ZERO,
         }
     }
 
+    /// Create new terminal whose raw mode is managed externally (e.g. by
+    /// crossterm or termion). See [`Terminal::set_external_raw_mode`].
+    pub fn external(io: T) -> Self {
+        let mut res = Self::new(io);
+        res.external_raw = true;
+        res
+    }
+
+    /// Sets whether raw mode is managed externally by other library. If it
+    /// is, raw mode is detected from the actual state of the terminal (see
+    /// [`super::is_terminal_raw`]) instead of the state of raw mode enabled
+    /// by termal. Termal never toggles raw mode through [`Terminal`], so this
+    /// allows using it in apps that use other terminal library.
+    pub fn set_external_raw_mode(&mut self, v: bool) {
+        self.external_raw = v;
+    }
+
+    /// Checks whether raw mode is managed externally. See
+    /// [`Terminal::set_external_raw_mode`].
+    pub fn is_external_raw_mode(&self) -> bool {
+        self.external_raw
+    }
+
+    /// Checks whether the output is in raw mode. If raw mode is managed
+    /// externally, this checks the actual state of the terminal.
+    pub fn is_out_raw(&self) -> bool {
+        if self.external_raw {
+            is_terminal_raw()
+        } else {
+            self.io.is_out_raw()
+        }
+    }
+
+    /// Gets the io provider of the terminal.
+    pub fn io(&self) -> &T {
+        &self.io
+    }
+
+    /// Gets mutable reference to the io provider of the terminal.
+    pub fn io_mut(&mut self) -> &mut T {
+        &mut self.io
+    }
+
+    /// Gets the private modes that termal believes are set in the terminal.
+    /// The state is shared by the whole process and it is tracked from the
+    /// codes written by termal. It may be verified with the terminal (see
+    /// `Terminal::verify_modes`). See [`ActiveModes`].
+    pub fn active_modes(&self) -> ActiveModes {
+        active_modes()
+    }
+
     /// Read next byte from stdin. May block.
     pub fn read_byte(&mut self) -> Result<u8> {
         if let Some(b) = self.buffer.pop_front() {
@@ -54,6 +204,10 @@ impl<T: IoProvider> Terminal<T> {
 
     /// Checks whether there is any buffered input in [`Terminal`]
     pub fn has_buffered_input(&self) -> bool {
+        #[cfg(feature = "events")]
+        if !self.pending.is_empty() {
+            return true;
+        }
         !self.buffer.is_empty()
     }
 
@@ -160,6 +314,138 @@ impl<T: IoProvider> Terminal<T> {
         }
     }
 
+    /// Sets the scroll region to the given rows (numbered from 1), calls `f`
+    /// and resets the scroll region. The scroll region is reset even if `f`
+    /// fails.
+    ///
+    /// Fails if the region is empty or if it doesn't fit into the terminal
+    /// with the size from [`IoProvider::term_size`]. If the size is not
+    /// known, the region is not limited.
+    ///
+    /// Note that setting and resetting the scroll region moves the cursor to
+    /// the top left.
+    pub fn with_scroll_region<R>(
+        &mut self,
+        rows: RangeInclusive<usize>,
+        f: impl FnOnce(&mut Self) -> Result<R>,
+    ) -> Result<R> {
+        let (top, bottom) = rows.into_inner();
+        let height = self.io.term_size().map_or(usize::MAX, |s| s.char_height);
+        let code = codes::checked_scroll_region(top, bottom, height)?;
+        self.write_all(code.as_bytes())?;
+        self.flush()?;
+
+        let res = f(self);
+
+        self.write_all(codes::RESET_SCROLL_REGION.as_bytes())?;
+        self.flush()?;
+        res
+    }
+
+    /// Calls `f` within synchronized update, so that the terminal shows all
+    /// the changes at once when the update ends. The update is ended even
+    /// if `f` fails. Terminals that don't support synchronized updates
+    /// show the changes as they come.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::io::Write;
+    /// use termal_core::{codes, raw::Terminal};
+    ///
+    /// let mut term = Terminal::stdio();
+    /// term.synchronized(|t| {
+    ///     t.write_all(codes::ERASE_ALL.as_bytes())?;
+    ///     t.write_all(b"redrawn without tearing")?;
+    ///     Ok(())
+    /// })?;
+    /// # Ok::<_, termal_core::error::Error>(())
+    /// ```
+    pub fn synchronized<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<R>,
+    ) -> Result<R> {
+        self.write_all(codes::BEGIN_SYNCHRONIZED_UPDATE.as_bytes())?;
+
+        let res = f(self);
+
+        self.write_all(codes::END_SYNCHRONIZED_UPDATE.as_bytes())?;
+        self.flush()?;
+        res
+    }
+
+    /// Flashes the screen by enabling reverse colors for the duration
+    /// `dur`. This is alternative to [`codes::BELL`] for apps where audible
+    /// bell is not acceptable. This blocks for the duration of the flash.
+    pub fn visual_bell(&mut self, dur: Duration) -> Result<()> {
+        self.write_all(codes::ENABLE_REVERSE_COLOR.as_bytes())?;
+        self.flush()?;
+        std::thread::sleep(dur);
+        self.write_all(codes::DISABLE_REVERSE_COLOR.as_bytes())?;
+        self.flush()?;
+        Ok(())
+    }
+
+    /// Asks the terminal to get the users attention with
+    /// [`codes::REQUEST_ATTENTION`]. Terminals that don't support it ignore
+    /// it.
+    pub fn request_attention(&mut self) -> Result<()> {
+        self.write_all(codes::REQUEST_ATTENTION.as_bytes())?;
+        self.flush()?;
+        Ok(())
+    }
+
+    /// Enables or disables 8-bit control introducers. When enabled, 7-bit
+    /// escape sequence introducers (e.g. [`codes::CSI`]) written to the
+    /// terminal are converted to their 8-bit versions (e.g.
+    /// [`codes::CSI8`]) and the 8-bit introducers are recognized when
+    /// parsing the input. Disabled by default.
+    ///
+    /// The 8-bit controls are not valid UTF-8 so this should be used only
+    /// with terminals that don't expect UTF-8 or when bandwidth matters. To
+    /// make the terminal respond with 8-bit controls, send it
+    /// [`codes::ENABLE_8BIT_RESPONSES`].
+    pub fn set_8bit_controls(&mut self, v: bool) {
+        self.c1 = v;
+    }
+
+    /// Checks whether 8-bit control introducers are enabled. See
+    /// [`Terminal::set_8bit_controls`].
+    pub fn is_8bit_controls(&self) -> bool {
+        self.c1
+    }
+
+    /// Enables or disables recording of the input. When enabled, the bytes
+    /// received from the input can be retrieved with
+    /// [`Terminal::take_recorded_input`]. This is useful to see which bytes
+    /// produced the last event. Disabled by default.
+    ///
+    /// Bytes read with [`Terminal::read_raw`] and its variants when there
+    /// is no buffered input are not recorded.
+    pub fn set_record_input(&mut self, v: bool) {
+        if !v {
+            self.recorded = None;
+        } else if self.recorded.is_none() {
+            self.recorded = Some(vec![]);
+        }
+    }
+
+    /// Checks whether the input is recorded. See
+    /// [`Terminal::set_record_input`].
+    pub fn is_recording_input(&self) -> bool {
+        self.recorded.is_some()
+    }
+
+    /// Takes the recorded input that was already consumed. Bytes that are
+    /// buffered but not yet consumed stay recorded. Returns empty vector if
+    /// recording is disabled.
+    pub fn take_recorded_input(&mut self) -> Vec<u8> {
+        let Some(rec) = &mut self.recorded else {
+            return vec![];
+        };
+        let len = rec.len().saturating_sub(self.buffer.len());
+        rec.drain(..len).collect()
+    }
+
     fn read_buffered(&mut self, mut res: &mut [u8]) -> Result<usize> {
         let (s1, s2) = self.buffer.as_slices();
 
@@ -189,10 +475,186 @@ impl<T: IoProvider> Terminal<T> {
         let mut stdin = self.io.get_in();
         let buf = stdin.fill_buf()?;
         self.buffer.extend(buf);
+        if let Some(rec) = &mut self.recorded {
+            rec.extend_from_slice(buf);
+        }
         let len = buf.len();
         stdin.consume(len);
         Ok(())
     }
+
+    /// Appends to the queued output. The queued output is written at once
+    /// with [`Terminal::flush_queued`]. This is much faster than writing many
+    /// small codes separately, e.g. when rendering frame of tui.
+    ///
+    /// Any other write to the terminal writes the queued output first, so
+    /// the order of the output is preserved. Newlines are not translated.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use termal_core::{codes, raw::testing::ScriptedInput};
+    ///
+    /// let mut term = ScriptedInput::new().terminal();
+    /// term.queue(codes::move_to!(1, 1));
+    /// term.queue("hello");
+    /// assert!(term.io().written().is_empty());
+    ///
+    /// term.flush_queued()?;
+    /// assert_eq!(term.io().written_str(), "\x1b[1;1Hhello");
+    /// # Ok::<_, termal_core::error::Error>(())
+    /// ```
+    pub fn queue(&mut self, s: impl AsRef<str>) {
+        self.queued += s.as_ref();
+    }
+
+    /// Writes the queued output (see [`Terminal::queue`]) in single write
+    /// and flushes the output. This is the same as [`Write::flush`]. If the
+    /// write fails, the output stays queued so that it may be written again
+    /// (part of it may have already been written).
+    pub fn flush_queued(&mut self) -> Result<()> {
+        self.flush()?;
+        Ok(())
+    }
+
+    /// Gets the output that is queued and wasn't written yet.
+    pub fn queued(&self) -> &str {
+        &self.queued
+    }
+
+    /// Discards the queued output.
+    pub fn clear_queued(&mut self) {
+        self.queued.clear();
+    }
+
+    /// Writes the queued output without flushing. If the write fails, the
+    /// output stays queued.
+    fn write_queued(&mut self) -> std::io::Result<()> {
+        if self.queued.is_empty() {
+            return Ok(());
+        }
+        let mut queued = std::mem::take(&mut self.queued);
+        let res = self.write_out(queued.as_bytes());
+        if res.is_ok() {
+            // Keep the allocation for the next frame.
+            queued.clear();
+        }
+        self.queued = queued;
+        res
+    }
+
+    /// Updates the tracked modes from the written data. The state of the
+    /// alternate buffer is also kept for this terminal, because it decides
+    /// whether mouse wheel is translated to arrows. Codes written by other
+    /// means are still visible through the state shared by the process.
+    fn track_written_modes(&mut self, buf: &[u8]) {
+        if !self.mode_tracker.is_pending() && !buf.contains(&0x1b) {
+            return;
+        }
+        let tracker = &mut self.mode_tracker;
+        #[cfg(feature = "events")]
+        let alternate = &mut self.alternate_buffer;
+        modify_active_modes(|modes| {
+            tracker.feed(buf, |change| {
+                modes.apply(change);
+                #[cfg(feature = "events")]
+                match change {
+                    ModeChange::Reset => *alternate = None,
+                    ModeChange::Set(1049, v) => *alternate = Some(v),
+                    _ => {}
+                }
+            })
+        });
+    }
+
+    /// Writes the whole buffer to the output.
+    fn write_out(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        if !self.c1 {
+            self.io.get_out().write_all(buf)?;
+            self.track_written_modes(buf);
+            return Ok(());
+        }
+
+        // Escape may be the last byte in one write and the introducer the
+        // first byte in the next write, so escape is kept pending.
+        let mut res = Vec::with_capacity(buf.len());
+        for &b in buf {
+            if self.pending_esc {
+                self.pending_esc = false;
+                if let Some(c1) = codes::c1_of(b) {
+                    res.push(c1);
+                    continue;
+                }
+                res.push(0x1b);
+            }
+            if b == 0x1b {
+                self.pending_esc = true;
+            } else {
+                res.push(b);
+            }
+        }
+        self.io.get_out().write_all(&res)?;
+        self.track_written_modes(buf);
+        Ok(())
+    }
+
+    /// Prints to the output. Newlines are translated based on the newline
+    /// policy (see [`Terminal::set_newline_policy`]).
+    pub fn print(&mut self, s: impl AsRef<str>) -> Result<()> {
+        let s = s.as_ref();
+        if !self.translates_newline() {
+            self.write_all(s.as_bytes())?;
+            return Ok(());
+        }
+
+        for (i, s) in s.split('\n').enumerate() {
+            if i != 0 {
+                self.write_all(b"\r\n")?;
+            }
+            self.write_all(s.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Prints to the output and appends newline. Newlines are translated
+    /// based on the newline policy (see [`Terminal::set_newline_policy`]).
+    pub fn println(&mut self, s: impl AsRef<str>) -> Result<()> {
+        self.print(s)?;
+        let nl = self.newline();
+        self.write_all(nl.as_bytes())?;
+        Ok(())
+    }
+
+    /// Gets the newline translation policy.
+    pub fn newline_policy(&self) -> NewlinePolicy {
+        self.newline
+    }
+
+    /// Sets the newline translation policy. It is used by
+    /// [`Terminal::print`], [`Terminal::println`] and by the readers.
+    ///
+    /// The default is [`NewlinePolicy::Platform`] which translates newlines
+    /// only if the output is terminal in raw mode. Use [`NewlinePolicy::Off`]
+    /// if the terminal already translates newlines (e.g. `ONLCR` is enabled)
+    /// or if the output is piped data that must not be modified. Use
+    /// [`NewlinePolicy::RawMode`] to translate newlines whenever raw mode is
+    /// enabled regardless of the output.
+    pub fn set_newline_policy(&mut self, policy: NewlinePolicy) {
+        self.newline = policy;
+    }
+
+    /// Gets the string that should be printed as newline based on the
+    /// newline policy. It is either `"\r\n"` or `"\n"`.
+    pub fn newline(&self) -> &'static str {
+        self.newline.newline(self.is_out_raw_terminal())
+    }
+
+    fn translates_newline(&self) -> bool {
+        self.newline.translates(self.is_out_raw_terminal())
+    }
+
+    fn is_out_raw_terminal(&self) -> bool {
+        self.is_out_raw() && self.io.is_out_terminal()
+    }
 }
 
 #[cfg(feature = "readers")]
@@ -244,6 +706,102 @@ impl<T: IoProvider> Terminal<T> {
         reader.set_prompt(prompt);
         reader.read_to_str(s)
     }
+
+    /// Asks the user yes or no question. `[Y/n]` or `[y/N]` is appended to
+    /// the prompt based on the `default` answer that is used when the user
+    /// enters nothing. The question is repeated until the user answers `y`,
+    /// `yes`, `n` or `no` (case insensitive). The cursor is moved to the next
+    /// line after each answer. Returns [`Error::Eof`] at the end of stdin.
+    pub fn prompt_confirm(
+        &mut self,
+        prompt: impl AsRef<str>,
+        default: bool,
+    ) -> Result<bool> {
+        let hint = if default { "[Y/n]" } else { "[y/N]" };
+        let prompt = format!("{} {hint} ", prompt.as_ref());
+        loop {
+            let ans = self.prompt_line(&prompt)?;
+            match ans.trim().to_lowercase().as_str() {
+                "" => return Ok(default),
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                _ => self.prompt_error("Answer `y` or `n`.")?,
+            }
+        }
+    }
+
+    /// Prompts the user until the entered text can be parsed. Leading and
+    /// trailing whitespace is trimmed before parsing. The parse error is
+    /// shown below the input with the theme style `prompt.error` (see
+    /// [`theme::style`]). The cursor is moved to the next line after each
+    /// answer. Returns [`Error::Eof`] at the end of stdin.
+    pub fn prompt_parse<R>(&mut self, prompt: impl AsRef<str>) -> Result<R>
+    where
+        R: FromStr,
+        R::Err: Display,
+    {
+        loop {
+            let ans = self.prompt_line(prompt.as_ref())?;
+            match ans.trim().parse() {
+                Ok(r) => return Ok(r),
+                Err(e) => self.prompt_error(&e.to_string())?,
+            }
+        }
+    }
+
+    /// Lets the user select one of the items and returns its index. See
+    /// [`Select`].
+    pub fn select<I>(
+        &mut self,
+        prompt: impl AsRef<str>,
+        items: I,
+    ) -> Result<usize>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Select::new(prompt, items).read(self)
+    }
+
+    /// Lets the user check any number of the items and returns indexes of
+    /// the checked items. See [`Select`].
+    pub fn multi_select<I>(
+        &mut self,
+        prompt: impl AsRef<str>,
+        items: I,
+    ) -> Result<Vec<usize>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Select::new(prompt, items).read_multi(self)
+    }
+
+    /// Prompts the user and moves the cursor to the next line. End of stdin
+    /// cancels the input with [`Error::Eof`] so that the callers don't
+    /// repeat the prompt forever.
+    fn prompt_line(&mut self, prompt: &str) -> Result<String> {
+        let mut reader = TermRead::lines(self);
+        reader.set_prompt(prompt);
+        reader.set_cancel_policy(CancelPolicy {
+            stdin_eof: true,
+            ..CancelPolicy::default()
+        });
+        let ans = reader.read_str()?;
+        self.write_all(self.newline().as_bytes())?;
+        Ok(ans)
+    }
+
+    /// Prints error message of prompt on its own line.
+    fn prompt_error(&mut self, msg: &str) -> Result<()> {
+        let style = theme::style("prompt.error", None)
+            .unwrap_or_else(|| Style::new().fg(theme::palette().error));
+        let msg =
+            format!("{}{msg}{}{}", style.code(), codes::RESET, self.newline());
+        self.write_all(msg.as_bytes())?;
+        self.flush()?;
+        Ok(())
+    }
 }
 
 #[cfg(feature = "events")]
@@ -263,22 +821,431 @@ impl<T: IoProvider> Terminal<T> {
         &mut self,
         timeout: Duration,
     ) -> Result<Option<Event>> {
-        if self.wait_for_input(timeout)? {
-            Ok(Some(self.read()?))
-        } else {
-            Ok(None)
+        match self.wait_for_event(Some(timeout))? {
+            Wait::Input => Ok(Some(self.read()?)),
+            Wait::Resize(evt) => Ok(Some(evt)),
+            Wait::Timeout => Ok(None),
         }
     }
 
     /// Read the next event on stdin. May block.
     pub fn read_ambigous(&mut self) -> Result<AmbigousEvent> {
-        if self.bracketed_paste_open {
-            self.read_bracketed()
-        } else if self.cur()? == 0x1b && self.buffer.len() != 1 {
-            self.read_escape()
+        if let Some(evt) = self.pending.pop_front() {
+            return Ok(evt);
+        }
+        if let Wait::Resize(evt) = self.wait_for_event(None)? {
+            return Ok(AmbigousEvent::event(evt));
+        }
+        self.read_new_ambigous()
+    }
+
+    /// Sets whether [`Event::Resize`] should be read when the terminal is
+    /// resized. The size of the terminal (see [`IoProvider::term_size`]) is
+    /// checked before each read and periodically while waiting for input,
+    /// so it works the same on all platforms. Resize is not detected if the
+    /// io provider doesn't know the size of the terminal.
+    pub fn set_resize_events(&mut self, v: bool) {
+        self.resize_events = v;
+        self.size = self.io.term_size().map(|s| (s.char_width, s.char_height));
+    }
+
+    /// Checks whether [`Event::Resize`] is read when the terminal is
+    /// resized.
+    pub fn is_resize_events(&self) -> bool {
+        self.resize_events
+    }
+
+    /// Sets how long to wait for the rest of escape sequence when lone
+    /// escape byte is read (similar to `ttimeoutlen` in vim). If more input
+    /// arrives within the timeout, it is parsed together with the escape.
+    /// Otherwise [`KeyCode::Esc`] is read.
+    ///
+    /// This prevents escape sequences split by slow connection (e.g. ssh)
+    /// from being read as escape followed by other keys. The default is
+    /// zero, so the escape is read immediately.
+    pub fn set_esc_timeout(&mut self, timeout: Duration) {
+        self.esc_timeout = timeout;
+    }
+
+    /// Gets how long to wait for the rest of escape sequence. See
+    /// [`Terminal::set_esc_timeout`].
+    pub fn esc_timeout(&self) -> Duration {
+        self.esc_timeout
+    }
+
+    /// Waits until there is input or the terminal is resized. Without
+    /// timeout, this doesn't wait if resize events are disabled.
+    fn wait_for_event(&mut self, timeout: Option<Duration>) -> Result<Wait> {
+        if !self.resize_events || self.size.is_none() {
+            return match timeout {
+                Some(t) if !self.wait_for_input(t)? => Ok(Wait::Timeout),
+                _ => Ok(Wait::Input),
+            };
+        }
+
+        let start = Instant::now();
+        loop {
+            if let Some(evt) = self.check_resize() {
+                return Ok(Wait::Resize(evt));
+            }
+            let mut wait = RESIZE_POLL_INTERVAL;
+            if let Some(t) = timeout {
+                wait = wait.min(t.saturating_sub(start.elapsed()));
+            }
+            if self.wait_for_input(wait)? {
+                return Ok(Wait::Input);
+            }
+            if timeout.is_some_and(|t| start.elapsed() >= t) {
+                return Ok(Wait::Timeout);
+            }
+        }
+    }
+
+    /// Checks whether the terminal size changed since the last check.
+    fn check_resize(&mut self) -> Option<Event> {
+        let size = self.io.term_size()?;
+        let size = (size.char_width, size.char_height);
+        if self.size == Some(size) {
+            return None;
+        }
+        self.size = Some(size);
+        Some(Event::Resize {
+            w: size.0,
+            h: size.1,
+        })
+    }
+
+    /// Sends request to the terminal and waits for the response. `f` is
+    /// called with the received events and it should return `Some` for the
+    /// response. Other events received while waiting are not lost, they will
+    /// be returned by the next reads.
+    ///
+    /// Returns `None` if no response came within the timeout given by
+    /// [`Terminal::request_policy`]. The request may be retried according to
+    /// the policy.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use termal_core::{
+    ///     codes,
+    ///     raw::{
+    ///         events::{Event, Status},
+    ///         enable_raw_mode, disable_raw_mode, Terminal,
+    ///     },
+    /// };
+    ///
+    /// enable_raw_mode()?;
+    /// let mut term = Terminal::stdio();
+    /// let pos = term.request(codes::REQUEST_CURSOR_POSITION2, |e| match e {
+    ///     Event::Status(Status::CursorPosition { x, y }) => Some((*x, *y)),
+    ///     _ => None,
+    /// })?;
+    /// disable_raw_mode()?;
+    /// # Ok::<_, termal_core::error::Error>(())
+    /// ```
+    pub fn request<R>(
+        &mut self,
+        code: impl AsRef<str>,
+        mut f: impl FnMut(&Event) -> Option<R>,
+    ) -> Result<Option<R>> {
+        let code = code.as_ref();
+        self.request_stats.requests += 1;
+        let mut delay = self.request_policy.retry_delay;
+        let mut retries = 0;
+
+        loop {
+            if let Some(res) = self.request_once(code, &mut f)? {
+                self.request_stats.responses += 1;
+                if retries != 0 {
+                    self.request_stats.retry_successes += 1;
+                }
+                if !self.supported_requests.contains(code) {
+                    self.supported_requests.insert(code.to_string());
+                }
+                return Ok(Some(res));
+            }
+
+            self.request_stats.timeouts += 1;
+            if retries >= self.request_policy.retries
+                || !self.supported_requests.contains(code)
+            {
+                return Ok(None);
+            }
+            retries += 1;
+            self.request_stats.retries += 1;
+            std::thread::sleep(delay);
+            delay *= 2;
+        }
+    }
+
+    /// Gets the counts of requests made with [`Terminal::request`].
+    pub fn request_stats(&self) -> &RequestStats {
+        &self.request_stats
+    }
+
+    /// Resets the counts of requests to zero.
+    pub fn reset_request_stats(&mut self) {
+        self.request_stats = RequestStats::default();
+    }
+
+    fn request_once<R>(
+        &mut self,
+        code: &str,
+        mut f: impl FnMut(&Event) -> Option<R>,
+    ) -> Result<Option<R>> {
+        let timeout = self.request_policy.timeout();
+        self.write_all(code.as_bytes())?;
+        self.flush()?;
+        let start = Instant::now();
+
+        loop {
+            let remaining = timeout.saturating_sub(start.elapsed());
+            let ready =
+                !self.buffer.is_empty() || self.io.wait_for_in(remaining)?;
+            if !ready {
+                return Ok(None);
+            }
+
+            let evt = self.read_new_ambigous()?;
+            let res = match &evt.event {
+                AnyEvent::Known(e) => f(e),
+                AnyEvent::Unknown(_) => None,
+            }
+            .or_else(|| evt.other.iter().find_map(&mut f));
+
+            if let Some(res) = res {
+                self.request_policy.record(start.elapsed());
+                return Ok(Some(res));
+            }
+            self.pending.push_back(evt);
+        }
+    }
+
+    /// Sends request to the terminal and waits for status response of the
+    /// given kind. See [`Terminal::request`].
+    pub fn request_status(
+        &mut self,
+        code: impl AsRef<str>,
+        kind: StatusCode,
+    ) -> Result<Option<Status>> {
+        self.request(code, |e| match e {
+            Event::Status(s) if s.code() == kind => Some(s.clone()),
+            _ => None,
+        })
+    }
+
+    /// Asks the terminal for the state of the private mode (DECRQM) and
+    /// updates the believed state of the mode (see
+    /// [`Terminal::active_modes`]). Returns [`None`] if the terminal didn't
+    /// respond.
+    pub fn request_mode(&mut self, mode: u32) -> Result<Option<ModeState>> {
+        let res = self.request(codes::request_mode!(mode), |e| match e {
+            Event::Status(Status::Mode { mode: m, state }) if *m == mode => {
+                Some(*state)
+            }
+            _ => None,
+        })?;
+        if let Some(enabled) = res.and_then(|s| s.is_set()) {
+            modify_active_modes(|m| m.set(mode, enabled, true));
+        }
+        Ok(res)
+    }
+
+    /// Asks the terminal for the value of termcap/terminfo capability
+    /// (XTGETTCAP). Returns [`None`] if the terminal didn't respond or if it
+    /// doesn't know the capability. Boolean capabilities have empty value.
+    pub fn request_termcap(&mut self, name: &str) -> Result<Option<String>> {
+        let res = self.request(codes::request_termcap(name), |e| match e {
+            Event::Status(Status::TermCap { name: n, value }) if n == name => {
+                Some(value.clone())
+            }
+            _ => None,
+        })?;
+        Ok(res.flatten())
+    }
+
+    /// Asks the terminal for the position of the cursor. Returns `(x, y)`
+    /// where the top left corner is `(1, 1)`, or [`None`] if the terminal
+    /// didn't respond. See [`super::request::cursor_position`].
+    pub fn cursor_position(&mut self) -> Result<Option<(usize, usize)>> {
+        // Status report is requested after the unambiguous request. The
+        // terminal responds in order, so if the status report comes first,
+        // the unambiguous request is not supported and the widely supported
+        // request is sent right away instead of waiting for the timeout.
+        let code = [
+            codes::REQUEST_CURSOR_POSITION2,
+            codes::REQUEST_STATUS_REPORT,
+        ]
+        .concat();
+        let res = self.request(code, |e| match e {
+            Event::Status(Status::CursorPosition { x, y }) => {
+                Some(Some((*x, *y)))
+            }
+            Event::Status(Status::Ok) => Some(None),
+            _ => None,
+        })?;
+
+        match res {
+            Some(Some(pos)) => {
+                self.skip_status_ok()?;
+                Ok(Some(pos))
+            }
+            Some(None) => {
+                self.request(codes::REQUEST_CURSOR_POSITION, |e| match e {
+                    Event::Status(Status::CursorPosition { x, y }) => {
+                        Some((*x, *y))
+                    }
+                    _ => None,
+                })
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Waits for the response to status report that was requested after
+    /// other request, so that it is not returned by the next reads. The
+    /// round-trip time is not recorded, because the response comes right
+    /// after the response to the other request.
+    fn skip_status_ok(&mut self) -> Result<()> {
+        let policy = self.request_policy;
+        let res = self.request_once("", |e| {
+            matches!(e, Event::Status(Status::Ok)).then_some(())
+        });
+        self.request_policy = policy;
+        res.map(|_| ())
+    }
+
+    /// Asks the terminal for the size of single character cell in pixels.
+    /// Returns `(width, height)`, or [`None`] if the terminal didn't
+    /// respond. See [`super::request::char_size`].
+    pub fn char_size(&mut self) -> Result<Option<(usize, usize)>> {
+        let res = self
+            .request_status(codes::REQUEST_CHAR_SIZE, StatusCode::CharSize);
+        Ok(match res? {
+            Some(Status::CharSize { w, h }) => Some((w, h)),
+            _ => None,
+        })
+    }
+
+    /// Saves the position of the cursor on stack, so that it can be restored
+    /// with [`Terminal::pop_cursor`]. Unlike [`codes::CUR_SAVE`], the saved
+    /// positions may be nested. Returns the saved position, or [`None`] if
+    /// the terminal didn't report the position. In that case, the matching
+    /// [`Terminal::pop_cursor`] doesn't move the cursor.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use termal_core::{codes, raw::Terminal};
+    ///
+    /// let mut term = Terminal::stdio();
+    /// term.push_cursor()?;
+    /// term.print(codes::move_to!(1, 1))?;
+    /// term.print("status line")?;
+    /// term.pop_cursor()?;
+    /// # Ok::<_, termal_core::error::Error>(())
+    /// ```
+    pub fn push_cursor(&mut self) -> Result<Option<(usize, usize)>> {
+        let pos = self.cursor_position()?;
+        self.cursor_stack.push(pos);
+        Ok(pos)
+    }
+
+    /// Moves the cursor to the position saved with the last
+    /// [`Terminal::push_cursor`] and removes it from the stack. Returns the
+    /// position, or [`None`] if the stack is empty or the position was not
+    /// known.
+    pub fn pop_cursor(&mut self) -> Result<Option<(usize, usize)>> {
+        let Some((x, y)) = self.cursor_stack.pop().flatten() else {
+            return Ok(None);
+        };
+        self.write_all(codes::move_to!(x, y).as_bytes())?;
+        self.flush()?;
+        Ok(Some((x, y)))
+    }
+
+    /// Gets the number of positions saved with [`Terminal::push_cursor`].
+    pub fn cursor_stack_len(&self) -> usize {
+        self.cursor_stack.len()
+    }
+
+    /// Asks the terminal whether it supports synchronized updates (see
+    /// [`Terminal::synchronized`]). Returns [`None`] if the terminal didn't
+    /// respond.
+    pub fn supports_synchronized_update(&mut self) -> Result<Option<bool>> {
+        Ok(self.request_mode(2026)?.map(|s| {
+            !matches!(
+                s,
+                ModeState::NotRecognized | ModeState::PermanentlyReset
+            )
+        }))
+    }
+
+    /// Verifies the believed state of the private modes by asking the
+    /// terminal (DECRQM) and returns the updated state. The tracked modes
+    /// and the commonly used modes (mouse, focus, bracketed paste,
+    /// alternate buffer and scroll and cursor visibility) are verified.
+    ///
+    /// If the terminal doesn't respond to the first request, it probably
+    /// doesn't support DECRQM, so the other modes are not requested and the
+    /// state is unchanged.
+    pub fn verify_modes(&mut self) -> Result<ActiveModes> {
+        let mut modes: Vec<_> =
+            active_modes().iter().map(|(m, _)| m).collect();
+        modes.extend(KNOWN_MODES);
+        modes.sort_unstable();
+        modes.dedup();
+
+        for (i, mode) in modes.into_iter().enumerate() {
+            if self.request_mode(mode)?.is_none() && i == 0 {
+                break;
+            }
+        }
+
+        Ok(active_modes())
+    }
+
+    /// Gets the policy for timeouts of requests.
+    pub fn request_policy(&self) -> &RequestPolicy {
+        &self.request_policy
+    }
+
+    /// Gets mutable reference to the policy for timeouts of requests.
+    pub fn request_policy_mut(&mut self) -> &mut RequestPolicy {
+        &mut self.request_policy
+    }
+
+    /// Sets the policy for timeouts of requests.
+    pub fn set_request_policy(&mut self, policy: RequestPolicy) {
+        self.request_policy = policy;
+    }
+
+    fn read_new_ambigous(&mut self) -> Result<AmbigousEvent> {
+        if !self.bracketed_paste_open
+            && self.cur()? == 0x1b
+            && self.buffer.len() == 1
+            && !self.esc_timeout.is_zero()
+            && self.io.wait_for_in(self.esc_timeout)?
+        {
+            self.fill_buffer()?;
+        }
+
+        let evt = if self.bracketed_paste_open {
+            self.read_bracketed()?
+        } else if (self.cur()? == 0x1b && self.buffer.len() != 1)
+            || self.expand_c1()?
+        {
+            self.read_escape()?
         } else {
             // TODO should \r\n be single event?
-            self.read_char()
+            self.read_char()?
+        };
+
+        if (self.wheel_to_arrows || self.alternate_scroll)
+            && self.is_alternate_buffer()
+        {
+            Ok(wheel_to_arrows(evt))
+        } else {
+            Ok(evt)
         }
     }
 
@@ -287,10 +1254,13 @@ impl<T: IoProvider> Terminal<T> {
         &mut self,
         timeout: Duration,
     ) -> Result<Option<AmbigousEvent>> {
-        if self.wait_for_input(timeout)? {
-            Ok(Some(self.read_ambigous()?))
-        } else {
-            Ok(None)
+        if let Some(evt) = self.pending.pop_front() {
+            return Ok(Some(evt));
+        }
+        match self.wait_for_event(Some(timeout))? {
+            Wait::Input => Ok(Some(self.read_new_ambigous()?)),
+            Wait::Resize(evt) => Ok(Some(AmbigousEvent::event(evt))),
+            Wait::Timeout => Ok(None),
         }
     }
 
@@ -304,20 +1274,59 @@ impl<T: IoProvider> Terminal<T> {
         self.io.is_in_terminal()
     }
 
-    /// Prints to the output. Properly handles newlines if output is raw
-    /// terminal.
-    pub fn print(&mut self, s: impl AsRef<str>) -> Result<()> {
-        if !self.io.is_out_raw() || !self.is_out_terminal() {
-            self.write_all(s.as_ref().as_bytes())?;
+    /// Enables or disables alternate scroll mode. This will send the code
+    /// [`codes::ENABLE_ALTERNATE_SCROLL`] or
+    /// [`codes::DISABLE_ALTERNATE_SCROLL`] to the terminal and it will also
+    /// enable or disable translation of mouse wheel events to arrow keys
+    /// while the alternate buffer is active (see
+    /// [`Terminal::is_alternate_buffer`]).
+    ///
+    /// With this, mouse wheel will work as arrow keys in alternate buffer
+    /// regardless of whether mouse tracking is enabled or not.
+    pub fn set_alternate_scroll(&mut self, v: bool) -> Result<()> {
+        let code = if v {
+            codes::ENABLE_ALTERNATE_SCROLL
         } else {
-            let mut out = self.io.get_out();
-            for s in s.as_ref().split('\n') {
-                write!(out, "{s}\n\r")?;
-            }
-        }
+            codes::DISABLE_ALTERNATE_SCROLL
+        };
+        self.write_all(code.as_bytes())?;
+        self.flush()?;
+        self.alternate_scroll = v;
         Ok(())
     }
 
+    /// Checks whether mouse wheel events are translated to arrow keys in
+    /// alternate buffer (see [`Terminal::set_alternate_scroll`]).
+    pub fn is_alternate_scroll(&self) -> bool {
+        self.alternate_scroll
+    }
+
+    /// Checks whether the alternate buffer is active. Codes written through
+    /// this terminal (e.g. [`codes::ENABLE_ALTERNATIVE_BUFFER`]) take
+    /// precedence. If this terminal didn't switch the buffer, the state
+    /// shared by the process is used (see [`Terminal::active_modes`]), so
+    /// alternate buffer enabled by [`super::TuiSession`] or tracked with
+    /// [`super::track_modes`] is also considered.
+    pub fn is_alternate_buffer(&self) -> bool {
+        self.alternate_buffer
+            .unwrap_or_else(|| active_modes().alternate_buffer())
+    }
+
+    /// Sets whether mouse wheel events should be translated to up and down
+    /// arrow key presses while the alternate buffer is active (see
+    /// [`Terminal::is_alternate_buffer`]). This is the same what terminals
+    /// do with alternate scroll mode, but it works also when mouse tracking
+    /// is enabled and it doesn't send any code to the terminal.
+    pub fn set_wheel_to_arrows(&mut self, v: bool) {
+        self.wheel_to_arrows = v;
+    }
+
+    /// Checks whether mouse wheel events are translated to up and down arrow
+    /// key presses in alternate buffer.
+    pub fn is_wheel_to_arrows(&self) -> bool {
+        self.wheel_to_arrows
+    }
+
     /// Opens bracketed paste mode. It will start automatically with
     /// start of paste text and end with end of paste text if bracketed paste
     /// mode is enabled (with [`codes::ENABLE_BRACKETED_PASTE_MODE`]).
@@ -338,6 +1347,44 @@ impl<T: IoProvider> Terminal<T> {
         self.bracketed_paste_open
     }
 
+    /// Replaces 8-bit control introducer at the start of the buffer with its
+    /// 7-bit version. Returns `true` if the introducer was replaced.
+    ///
+    /// [`codes::CSI8`] and [`codes::SS3_8`] are always replaced, because
+    /// they can't start valid UTF-8 character and they are sent by some
+    /// terminals for keys. Introducers of strings are replaced only if 8-bit
+    /// controls are enabled, because the strings are terminated with
+    /// [`codes::ST8`].
+    fn expand_c1(&mut self) -> Result<bool> {
+        let cur = self.cur()?;
+        let expand = match cur {
+            codes::CSI8 | codes::SS3_8 => true,
+            codes::DCS8 | codes::OSC8 => self.c1,
+            _ => false,
+        };
+        if !expand {
+            return Ok(false);
+        }
+        self.buffer[0] = cur - 0x40;
+        self.buffer.push_front(0x1b);
+        Ok(true)
+    }
+
+    /// Checks if the code ends with string terminator. If 8-bit controls
+    /// are enabled and the code ends with [`codes::ST8`], it is replaced with
+    /// [`codes::ST`].
+    fn ends_with_st(&self, code: &mut Vec<u8>) -> bool {
+        if code.ends_with(codes::ST.as_bytes()) {
+            return true;
+        }
+        if self.c1 && code.last() == Some(&codes::ST8) {
+            code.pop();
+            code.extend_from_slice(codes::ST.as_bytes());
+            return true;
+        }
+        false
+    }
+
     fn read_escape(&mut self) -> Result<AmbigousEvent> {
         self.read_byte()?;
         let cur = self.cur()?;
@@ -346,6 +1393,17 @@ impl<T: IoProvider> Terminal<T> {
             b'O' if self.buffer.len() > 1 => self.read_ss3(),
             b'P' => self.read_dcs(),
             b']' => self.read_osc(),
+            // Some terminals send alt + key as escape followed by the code
+            // of the key (e.g. `ESC ESC [ A` for alt + up).
+            0x1b if self.buffer.len() > 2
+                && matches!(self.buffer[1], b'[' | b'O') =>
+            {
+                let mut evt = self.read_escape()?;
+                // If it is not key (e.g. response to request), the escape is
+                // dropped so that the response isn't lost.
+                evt.add_alt();
+                Ok(evt)
+            }
             _ => self.read_alt(),
         }
     }
@@ -356,7 +1414,7 @@ impl<T: IoProvider> Terminal<T> {
         if self.buffer.is_empty() {
             return Ok(AmbigousEvent::from_code(&code));
         }
-        let mut cur = self.read_byte()?;
+        let cur = self.read_byte()?;
 
         if cur == b'M' {
             // Special mouse event that actually doesn't conform to CSI
@@ -388,17 +1446,11 @@ impl<T: IoProvider> Terminal<T> {
             return Ok(AmbigousEvent::from_code(&code));
         }
 
-        while (0x30..=0x3F).contains(&cur) {
-            code.push(cur);
-            cur = self.read_byte()?;
-        }
-
-        while (0x20..=0x2F).contains(&cur) {
-            code.push(cur);
-            cur = self.read_byte()?;
+        code.push(cur);
+        while csi_len(&code).is_none() {
+            code.push(self.read_byte()?);
         }
 
-        code.push(cur);
         if code == codes::BRACKETED_PASTE_START.as_bytes() {
             self.bracketed_paste_open = true;
             Ok(AmbigousEvent::state_change(
@@ -440,8 +1492,7 @@ impl<T: IoProvider> Terminal<T> {
     fn read_dcs(&mut self) -> Result<AmbigousEvent> {
         self.read_byte()?;
         let mut code: Vec<_> = b"\x1bP".into();
-        while !self.buffer.is_empty() && !code.ends_with(codes::ST.as_bytes())
-        {
+        while !self.buffer.is_empty() && !self.ends_with_st(&mut code) {
             code.push(self.read_byte()?);
         }
         Ok(AmbigousEvent::from_code(&code))
@@ -460,7 +1511,7 @@ impl<T: IoProvider> Terminal<T> {
     }
 
     fn read_until_st(&mut self, res: &mut Vec<u8>) -> Result<()> {
-        while !res.ends_with(codes::ST.as_bytes()) && !res.ends_with(b"\x07") {
+        while !self.ends_with_st(res) && !res.ends_with(b"\x07") {
             res.push(self.read_byte()?);
         }
         Ok(())
@@ -554,10 +1605,23 @@ impl<T: IoProvider> Read for Terminal<T> {
 
 impl<T: IoProvider> Write for Terminal<T> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.io.get_out().write(buf)
+        self.write_queued()?;
+        if !self.c1 {
+            let len = self.io.get_out().write(buf)?;
+            self.track_written_modes(&buf[..len]);
+            return Ok(len);
+        }
+
+        self.write_out(buf)?;
+        Ok(buf.len())
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
+        self.write_queued()?;
+        if self.pending_esc {
+            self.pending_esc = false;
+            self.io.get_out().write_all(b"\x1b")?;
+        }
         self.io.get_out().flush()
     }
 }
@@ -573,6 +1637,23 @@ fn read_stdin_once(stdin: &mut impl BufRead, res: &mut [u8]) -> Result<usize> {
     Ok(len)
 }
 
+#[cfg(feature = "events")]
+fn wheel_to_arrows(mut evt: AmbigousEvent) -> AmbigousEvent {
+    let AnyEvent::Known(Event::Mouse(m)) = evt.event else {
+        return evt;
+    };
+
+    let code = match m.event {
+        mouse::Event::ScrollUp => KeyCode::Up,
+        mouse::Event::ScrollDown => KeyCode::Down,
+        _ => return evt,
+    };
+
+    evt.event =
+        AnyEvent::Known(Event::KeyPress(Key::mcode(code, m.modifiers)));
+    evt
+}
+
 #[cfg(feature = "events")]
 fn utf8_code_len(first: u8) -> usize {
     if (first & 0x80) == 0 {
@@ -0,0 +1,313 @@
+//! Utilities for testing apps that use [`Terminal`] without real terminal.
+//!
+//! [`ScriptedInput`] describes the input that the app will receive and
+//! [`RecordingIoProvider`] feeds it to the app and records everything that
+//! the app writes and reads.
+//!
+//! # Examples
+//! ```rust
+//! use termal_core::raw::{
+//!     events::{Event, Key, KeyCode},
+//!     testing::ScriptedInput,
+//! };
+//!
+//! let mut term = ScriptedInput::new()
+//!     .key(Key::code(KeyCode::Up))
+//!     .text("q")
+//!     .terminal();
+//!
+//! assert_eq!(term.read()?, Event::KeyPress(Key::code(KeyCode::Up)));
+//! term.print("hello\nthere")?;
+//!
+//! assert_eq!(term.io().written(), b"hello\r\nthere");
+//! assert_eq!(term.io().read_bytes(), b"\x1b[A");
+//! # Ok::<_, termal_core::error::Error>(())
+//! ```
+
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    io::{BufRead, Read, Write},
+    time::Duration,
+};
+
+use crate::error::Result;
+
+use super::{IoProvider, TermSize, Terminal, ValueOrMut, WaitForIn};
+
+#[cfg(feature = "events")]
+use super::events::{encode_key, encode_mouse, mouse::Mouse, Key};
+
+/// Builder of the input that will be received by the app. Each call adds
+/// separate chunk of input that is read at once (the same as if the user
+/// pressed key).
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedInput {
+    chunks: Vec<Vec<u8>>,
+}
+
+/// Io provider that serves scripted input and records all the written and
+/// read bytes.
+///
+/// By default it behaves like terminal in raw mode.
+#[derive(Debug, Clone)]
+pub struct RecordingIoProvider {
+    input: VecDeque<Vec<u8>>,
+    pos: usize,
+    written: Vec<u8>,
+    read: Vec<u8>,
+    eof_panic: usize,
+    in_terminal: bool,
+    out_terminal: bool,
+    out_raw: bool,
+    size: Option<TermSize>,
+}
+
+impl ScriptedInput {
+    /// Creates empty input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds raw bytes as single chunk of input.
+    pub fn bytes(mut self, b: impl Into<Vec<u8>>) -> Self {
+        let b = b.into();
+        if !b.is_empty() {
+            self.chunks.push(b);
+        }
+        self
+    }
+
+    /// Adds text as single chunk of input. This is the same as if the text
+    /// was pasted without bracketed paste.
+    pub fn text(self, s: impl AsRef<str>) -> Self {
+        self.bytes(s.as_ref().as_bytes())
+    }
+
+    /// Adds each char of the text as separate chunk of input. This is the
+    /// same as if the text was typed.
+    pub fn typed(self, s: impl AsRef<str>) -> Self {
+        s.as_ref()
+            .chars()
+            .fold(self, |s, c| s.text(c.encode_utf8(&mut [0; 4])))
+    }
+
+    /// Adds key press.
+    #[cfg(feature = "events")]
+    pub fn key(self, key: Key) -> Self {
+        let mut res = vec![];
+        encode_key(&key, &mut res);
+        self.bytes(res)
+    }
+
+    /// Adds mouse event. The mouse event is encoded with the SGR encoding.
+    #[cfg(feature = "events")]
+    pub fn mouse(self, mouse: Mouse) -> Self {
+        let mut res = vec![];
+        encode_mouse(&mouse, &mut res);
+        self.bytes(res)
+    }
+
+    /// Gets the chunks of the input.
+    pub fn chunks(&self) -> &[Vec<u8>] {
+        &self.chunks
+    }
+
+    /// Creates io provider that will serve this input.
+    pub fn build(self) -> RecordingIoProvider {
+        RecordingIoProvider::new(self)
+    }
+
+    /// Creates terminal that will receive this input.
+    pub fn terminal(self) -> Terminal<RecordingIoProvider> {
+        Terminal::new(self.build())
+    }
+}
+
+impl RecordingIoProvider {
+    /// Creates provider that will serve the given input.
+    pub fn new(input: ScriptedInput) -> Self {
+        Self {
+            input: input.chunks.into(),
+            pos: 0,
+            written: vec![],
+            read: vec![],
+            eof_panic: 0,
+            in_terminal: true,
+            out_terminal: true,
+            out_raw: true,
+            size: None,
+        }
+    }
+
+    /// Adds more input that will be served after the current input.
+    pub fn push_input(&mut self, input: ScriptedInput) {
+        self.input.extend(input.chunks);
+    }
+
+    /// Checks whether there is any remaining input.
+    pub fn has_input(&self) -> bool {
+        !self.input.is_empty()
+    }
+
+    /// Gets all the bytes written to the output.
+    pub fn written(&self) -> &[u8] {
+        &self.written
+    }
+
+    /// Gets all the bytes written to the output as string. Invalid utf-8 is
+    /// replaced.
+    pub fn written_str(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.written)
+    }
+
+    /// Takes all the bytes written so far. The recording of written bytes
+    /// starts over.
+    pub fn take_written(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.written)
+    }
+
+    /// Gets all the bytes read from the input.
+    pub fn read_bytes(&self) -> &[u8] {
+        &self.read
+    }
+
+    /// Panic when reading after the end of input for the `cnt`-th time. This
+    /// is useful to detect that the tested code would wait for input
+    /// forever.
+    pub fn eof_panic(&mut self, cnt: usize) {
+        self.eof_panic = cnt + 1;
+    }
+
+    /// Sets whether the input and output should behave as terminal.
+    pub fn set_terminal(&mut self, v: bool) {
+        self.in_terminal = v;
+        self.out_terminal = v;
+    }
+
+    /// Sets whether the output should behave as terminal in raw mode.
+    pub fn set_out_raw(&mut self, v: bool) {
+        self.out_raw = v;
+    }
+
+    /// Sets the size of the terminal in characters. It is unknown by
+    /// default.
+    pub fn set_size(&mut self, w: usize, h: usize) {
+        self.size = Some(TermSize {
+            char_width: w,
+            char_height: h,
+            pixel_width: 0,
+            pixel_height: 0,
+        });
+    }
+
+    /// Creates provider with the same settings but without any input and
+    /// recorded data.
+    pub(crate) fn without_io(&self) -> Self {
+        Self {
+            input: VecDeque::new(),
+            pos: 0,
+            written: vec![],
+            read: vec![],
+            ..self.clone()
+        }
+    }
+
+    fn check_eof_panic(&mut self) {
+        if self.eof_panic == 1 {
+            panic!("Reading after end of scripted input.");
+        }
+        self.eof_panic = self.eof_panic.saturating_sub(1);
+    }
+}
+
+impl Default for RecordingIoProvider {
+    fn default() -> Self {
+        Self::new(ScriptedInput::new())
+    }
+}
+
+impl From<ScriptedInput> for RecordingIoProvider {
+    fn from(value: ScriptedInput) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Read for RecordingIoProvider {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let data = self.fill_buf()?;
+        let len = buf.len().min(data.len());
+        buf[..len].copy_from_slice(&data[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl BufRead for RecordingIoProvider {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.input.is_empty() {
+            self.check_eof_panic();
+            return Ok(&[]);
+        }
+        Ok(&self.input[0][self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let Some(chunk) = self.input.front() else {
+            return;
+        };
+        let end = (self.pos + amt).min(chunk.len());
+        self.read.extend_from_slice(&chunk[self.pos..end]);
+        self.pos = end;
+        if self.pos >= chunk.len() {
+            self.pos = 0;
+            self.input.pop_front();
+        }
+    }
+}
+
+impl Write for RecordingIoProvider {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.written.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl WaitForIn for RecordingIoProvider {
+    fn wait_for_in(&self, _timeout: Duration) -> Result<bool> {
+        Ok(self.has_input())
+    }
+}
+
+impl IoProvider for RecordingIoProvider {
+    type Out = Self;
+    type In = Self;
+
+    fn get_out(&mut self) -> ValueOrMut<'_, Self::Out> {
+        ValueOrMut::Mut(self)
+    }
+
+    fn get_in(&mut self) -> ValueOrMut<'_, Self::In> {
+        ValueOrMut::Mut(self)
+    }
+
+    fn is_out_terminal(&self) -> bool {
+        self.out_terminal
+    }
+
+    fn is_in_terminal(&self) -> bool {
+        self.in_terminal
+    }
+
+    fn is_out_raw(&self) -> bool {
+        self.out_raw
+    }
+
+    fn term_size(&self) -> Option<TermSize> {
+        self.size
+    }
+}
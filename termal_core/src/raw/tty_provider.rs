@@ -0,0 +1,155 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, IsTerminal, Read},
+    time::Duration,
+};
+
+use crate::error::{Error, Result};
+
+use super::{
+    sys::{file_term_size, is_file_raw, wait_for_file},
+    IoProvider, TermSize, Terminal, ValueOrMut, WaitForIn,
+};
+
+/// IoProvider that reads from and writes to the given files. By default it
+/// opens the controlling terminal directly (`/dev/tty` on unix and
+/// `CONIN$`/`CONOUT$` on windows), so interactive input works even if stdin
+/// or stdout is redirected.
+#[derive(Debug)]
+pub struct TtyProvider {
+    input: TtyIn,
+    output: File,
+}
+
+/// Buffered input of [`TtyProvider`].
+#[derive(Debug)]
+pub struct TtyIn(BufReader<File>);
+
+impl TtyProvider {
+    /// Opens the controlling terminal.
+    ///
+    /// # Support
+    /// - Unix (Linux)
+    /// - Windows (not tested)
+    pub fn open() -> Result<Self> {
+        #[cfg(unix)]
+        {
+            let tty = open_rw("/dev/tty")?;
+            return Ok(Self::from_files(tty.try_clone()?, tty));
+        }
+
+        #[cfg(windows)]
+        return Ok(Self::from_files(open_rw("CONIN$")?, open_rw("CONOUT$")?));
+
+        #[allow(unreachable_code)]
+        Err(Error::NotSupportedOnPlatform("opening terminal"))
+    }
+
+    /// Creates provider that reads from `input` and writes to `output`.
+    pub fn from_files(input: File, output: File) -> Self {
+        Self {
+            input: TtyIn(BufReader::new(input)),
+            output,
+        }
+    }
+
+    /// Creates new provider that reads from and writes to the same files.
+    pub fn try_clone(&self) -> Result<Self> {
+        Ok(Self::from_files(
+            self.input.0.get_ref().try_clone()?,
+            self.output.try_clone()?,
+        ))
+    }
+
+    /// Creates provider that reads from and writes to the given file
+    /// descriptor (handle on windows). `fd` may be anything that converts
+    /// into [`File`] (e.g. [`std::os::fd::OwnedFd`]).
+    pub fn from_fd(fd: impl Into<File>) -> Result<Self> {
+        let file = fd.into();
+        Ok(Self::from_files(file.try_clone()?, file))
+    }
+}
+
+impl Terminal<TtyProvider> {
+    /// Creates terminal that uses the controlling terminal directly. See
+    /// [`TtyProvider::open`].
+    pub fn tty() -> Result<Self> {
+        Ok(Self::new(TtyProvider::open()?))
+    }
+
+    /// Creates terminal that reads from and writes to the given file
+    /// descriptor (handle on windows). See [`TtyProvider::from_fd`].
+    pub fn from_fd(fd: impl Into<File>) -> Result<Self> {
+        Ok(Self::new(TtyProvider::from_fd(fd)?))
+    }
+}
+
+impl WaitForIn for TtyProvider {
+    fn wait_for_in(&self, timeout: Duration) -> Result<bool> {
+        self.input.wait_for_in(timeout)
+    }
+}
+
+impl IoProvider for TtyProvider {
+    type Out = File;
+    type In = TtyIn;
+
+    fn get_out(&mut self) -> ValueOrMut<'_, Self::Out> {
+        ValueOrMut::Mut(&mut self.output)
+    }
+
+    fn get_in(&mut self) -> ValueOrMut<'_, Self::In> {
+        ValueOrMut::Mut(&mut self.input)
+    }
+
+    fn is_out_terminal(&self) -> bool {
+        self.output.is_terminal()
+    }
+
+    fn is_in_terminal(&self) -> bool {
+        self.input.0.get_ref().is_terminal()
+    }
+
+    fn is_out_raw(&self) -> bool {
+        // Raw mode is mode of the console input on windows.
+        #[cfg(windows)]
+        return is_file_raw(self.input.0.get_ref());
+
+        #[allow(unreachable_code)]
+        is_file_raw(&self.output)
+    }
+
+    fn term_size(&self) -> Option<TermSize> {
+        file_term_size(&self.output).ok()
+    }
+}
+
+impl WaitForIn for TtyIn {
+    fn wait_for_in(&self, timeout: Duration) -> Result<bool> {
+        if !self.0.buffer().is_empty() {
+            return Ok(true);
+        }
+        wait_for_file(self.0.get_ref(), timeout)
+    }
+}
+
+impl Read for TtyIn {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl BufRead for TtyIn {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.0.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.0.consume(amt)
+    }
+}
+
+#[cfg(any(unix, windows))]
+fn open_rw(path: &str) -> Result<File> {
+    Ok(OpenOptions::new().read(true).write(true).open(path)?)
+}
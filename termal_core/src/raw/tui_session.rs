@@ -0,0 +1,219 @@
+use std::{
+    io::{self, Write},
+    sync::Once,
+};
+
+use crate::{codes, error::Result, register_reset_on_panic};
+
+use super::{raw_mode_guard, track_modes, Encoding, MouseMode, RawModeGuard};
+
+static REGISTER_RESET: Once = Once::new();
+
+/// Configuration of [`TuiSession`]. Nothing is enabled by default so pick
+/// only what you need.
+///
+/// # Examples
+/// ```no_run
+/// use termal_core::raw::{Encoding, MouseMode, TuiSession};
+///
+/// let session = TuiSession::builder()
+///     .alternate_buffer(true)
+///     .hide_cursor(true)
+///     .raw_mode(true)
+///     .mouse(MouseMode::Press, Encoding::Sgr)
+///     .enter()?;
+///
+/// // Draw the tui here.
+///
+/// session.leave()?;
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TuiSessionBuilder {
+    alternate_buffer: bool,
+    alternate_scroll: bool,
+    hide_cursor: bool,
+    mouse: Option<(MouseMode, Encoding)>,
+    focus_events: bool,
+    bracketed_paste: bool,
+    raw_mode: bool,
+    reset_on_panic: bool,
+}
+
+/// Terminal session for tui apps. It enables the configured terminal modes
+/// and reverses them when dropped. Create it with [`TuiSession::builder`].
+#[derive(Debug)]
+pub struct TuiSession {
+    conf: TuiSessionBuilder,
+    _raw: Option<RawModeGuard>,
+}
+
+impl TuiSessionBuilder {
+    /// Switch to the alternative buffer.
+    pub fn alternate_buffer(mut self, v: bool) -> Self {
+        self.alternate_buffer = v;
+        self
+    }
+
+    /// Enable alternate scroll mode. The terminal will send mouse wheel as
+    /// up and down arrow keys in the alternate buffer if mouse is not
+    /// captured. Use [`super::Terminal::set_alternate_scroll`] to translate
+    /// the wheel also when mouse is captured.
+    pub fn alternate_scroll(mut self, v: bool) -> Self {
+        self.alternate_scroll = v;
+        self
+    }
+
+    /// Hide the cursor.
+    pub fn hide_cursor(mut self, v: bool) -> Self {
+        self.hide_cursor = v;
+        self
+    }
+
+    /// Enable mouse capture with the given mode and encoding.
+    pub fn mouse(mut self, mode: MouseMode, encoding: Encoding) -> Self {
+        self.mouse = Some((mode, encoding));
+        self
+    }
+
+    /// Enable focus events.
+    pub fn focus_events(mut self, v: bool) -> Self {
+        self.focus_events = v;
+        self
+    }
+
+    /// Enable bracketed paste mode.
+    pub fn bracketed_paste(mut self, v: bool) -> Self {
+        self.bracketed_paste = v;
+        self
+    }
+
+    /// Enable raw mode. Raw mode is managed with [`RawModeGuard`].
+    pub fn raw_mode(mut self, v: bool) -> Self {
+        self.raw_mode = v;
+        self
+    }
+
+    /// Reset the terminal on panic. The panic hook is registered with
+    /// [`register_reset_on_panic`] at most once.
+    pub fn reset_on_panic(mut self, v: bool) -> Self {
+        self.reset_on_panic = v;
+        self
+    }
+
+    /// Gets the code that enables the configured terminal modes. Doesn't
+    /// include raw mode.
+    pub fn enable_code(&self) -> String {
+        let mut res = String::new();
+        if self.alternate_buffer {
+            res += codes::ENABLE_ALTERNATIVE_BUFFER;
+        }
+        if self.alternate_scroll {
+            res += codes::ENABLE_ALTERNATE_SCROLL;
+        }
+        if self.hide_cursor {
+            res += codes::HIDE_CURSOR;
+        }
+        if let Some((mode, encoding)) = self.mouse {
+            res += mode.enable_code();
+            res += encoding.enable_code();
+        }
+        if self.focus_events {
+            res += codes::ENABLE_FOCUS_EVENT;
+        }
+        if self.bracketed_paste {
+            res += codes::ENABLE_BRACKETED_PASTE_MODE;
+        }
+        res
+    }
+
+    /// Gets the code that disables the configured terminal modes. Doesn't
+    /// include raw mode.
+    pub fn disable_code(&self) -> String {
+        let mut res = String::new();
+        if self.bracketed_paste {
+            res += codes::DISABLE_BRACKETED_PASTE_MODE;
+        }
+        if self.focus_events {
+            res += codes::DISABLE_FOCUS_EVENT;
+        }
+        if let Some((mode, encoding)) = self.mouse {
+            res += encoding.disable_code();
+            res += mode.disable_code();
+        }
+        if self.hide_cursor {
+            res += codes::SHOW_CURSOR;
+        }
+        if self.alternate_scroll {
+            res += codes::DISABLE_ALTERNATE_SCROLL;
+        }
+        if self.alternate_buffer {
+            res += codes::DISABLE_ALTERNATIVE_BUFFER;
+        }
+        res
+    }
+
+    /// Enables the configured modes and returns the session that will
+    /// disable them when dropped.
+    pub fn enter(self) -> Result<TuiSession> {
+        if self.reset_on_panic {
+            REGISTER_RESET.call_once(register_reset_on_panic);
+        }
+
+        let raw = if self.raw_mode {
+            Some(raw_mode_guard()?)
+        } else {
+            None
+        };
+
+        let mut out = io::stdout().lock();
+        let code = self.enable_code();
+        out.write_all(code.as_bytes())?;
+        track_modes(&code);
+        out.flush()?;
+
+        Ok(TuiSession {
+            conf: self,
+            _raw: raw,
+        })
+    }
+}
+
+impl TuiSession {
+    /// Creates builder for tui session.
+    pub fn builder() -> TuiSessionBuilder {
+        TuiSessionBuilder::default()
+    }
+
+    /// Gets the configuration of this session.
+    pub fn conf(&self) -> &TuiSessionBuilder {
+        &self.conf
+    }
+
+    /// Disables the terminal modes of this session. Unlike drop, this will
+    /// report errors.
+    pub fn leave(mut self) -> Result<()> {
+        let res = self.write_disable();
+        // Prevent the modes from being disabled twice.
+        self.conf = TuiSessionBuilder {
+            raw_mode: self.conf.raw_mode,
+            ..Default::default()
+        };
+        res
+    }
+
+    fn write_disable(&self) -> Result<()> {
+        let mut out = io::stdout().lock();
+        let code = self.conf.disable_code();
+        out.write_all(code.as_bytes())?;
+        track_modes(&code);
+        out.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for TuiSession {
+    fn drop(&mut self) {
+        _ = self.write_disable();
+    }
+}
@@ -0,0 +1,181 @@
+//! Limiting of the rate of redraws shared by everything that redraws the
+//! terminal.
+//!
+//! [`RedrawScheduler`] is token bucket. Each redraw takes one token and the
+//! tokens are refilled at the configured rate. Updates that come when there
+//! is no token are coalesced into single pending redraw, that is drawn when
+//! a token is available again. The scheduler is cheap to clone and the
+//! clones share the same bucket, so that animations (e.g. spinners and
+//! progress bars) updated from many threads together don't exceed the rate.
+//!
+//! # Examples
+//! ```rust
+//! use termal_core::redraw::RedrawScheduler;
+//!
+//! let sched = RedrawScheduler::new(30.);
+//! let shared = sched.clone();
+//!
+//! // The first redraw is allowed, the second comes too soon and is
+//! // coalesced.
+//! assert!(sched.request());
+//! assert!(!shared.request());
+//! assert!(sched.is_pending());
+//! ```
+
+use std::{
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+/// Default maximum number of redraws per second of
+/// [`RedrawScheduler::global`].
+pub const DEFAULT_REDRAW_RATE: f32 = 30.;
+
+/// Token bucket that limits the rate of redraws. See the
+/// [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct RedrawScheduler(Arc<Mutex<Bucket>>);
+
+#[derive(Debug)]
+struct Bucket {
+    /// Maximum number of redraws per second.
+    rate: f32,
+    /// Maximum number of tokens.
+    burst: u32,
+    tokens: f32,
+    last: Instant,
+    /// Whether there was update that wasn't drawn yet.
+    pending: bool,
+}
+
+impl RedrawScheduler {
+    /// Creates scheduler that allows at most `rate` redraws per second. The
+    /// burst is 1, so the redraws are evenly spaced.
+    pub fn new(rate: f32) -> Self {
+        Self::with_burst(rate, 1)
+    }
+
+    /// Creates scheduler that allows at most `rate` redraws per second and
+    /// at most `burst` redraws at once. See [`RedrawScheduler::set_burst`].
+    pub fn with_burst(rate: f32, burst: u32) -> Self {
+        let burst = burst.max(1);
+        Self(Arc::new(Mutex::new(Bucket {
+            rate,
+            burst,
+            tokens: burst as f32,
+            last: Instant::now(),
+            pending: false,
+        })))
+    }
+
+    /// Gets the scheduler shared by the whole process. Its rate is
+    /// [`DEFAULT_REDRAW_RATE`] and it may be changed with
+    /// [`RedrawScheduler::set_rate`].
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<RedrawScheduler> = OnceLock::new();
+        GLOBAL.get_or_init(|| Self::new(DEFAULT_REDRAW_RATE))
+    }
+
+    /// Sets the maximum number of redraws per second. Rate `0` disables the
+    /// limit.
+    pub fn set_rate(&self, rate: f32) {
+        self.with(|b| b.rate = rate);
+    }
+
+    /// Gets the maximum number of redraws per second.
+    pub fn rate(&self) -> f32 {
+        self.with(|b| b.rate)
+    }
+
+    /// Sets how many redraws may be done at once after there were no
+    /// redraws for some time. The minimum is 1.
+    pub fn set_burst(&self, burst: u32) {
+        self.with(|b| {
+            b.burst = burst.max(1);
+            b.tokens = b.tokens.min(b.burst as f32);
+        });
+    }
+
+    /// Asks whether redraw may be done now. If it may, it is counted as
+    /// done and `true` is returned. Otherwise the update is coalesced into
+    /// the pending redraw and `false` is returned.
+    pub fn request(&self) -> bool {
+        self.with(|b| {
+            if b.take() {
+                b.pending = false;
+                true
+            } else {
+                b.pending = true;
+                false
+            }
+        })
+    }
+
+    /// Checks whether there is pending redraw and it may be done now. If it
+    /// may, it is counted as done and `true` is returned. Call this
+    /// periodically (e.g. after [`RedrawScheduler::wait_time`]) so that the
+    /// last update is not lost.
+    pub fn request_pending(&self) -> bool {
+        self.with(|b| {
+            if b.pending && b.take() {
+                b.pending = false;
+                true
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Checks whether there was update that wasn't drawn yet.
+    pub fn is_pending(&self) -> bool {
+        self.with(|b| b.pending)
+    }
+
+    /// Gets the time until the next redraw is allowed. Returns
+    /// [`Duration::ZERO`] if redraw is allowed now.
+    pub fn wait_time(&self) -> Duration {
+        self.with(|b| {
+            b.refill();
+            if b.tokens >= 1. || b.rate <= 0. {
+                Duration::ZERO
+            } else {
+                Duration::from_secs_f32((1. - b.tokens) / b.rate)
+            }
+        })
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut Bucket) -> R) -> R {
+        let mut b = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut b)
+    }
+}
+
+impl Default for RedrawScheduler {
+    fn default() -> Self {
+        Self::new(DEFAULT_REDRAW_RATE)
+    }
+}
+
+impl Bucket {
+    fn refill(&mut self) {
+        let now = Instant::now();
+        if self.rate <= 0. {
+            // Zero rate means no limit.
+            self.tokens = self.burst as f32;
+        } else {
+            self.tokens += (now - self.last).as_secs_f32() * self.rate;
+            self.tokens = self.tokens.min(self.burst as f32);
+        }
+        self.last = now;
+    }
+
+    fn take(&mut self) -> bool {
+        self.refill();
+        if self.tokens >= 1. {
+            self.tokens -= 1.;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -4,10 +4,13 @@ use std::{
     str::FromStr,
 };
 
-use crate::{codes::fg, error::Error};
+use crate::{
+    codes::{bg, fg, underline_rgb},
+    error::Error,
+};
 
 /// Single RGB pixel.
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Rgb<T = u8> {
     /// Red component of the pixel.
     pub r: T,
@@ -17,6 +20,19 @@ pub struct Rgb<T = u8> {
     pub b: T,
 }
 
+/// Single RGB pixel with alpha channel.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rgba<T = u8> {
+    /// Red component of the pixel.
+    pub r: T,
+    /// Green component of the pixel.
+    pub g: T,
+    /// Blue component of the pixel.
+    pub b: T,
+    /// Alpha (opacity) of the pixel.
+    pub a: T,
+}
+
 impl<T> Rgb<T> {
     /// Create new rgb pixel.
     pub const fn new(r: T, g: T, b: T) -> Self {
@@ -37,6 +53,8 @@ impl<T> Rgb<T> {
 impl Rgb {
     /// Black color.
     pub const BLACK: Self = Self::new(0, 0, 0);
+    /// White color.
+    pub const WHITE: Self = Self::new(255, 255, 255);
 
     /// Create new rgb pixel from single byte rgb pixel.
     ///
@@ -58,6 +76,80 @@ impl Rgb {
         Self::new(r, g, b)
     }
 
+    /// Parse hex color in the form `#RGB`. The leading `#` is optional.
+    ///
+    /// The number of digits determines how the color is interpreted:
+    /// - **1 digit:** shade of gray (`#A` is `#AAAAAA`)
+    /// - **2 digits:** shade of gray (`#AB` is `#ABABAB`)
+    /// - **3 digits:** each digit is repeated (`#ABC` is `#AABBCC`)
+    /// - **4 digits:** same as 3 digits, the last digit (alpha) is ignored.
+    /// - **6 digits:** normal RGB color (`#FF0000` is pure red)
+    /// - **8 digits:** same as 6 digits, the last two digits (alpha) are
+    ///   ignored.
+    ///
+    /// This is the same parsing that is used by the color templates, both
+    /// in the macros and at runtime.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidRgbFormat`] if the string is not valid hex color.
+    pub fn from_hex(s: &str) -> Result<Self, Error> {
+        Self::parse_hex(s).ok_or(Error::InvalidRgbFormat)
+    }
+
+    /// Const version of [`Self::from_hex`]. Returns [`None`] if the string
+    /// is not valid hex color.
+    ///
+    /// ```
+    /// use termal_core::Rgb;
+    ///
+    /// const RED: Rgb = Rgb::parse_hex("#f00").unwrap();
+    /// assert_eq!(RED, Rgb::new(0xff, 0, 0));
+    /// ```
+    pub const fn parse_hex(s: &str) -> Option<Self> {
+        let mut s = s.as_bytes();
+        if let [b'#', rest @ ..] = s {
+            s = rest;
+        }
+
+        let mut c: u32 = 0;
+        let mut i = 0;
+        while i < s.len() {
+            let d = match s[i] {
+                d @ b'0'..=b'9' => d - b'0',
+                d @ b'a'..=b'f' => d - b'a' + 10,
+                d @ b'A'..=b'F' => d - b'A' + 10,
+                _ => return None,
+            };
+            if i < 8 {
+                c = (c << 4) | d as u32;
+            }
+            i += 1;
+        }
+
+        let (r, g, b) = match s.len() {
+            1 => {
+                let c = c | (c << 4);
+                (c, c, c)
+            }
+            2 => (c, c, c),
+            3 => (c >> 8, (c >> 4) & 0xF, c & 0xF),
+            4 => (c >> 12, (c >> 8) & 0xF, (c >> 4) & 0xF),
+            6 => (c >> 16, (c >> 8) & 0xFF, c & 0xFF),
+            8 => (c >> 24, (c >> 16) & 0xFF, (c >> 8) & 0xFF),
+            _ => return None,
+        };
+
+        if matches!(s.len(), 3 | 4) {
+            Some(Self::new(
+                (r | (r << 4)) as u8,
+                (g | (g << 4)) as u8,
+                (b | (b << 4)) as u8,
+            ))
+        } else {
+            Some(Self::new(r as u8, g as u8, b as u8))
+        }
+    }
+
     /// Convert this pixel to a single byte RGB value.
     ///
     /// The single byte has the components (from high bits to low bits):
@@ -78,10 +170,66 @@ impl Rgb {
         Rgb::new(self.r as f32, self.g as f32, self.b as f32)
     }
 
+    /// Linearly interpolate between this color (`t = 0`) and `other`
+    /// (`t = 1`). `t` is clamped to the range `0..=1`.
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        let t = t.clamp(0., 1.);
+        let s = self.as_f32();
+        (s + (other.as_f32() - s) * t).as_u8()
+    }
+
+    /// Gets the relative luminance of the color in range `0..=1` as defined
+    /// by WCAG. Unlike [`crate::theme::luminance`], this takes the gamma of
+    /// sRGB into account.
+    pub fn luminance(&self) -> f32 {
+        fn linear(c: u8) -> f32 {
+            let c = c as f32 / 255.;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        }
+
+        0.2126 * linear(self.r)
+            + 0.7152 * linear(self.g)
+            + 0.0722 * linear(self.b)
+    }
+
+    /// Gets the contrast ratio between the two colors as defined by WCAG.
+    /// The result is in range `1..=21`. The order of the colors doesn't
+    /// matter.
+    pub fn contrast_ratio(&self, other: Self) -> f32 {
+        let a = self.luminance();
+        let b = other.luminance();
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Gets black or white, whichever is more readable as text on this
+    /// background color.
+    pub fn contrasting_text_color(&self) -> Self {
+        if self.contrast_ratio(Self::BLACK) >= self.contrast_ratio(Self::WHITE)
+        {
+            Self::BLACK
+        } else {
+            Self::WHITE
+        }
+    }
+
     /// Get the foreground code of the rgb.
     pub fn fg(&self) -> String {
         fg!(self.r, self.g, self.b)
     }
+
+    /// Get the background code of the rgb.
+    pub fn bg(&self) -> String {
+        bg!(self.r, self.g, self.b)
+    }
+
+    /// Get the underline color code of the rgb.
+    pub fn underline(&self) -> String {
+        underline_rgb!(self.r, self.g, self.b)
+    }
 }
 
 impl Rgb<usize> {
@@ -126,6 +274,42 @@ impl Rgb<u16> {
     }
 }
 
+impl<T> Rgba<T> {
+    /// Create new rgba pixel.
+    pub const fn new(r: T, g: T, b: T, a: T) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Gets the color without the alpha channel.
+    pub fn rgb(self) -> Rgb<T> {
+        Rgb::new(self.r, self.g, self.b)
+    }
+}
+
+impl Rgba {
+    /// Create fully opaque pixel.
+    pub const fn opaque(c: Rgb) -> Self {
+        Self::new(c.r, c.g, c.b, 255)
+    }
+
+    /// Composite this color over the opaque background `bg`.
+    pub fn over(self, bg: Rgb) -> Rgb {
+        bg.lerp(self.rgb(), self.a as f32 / 255.)
+    }
+}
+
+impl From<Rgb> for Rgba {
+    fn from(value: Rgb) -> Self {
+        Self::opaque(value)
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for Rgba {
+    fn from((r, g, b, a): (u8, u8, u8, u8)) -> Self {
+        Self::new(r, g, b, a)
+    }
+}
+
 impl From<(u8, u8, u8)> for Rgb {
     fn from((r, g, b): (u8, u8, u8)) -> Self {
         Self::new(r, g, b)
@@ -0,0 +1,545 @@
+use std::{
+    fmt::Display,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub, SubAssign},
+};
+
+use crate::Rgb;
+
+/// Color of text, background or underline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// One of the 16 basic colors (value in range `0..16`). The colors
+    /// `0..8` are the dark variants and `8..16` are the bright variants.
+    Basic(u8),
+    /// One of the 256 colors.
+    Ansi256(u8),
+    /// True rgb color.
+    Rgb(Rgb),
+}
+
+/// Set of text attributes (e.g. bold, italic, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Attrs(u16);
+
+/// Text style. Colors with value [`None`] are the default colors of the
+/// terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Style {
+    /// Foreground color.
+    pub fg: Option<Color>,
+    /// Background color.
+    pub bg: Option<Color>,
+    /// Color of underline.
+    pub underline_color: Option<Color>,
+    /// Text attributes.
+    pub attrs: Attrs,
+}
+
+/// How styles are handled at the ends of lines in rendered output (e.g. by
+/// [`crate::StyledWriter`], [`crate::wrap`] and [`crate::columns`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ResetPolicy {
+    /// Reset the style before each newline and set it again on the next
+    /// line. This prevents the background color from bleeding to the end of
+    /// the line when the terminal scrolls.
+    #[default]
+    EachLine,
+    /// Keep the style running across newlines. It is reset only after the
+    /// newline or at the end of the output.
+    Keep,
+}
+
+/// Attributes with their set and reset codes. Attributes with the same reset
+/// code are next to each other.
+const ATTR_CODES: [(Attrs, &str, u8); 13] = [
+    (Attrs::BOLD, "1", 22),
+    (Attrs::FAINT, "2", 22),
+    (Attrs::ITALIC, "3", 23),
+    (Attrs::UNDERLINE, "4", 24),
+    (Attrs::DOUBLE_UNDERLINE, "21", 24),
+    (Attrs::CURLY_UNDERLINE, "4:3", 24),
+    (Attrs::DOTTED_UNDERLINE, "4:4", 24),
+    (Attrs::DASHED_UNDERLINE, "4:5", 24),
+    (Attrs::BLINK, "5", 25),
+    (Attrs::INVERSE, "7", 27),
+    (Attrs::INVISIBLE, "8", 28),
+    (Attrs::STRIKETROUGH, "9", 29),
+    (Attrs::OVERLINE, "53", 55),
+];
+
+impl Color {
+    /// Gets the code that sets this as foreground color.
+    pub fn fg_code(&self) -> String {
+        let mut args = vec![];
+        self.push_args(&mut args, 38);
+        sgr(&args)
+    }
+
+    /// Gets the code that sets this as background color.
+    pub fn bg_code(&self) -> String {
+        let mut args = vec![];
+        self.push_args(&mut args, 48);
+        sgr(&args)
+    }
+
+    /// Gets the code that sets this as underline color. Underline color
+    /// doesn't have the basic colors, so [`Color::Basic`] is set as 256
+    /// color.
+    pub fn underline_code(&self) -> String {
+        let mut args = vec![];
+        self.push_args(&mut args, 58);
+        sgr(&args)
+    }
+
+    /// Appends the SGR arguments of this color to `res`. `base` is the
+    /// first argument of the extended color (38, 48 or 58).
+    fn push_args(&self, res: &mut Vec<String>, base: u8) {
+        match self {
+            Self::Basic(c) if base != 58 => {
+                let c = c & 15;
+                let off = if c < 8 { 30 } else { 90 - 8 };
+                res.push((c + off + base - 38).to_string());
+            }
+            Self::Basic(c) | Self::Ansi256(c) => {
+                res.extend([base.to_string(), "5".into(), c.to_string()]);
+            }
+            Self::Rgb(c) => res.extend([
+                base.to_string(),
+                "2".into(),
+                c.r.to_string(),
+                c.g.to_string(),
+                c.b.to_string(),
+            ]),
+        }
+    }
+}
+
+/// Creates SGR code from the given arguments. Returns empty string if there
+/// are no arguments.
+fn sgr(args: &[String]) -> String {
+    if args.is_empty() {
+        return String::new();
+    }
+
+    let mut res = "\x1b[".to_string();
+    for (i, a) in args.iter().enumerate() {
+        if i != 0 {
+            res.push(';');
+        }
+        res += a;
+    }
+    res.push('m');
+    res
+}
+
+impl From<Rgb> for Color {
+    fn from(value: Rgb) -> Self {
+        Self::Rgb(value)
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from(value: (u8, u8, u8)) -> Self {
+        Self::Rgb(value.into())
+    }
+}
+
+impl From<u8> for Color {
+    fn from(value: u8) -> Self {
+        Self::Ansi256(value)
+    }
+}
+
+impl Attrs {
+    /// No attributes.
+    pub const NONE: Self = Self(0);
+    /// Bold text (on some terminals may be just brighter color).
+    pub const BOLD: Self = Self(0x1);
+    /// Dim/faint text.
+    pub const FAINT: Self = Self(0x2);
+    /// Italic text.
+    pub const ITALIC: Self = Self(0x4);
+    /// Underlined text.
+    pub const UNDERLINE: Self = Self(0x8);
+    /// Double underlined text.
+    pub const DOUBLE_UNDERLINE: Self = Self(0x10);
+    /// Blinking text.
+    pub const BLINK: Self = Self(0x20);
+    /// Inverse foreground and background.
+    pub const INVERSE: Self = Self(0x40);
+    /// Invisible (concealed) text.
+    pub const INVISIBLE: Self = Self(0x80);
+    /// Striketrough text.
+    pub const STRIKETROUGH: Self = Self(0x100);
+    /// Overlined text.
+    pub const OVERLINE: Self = Self(0x200);
+    /// Text underlined with curly (wavy) line.
+    pub const CURLY_UNDERLINE: Self = Self(0x400);
+    /// Text underlined with dotted line.
+    pub const DOTTED_UNDERLINE: Self = Self(0x800);
+    /// Text underlined with dashed line.
+    pub const DASHED_UNDERLINE: Self = Self(0x1000);
+    /// All the underline styles.
+    pub const ANY_UNDERLINE: Self = Self(0x1c18);
+    /// All the attributes.
+    pub const ALL: Self = Self(0x1fff);
+
+    /// Gets the raw bits of the attributes.
+    pub const fn bits(&self) -> u16 {
+        self.0
+    }
+
+    /// Creates attributes from raw bits. Unknown bits are ignored.
+    pub const fn from_bits_truncate(bits: u16) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    /// Checks if there are no attributes.
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Checks if all the attributes in `other` are set.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Checks if any of the attributes in `other` is set.
+    pub const fn intersects(&self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Adds the attributes in `other`.
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Removes the attributes in `other`.
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    /// Adds or removes the attributes in `other`.
+    pub fn set(&mut self, other: Self, v: bool) {
+        if v {
+            self.insert(other);
+        } else {
+            self.remove(other);
+        }
+    }
+}
+
+impl BitOr for Attrs {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Attrs {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for Attrs {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl BitAndAssign for Attrs {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl Sub for Attrs {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 & !rhs.0)
+    }
+}
+
+impl SubAssign for Attrs {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 &= !rhs.0;
+    }
+}
+
+impl Not for Attrs {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self(!self.0 & Self::ALL.0)
+    }
+}
+
+impl Style {
+    /// Style with default colors and no attributes.
+    pub const DEFAULT: Self = Self {
+        fg: None,
+        bg: None,
+        underline_color: None,
+        attrs: Attrs::NONE,
+    };
+
+    /// Creates style with default colors and no attributes.
+    pub const fn new() -> Self {
+        Self::DEFAULT
+    }
+
+    /// Sets the foreground color.
+    pub fn fg(mut self, c: impl Into<Color>) -> Self {
+        self.fg = Some(c.into());
+        self
+    }
+
+    /// Sets the background color.
+    pub fn bg(mut self, c: impl Into<Color>) -> Self {
+        self.bg = Some(c.into());
+        self
+    }
+
+    /// Sets the underline color.
+    pub fn underline_color(mut self, c: impl Into<Color>) -> Self {
+        self.underline_color = Some(c.into());
+        self
+    }
+
+    /// Adds the given attributes.
+    pub fn attr(mut self, attrs: Attrs) -> Self {
+        self.attrs |= attrs;
+        self
+    }
+
+    /// Checks if this is the default style.
+    pub fn is_default(&self) -> bool {
+        *self == Self::DEFAULT
+    }
+
+    /// Gets the code that sets this style when the terminal is in the default
+    /// style.
+    pub fn code(&self) -> String {
+        Self::DEFAULT.transition(self)
+    }
+
+    /// Gets the code that resets this style back to the default style.
+    pub fn reset_code(&self) -> String {
+        self.transition(&Self::DEFAULT)
+    }
+
+    /// Gets the shortest code that changes the style from this style to
+    /// `to`. Returns empty string if the styles are the same.
+    pub fn transition(&self, to: &Style) -> String {
+        let diff = self.diff_args(to);
+        let mut full = vec!["0".to_string()];
+        full.extend(Self::DEFAULT.diff_args(to));
+        let args = if full.iter().map(|a| a.len() + 1).sum::<usize>()
+            < diff.iter().map(|a| a.len() + 1).sum::<usize>()
+        {
+            full
+        } else {
+            diff
+        };
+
+        sgr(&args)
+    }
+
+    /// Modifies the style as the terminal would when it receives the SGR
+    /// code (e.g. `"\x1b[1;38:2::255:0:0m"`). Both `;` and `:` separated
+    /// arguments (e.g. `4:3` for curly underline) are supported. Returns
+    /// `false` and doesn't change the style if the code is not SGR code.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use termal_core::{codes, Attrs, Color, Style};
+    ///
+    /// let mut style = Style::new();
+    /// assert!(style.apply_sgr(codes::CURLY_UNDERLINE));
+    /// assert!(style.apply_sgr("\x1b[58:5:196m"));
+    /// assert_eq!(
+    ///     style,
+    ///     Style::new()
+    ///         .attr(Attrs::CURLY_UNDERLINE)
+    ///         .underline_color(196)
+    /// );
+    /// assert!(!style.apply_sgr(codes::CLEAR));
+    /// ```
+    pub fn apply_sgr(&mut self, code: &str) -> bool {
+        let Some(params) = code
+            .strip_prefix("\x1b[")
+            .and_then(|c| c.strip_suffix('m'))
+            .filter(|p| {
+                p.bytes()
+                    .all(|b| b.is_ascii_digit() || b == b';' || b == b':')
+            })
+        else {
+            return false;
+        };
+
+        let groups: Vec<Vec<Option<u32>>> = params
+            .split(';')
+            .map(|g| g.split(':').map(|a| a.parse().ok()).collect())
+            .collect();
+
+        let mut i = 0;
+        while i < groups.len() {
+            let group = &groups[i];
+            i += 1;
+            let code = group[0].unwrap_or_default();
+            match code {
+                0 => *self = Self::DEFAULT,
+                4 if group.len() > 1 => {
+                    self.attrs -= Attrs::ANY_UNDERLINE;
+                    self.attrs |= match group[1].unwrap_or_default() {
+                        1 => Attrs::UNDERLINE,
+                        2 => Attrs::DOUBLE_UNDERLINE,
+                        3 => Attrs::CURLY_UNDERLINE,
+                        4 => Attrs::DOTTED_UNDERLINE,
+                        5 => Attrs::DASHED_UNDERLINE,
+                        _ => Attrs::NONE,
+                    };
+                }
+                4 | 21 => {
+                    self.attrs -= Attrs::ANY_UNDERLINE;
+                    self.attrs |= if code == 4 {
+                        Attrs::UNDERLINE
+                    } else {
+                        Attrs::DOUBLE_UNDERLINE
+                    };
+                }
+                30..=37 => self.fg = Some(Color::Basic(code as u8 - 30)),
+                90..=97 => self.fg = Some(Color::Basic(code as u8 - 90 + 8)),
+                40..=47 => self.bg = Some(Color::Basic(code as u8 - 40)),
+                100..=107 => {
+                    self.bg = Some(Color::Basic(code as u8 - 100 + 8))
+                }
+                39 => self.fg = None,
+                49 => self.bg = None,
+                59 => self.underline_color = None,
+                38 | 48 | 58 => {
+                    let color = if group.len() > 1 {
+                        extended_color(&group[1..], true)
+                    } else {
+                        // The arguments are separated with `;`.
+                        let len = match groups.get(i).map(|g| g[0]) {
+                            Some(Some(5)) => 2,
+                            Some(Some(2)) => 4,
+                            _ => 1,
+                        };
+                        let args: Vec<_> = groups[i..]
+                            .iter()
+                            .take(len)
+                            .map(|g| g[0])
+                            .collect();
+                        i += len;
+                        extended_color(&args, false)
+                    };
+                    let Some(color) = color else {
+                        continue;
+                    };
+                    match code {
+                        38 => self.fg = Some(color),
+                        48 => self.bg = Some(color),
+                        _ => self.underline_color = Some(color),
+                    }
+                }
+                _ => {
+                    for (attr, set, rst) in ATTR_CODES {
+                        // Attributes with the same reset code are all reset.
+                        if set.parse() == Ok(code) {
+                            self.attrs |= attr;
+                        } else if u32::from(rst) == code {
+                            self.attrs -= attr;
+                        }
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    fn diff_args(&self, to: &Style) -> Vec<String> {
+        let mut res = vec![];
+
+        let removed = self.attrs - to.attrs;
+        let mut resets: Vec<u8> = vec![];
+        for (attr, _, rst) in ATTR_CODES {
+            if removed.contains(attr) && !resets.contains(&rst) {
+                resets.push(rst);
+            }
+        }
+
+        let mut added = to.attrs - self.attrs;
+        for (attr, _, rst) in ATTR_CODES {
+            // Reset code may reset also attributes that should be kept.
+            if resets.contains(&rst) {
+                added |= to.attrs & attr;
+            }
+        }
+
+        res.extend(resets.iter().map(|r| r.to_string()));
+        for (attr, set, _) in ATTR_CODES {
+            if added.contains(attr) {
+                res.push(set.to_string());
+            }
+        }
+
+        let colors = [
+            (self.fg, to.fg, 38, 39),
+            (self.bg, to.bg, 48, 49),
+            (self.underline_color, to.underline_color, 58, 59),
+        ];
+        for (from, to, base, rst) in colors {
+            if from == to {
+                continue;
+            }
+            match to {
+                Some(c) => c.push_args(&mut res, base),
+                None => res.push(rst.to_string()),
+            }
+        }
+
+        res
+    }
+}
+
+/// Gets the extended color from the arguments after 38, 48 or 58. If `colon`
+/// is `true`, the arguments were separated with `:` and RGB color may have
+/// color space id before the components (e.g. `2::255:0:0`).
+fn extended_color(args: &[Option<u32>], colon: bool) -> Option<Color> {
+    let comp = |c: &Option<u32>| u8::try_from(c.unwrap_or_default()).ok();
+    let (r, g, b) = match args {
+        [Some(5), Some(c), ..] => {
+            return u8::try_from(*c).ok().map(Color::Ansi256)
+        }
+        [Some(2), _, r, g, b, ..] if colon => (r, g, b),
+        [Some(2), r, g, b, ..] => (r, g, b),
+        _ => return None,
+    };
+    Some(Color::Rgb(Rgb::new(comp(r)?, comp(g)?, comp(b)?)))
+}
+
+impl Display for Style {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.code())
+    }
+}
+
+impl From<Color> for Style {
+    fn from(value: Color) -> Self {
+        Self::new().fg(value)
+    }
+}
+
+impl From<Attrs> for Style {
+    fn from(value: Attrs) -> Self {
+        Self::new().attr(value)
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt::{self, Write};
+
+use crate::{ResetPolicy, Style};
+
+/// Writer that tracks the current [`Style`] and inserts the codes that change
+/// it only when text is written. The style is always reset at the end, so
+/// the output can't leak styles. By default, the style is also reset before
+/// each newline (see [`ResetPolicy`]).
+///
+/// # Examples
+/// ```rust
+/// use std::fmt::Write;
+/// use termal_core::{Attrs, Color, Style, StyledWriter};
+///
+/// let mut w = StyledWriter::new(String::new());
+/// w.set_style(Color::Basic(1));
+/// write!(w, "error")?;
+/// w.set_style(Style::new());
+/// write!(w, ": ")?;
+/// w.set_style(Attrs::BOLD);
+/// write!(w, "oops")?;
+///
+/// assert_eq!(w.finish()?, "\x1b[31merror\x1b[0m: \x1b[1moops\x1b[0m");
+/// # Ok::<_, std::fmt::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct StyledWriter<W: Write> {
+    // This is [`None`] only after the writer is finished.
+    out: Option<W>,
+    /// Style of the next written text.
+    style: Style,
+    /// Style that is currently set in the output.
+    written: Style,
+    reset: ResetPolicy,
+}
+
+impl<W: Write> StyledWriter<W> {
+    /// Creates writer that writes to `out`. The output is expected to be in
+    /// the default style.
+    pub fn new(out: W) -> Self {
+        Self {
+            out: Some(out),
+            style: Style::DEFAULT,
+            written: Style::DEFAULT,
+            reset: ResetPolicy::default(),
+        }
+    }
+
+    /// Sets how the style is handled at the ends of lines.
+    pub fn set_reset_policy(&mut self, policy: ResetPolicy) {
+        self.reset = policy;
+    }
+
+    /// Gets how the style is handled at the ends of lines.
+    pub fn reset_policy(&self) -> ResetPolicy {
+        self.reset
+    }
+
+    /// Gets the style of the next written text.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Sets the style of the next written text. Nothing is written until
+    /// there is text with the style.
+    pub fn set_style(&mut self, style: impl Into<Style>) {
+        self.style = style.into();
+    }
+
+    /// Modifies the style of the next written text.
+    pub fn modify_style(&mut self, f: impl FnOnce(&mut Style)) {
+        f(&mut self.style)
+    }
+
+    /// Sets the style to the default style.
+    pub fn reset_style(&mut self) {
+        self.style = Style::DEFAULT;
+    }
+
+    /// Writes the text with the given style. The previous style is restored
+    /// after.
+    pub fn write_styled(
+        &mut self,
+        style: impl Into<Style>,
+        s: &str,
+    ) -> fmt::Result {
+        let prev = std::mem::replace(&mut self.style, style.into());
+        let res = self.write_str(s);
+        self.style = prev;
+        res
+    }
+
+    /// Gets the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.out.as_ref().unwrap()
+    }
+
+    /// Gets the underlying writer. Writing directly to it may break the
+    /// style tracking.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.out.as_mut().unwrap()
+    }
+
+    /// Resets the style of the output and gets the underlying writer.
+    pub fn finish(mut self) -> Result<W, fmt::Error> {
+        self.write_reset()?;
+        Ok(self.out.take().unwrap())
+    }
+
+    fn write_line(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+        let out = self.out.as_mut().unwrap();
+        out.write_str(&self.written.transition(&self.style))?;
+        self.written = self.style;
+        out.write_str(s)
+    }
+
+    fn write_reset(&mut self) -> fmt::Result {
+        let Some(out) = &mut self.out else {
+            return Ok(());
+        };
+        out.write_str(&self.written.reset_code())?;
+        self.written = Style::DEFAULT;
+        Ok(())
+    }
+}
+
+impl<W: Write + Default> Default for StyledWriter<W> {
+    fn default() -> Self {
+        Self::new(W::default())
+    }
+}
+
+impl<W: Write> Write for StyledWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.reset == ResetPolicy::Keep {
+            return self.write_line(s);
+        }
+
+        for (i, line) in s.split('\n').enumerate() {
+            if i != 0 {
+                self.write_reset()?;
+                self.get_mut().write_char('\n')?;
+            }
+            self.write_line(line)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Drop for StyledWriter<W> {
+    fn drop(&mut self) {
+        _ = self.write_reset();
+    }
+}
@@ -0,0 +1,552 @@
+//! Runtime parsing of the color templates used by the macros (e.g.
+//! `formatc!`).
+
+use std::iter::Peekable;
+
+use crate::{
+    codes,
+    error::{Error, Result},
+    move_to,
+    rgb::Rgb,
+    theme::{palette_code, Palette},
+};
+
+/// Removes the color blocks (e.g. `{'yellow}`) from the template. This is
+/// the runtime equivalent of the `formatnc!` macro without the
+/// formatting, so it can be used with templates loaded at runtime (e.g. from
+/// config files).
+///
+/// # Examples
+/// ```rust
+/// use termal_core::uncolor_str;
+///
+/// assert_eq!(uncolor_str("{'yellow}warning{'_}: {}")?, "warning: {}");
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub fn uncolor_str(s: &str) -> Result<String> {
+    let mut i = s.chars().peekable();
+    let mut res = String::new();
+
+    while let Some(c) = i.next() {
+        match c {
+            '{' => match i.next() {
+                Some('\'') => skip_block(&mut i)?,
+                Some(c) => {
+                    res.push('{');
+                    res.push(c);
+                }
+                None => res.push('{'),
+            },
+            _ => res.push(c),
+        }
+    }
+
+    Ok(res)
+}
+
+fn skip_block<I>(i: &mut Peekable<I>) -> Result<()>
+where
+    I: Iterator<Item = char>,
+{
+    while let Some(c) = i.peek() {
+        match c {
+            '}' => {
+                i.next();
+                return Ok(());
+            }
+            _ => _ = i.next(),
+        }
+    }
+
+    Err(template_error("Missing '}}' at the end of color pattern"))
+}
+
+/// Replaces the color blocks (e.g. `{'yellow}` or `{'#FF0000}`) in the
+/// template with the codes. This is the runtime equivalent of the
+/// `formatc!` macro without the formatting, so it can be used with
+/// templates loaded at runtime (e.g. from config files). Other text
+/// (including `{` that is not followed by `'`) is kept as is.
+///
+/// # Errors
+/// [`Error::InvalidTemplate`] if the template has invalid color block. The
+/// error message is the same as the compile error of the macros.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::{codes, colorize_str};
+///
+/// assert_eq!(
+///     colorize_str("{'yellow}warning{'_}: {}")?,
+///     format!("{}warning{}: {{}}", codes::YELLOW_FG, codes::RESET),
+/// );
+/// assert!(colorize_str("{'nonsense}").is_err());
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+pub fn colorize_str(s: &str) -> Result<String> {
+    parse_template(s, false)
+}
+
+/// Prefix of the constants with named styles defined with `define_style!`.
+pub(crate) const NAMED_STYLE_PREFIX: &str = "__termal_style_";
+/// Prefix of the format arguments with colors from the current palette.
+pub(crate) const THEME_PREFIX: &str = "__termal_theme_";
+
+/// Replaces the color blocks in the template with the codes. If `named` is
+/// `true`, named styles (e.g. `{'@err}`) are replaced with format argument
+/// that captures the constant with the style and colors from the palette
+/// (e.g. `{'t:error}`) are replaced with format argument that must be
+/// provided. Otherwise colors from the palette are
+/// replaced with their current codes.
+pub(crate) fn parse_template(s: &str, named: bool) -> Result<String> {
+    let mut i = s.chars().peekable();
+    let mut res = String::new();
+
+    while let Some(c) = i.next() {
+        match c {
+            '{' => match i.next() {
+                Some('\'') => parse_block(&mut res, &mut i, named)?,
+                Some(c) => {
+                    res.push('{');
+                    res.push(c);
+                }
+                None => res.push('{'),
+            },
+            _ => res.push(c),
+        }
+    }
+
+    Ok(res)
+}
+
+fn parse_block<I>(
+    res: &mut String,
+    i: &mut Peekable<I>,
+    named: bool,
+) -> Result<()>
+where
+    I: Iterator<Item = char>,
+{
+    while let Some(c) = i.peek() {
+        match c {
+            c if c.is_ascii_alphabetic() || *c == '_' => {
+                parse_variable(res, i, named)?
+            }
+            '}' => {
+                i.next();
+                return Ok(());
+            }
+            '#' => parse_color(res, i)?,
+            '@' if named => parse_named(res, i)?,
+            '@' => {
+                return Err(template_error(
+                    "Named styles are supported only in the macros",
+                ))
+            }
+            ' ' => _ = i.next(),
+            _ => {
+                return Err(template_error(format!(
+                    "Invalid color format, didn't expect character '{}'",
+                    c
+                )));
+            }
+        }
+    }
+
+    Err(template_error("Missing '}}' at the end of color pattern"))
+}
+
+fn parse_named<I>(res: &mut String, i: &mut Peekable<I>) -> Result<()>
+where
+    I: Iterator<Item = char>,
+{
+    i.next();
+    let mut name = String::new();
+    read_while(&mut name, i, |c| c.is_ascii_alphanumeric() || c == '_');
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(template_error("Invalid name of named style"));
+    }
+    if !matches!(i.peek(), Some(' ' | '}')) {
+        return Err(template_error("Expected ' ' or '}' after named style"));
+    }
+
+    *res += "{";
+    *res += NAMED_STYLE_PREFIX;
+    *res += &name;
+    *res += "}";
+    Ok(())
+}
+
+fn parse_theme<I>(
+    res: &mut String,
+    i: &mut Peekable<I>,
+    named: bool,
+) -> Result<()>
+where
+    I: Iterator<Item = char>,
+{
+    i.next();
+    let mut name = String::new();
+    read_while(&mut name, i, |c| c.is_ascii_alphanumeric() || c == '_');
+    let color = name.strip_suffix('_').unwrap_or(&name);
+    if !Palette::NAMES.contains(&color) {
+        return Err(template_error(format!(
+            "Unknown palette color '{name}', expected one of: {}",
+            Palette::NAMES.join(", ")
+        )));
+    }
+    if !matches!(i.peek(), Some(' ' | '}')) {
+        return Err(template_error("Expected ' ' or '}' after palette color"));
+    }
+
+    if named {
+        *res += "{";
+        *res += THEME_PREFIX;
+        *res += &name;
+        *res += "}";
+    } else {
+        *res += &palette_code(&name);
+    }
+    Ok(())
+}
+
+fn parse_variable<I>(
+    res: &mut String,
+    i: &mut Peekable<I>,
+    named: bool,
+) -> Result<()>
+where
+    I: Iterator<Item = char>,
+{
+    let mut s = String::new();
+
+    while let Some(c) = i.peek() {
+        match c {
+            c if c.is_ascii_alphabetic() || *c == '_' => {
+                s.push(*c);
+                i.next();
+            }
+            '}' | ' ' | '#' => break,
+            ':' if s == "t" => return parse_theme(res, i, named),
+            c if c.is_ascii_digit() || *c == ',' => break,
+            _ => {
+                return Err(template_error(format!(
+                    "Invalid color format, didn't expect character '{}'",
+                    c
+                )));
+            }
+        }
+    }
+
+    /// macro, default, owner
+    macro_rules! m_arm {
+        ($m:ident, $d:literal, $o:ident) => {{
+            $o = codes::$m!(maybe_read_num(i).unwrap_or($d));
+            &$o
+        }};
+    }
+
+    let owner;
+
+    let var = match s.to_lowercase().as_str() {
+        "bell" => "\x07",
+        "backspace" => "\x08",
+        "htab" | "tab" => "\t",
+        "move_down_scrl" | "mds" => "\n",
+        "newline" | "nl" => "\n\r",
+        "vtab" => "\x0b",
+        "carriage_return" | "cr" => "\r",
+        "delete" | "del" => "\x7f",
+
+        "move_to" | "mt" => {
+            let x = maybe_read_num(i);
+            if matches!(i.peek(), Some(',')) && x.is_some() {
+                i.next();
+            } else if x.is_some() {
+                return Err(template_error(format!(
+                    "'{}', takes two arguments",
+                    s
+                )));
+            }
+            let y = maybe_read_num(i);
+            if x.is_none() && y.is_none() {
+                "\x1b[H"
+            } else {
+                owner = move_to!(x.unwrap_or_default(), y.unwrap_or_default());
+                &owner
+            }
+        }
+        "move_up" | "mu" => m_arm!(move_up, 1, owner),
+        "move_down" | "md" => m_arm!(move_down, 1, owner),
+        "move_right" | "mr" => m_arm!(move_right, 1, owner),
+        "move_left" | "ml" => m_arm!(move_left, 1, owner),
+        "set_down" | "sd" => m_arm!(set_down, 1, owner),
+        "set_up" | "su" => m_arm!(set_up, 1, owner),
+        "move_to_column" | "mc" => m_arm!(column, 0, owner),
+        "move_to_row" | "mrow" => m_arm!(row, 0, owner),
+        "scroll_up" | "scu" => m_arm!(scroll_up, 1, owner),
+        "scroll_down" | "scd" => m_arm!(scroll_down, 1, owner),
+        "forward_tab" | "ftab" => m_arm!(cursor_forward_tab, 1, owner),
+        "set_tab" | "stab" => codes::SET_TAB_STOP,
+        "clear_tab" | "ctab" => codes::CLEAR_TAB_STOP,
+        "clear_all_tabs" | "ctabs" => codes::CLEAR_ALL_TAB_STOPS,
+
+        "move_up_scrl" | "mus" => codes::UP_SCRL,
+        "save_cur" | "save" | "s" => codes::CUR_SAVE,
+        "load_cur" | "load" | "l" => codes::CUR_LOAD,
+
+        "erase_to_end" | "e_" => codes::ERASE_TO_END,
+        "erase_from_start" | "_e" => codes::ERASE_FROM_START,
+        "erase_screen" | "_e_" => codes::ERASE_SCREEN,
+        "erase_all" | "e" => codes::ERASE_ALL,
+        "erase_ln_end" | "el_" => codes::ERASE_TO_LN_END,
+        "erase_ln_start" | "_el" => codes::ERASE_FROM_LN_START,
+        "erase_line" | "erase_ln" | "_el_" | "el" => codes::ERASE_LINE,
+
+        "reset" | "_" => codes::RESET,
+
+        "bold" => codes::BOLD,
+        "faint" | "f" => codes::FAINT,
+        "italic" | "i" => codes::ITALIC,
+        "underline" | "u" => codes::UNDERLINE,
+        "blinking" | "blink" => codes::BLINKING,
+        "inverse" => codes::INVERSE,
+        "invisible" | "invis" => codes::INVISIBLE,
+        "striketrough" | "strike" => codes::STRIKETROUGH,
+        "double_underline" | "dunderline" | "dun" => codes::DOUBLE_UNDERLINE,
+        "overline" | "ol" => codes::OVERLINE,
+        "curly_underline" | "curly" => codes::CURLY_UNDERLINE,
+        "dotted_underline" | "dotted" => codes::DOTTED_UNDERLINE,
+        "dashed_underline" | "dashed" => codes::DASHED_UNDERLINE,
+
+        "_bold" => codes::RESET_BOLD,
+        "_italic" | "_i" => codes::RESET_ITALIC,
+        "_underline" | "_u" => codes::RESET_UNDERLINE,
+        "_blinking" | "_blink" => codes::RESET_BLINKING,
+        "_inverse" => codes::RESET_INVERSE,
+        "_invisible" | "_invis" => codes::RESET_INVISIBLE,
+        "_striketrough" | "_strike" => codes::RESET_STRIKETROUGH,
+        "_overline" | "_ol" => codes::RESET_OVERLINE,
+
+        "black_fg" | "black" | "bl" => codes::BLACK_FG,
+        "white_fg" | "white" | "w" => codes::WHITE_FG,
+        "gray_fg" | "gray" | "gr" => codes::GRAY_FG,
+        "bright_gray_fg" | "bgray" | "bgr" => codes::GRAY_BRIGHT_FG,
+
+        "red_fg" | "red" | "r" => codes::RED_FG,
+        "green_fg" | "green" | "g" => codes::GREEN_FG,
+        "yellow_fg" | "yellow" | "y" => codes::YELLOW_FG,
+        "blue_fg" | "blue" | "b" => codes::BLUE_FG,
+        "magenta_fg" | "magenta" | "m" => codes::MAGENTA_FG,
+        "cyan_fg" | "cyan" | "c" => codes::CYAN_FG,
+
+        "dark_red_fg" | "dred" | "dr" => codes::RED_DARK_FG,
+        "dark_green_fg" | "dgreen" | "dg" => codes::GREEN_DARK_FG,
+        "dark_yellow_fg" | "dyellow" | "dy" => codes::YELLOW_DARK_FG,
+        "dark_blue_fg" | "dblue" | "db" => codes::BLUE_DARK_FG,
+        "dark_magenta_fg" | "dmagenta" | "dm" => codes::MAGENTA_DARK_FG,
+        "dark_cyan_fg" | "dcyan" | "dc" => codes::CYAN_DARK_FG,
+
+        "_fg" => codes::RESET_FG,
+
+        "black_bg" | "blackb" | "blb" => codes::BLACK_BG,
+        "white_bg" | "whiteb" | "wb" => codes::WHITE_BG,
+        "gray_bg" | "grayb" | "grb" => codes::GRAY_BG,
+        "bright_gray_bg" | "bgrayb" | "bgrb" => codes::GRAY_BRIGHT_BG,
+
+        "red_bg" | "redb" | "rb" => codes::RED_BG,
+        "green_bg" | "greenb" | "gb" => codes::GREEN_BG,
+        "yellow_bg" | "yellowb" | "yb" => codes::YELLOW_BG,
+        "blue_bg" | "blueb" | "bb" => codes::BLUE_BG,
+        "magenta_bg" | "magentab" | "mb" => codes::MAGENTA_BG,
+        "cyan_bg" | "cyanb" | "cb" => codes::CYAN_BG,
+
+        "dark_red_bg" | "dredb" | "drb" => codes::RED_DARK_BG,
+        "dark_green_bg" | "dgreenb" | "dgb" => codes::GREEN_DARK_BG,
+        "dark_yellow_bg" | "dyellowb" | "dyb" => codes::YELLOW_DARK_BG,
+        "dark_blue_bg" | "dblueb" | "dbb" => codes::BLUE_DARK_BG,
+        "dark_magenta_bg" | "dmagentab" | "dmb" => codes::MAGENTA_DARK_BG,
+        "dark_cyan_bg" | "dcyanb" | "dcb" => codes::CYAN_DARK_BG,
+
+        "_bg" => codes::RESET_BG,
+
+        "fg" => {
+            let c = match maybe_read_num(i) {
+                Some(c) if (0..256).contains(&c) => c,
+                _ => {
+                    return Err(template_error(format!(
+                    "The '{}' in color format expects value in range 0..256",
+                    s,
+                )))
+                }
+            };
+            owner = codes::fg256!(c);
+            &owner
+        }
+        "bg" => {
+            let c = match maybe_read_num(i) {
+                Some(c) if (0..256).contains(&c) => c,
+                _ => {
+                    return Err(template_error(format!(
+                    "The '{}' in color format expects value in range 0..256",
+                    s,
+                )))
+                }
+            };
+            owner = codes::bg256!(c);
+            &owner
+        }
+        "ucolor" | "uc" if matches!(i.peek(), Some('#')) => {
+            let (r, g, b) = read_hex_color(i)?;
+            owner = codes::underline_rgb!(r, g, b);
+            &owner
+        }
+        "ucolor" | "uc" => {
+            let c = match maybe_read_num(i) {
+                Some(c) if (0..256).contains(&c) => c,
+                _ => {
+                    return Err(template_error(format!(
+                    "The '{}' in color format expects value in range 0..256",
+                    s,
+                )))
+                }
+            };
+            owner = codes::underline256!(c);
+            &owner
+        }
+
+        "_ucolor" | "_uc" => codes::RESET_UNDERLINE_COLOR,
+
+        "line_wrap" | "wrap" => codes::ENABLE_LINE_WRAP,
+        "_line_wrap" | "_wrap" => codes::DISABLE_LINE_WRAP,
+
+        "hide_cursor" | "nocur" => codes::HIDE_CURSOR,
+        "show_cursor" | "_nocur" => codes::SHOW_CURSOR,
+        "save_screen" | "sscr" => codes::SAVE_SCREEN,
+        "load_screen" | "lscr" => codes::LOAD_SCREEN,
+        "alt_buf" | "abuf" => codes::ENABLE_ALTERNATIVE_BUFFER,
+        "_alt_buf" | "_abuf" => codes::DISABLE_ALTERNATIVE_BUFFER,
+
+        "clear" | "cls" => codes::CLEAR,
+        _ => {
+            return Err(template_error(format!(
+                "Unknown color format variable {}",
+                s
+            )))
+        }
+    };
+
+    match i.peek() {
+        Some(' ' | '}') => {}
+        Some(c) => {
+            return Err(template_error(format!(
+                "Invalid character '{}', expected ' ' or '}}'",
+                c
+            )))
+        }
+        None => {
+            return Err(template_error(
+                "Unexpected end, expected ' ' or '}}'".to_owned(),
+            ))
+        }
+    }
+
+    res.push_str(var);
+
+    Ok(())
+}
+
+fn parse_color<I>(res: &mut String, i: &mut Peekable<I>) -> Result<()>
+where
+    I: Iterator<Item = char>,
+{
+    let (r, g, b) = read_hex_color(i)?;
+
+    match i.peek() {
+        Some('_') => {
+            i.next();
+            res.push_str(codes::bg!(r, g, b).as_str());
+            Ok(())
+        }
+        Some(' ' | '}') => {
+            res.push_str(codes::fg!(r, g, b).as_str());
+            Ok(())
+        }
+        Some('u') => {
+            i.next();
+            res.push_str(codes::underline_rgb!(r, g, b).as_str());
+            Ok(())
+        }
+        Some(c) => Err(template_error(format!(
+            "Invalid character, didn't expect '{}'",
+            c
+        ))),
+        None => Err(template_error(
+            "color format not ended with '}}'".to_owned(),
+        )),
+    }
+}
+
+/// Reads hex color starting with `#`.
+fn read_hex_color<I>(i: &mut Peekable<I>) -> Result<(u8, u8, u8)>
+where
+    I: Iterator<Item = char>,
+{
+    i.next();
+    let mut s = String::new();
+
+    while let Some(c) = i.peek() {
+        match c {
+            c if c.is_ascii_hexdigit() => {
+                s.push(*c);
+                i.next();
+            }
+            '}' | ' ' | '_' | 'u' => break,
+            _ => {
+                return Err(template_error(format!(
+                    "Invalid hex color, didn't expect character '{}'",
+                    c
+                )))
+            }
+        }
+    }
+
+    if !matches!(s.len(), 1 | 2 | 3 | 4 | 6 | 8) {
+        return Err(template_error(
+            "Invalid hex color length, must be 1, 2, 3, 4, 6 or 8".to_owned(),
+        ));
+    }
+
+    let Some(Rgb { r, g, b }) = Rgb::parse_hex(&s) else {
+        return Err(template_error("Invalid hex color"));
+    };
+
+    Ok((r, g, b))
+}
+
+fn maybe_read_num<I>(i: &mut Peekable<I>) -> Option<i32>
+where
+    I: Iterator<Item = char>,
+{
+    let mut s = String::new();
+    read_while(&mut s, i, |c| c.is_ascii_digit());
+    s.parse().ok()
+}
+
+fn read_while<I, F>(res: &mut String, i: &mut Peekable<I>, f: F)
+where
+    I: Iterator<Item = char>,
+    F: Fn(char) -> bool,
+{
+    while let Some(c) = i.peek() {
+        if f(*c) {
+            res.push(*c);
+            i.next();
+        } else {
+            break;
+        }
+    }
+}
+
+fn template_error(msg: impl Into<String>) -> Error {
+    Error::InvalidTemplate(msg.into())
+}
@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+
+use crate::fill::char_width;
+
+use super::TermTextSpans;
+
+/// First strong isolate. Text until [`PDI`] is isolated from the surrounding
+/// text and its direction is detected from its first strong character.
+pub const FSI: char = '\u{2068}';
+/// Pop directional isolate. Ends isolate started with [`FSI`].
+pub const PDI: char = '\u{2069}';
+
+/// How right to left text (e.g. Arabic and Hebrew) is handled when the text
+/// is split into lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BidiPolicy {
+    /// Keep the text in logical order and let the terminal reorder it. This
+    /// is correct for terminals that implement the bidi algorithm.
+    #[default]
+    Logical,
+    /// Keep the text in logical order, but wrap the right to left runs on
+    /// each line in isolates ([`FSI`] and [`PDI`]), so that they don't
+    /// reorder the surrounding text (e.g. punctuation and indents).
+    Isolate,
+    /// Reverse the right to left runs on each line, so that they are shown
+    /// correctly on terminals that don't implement the bidi algorithm.
+    Visual,
+}
+
+/// Checks if the character is from right to left script (e.g. Arabic or
+/// Hebrew).
+pub fn is_rtl(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0590..=0x08ff
+            | 0xfb1d..=0xfdff
+            | 0xfe70..=0xfeff
+            | 0x10800..=0x10fff
+            | 0x1e800..=0x1efff
+    )
+}
+
+/// Applies the bidi policy to single line of text. Control sequences are
+/// kept as they are and right to left runs don't continue across them.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::term_text::{apply_bidi, BidiPolicy};
+///
+/// assert_eq!(apply_bidi("id: אבג", BidiPolicy::Visual), "id: גבא");
+/// assert_eq!(
+///     apply_bidi("id: אבג", BidiPolicy::Isolate),
+///     "id: \u{2068}אבג\u{2069}"
+/// );
+/// ```
+pub fn apply_bidi(line: &str, policy: BidiPolicy) -> Cow<'_, str> {
+    if policy == BidiPolicy::Logical || !line.chars().any(is_rtl) {
+        return line.into();
+    }
+
+    let mut res = String::with_capacity(line.len());
+    for span in TermTextSpans::new(line) {
+        if span.is_control() {
+            res += span.text();
+            continue;
+        }
+
+        let mut text = span.text();
+        while let Some((start, end)) = next_rtl_run(text) {
+            res += &text[..start];
+            let run = &text[start..end];
+            match policy {
+                BidiPolicy::Isolate => {
+                    res.push(FSI);
+                    res += run;
+                    res.push(PDI);
+                }
+                _ => push_reversed(&mut res, run),
+            }
+            text = &text[end..];
+        }
+        res += text;
+    }
+
+    res.into()
+}
+
+/// Finds the byte range of the next right to left run. The run may contain
+/// spaces between the right to left characters.
+fn next_rtl_run(s: &str) -> Option<(usize, usize)> {
+    let start = s.find(is_rtl)?;
+    let mut end = start;
+    for (i, c) in s[start..].char_indices() {
+        if is_rtl(c) {
+            end = start + i + c.len_utf8();
+        } else if c != ' ' {
+            break;
+        }
+    }
+    Some((start, end))
+}
+
+/// Appends the characters in reversed order. Zero width characters (e.g.
+/// combining marks) are kept after their base character.
+fn push_reversed(res: &mut String, s: &str) {
+    let mut clusters: Vec<&str> = vec![];
+    let mut start = 0;
+    for (i, c) in s.char_indices().skip(1) {
+        if char_width(c) != 0 {
+            clusters.push(&s[start..i]);
+            start = i;
+        }
+    }
+    clusters.push(&s[start..]);
+    res.extend(clusters.into_iter().rev());
+}
@@ -0,0 +1,127 @@
+use crate::fill::{char_width, str_width};
+
+use super::TermTextSpans;
+
+/// Visual line of text as shown by terminal. See [`layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Line {
+    /// Byte offset of the start of the line in the text.
+    pub start: usize,
+    /// Byte offset of the end of the line in the text. The newline that ends
+    /// the line is not part of the line.
+    pub end: usize,
+    /// Display width of the line in columns.
+    pub width: usize,
+    /// `true` if the line ends because the next character doesn't fit on
+    /// the line (soft wrap). `false` if it ends with newline or with the
+    /// text.
+    pub wrapped: bool,
+}
+
+impl Line {
+    /// Gets the text of the line.
+    pub fn text<'a>(&self, text: &'a str) -> &'a str {
+        &text[self.start..self.end]
+    }
+
+    /// Gets the column of the byte offset within this line. Offsets outside
+    /// of the line are clamped to the line.
+    ///
+    /// # Panics
+    /// If the offset is not on char boundary.
+    pub fn column(&self, text: &str, offset: usize) -> usize {
+        let end = offset.clamp(self.start, self.end);
+        display_width(&text[self.start..end])
+    }
+}
+
+/// Splits the text into lines as they would be shown by terminal with the
+/// given width. Lines are broken at newlines and where the next character
+/// doesn't fit on the line (terminals move wide characters that don't fit
+/// to the next line). Control sequences have zero width. If the width is
+/// `0`, lines are broken only at newlines.
+///
+/// There is always at least one line. The text is expected to be printed
+/// from the start of the line.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::term_text::{layout, position, Line};
+///
+/// let text = "\x1b[1mhello\x1b[0m there\nworld";
+/// let lines = layout(text, 8);
+/// assert_eq!(lines.len(), 3);
+/// assert_eq!(lines[0].text(text), "\x1b[1mhello\x1b[0m th");
+/// assert_eq!(lines[0].width, 8);
+/// assert!(lines[0].wrapped);
+/// assert_eq!(lines[1].text(text), "ere");
+/// assert_eq!(lines[2].text(text), "world");
+///
+/// // Byte offset of `w` is on the third row in the first column.
+/// assert_eq!(position(text, &lines, text.find('w').unwrap()), (2, 0));
+/// ```
+pub fn layout(text: &str, width: usize) -> Vec<Line> {
+    let width = if width == 0 { usize::MAX } else { width };
+    let mut lines = vec![];
+    let mut cur = Line::default();
+    let mut pos = 0;
+
+    for span in TermTextSpans::new(text) {
+        let s = span.text();
+        if span.is_control() {
+            if s == "\n" {
+                cur.end = pos;
+                lines.push(cur);
+                cur = Line {
+                    start: pos + 1,
+                    ..Default::default()
+                };
+            }
+            pos += s.len();
+            continue;
+        }
+
+        for (i, c) in s.char_indices() {
+            let w = char_width(c);
+            if w != 0 && cur.width != 0 && cur.width + w > width {
+                cur.end = pos + i;
+                cur.wrapped = true;
+                lines.push(cur);
+                cur = Line {
+                    start: pos + i,
+                    ..Default::default()
+                };
+            }
+            cur.width += w;
+        }
+        pos += s.len();
+    }
+
+    cur.end = pos;
+    lines.push(cur);
+    lines
+}
+
+/// Gets the row and column of the byte offset in the text with the given
+/// lines (see [`layout`]). Offset at the end of wrapped line is at the start
+/// of the next line, because that is where the cursor is after printing the
+/// text before the offset.
+///
+/// # Panics
+/// If the offset is not on char boundary.
+pub fn position(text: &str, lines: &[Line], offset: usize) -> (usize, usize) {
+    let row = lines
+        .iter()
+        .rposition(|l| l.start <= offset)
+        .unwrap_or_default();
+    let col = lines.get(row).map_or(0, |l| l.column(text, offset));
+    (row, col)
+}
+
+/// Gets the display width of text that may contain control sequences.
+fn display_width(text: &str) -> usize {
+    TermTextSpans::new(text)
+        .filter(|s| !s.is_control())
+        .map(|s| str_width(s.text()))
+        .sum()
+}
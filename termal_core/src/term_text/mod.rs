@@ -1,10 +1,26 @@
-use std::{borrow::Cow, cell::Cell, fmt::Display};
-
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    fmt::Display,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{fill::char_width, Style};
+
+mod bidi;
+mod layout;
+mod sanitize;
+mod strip_stream;
 mod term_text_metadata;
 mod term_text_span;
 mod term_text_spans;
 
-pub use self::{term_text_metadata::*, term_text_span::*, term_text_spans::*};
+use self::term_text_span::{char_count, control_ranges};
+
+pub use self::{
+    bidi::*, layout::*, sanitize::*, strip_stream::*, term_text_metadata::*,
+    term_text_span::*, term_text_spans::*,
+};
 
 /// String with control escape sequences.
 ///
@@ -120,51 +136,197 @@ impl<'a> TermText<'a> {
         TermTextSpans::new(&self.text)
     }
 
-    /// Strips the string of control sequences
+    /// Checks if the text contains characters from right to left script
+    /// (e.g. Arabic or Hebrew). Control sequences are ignored. See
+    /// [`apply_bidi`].
+    pub fn contains_rtl(&self) -> bool {
+        self.spans()
+            .filter(|s| !s.is_control())
+            .any(|s| s.text().chars().any(is_rtl))
+    }
+
+    /// Splits the text into lines as they would be shown by terminal with
+    /// the given width. See [`layout`].
+    pub fn layout(&self, width: usize) -> Vec<Line> {
+        layout(&self.text, width)
+    }
+
+    /// Gets the part of the text that is displayed in the given range of
+    /// columns. The result starts with the SGR codes needed to reproduce the
+    /// style that is active at the start of the range and it ends with reset
+    /// of the style, so that the style doesn't leak out of the slice.
+    ///
+    /// Control sequences within the range are kept. Other control sequences
+    /// (e.g. hyperlinks) before the range are not reproduced. Wide
+    /// characters that are cut by the range are replaced with spaces, so
+    /// the width of the result is exactly the width of the range (if the
+    /// text is long enough).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use termal_core::term_text::TermText;
+    ///
+    /// let text = TermText::new("ab\x1b[1mcd\x1b[0mef");
+    /// assert_eq!(text.slice_display(1..3).as_str(), "b\x1b[1mc\x1b[0m");
+    /// assert_eq!(text.slice_display(3..).as_str(), "\x1b[1md\x1b[0mef");
+    /// ```
+    pub fn slice_display(
+        &self,
+        range: impl RangeBounds<usize>,
+    ) -> TermText<'static> {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e.saturating_add(1),
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => usize::MAX,
+        };
+
+        let mut res = String::new();
+        let mut style = Style::new();
+        let mut started = false;
+        let mut col = 0;
+
+        'spans: for span in self.spans() {
+            if span.is_control() {
+                if col >= end {
+                    break;
+                }
+                // SGR codes before the first character are part of the
+                // initial style.
+                let sgr = style.apply_sgr(span.text());
+                if col >= start && (started || !sgr) {
+                    start_slice(&mut res, &mut started, &style);
+                    res += span.text();
+                }
+                continue;
+            }
+
+            for c in span.text().chars() {
+                let w = char_width(c);
+                if col + w > end {
+                    if col < end && col >= start {
+                        // Wide character cut at the end.
+                        start_slice(&mut res, &mut started, &style);
+                        res.extend((col..end).map(|_| ' '));
+                    }
+                    break 'spans;
+                }
+                col += w;
+                if col <= start {
+                    continue;
+                }
+                start_slice(&mut res, &mut started, &style);
+                if col - w < start {
+                    // Wide character cut at the start.
+                    res.extend((start..col).map(|_| ' '));
+                } else {
+                    res.push(c);
+                }
+            }
+        }
+
+        if !style.is_default() && !res.is_empty() {
+            res += &style.reset_code();
+        }
+        TermText::new(res)
+    }
+
+    /// Gets the style that is active at the end of the text if the text is
+    /// printed in the default style. Only SGR codes are considered.
+    pub fn end_style(&self) -> Style {
+        let mut style = Style::new();
+        for span in self.spans().filter(|s| s.is_control()) {
+            style.apply_sgr(span.text());
+        }
+        style
+    }
+
+    /// Checks whether the text leaves some style open (see
+    /// [`TermText::end_style`]) and if it does, appends the shortest code
+    /// that resets it. This prevents the style from bleeding into the text
+    /// that is printed after this text.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use termal_core::term_text::TermText;
+    ///
+    /// let mut text = TermText::new("\x1b[1mbold");
+    /// text.ensure_reset();
+    /// assert_eq!(text.as_str(), "\x1b[1mbold\x1b[0m");
+    ///
+    /// let mut text = TermText::new("\x1b[1mbold\x1b[0m");
+    /// text.ensure_reset();
+    /// assert_eq!(text.as_str(), "\x1b[1mbold\x1b[0m");
+    /// ```
+    pub fn ensure_reset(&mut self) {
+        let code = self.end_style().reset_code();
+        if code.is_empty() {
+            return;
+        }
+        if let Some(mut meta) = self.metadata.get() {
+            meta.add_length(&code);
+            self.metadata.set(Some(meta));
+        }
+        self.text.to_mut().push_str(&code);
+    }
+
+    /// Splits the text at the given column. Both parts contain the codes
+    /// needed to reproduce the styles. See [`TermText::slice_display`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use termal_core::term_text::TermText;
+    ///
+    /// let text = TermText::new("\x1b[31mhello\x1b[0m");
+    /// let (a, b) = text.split_at_display(2);
+    /// assert_eq!(a.as_str(), "\x1b[31mhe\x1b[0m");
+    /// assert_eq!(b.as_str(), "\x1b[31mllo\x1b[0m");
+    /// ```
+    pub fn split_at_display(
+        &self,
+        width: usize,
+    ) -> (TermText<'static>, TermText<'static>) {
+        (self.slice_display(..width), self.slice_display(width..))
+    }
+
+    /// Strips the string of control sequences. The metadata is cached if it
+    /// is not already cached.
     #[inline]
     pub fn strip_control(&self) -> String {
+        let cached = self.metadata.get();
         let mut res = String::new();
-        if let Some(meta) = self.metadata.get() {
+        if let Some(meta) = cached {
             res.reserve_exact(self.text.len() - meta.control_bytes);
-            for span in self.spans().filter(|s| !s.is_control()) {
-                res.push_str(span.text());
-            }
-        } else {
-            let mut meta = TermTextMetadata::default();
-
-            for span in self.spans().filter(|s| !s.is_control()) {
-                res.push_str(span.text());
-                meta.chars += span.chars();
-                if span.is_control() {
-                    meta.control_chars += span.chars();
-                    meta.control_bytes += span.text().len();
-                }
+        }
+
+        let mut meta = TermTextMetadata::default();
+        let mut last = 0;
+        for r in control_ranges(&self.text) {
+            res.push_str(&self.text[last..r.start]);
+            if cached.is_none() {
+                meta.control_chars += char_count(&self.text[r.clone()]);
+                meta.control_bytes += r.len();
             }
+            last = r.end;
         }
+        res.push_str(&self.text[last..]);
 
+        if cached.is_none() {
+            meta.chars = char_count(&self.text);
+            self.metadata.set(Some(meta));
+        }
         res
     }
 
     /// Converts the text to string. This will also cache the metadata if it is
     /// not already cached. To avoid caching use `.as_str().to_string()`
     pub fn to_string_cache(&self) -> String {
-        if self.metadata.get().is_some() {
-            self.to_string()
-        } else {
-            let mut res = String::with_capacity(self.text.len());
-
-            let mut meta = TermTextMetadata::default();
-            for span in self.spans() {
-                meta.chars += span.chars();
-                res.push_str(span.text());
-                if span.is_control() {
-                    meta.control_bytes += span.text().len();
-                    meta.control_chars += span.chars();
-                }
-            }
-
-            res
-        }
+        self.get_metadata();
+        self.to_string()
     }
 
     /// Get the unerlying [`Cow`]
@@ -174,22 +336,47 @@ impl<'a> TermText<'a> {
 
     /// Get owned version of the term text.
     pub fn to_owned(self) -> TermText<'static> {
-        match self.text {
-            Cow::Owned(s) => TermText {
-                text: Cow::Owned(s),
-                metadata: self.metadata,
-            },
-            Cow::Borrowed(_) => {
-                let text = self.to_string();
-                TermText {
-                    text: Cow::Owned(text),
-                    metadata: self.metadata,
-                }
-            }
+        TermText {
+            text: Cow::Owned(self.text.into_owned()),
+            metadata: self.metadata,
         }
     }
 }
 
+/// Concatenates the parts so that styles of one part don't bleed into the
+/// following parts. Each part that leaves some style open is followed by the
+/// shortest code that resets it (see [`TermText::ensure_reset`]).
+///
+/// # Examples
+/// ```rust
+/// use termal_core::term_text::concat_styled;
+///
+/// let text = concat_styled(["\x1b[31mred", "plain", "\x1b[1mbold\x1b[0m"]);
+/// assert_eq!(text.as_str(), "\x1b[31mred\x1b[0mplain\x1b[1mbold\x1b[0m");
+/// ```
+pub fn concat_styled<I>(parts: I) -> TermText<'static>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut res = String::new();
+    for part in parts {
+        let part = TermText::new(part.as_ref());
+        res += part.as_str();
+        res += &part.end_style().reset_code();
+    }
+    TermText::new(res)
+}
+
+/// Writes the initial style of slice if the slice is not started yet. See
+/// [`TermText::slice_display`].
+fn start_slice(res: &mut String, started: &mut bool, style: &Style) {
+    if !*started {
+        *started = true;
+        *res += &style.code();
+    }
+}
+
 impl AsRef<str> for TermText<'_> {
     fn as_ref(&self) -> &str {
         self.as_str()
@@ -198,7 +385,7 @@ impl AsRef<str> for TermText<'_> {
 
 impl Display for TermText<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.as_str())
+        f.write_str(self.as_str())
     }
 }
 
@@ -0,0 +1,87 @@
+use std::fmt::{Display, Write};
+
+use super::TermTextSpans;
+
+/// Wrapper that sanitizes the displayed value with [`sanitize`]. Use it to
+/// interpolate untrusted data into colorized output, so that the data can't
+/// inject control sequences.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::{codes, Sanitized};
+///
+/// let user = "\x1b[2Jbob";
+/// assert_eq!(
+///     format!("{}{}{}", codes::BOLD, Sanitized(user), codes::RESET),
+///     "\x1b[1m\\x1b[2Jbob\x1b[0m",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Sanitized<T>(pub T);
+
+/// Escapes all the control characters in the string, so that it can be
+/// safely printed to terminal. The escaped sequences are visible (e.g. `ESC`
+/// is shown as `\x1b`). Newlines and tabs are kept. C1 control characters
+/// (`U+0080..U+00A0`) are also escaped, because some terminals interpret
+/// them.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::sanitize;
+///
+/// assert_eq!(sanitize("a\x1b[31mb\rc\n"), "a\\x1b[31mb\\rc\n");
+/// ```
+pub fn sanitize(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    _ = write_sanitized(&mut res, s);
+    res
+}
+
+/// Removes all the control sequences and control characters from the
+/// string. Newlines and tabs are kept. Control sequences are detected the
+/// same way as in [`super::TermText`], so the whole sequence is removed.
+///
+/// # Examples
+/// ```rust
+/// use termal_core::strip_unsafe;
+///
+/// assert_eq!(strip_unsafe("a\x1b[31mb\rc\n"), "abc\n");
+/// ```
+pub fn strip_unsafe(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for span in TermTextSpans::new(s) {
+        if span.is_control() {
+            res.extend(span.text().chars().filter(|c| is_kept(*c)));
+        } else {
+            res.extend(span.text().chars().filter(|c| !is_c1(*c)));
+        }
+    }
+    res
+}
+
+fn write_sanitized(res: &mut impl Write, s: &str) -> std::fmt::Result {
+    for c in s.chars() {
+        match c {
+            c if is_kept(c) => res.write_char(c)?,
+            '\r' => res.write_str("\\r")?,
+            c if c.is_ascii_control() => write!(res, "\\x{:02x}", c as u32)?,
+            c if is_c1(c) => write!(res, "\\u{{{:x}}}", c as u32)?,
+            c => res.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+fn is_kept(c: char) -> bool {
+    matches!(c, '\n' | '\t')
+}
+
+fn is_c1(c: char) -> bool {
+    ('\u{80}'..'\u{a0}').contains(&c)
+}
+
+impl<T: Display> Display for Sanitized<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_sanitized(f, &self.0.to_string())
+    }
+}
@@ -0,0 +1,228 @@
+use std::io::{self, Read, Write};
+
+use crate::codes;
+
+/// Size of the buffer used by [`strip_stream`].
+const BUFFER_SIZE: usize = 8192;
+
+/// Writer that removes control sequences from the data written to it and
+/// writes the rest to the inner writer. The result is the same as with
+/// [`super::strip_unsafe`]: newlines and tabs are kept and C1 control
+/// characters are removed.
+///
+/// Sequences may be split across multiple writes. Incomplete sequence at the
+/// end of the data is removed.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Write;
+/// use termal_core::term_text::StripWriter;
+///
+/// let mut w = StripWriter::new(vec![]);
+/// w.write_all(b"a\x1b[3")?;
+/// w.write_all(b"1mb\x1b]0;ti")?;
+/// w.write_all(b"tle\x1b\\c")?;
+/// w.write_all(b"\x1b]0;bel\x07\n")?;
+/// assert_eq!(w.into_inner(), b"abc\n");
+/// # Ok::<_, std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct StripWriter<W: Write> {
+    inner: W,
+    state: State,
+    written: u64,
+}
+
+/// State of the parser of control sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Text,
+    /// After escape character.
+    Esc,
+    /// In CSI sequence.
+    Csi,
+    /// In nF escape sequence.
+    Nf,
+    /// In string sequence (DCS, PM or APC). `true` if the last byte was
+    /// escape.
+    Str(bool),
+    /// In OSC sequence. It may also end with BEL. `true` if the last byte was
+    /// escape.
+    Osc(bool),
+    /// After SS2 or SS3, the next char is part of the sequence. The value
+    /// is the number of remaining bytes of the char (`0` if it didn't start
+    /// yet).
+    Ss(usize),
+    /// After the first byte of two byte UTF-8 char that may be C1 control
+    /// character.
+    C1Lead,
+}
+
+/// First byte of C1 control characters (`U+0080..U+00A0`) in UTF-8.
+const C1_LEAD: u8 = 0xc2;
+
+/// Removes all control sequences from the data read from `reader` and
+/// writes the rest to `writer`. Only small fixed buffer is used, so the data
+/// may be arbitrarily large. Returns the number of bytes written. See
+/// [`StripWriter`].
+///
+/// # Examples
+/// ```no_run
+/// use std::process::{Command, Stdio};
+/// use termal_core::term_text::strip_stream;
+///
+/// let mut child = Command::new("ls")
+///     .arg("--color=always")
+///     .stdout(Stdio::piped())
+///     .spawn()?;
+/// strip_stream(child.stdout.take().unwrap(), std::io::stdout())?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn strip_stream(
+    mut reader: impl Read,
+    writer: impl Write,
+) -> io::Result<u64> {
+    let mut w = StripWriter::new(writer);
+    let mut buf = [0; BUFFER_SIZE];
+    loop {
+        let len = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        w.write_all(&buf[..len])?;
+    }
+    w.flush()?;
+    Ok(w.written())
+}
+
+impl<W: Write> StripWriter<W> {
+    /// Creates writer that strips control sequences and writes the rest to
+    /// `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            state: State::Text,
+            written: 0,
+        }
+    }
+
+    /// Gets the number of bytes written to the inner writer.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Checks whether the data written so far ends inside control sequence.
+    pub fn is_in_sequence(&self) -> bool {
+        self.state != State::Text
+    }
+
+    /// Gets reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes the text at the start of the data. Returns the length of the
+    /// text.
+    fn write_text(&mut self, data: &[u8]) -> io::Result<usize> {
+        let len = data
+            .iter()
+            .position(|&b| {
+                (b < 0x20 && !matches!(b, b'\n' | b'\t'))
+                    || b == 0x7f
+                    || b == C1_LEAD
+            })
+            .unwrap_or(data.len());
+        self.write_inner(&data[..len])?;
+        Ok(len)
+    }
+
+    fn write_inner(&mut self, data: &[u8]) -> io::Result<()> {
+        self.inner.write_all(data)?;
+        self.written += data.len() as u64;
+        Ok(())
+    }
+
+    /// Moves the state by the given byte of control sequence.
+    fn next_state(&self, b: u8) -> State {
+        match self.state {
+            State::Text if b == codes::ESC as u8 => State::Esc,
+            State::Text if b == C1_LEAD => State::C1Lead,
+            // Single char control sequence
+            State::Text => State::Text,
+            State::Esc => match b {
+                // DCS | PM | APC
+                0x50 | 0x5e | 0x5f => State::Str(false),
+                // OSC
+                0x5d => State::Osc(false),
+                0x5b => State::Csi,
+                // SS2 | SS3
+                0x4e | 0x4f => State::Ss(0),
+                // nF escape sequence
+                0x20..=0x2f => State::Nf,
+                // Two char escape sequence
+                _ => State::Text,
+            },
+            State::Csi if (0x40..0x7f).contains(&b) => State::Text,
+            State::Nf if (0x30..0x7f).contains(&b) => State::Text,
+            State::Csi | State::Nf => self.state,
+            State::Str(true) if b == b'\\' => State::Text,
+            State::Str(_) => State::Str(b == codes::ESC as u8),
+            State::Osc(true) if b == b'\\' => State::Text,
+            State::Osc(_) if b == codes::BELL as u8 => State::Text,
+            State::Osc(_) => State::Osc(b == codes::ESC as u8),
+            State::Ss(0) => match b {
+                0xf0.. => State::Ss(3),
+                0xe0.. => State::Ss(2),
+                0xc0.. => State::Ss(1),
+                _ => State::Text,
+            },
+            State::Ss(1) | State::C1Lead => State::Text,
+            State::Ss(n) => State::Ss(n - 1),
+        }
+    }
+}
+
+impl<W: Write> Write for StripWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut pos = 0;
+        while pos < buf.len() {
+            if self.state == State::Text {
+                pos += self.write_text(&buf[pos..])?;
+                if pos == buf.len() {
+                    break;
+                }
+            }
+
+            let b = buf[pos];
+            match self.state {
+                // Invalid escape sequence. The escape is control sequence and
+                // the next byte is processed as text.
+                State::Esc if !(0x20..0x7f).contains(&b) => {
+                    self.state = State::Text;
+                    continue;
+                }
+                // Not C1 control character, the lead byte is text.
+                State::C1Lead if !(0x80..0xa0).contains(&b) => {
+                    self.write_inner(&[C1_LEAD])?;
+                    self.state = State::Text;
+                    continue;
+                }
+                _ => {}
+            }
+            self.state = self.next_state(b);
+            pos += 1;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
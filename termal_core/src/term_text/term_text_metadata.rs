@@ -1,4 +1,4 @@
-use super::TermTextSpans;
+use super::term_text_span::{char_count, control_ranges};
 
 /// Information about text with control sequences.
 #[derive(Debug, Copy, Clone, Default)]
@@ -21,12 +21,10 @@ impl TermTextMetadata {
 
     /// Add to the metadata with metadata of the given text.
     pub fn add_length(&mut self, text: &str) {
-        for span in TermTextSpans::new(text) {
-            self.chars += span.chars();
-            if span.is_control() {
-                self.control_chars += span.chars();
-                self.control_bytes += span.text().len();
-            }
+        self.chars += char_count(text);
+        for r in control_ranges(text) {
+            self.control_chars += char_count(&text[r.clone()]);
+            self.control_bytes += r.len();
         }
     }
 
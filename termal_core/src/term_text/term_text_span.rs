@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use crate::codes;
 
 /// Span of single plain text or single control sequence. Note that all
@@ -29,125 +31,155 @@ impl<'a> TermTextSpan<'a> {
     /// # Returns
     /// The span and rest of the string.
     pub fn create(text: &str) -> (TermTextSpan<'_>, &str) {
-        let found = text
-            .char_indices()
-            .chain([(usize::MAX, '\0')])
-            .enumerate()
-            .find(|(_, (_, c))| c.is_ascii_control());
-        let Some((idx, (ind, chr))) = found else {
-            unreachable!();
+        let bytes = text.as_bytes();
+        let len = match find_control(bytes) {
+            // The whole text is not control sequence
+            None => return Self::split_from(text, text.len(), false),
+            // The first part is not control sequence
+            Some(i) if i != 0 => return Self::split_from(text, i, false),
+            Some(_) => Self::control_len(bytes),
         };
+        Self::split_from(text, len, true)
+    }
 
-        // The whole text is not control sequence
-        if ind == usize::MAX && chr == '\0' {
-            return (
-                TermTextSpan {
-                    text,
-                    chars: idx,
-                    control: false,
-                },
-                "",
-            );
-        }
-
-        // The first part is not control sequence
-        if ind != 0 {
-            return Self::split_from(text, ind, idx, false);
-        }
-
+    /// Gets the length of the control sequence at the start of the bytes.
+    fn control_len(bytes: &[u8]) -> usize {
         // Single char control sequence
-        if chr != codes::ESC {
-            return Self::split_from(text, 1, 1, true);
+        if bytes[0] != codes::ESC as u8 {
+            return 1;
         }
 
         // Only the escape char. This is invalid escape sequence but it is a
         // control sequence.
-        let Some(chr) = text.chars().nth(1) else {
-            return Self::split_from(text, 1, 1, true);
+        let Some(&chr) = bytes.get(1) else {
+            return 1;
         };
 
-        match chr as u32 {
-            // DCS | OSC | PM | APC
-            0x50 | 0x5d | 0x5e | 0x5f => Self::end_with_str(text, "\x1b\x5c"),
+        match chr {
+            // OSC, may also end with BEL
+            0x5d => {
+                let st = memchr::memmem::find(bytes, codes::ST.as_bytes())
+                    .map_or(bytes.len(), |p| p + codes::ST.len());
+                memchr::memchr(codes::BELL as u8, &bytes[..st])
+                    .map_or(st, |p| p + 1)
+            }
+            // DCS | PM | APC
+            0x50 | 0x5e | 0x5f => {
+                memchr::memmem::find(bytes, codes::ST.as_bytes())
+                    .map_or(bytes.len(), |p| p + codes::ST.len())
+            }
             // CSI
-            0x5b => Self::end_with_pat(text, 2, |c| {
-                (0x40..0x7f).contains(&(c as u32))
-            }),
+            0x5b => end_with(bytes, 2, 0x40..0x7f),
             // SS2 | SS3
-            0x4e | 0x4f => Self::split_from(text, 3, 3, true),
+            0x4e | 0x4f => bytes
+                .get(2)
+                .map_or(2, |&b| 2 + utf8_len(b).min(bytes.len() - 2)),
             // Two char C1 escape sequence
-            0x40..=0x5f => Self::split_from(text, 2, 2, true),
+            0x40..=0x5f => 2,
+            // nF escape sequence (e.g. character set designation `ESC ( 0`)
+            0x20..=0x2f => end_with(bytes, 2, 0x30..0x7f),
+            // Other two char escape sequence (e.g. `ESC 7`)
+            0x30..=0x7e => 2,
             // Invalid escape sequence
-            _ => Self::split_from(text, 1, 1, true),
+            _ => 1,
         }
     }
 
     fn split_from(
         text: &str,
         ind: usize,
-        chars: usize,
         control: bool,
     ) -> (TermTextSpan<'_>, &str) {
+        let (text, rest) = text.split_at(ind);
         (
             TermTextSpan {
-                text: &text[..ind],
-                chars,
+                text,
+                chars: char_count(text),
                 control,
             },
-            &text[ind..],
+            rest,
         )
     }
+}
+
+/// Gets the byte ranges of the control sequences in the text. This is faster
+/// than iterating over all the spans when only the control sequences are
+/// needed.
+pub(crate) fn control_ranges(
+    text: &str,
+) -> impl Iterator<Item = Range<usize>> + '_ {
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let start = pos + find_control(&bytes[pos..])?;
+        pos = start + TermTextSpan::control_len(&bytes[start..]);
+        Some(start..pos)
+    })
+}
+
+/// Finds the first ASCII control character.
+fn find_control(bytes: &[u8]) -> Option<usize> {
+    const LO: u64 = u64::from_ne_bytes([0x01; 8]);
+    const HI: u64 = u64::from_ne_bytes([0x80; 8]);
+    const SPACE: u64 = u64::from_ne_bytes([0x20; 8]);
+    const DEL: u64 = u64::from_ne_bytes([0x7f; 8]);
+
+    // Check 8 bytes at once. Bytes with the highest bit set (non ASCII) are
+    // never control characters.
+    let has_control = |w: u64| {
+        let lt_space = w.wrapping_sub(SPACE) & !w & HI;
+        let del = (w ^ DEL).wrapping_sub(LO) & !(w ^ DEL) & HI;
+        lt_space | del != 0
+    };
 
-    fn end_with_str<'b>(
-        text: &'b str,
-        pat: &str,
-    ) -> (TermTextSpan<'b>, &'b str) {
-        if let Some(p) = text.find(pat) {
-            let ind = p + pat.len();
-            Self::split_from(text, ind, text[..ind].chars().count(), true)
-        } else {
-            (
-                TermTextSpan {
-                    text,
-                    chars: text.chars().count(),
-                    control: true,
-                },
-                "",
-            )
+    let mut words = bytes.chunks_exact(8);
+    for (i, word) in words.by_ref().enumerate() {
+        if has_control(u64::from_ne_bytes(word.try_into().unwrap())) {
+            if let Some(p) = word.iter().position(is_control) {
+                return Some(i * 8 + p);
+            }
         }
     }
+    let rem = words.remainder();
+    rem.iter()
+        .position(is_control)
+        .map(|p| bytes.len() - rem.len() + p)
+}
 
-    fn end_with_pat(
-        text: &str,
-        skip: usize,
-        f: impl Fn(char) -> bool,
-    ) -> (TermTextSpan, &str) {
-        let end = text[skip..]
-            .char_indices()
-            .chain([(usize::MAX, '0')])
-            .enumerate()
-            .find(|(_, (_, c))| f(*c));
-        let Some((idx, (ind, c))) = end else {
-            unreachable!();
-        };
+fn is_control(b: &u8) -> bool {
+    *b < 0x20 || *b == 0x7f
+}
 
-        if ind == usize::MAX && c == '0' {
-            // sequence is missing the final character
-            (
-                TermTextSpan {
-                    text,
-                    chars: idx + skip,
-                    control: true,
-                },
-                "",
-            )
-        } else {
-            Self::split_from(
-                text,
-                ind + c.len_utf8() + skip,
-                idx + 1 + skip,
-                true,
-            )
-        }
+/// Gets the length of sequence that ends with byte in the range `end`. The
+/// first `skip` bytes are not checked. If the sequence is missing the final
+/// byte, it is the whole text.
+fn end_with(bytes: &[u8], skip: usize, end: Range<u8>) -> usize {
+    bytes[skip..]
+        .iter()
+        .position(|b| end.contains(b))
+        .map_or(bytes.len(), |p| p + skip + 1)
+}
+
+/// Counts the chars in the string. This is faster than `chars().count()`.
+pub(crate) fn char_count(s: &str) -> usize {
+    // Count all bytes that are not UTF-8 continuation bytes. Counting in
+    // chunks that fit into `u8` allows the compiler to vectorize it.
+    if s.len() < 32 {
+        return s.bytes().filter(|&b| (b as i8) >= -0x40).count();
+    }
+    s.as_bytes()
+        .chunks(u8::MAX as usize)
+        .map(|c| c.iter().fold(0, |n: u8, &b| n + ((b as i8) >= -0x40) as u8))
+        .map(usize::from)
+        .sum()
+}
+
+/// Gets the length of UTF-8 char from its first byte.
+fn utf8_len(b: u8) -> usize {
+    match b {
+        0xf0.. => 4,
+        0xe0.. => 3,
+        0xc0.. => 2,
+        _ => 1,
     }
 }
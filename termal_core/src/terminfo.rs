@@ -0,0 +1,375 @@
+//! Support for the terminfo database.
+//!
+//! Codes in [`crate::codes`] are the xterm codes which are supported by most
+//! modern terminals. Some terminals (e.g. `screen` or the `linux` console)
+//! use different codes for some capabilities. This module can load the
+//! terminfo entry of the terminal and fall back to its codes.
+//!
+//! # Examples
+//! ```no_run
+//! use termal_core::terminfo::{self, StrCap};
+//!
+//! // Uses the code from terminfo of `$TERM` if it is available, otherwise
+//! // uses the xterm code.
+//! print!("{}", terminfo::code(StrCap::EnterCaMode));
+//! ```
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::{
+    codes,
+    error::{Error, Result},
+};
+
+/// Capabilities of terminal loaded from terminfo.
+#[derive(Debug, Clone, Default)]
+pub struct Capabilities {
+    names: Vec<String>,
+    bools: Vec<bool>,
+    numbers: Vec<Option<i32>>,
+    strings: Vec<Option<String>>,
+}
+
+/// Boolean capabilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoolCap {
+    /// `am`: terminal has automatic margins.
+    AutoRightMargin = 1,
+    /// `bce`: screen erased with background color.
+    BackColorErase = 28,
+}
+
+/// Numeric capabilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumCap {
+    /// `cols`: number of columns.
+    Columns = 0,
+    /// `lines`: number of lines.
+    Lines = 2,
+    /// `colors`: maximum number of colors.
+    MaxColors = 13,
+}
+
+/// String capabilities without parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StrCap {
+    /// `bel`: audible signal.
+    Bell = 1,
+    /// `clear`: clear screen and home cursor.
+    ClearScreen = 5,
+    /// `el`: clear to end of line.
+    ClrEol = 6,
+    /// `ed`: clear to end of screen.
+    ClrEos = 7,
+    /// `home`: move cursor to the top left corner.
+    CursorHome = 12,
+    /// `civis`: make cursor invisible.
+    CursorInvisible = 13,
+    /// `cnorm`: make cursor visible.
+    CursorNormal = 16,
+    /// `blink`: turn on blinking.
+    EnterBlinkMode = 26,
+    /// `bold`: turn on bold.
+    EnterBoldMode = 27,
+    /// `smcup`: enter alternate buffer.
+    EnterCaMode = 28,
+    /// `dim`: turn on faint mode.
+    EnterDimMode = 30,
+    /// `invis`: turn on invisible mode.
+    EnterSecureMode = 32,
+    /// `rev`: turn on inverse mode.
+    EnterReverseMode = 34,
+    /// `smul`: turn on underline.
+    EnterUnderlineMode = 36,
+    /// `sgr0`: turn off all attributes.
+    ExitAttributeMode = 39,
+    /// `rmcup`: leave alternate buffer.
+    ExitCaMode = 40,
+    /// `rmul`: turn off underline.
+    ExitUnderlineMode = 44,
+    /// `rc`: restore cursor position.
+    RestoreCursor = 126,
+    /// `sc`: save cursor position.
+    SaveCursor = 128,
+    /// `op`: set default colors.
+    OrigPair = 297,
+    /// `sitm`: turn on italics.
+    EnterItalicsMode = 311,
+    /// `ritm`: turn off italics.
+    ExitItalicsMode = 321,
+}
+
+const MAGIC_16: i16 = 0o432;
+const MAGIC_32: i16 = 0o1036;
+
+impl StrCap {
+    /// Gets the xterm code of the capability.
+    pub fn xterm(&self) -> &'static str {
+        match self {
+            Self::Bell => "\x07",
+            Self::ClearScreen => "\x1b[H\x1b[2J",
+            Self::ClrEol => codes::ERASE_TO_LN_END,
+            Self::ClrEos => codes::ERASE_TO_END,
+            Self::CursorHome => "\x1b[H",
+            Self::CursorInvisible => codes::HIDE_CURSOR,
+            Self::CursorNormal => codes::SHOW_CURSOR,
+            Self::EnterBlinkMode => codes::BLINKING,
+            Self::EnterBoldMode => codes::BOLD,
+            Self::EnterCaMode => codes::ENABLE_ALTERNATIVE_BUFFER,
+            Self::EnterDimMode => codes::FAINT,
+            Self::EnterSecureMode => codes::INVISIBLE,
+            Self::EnterReverseMode => codes::INVERSE,
+            Self::EnterUnderlineMode => codes::UNDERLINE,
+            Self::ExitAttributeMode => codes::RESET,
+            Self::ExitCaMode => codes::DISABLE_ALTERNATIVE_BUFFER,
+            Self::ExitUnderlineMode => codes::RESET_UNDERLINE,
+            Self::RestoreCursor => codes::CUR_LOAD,
+            Self::SaveCursor => codes::CUR_SAVE,
+            Self::OrigPair => "\x1b[39;49m",
+            Self::EnterItalicsMode => codes::ITALIC,
+            Self::ExitItalicsMode => codes::RESET_ITALIC,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Loads the terminfo entry for the terminal in the environment variable
+    /// `TERM`.
+    pub fn from_env() -> Result<Self> {
+        let term = env::var("TERM")
+            .map_err(|_| Error::TerminfoNotFound(String::new()))?;
+        Self::load(&term)
+    }
+
+    /// Loads the terminfo entry for the terminal with the given name.
+    pub fn load(term: &str) -> Result<Self> {
+        let path = find_entry(term)
+            .ok_or_else(|| Error::TerminfoNotFound(term.into()))?;
+        Self::parse(&fs::read(path)?)
+    }
+
+    /// Parses compiled terminfo entry. Extended capabilities are ignored.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let mut r = Reader(data);
+        let magic = r.i16()?;
+        let num_size = match magic {
+            MAGIC_16 => 2,
+            MAGIC_32 => 4,
+            _ => return Err(Error::InvalidTerminfo),
+        };
+        let [names_len, bool_cnt, num_cnt, str_cnt, table_len] =
+            [r.len()?, r.len()?, r.len()?, r.len()?, r.len()?];
+
+        let names = r.take(names_len)?;
+        let names = String::from_utf8_lossy(
+            names.split(|b| *b == 0).next().unwrap_or_default(),
+        )
+        .split('|')
+        .map(|s| s.to_string())
+        .collect();
+
+        let bools = r.take(bool_cnt)?.iter().map(|b| *b == 1).collect();
+        if (names_len + bool_cnt) % 2 == 1 {
+            r.take(1)?;
+        }
+
+        let numbers = (0..num_cnt)
+            .map(|_| {
+                let n = if num_size == 2 {
+                    r.i16()? as i32
+                } else {
+                    r.i32()?
+                };
+                Ok((n >= 0).then_some(n))
+            })
+            .collect::<Result<_>>()?;
+
+        let offsets =
+            (0..str_cnt).map(|_| r.i16()).collect::<Result<Vec<_>>>()?;
+        let table = r.take(table_len)?;
+        let strings = offsets
+            .into_iter()
+            .map(|o| {
+                let s = table.get(usize::try_from(o).ok()?..)?;
+                let end = s.iter().position(|b| *b == 0)?;
+                Some(String::from_utf8_lossy(&s[..end]).into_owned())
+            })
+            .collect();
+
+        Ok(Self {
+            names,
+            bools,
+            numbers,
+            strings,
+        })
+    }
+
+    /// Gets the names of the terminal. The last name is usually the
+    /// description.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Gets the boolean capability.
+    pub fn get_bool(&self, cap: BoolCap) -> bool {
+        self.bools.get(cap as usize).copied().unwrap_or_default()
+    }
+
+    /// Gets the numeric capability.
+    pub fn get_num(&self, cap: NumCap) -> Option<i32> {
+        self.numbers.get(cap as usize).copied().flatten()
+    }
+
+    /// Gets the string capability. Padding (e.g. `$<5>`) is not removed.
+    pub fn get_str(&self, cap: StrCap) -> Option<&str> {
+        self.strings.get(cap as usize)?.as_deref()
+    }
+
+    /// Gets the code of the capability. Falls back to the xterm code if the
+    /// capability is not in terminfo. Padding is removed.
+    pub fn code(&self, cap: StrCap) -> String {
+        self.get_str(cap)
+            .map_or_else(|| cap.xterm().into(), strip_padding)
+    }
+
+    /// Checks whether the code of the capability differs from the xterm
+    /// code.
+    pub fn differs(&self, cap: StrCap) -> bool {
+        self.code(cap) != cap.xterm()
+    }
+}
+
+/// Gets the capabilities of the terminal in `TERM`. They are loaded only
+/// once. Returns [`None`] if the terminfo entry cannot be loaded.
+pub fn capabilities() -> Option<&'static Capabilities> {
+    static CAPS: OnceLock<Option<Capabilities>> = OnceLock::new();
+    CAPS.get_or_init(|| Capabilities::from_env().ok()).as_ref()
+}
+
+/// Gets the code of the capability for the terminal in `TERM`. Falls back to
+/// the xterm code if the terminfo entry or the capability is not available.
+pub fn code(cap: StrCap) -> &'static str {
+    static CODES: OnceLock<Vec<(StrCap, String)>> = OnceLock::new();
+    let Some(caps) = capabilities() else {
+        return cap.xterm();
+    };
+    let codes = CODES.get_or_init(|| {
+        ALL_STR_CAPS
+            .iter()
+            .filter(|c| caps.differs(**c))
+            .map(|c| (*c, caps.code(*c)))
+            .collect()
+    });
+    codes
+        .iter()
+        .find(|(c, _)| *c == cap)
+        .map_or_else(|| cap.xterm(), |(_, s)| s.as_str())
+}
+
+const ALL_STR_CAPS: [StrCap; 22] = [
+    StrCap::Bell,
+    StrCap::ClearScreen,
+    StrCap::ClrEol,
+    StrCap::ClrEos,
+    StrCap::CursorHome,
+    StrCap::CursorInvisible,
+    StrCap::CursorNormal,
+    StrCap::EnterBlinkMode,
+    StrCap::EnterBoldMode,
+    StrCap::EnterCaMode,
+    StrCap::EnterDimMode,
+    StrCap::EnterSecureMode,
+    StrCap::EnterReverseMode,
+    StrCap::EnterUnderlineMode,
+    StrCap::ExitAttributeMode,
+    StrCap::ExitCaMode,
+    StrCap::ExitUnderlineMode,
+    StrCap::RestoreCursor,
+    StrCap::SaveCursor,
+    StrCap::OrigPair,
+    StrCap::EnterItalicsMode,
+    StrCap::ExitItalicsMode,
+];
+
+/// Removes padding (e.g. `$<5>` or `$<100/>`) from terminfo string.
+fn strip_padding(s: &str) -> String {
+    let mut res = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.find("$<") {
+        res += &rest[..i];
+        match rest[i..].find('>') {
+            Some(e) => rest = &rest[i + e + 1..],
+            None => {
+                rest = &rest[i..];
+                break;
+            }
+        }
+    }
+    res + rest
+}
+
+/// Finds the compiled terminfo entry in the standard locations.
+fn find_entry(term: &str) -> Option<PathBuf> {
+    let first = term.chars().next()?;
+    if term.contains(['/', '\\']) || term.starts_with('.') {
+        return None;
+    }
+
+    let mut dirs = vec![];
+    if let Some(d) = env::var_os("TERMINFO") {
+        dirs.push(PathBuf::from(d));
+    }
+    if let Some(h) = env::var_os("HOME") {
+        dirs.push(Path::new(&h).join(".terminfo"));
+    }
+    if let Ok(d) = env::var("TERMINFO_DIRS") {
+        dirs.extend(d.split(':').filter(|d| !d.is_empty()).map(Into::into));
+    }
+    dirs.extend(
+        [
+            "/etc/terminfo",
+            "/lib/terminfo",
+            "/usr/share/terminfo",
+            "/usr/lib/terminfo",
+        ]
+        .map(PathBuf::from),
+    );
+
+    dirs.into_iter().find_map(|d| {
+        [first.to_string(), format!("{:x}", first as u32)]
+            .into_iter()
+            .map(|sub| d.join(sub).join(term))
+            .find(|p| p.is_file())
+    })
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.0.len() < n {
+            return Err(Error::InvalidTerminfo);
+        }
+        let (res, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(res)
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        let b = self.take(2)?;
+        Ok(i16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        let b = self.take(4)?;
+        Ok(i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn len(&mut self) -> Result<usize> {
+        usize::try_from(self.i16()?).map_err(|_| Error::InvalidTerminfo)
+    }
+}
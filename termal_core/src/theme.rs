@@ -0,0 +1,342 @@
+//! Palettes of semantic colors (e.g. error or success) that adapt to the
+//! background of the terminal.
+//!
+//! The colors of the current palette ([`palette`]) can be used in the
+//! colorize macros and in [`crate::colorize_str`] as `{'t:name}` (e.g.
+//! `{'t:error}`). Append `_` to the name to set it as background color
+//! (e.g. `{'t:dim_}`). The names are the fields of [`Palette`].
+//!
+//! The default palette is [`Palette::DARK`]. With the feature `events`, the
+//! palette may be selected by the background of the terminal with
+//! [`detect_palette`].
+//!
+//! Components (e.g. `ColumnPrinter`) get their styles by
+//! dotted keys (e.g. `table.header`) from [`StyleSet`]s. The lookup cascades
+//! from the most specific key to its parents (`table.header`, `table`).
+//! Each component may have its own [`StyleSet`] that overrides the theme
+//! styles ([`styles`]) just for that component. See [`style`].
+//!
+//! # Examples
+//! ```rust
+//! use termal_core::{
+//!     colorize_str,
+//!     theme::{set_palette, Palette},
+//! };
+//!
+//! set_palette(Palette::LIGHT);
+//! assert_eq!(
+//!     colorize_str("{'t:error}error{'_}")?,
+//!     format!("{}error\x1b[0m", Palette::LIGHT.error.fg_code()),
+//! );
+//! # Ok::<_, termal_core::error::Error>(())
+//! ```
+
+use std::{collections::BTreeMap, sync::RwLock};
+
+use crate::{Color, Rgb, Style};
+
+/// The palette used by `{'t:name}` color blocks.
+static PALETTE: RwLock<Palette> = RwLock::new(Palette::DARK);
+/// Styles of components in the theme.
+static STYLES: RwLock<StyleSet> = RwLock::new(StyleSet::new());
+
+/// Semantic colors of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Palette {
+    /// Main color of the app (e.g. for titles and prompts).
+    pub primary: Color,
+    /// Secondary color of the app.
+    pub secondary: Color,
+    /// Color of errors.
+    pub error: Color,
+    /// Color of warnings.
+    pub warning: Color,
+    /// Color of success messages.
+    pub success: Color,
+    /// Color of informative messages.
+    pub info: Color,
+    /// Color of less important text (e.g. hints).
+    pub dim: Color,
+    /// Color of highlighted text (e.g. matches in search).
+    pub highlight: Color,
+}
+
+impl Palette {
+    /// Palette for terminals with dark background.
+    pub const DARK: Self = Self {
+        primary: Color::Ansi256(75),
+        secondary: Color::Ansi256(141),
+        error: Color::Ansi256(203),
+        warning: Color::Ansi256(221),
+        success: Color::Ansi256(114),
+        info: Color::Ansi256(80),
+        dim: Color::Ansi256(245),
+        highlight: Color::Ansi256(229),
+    };
+
+    /// Palette for terminals with light background.
+    pub const LIGHT: Self = Self {
+        primary: Color::Ansi256(25),
+        secondary: Color::Ansi256(91),
+        error: Color::Ansi256(160),
+        warning: Color::Ansi256(130),
+        success: Color::Ansi256(28),
+        info: Color::Ansi256(31),
+        dim: Color::Ansi256(242),
+        highlight: Color::Ansi256(94),
+    };
+
+    /// Names of the colors in the palette in the order of the fields.
+    pub const NAMES: [&str; 8] = [
+        "primary",
+        "secondary",
+        "error",
+        "warning",
+        "success",
+        "info",
+        "dim",
+        "highlight",
+    ];
+
+    /// Gets the built-in palette that is readable on the given background
+    /// color. See [`is_dark`].
+    pub fn for_background(bg: Rgb<u16>) -> Self {
+        if is_dark(bg) {
+            Self::DARK
+        } else {
+            Self::LIGHT
+        }
+    }
+
+    /// Gets the color with the given name (name of the field). Returns
+    /// [`None`] if there is no such color.
+    pub fn get(&self, name: &str) -> Option<Color> {
+        match name {
+            "primary" => Some(self.primary),
+            "secondary" => Some(self.secondary),
+            "error" => Some(self.error),
+            "warning" => Some(self.warning),
+            "success" => Some(self.success),
+            "info" => Some(self.info),
+            "dim" => Some(self.dim),
+            "highlight" => Some(self.highlight),
+            _ => None,
+        }
+    }
+
+    /// Gets mutable reference to the color with the given name (name of the
+    /// field). Returns [`None`] if there is no such color.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Color> {
+        match name {
+            "primary" => Some(&mut self.primary),
+            "secondary" => Some(&mut self.secondary),
+            "error" => Some(&mut self.error),
+            "warning" => Some(&mut self.warning),
+            "success" => Some(&mut self.success),
+            "info" => Some(&mut self.info),
+            "dim" => Some(&mut self.dim),
+            "highlight" => Some(&mut self.highlight),
+            _ => None,
+        }
+    }
+
+    /// Gets the code that sets the color with the given name as foreground
+    /// color, or as background color if the name ends with `_`. Returns
+    /// empty string if there is no such color.
+    pub fn code(&self, name: &str) -> String {
+        match name.strip_suffix('_') {
+            Some(n) => self.get(n).map(|c| c.bg_code()),
+            None => self.get(name).map(|c| c.fg_code()),
+        }
+        .unwrap_or_default()
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+/// Styles identified by dotted keys (e.g. `progress.bar.filled`). See the
+/// [module documentation](self).
+///
+/// # Examples
+/// ```rust
+/// use termal_core::{theme::StyleSet, Attrs, Color, Style};
+///
+/// let styles = StyleSet::new()
+///     .with("progress", Attrs::BOLD)
+///     .with("progress.bar.filled", Color::Ansi256(2));
+///
+/// assert_eq!(
+///     styles.get("progress.bar.filled"),
+///     Some(Style::new().fg(Color::Ansi256(2))),
+/// );
+/// assert_eq!(styles.get("progress.bar.empty"), Some(Attrs::BOLD.into()));
+/// assert_eq!(styles.get("table.header"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyleSet {
+    styles: BTreeMap<String, Style>,
+}
+
+impl StyleSet {
+    /// Creates empty set of styles.
+    pub const fn new() -> Self {
+        Self {
+            styles: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the style with the given key.
+    pub fn with(
+        mut self,
+        key: impl Into<String>,
+        style: impl Into<Style>,
+    ) -> Self {
+        self.set(key, style);
+        self
+    }
+
+    /// Sets the style with the given key.
+    pub fn set(&mut self, key: impl Into<String>, style: impl Into<Style>) {
+        self.styles.insert(key.into(), style.into());
+    }
+
+    /// Removes the style with the given key.
+    pub fn remove(&mut self, key: &str) -> Option<Style> {
+        self.styles.remove(key)
+    }
+
+    /// Gets the style with exactly the given key.
+    pub fn get_exact(&self, key: &str) -> Option<Style> {
+        self.styles.get(key).copied()
+    }
+
+    /// Gets the style of the most specific key from the given key and its
+    /// parents (e.g. `table.header` and `table`).
+    pub fn get(&self, key: &str) -> Option<Style> {
+        key_cascade(key).find_map(|k| self.get_exact(k))
+    }
+
+    /// Checks if there are no styles.
+    pub fn is_empty(&self) -> bool {
+        self.styles.is_empty()
+    }
+
+    /// Iterates over the keys and their styles.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Style)> + '_ {
+        self.styles.iter().map(|(k, s)| (k.as_str(), *s))
+    }
+}
+
+/// Gets the styles of components in the theme.
+pub fn styles() -> StyleSet {
+    STYLES.read().map(|s| s.clone()).unwrap_or_default()
+}
+
+/// Sets the styles of components in the theme.
+pub fn set_styles(styles: StyleSet) {
+    if let Ok(mut s) = STYLES.write() {
+        *s = styles;
+    }
+}
+
+/// Modifies the styles of components in the theme.
+pub fn modify_styles(f: impl FnOnce(&mut StyleSet)) {
+    if let Ok(mut s) = STYLES.write() {
+        f(&mut s);
+    }
+}
+
+/// Gets the style with the given key for component with the given style
+/// overrides. The most specific key from the key and its parents is used.
+/// If both the overrides and the theme styles have the same key, the
+/// overrides are used. Returns [`None`] if there is no style for the key.
+pub fn style(key: &str, overrides: Option<&StyleSet>) -> Option<Style> {
+    let theme = STYLES.read().ok();
+    key_cascade(key).find_map(|k| {
+        overrides
+            .and_then(|o| o.get_exact(k))
+            .or_else(|| theme.as_ref().and_then(|t| t.get_exact(k)))
+    })
+}
+
+/// Iterates over the key and its parents from the most specific.
+fn key_cascade(key: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(key), |k| k.rsplit_once('.').map(|(p, _)| p))
+}
+
+/// Gets the current palette.
+pub fn palette() -> Palette {
+    PALETTE.read().map(|p| *p).unwrap_or_default()
+}
+
+/// Sets the current palette.
+pub fn set_palette(palette: Palette) {
+    if let Ok(mut p) = PALETTE.write() {
+        *p = palette;
+    }
+}
+
+/// Gets the code of the color with the given name in the current palette.
+/// See [`Palette::code`]. This is used by the colorize macros.
+pub fn palette_code(name: &str) -> String {
+    palette().code(name)
+}
+
+/// Gets the relative luminance of the color in range `0..=1`.
+pub fn luminance(c: Rgb<u16>) -> f32 {
+    let c = c.map(|a| a as f32 / u16::MAX as f32);
+    0.2126 * c.r + 0.7152 * c.g + 0.0722 * c.b
+}
+
+/// Checks whether the color is dark, so that light text should be used on
+/// it.
+pub fn is_dark(c: Rgb<u16>) -> bool {
+    luminance(c) < 0.5
+}
+
+/// Asks the terminal for its background color and sets the matching
+/// built-in palette as the current palette. If the terminal doesn't respond,
+/// the palette is not changed. Returns the current palette.
+///
+/// This enables raw mode for the duration of the request. See also
+/// [`detect_palette_on`].
+///
+/// # Examples
+/// ```no_run
+/// use termal_core::{colorize_str, theme::detect_palette};
+///
+/// detect_palette()?;
+/// println!("{}", colorize_str("{'t:success}done{'_}")?);
+/// # Ok::<_, termal_core::error::Error>(())
+/// ```
+#[cfg(feature = "events")]
+pub fn detect_palette() -> crate::error::Result<Palette> {
+    let _guard = crate::raw::raw_mode_guard()?;
+    detect_palette_on(&mut crate::raw::Terminal::stdio())
+}
+
+/// Asks the terminal for its background color and sets the matching
+/// built-in palette as the current palette. The terminal should be in raw
+/// mode. See [`detect_palette`].
+#[cfg(feature = "events")]
+pub fn detect_palette_on<T: crate::raw::IoProvider>(
+    term: &mut crate::raw::Terminal<T>,
+) -> crate::error::Result<Palette> {
+    use crate::{
+        codes,
+        raw::events::{Status, StatusCode},
+    };
+
+    let res = term.request_status(
+        codes::REQUEST_DEFAULT_BG_COLOR,
+        StatusCode::DefaultBgColor,
+    )?;
+    if let Some(Status::DefaultBgColor(c)) = res {
+        set_palette(Palette::for_background(c));
+    }
+    Ok(palette())
+}
@@ -0,0 +1,297 @@
+//! Wrapping of text with control sequences to the given width.
+//!
+//! Widths are in terminal columns and control sequences have zero width.
+//! By default, styles (SGR codes) are reset at the end of each line and set
+//! again after the indent on the next line, so indents may have their own
+//! style (see [`ResetPolicy`]). Right to left text is handled on each line
+//! according to [`BidiPolicy`].
+//!
+//! Words are separated by spaces. Non-breaking space (`U+00A0`) doesn't
+//! separate words. Words may be also broken at soft hyphen (`U+00AD`),
+//! where `-` is shown when the word is broken, and at zero width space
+//! (`U+200B`). Soft hyphens and zero width spaces are removed from the
+//! output.
+//!
+//! # Examples
+//! ```rust
+//! use termal_core::wrap::{wrap, WrapOptions};
+//!
+//! let text = "- first item of the list\n  - nested item";
+//! let opts = WrapOptions::new(14)
+//!     .subsequent_indent("  ")
+//!     .preserve_indent(true);
+//! assert_eq!(
+//!     wrap(text, &opts),
+//!     "- first item\n  of the list\n  - nested\n    item"
+//! );
+//! ```
+
+use std::borrow::Cow;
+
+use crate::{
+    codes,
+    fill::{char_width, str_width},
+    term_text::{apply_bidi, BidiPolicy, TermTextSpans},
+    ResetPolicy,
+};
+
+/// Soft hyphen. Words may be broken there with visible `-`.
+const SOFT_HYPHEN: char = '\u{ad}';
+/// Zero width space. Words may be broken there without any mark.
+const ZERO_WIDTH_SPACE: char = '\u{200b}';
+/// Characters where words may be broken.
+const BREAKS: [char; 2] = [SOFT_HYPHEN, ZERO_WIDTH_SPACE];
+
+/// Options of wrapping.
+#[derive(Debug, Clone, Default)]
+pub struct WrapOptions<'a> {
+    /// Maximum width of the lines in columns.
+    pub width: usize,
+    /// Indent of the first line of each paragraph (line of the input). It
+    /// may contain control sequences.
+    pub initial_indent: Cow<'a, str>,
+    /// Indent of the wrapped lines (hanging indent). It may contain control
+    /// sequences.
+    pub subsequent_indent: Cow<'a, str>,
+    /// If `true`, the leading whitespace of each paragraph is repeated after
+    /// the subsequent indent on the wrapped lines, so that nested text (e.g.
+    /// lists) keeps its structure.
+    pub preserve_indent: bool,
+    /// How right to left text is handled on the wrapped lines.
+    pub bidi: BidiPolicy,
+    /// How styles are handled at the ends of the lines. If the styles are
+    /// kept, the indents are in the style of the text around them.
+    pub reset: ResetPolicy,
+}
+
+impl<'a> WrapOptions<'a> {
+    /// Creates options for the given width without indents.
+    pub fn new(width: usize) -> Self {
+        Self {
+            width,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the indent of the first line of each paragraph.
+    pub fn initial_indent(mut self, s: impl Into<Cow<'a, str>>) -> Self {
+        self.initial_indent = s.into();
+        self
+    }
+
+    /// Sets the indent of the wrapped lines.
+    pub fn subsequent_indent(mut self, s: impl Into<Cow<'a, str>>) -> Self {
+        self.subsequent_indent = s.into();
+        self
+    }
+
+    /// Sets both the initial and the subsequent indent.
+    pub fn indent(self, s: impl Into<Cow<'a, str>>) -> Self {
+        let s = s.into();
+        self.initial_indent(s.clone()).subsequent_indent(s)
+    }
+
+    /// Sets whether the leading whitespace of paragraphs is repeated on the
+    /// wrapped lines.
+    pub fn preserve_indent(mut self, v: bool) -> Self {
+        self.preserve_indent = v;
+        self
+    }
+
+    /// Sets how right to left text is handled on the wrapped lines.
+    pub fn bidi(mut self, policy: BidiPolicy) -> Self {
+        self.bidi = policy;
+        self
+    }
+
+    /// Sets how styles are handled at the ends of the lines.
+    pub fn reset_policy(mut self, policy: ResetPolicy) -> Self {
+        self.reset = policy;
+        self
+    }
+}
+
+/// Wraps the text so that each line fits into the width. Each line of the
+/// input is wrapped separately. Words are separated by spaces and words that
+/// don't fit on single line are split at soft hyphens and zero width spaces
+/// or anywhere if that isn't enough. See the [module documentation](self).
+pub fn wrap_lines(text: &str, opts: &WrapOptions) -> Vec<String> {
+    let mut w = Wrapper {
+        width: opts.width,
+        bidi: opts.bidi,
+        reset: opts.reset,
+        lines: vec![],
+        cur: String::new(),
+        cur_width: 0,
+        has_text: false,
+        active: String::new(),
+        indent: String::new(),
+    };
+
+    for par in text.split('\n') {
+        let (lead, rest) = if opts.preserve_indent {
+            par.split_at(par.len() - par.trim_start_matches([' ', '\t']).len())
+        } else {
+            ("", par)
+        };
+
+        w.start_line(&format!("{}{lead}", opts.initial_indent));
+        w.indent = format!("{}{lead}", opts.subsequent_indent);
+
+        for word in rest.split(' ').filter(|w| !w.is_empty()) {
+            w.push_word(word);
+        }
+        w.end_line();
+    }
+
+    w.lines
+}
+
+/// Wraps the text so that each line fits into the width. The lines are
+/// joined with `'\n'`. See [`wrap_lines`].
+pub fn wrap(text: &str, opts: &WrapOptions) -> String {
+    wrap_lines(text, opts).join("\n")
+}
+
+/// Gets the width of the text in columns. Control sequences have zero width.
+pub fn visible_width(s: &str) -> usize {
+    TermTextSpans::new(s)
+        .filter(|s| !s.is_control())
+        .map(|s| str_width(s.text()))
+        .sum()
+}
+
+struct Wrapper {
+    width: usize,
+    bidi: BidiPolicy,
+    reset: ResetPolicy,
+    lines: Vec<String>,
+    cur: String,
+    cur_width: usize,
+    /// Whether there is text on the current line after the indent.
+    has_text: bool,
+    /// SGR codes that are active at the current position.
+    active: String,
+    /// Indent of the wrapped lines.
+    indent: String,
+}
+
+impl Wrapper {
+    fn start_line(&mut self, indent: &str) {
+        self.cur = indent.to_string();
+        self.cur_width = visible_width(indent);
+        if self.reset == ResetPolicy::EachLine {
+            self.cur += &self.active;
+        }
+        self.has_text = false;
+    }
+
+    fn end_line(&mut self) {
+        if self.reset == ResetPolicy::EachLine && !self.active.is_empty() {
+            self.cur += codes::RESET;
+        }
+        let line = std::mem::take(&mut self.cur);
+        self.lines.push(match apply_bidi(&line, self.bidi) {
+            Cow::Borrowed(_) => line,
+            Cow::Owned(l) => l,
+        });
+    }
+
+    fn break_line(&mut self) {
+        self.end_line();
+        let indent = std::mem::take(&mut self.indent);
+        self.start_line(&indent);
+        self.indent = indent;
+    }
+
+    fn push_word(&mut self, word: &str) {
+        let mut rest = word;
+        loop {
+            let sep = usize::from(self.has_text);
+            let avail = self.width.saturating_sub(self.cur_width + sep);
+            let full = rest.replace(BREAKS, "");
+            if !rest.contains(BREAKS) || visible_width(&full) <= avail {
+                self.push_part(&full);
+                return;
+            }
+
+            // The longest start of the word that fits with the hyphen.
+            let fit = rest
+                .match_indices(BREAKS)
+                .filter(|(i, _)| *i != 0)
+                .map(|(i, b)| (i, b.len(), b.starts_with(SOFT_HYPHEN)))
+                .take_while(|(i, _, hyphen)| {
+                    let w = visible_width(&rest[..*i].replace(BREAKS, ""));
+                    w + usize::from(*hyphen) <= avail
+                })
+                .last();
+
+            match fit {
+                Some((i, len, hyphen)) => {
+                    self.push_part(&rest[..i].replace(BREAKS, ""));
+                    if hyphen {
+                        self.cur.push('-');
+                        self.cur_width += 1;
+                    }
+                    self.break_line();
+                    rest = &rest[i + len..];
+                }
+                None if self.has_text => self.break_line(),
+                // Not even the first part fits on empty line.
+                None => {
+                    self.push_part(&full);
+                    return;
+                }
+            }
+        }
+    }
+
+    fn push_part(&mut self, word: &str) {
+        let width = visible_width(word);
+        let sep = usize::from(self.has_text);
+        if self.has_text && self.cur_width + sep + width > self.width {
+            self.break_line();
+        }
+
+        if self.has_text {
+            self.cur.push(' ');
+            self.cur_width += 1;
+        }
+
+        // The word doesn't fit even on empty line, so it is split.
+        let split = self.cur_width + width > self.width;
+        for span in TermTextSpans::new(word) {
+            if span.is_control() {
+                self.push_control(span.text());
+                continue;
+            }
+            if !split {
+                self.cur += span.text();
+                self.cur_width += str_width(span.text());
+                self.has_text = true;
+                continue;
+            }
+            for c in span.text().chars() {
+                let w = char_width(c);
+                if self.has_text && self.cur_width + w > self.width {
+                    self.break_line();
+                }
+                self.cur.push(c);
+                self.cur_width += w;
+                self.has_text = true;
+            }
+        }
+    }
+
+    fn push_control(&mut self, code: &str) {
+        self.cur += code;
+        if !code.starts_with("\x1b[") || !code.ends_with('m') {
+            return;
+        }
+        if code == codes::RESET || code == "\x1b[m" {
+            self.active.clear();
+        } else {
+            self.active += code;
+        }
+    }
+}
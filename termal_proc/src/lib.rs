@@ -1,4 +1,4 @@
-use proc_macro::TokenStream;
+use proc_macro::{Punct, Spacing, TokenStream, TokenTree};
 
 /// Creates formatted and colorized string. Expands to call to a [`format!`]
 /// macro.
@@ -19,3 +19,18 @@ pub fn uncolor(input: TokenStream) -> TokenStream {
         Err(r) => r.to_stream().into(),
     }
 }
+
+/// Defines named styles that can be used in the colorize macros as
+/// `{'@name}`.
+#[proc_macro]
+pub fn define_style(input: TokenStream) -> TokenStream {
+    match termal_core::proc::define_style(input.into()) {
+        Ok(r) => r.into(),
+        Err(r) => {
+            // The error is in item position, so it must end with `;`.
+            let mut res: TokenStream = r.to_stream().into();
+            res.extend([TokenTree::Punct(Punct::new(';', Spacing::Alone))]);
+            res
+        }
+    }
+}
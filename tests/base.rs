@@ -1,4 +1,8 @@
-use termal::{codes, formatc, formatmc, gradient, write_gradient};
+use termal::{
+    codes, color_choice, colorize_str, formatc, formatmc, gradient,
+    scoped_color_choice, uncolor_str, use_color, with_color_choice,
+    write_gradient, ColorChoice,
+};
 
 #[test]
 fn test_gradient() {
@@ -37,6 +41,8 @@ fn test_formatc_codes() {
     assert_eq!(formatc!("{'#123}"), formatc!("{'#112233}"));
     assert_eq!(formatc!("{'#12}"), formatc!("{'#121212}"));
     assert_eq!(formatc!("{'#1}"), formatc!("{'#111111}"));
+    assert_eq!(formatc!("{'#12345678}"), formatc!("{'#123456}"));
+    assert_eq!(formatc!("{'#1234}"), formatc!("{'#123}"));
 
     assert_eq!(formatc!("{'#123456_}"), codes::bg!(0x12, 0x34, 0x56));
     assert_eq!(formatc!("{'#123_}"), formatc!("{'#112233_}"));
@@ -81,6 +87,14 @@ fn test_formatc_codes() {
     assert_eq!(formatc!("{'su5}"), codes::set_up!(5));
     assert_eq!(formatc!("{'move_to_column5}"), codes::column!(5));
     assert_eq!(formatc!("{'mc5}"), codes::column!(5));
+    assert_eq!(formatc!("{'move_to_row5}"), codes::row!(5));
+    assert_eq!(formatc!("{'mrow5}"), codes::row!(5));
+    assert_eq!(formatc!("{'scroll_up5}"), codes::scroll_up!(5));
+    assert_eq!(formatc!("{'scu}"), codes::scroll_up!(1));
+    assert_eq!(formatc!("{'scroll_down5}"), codes::scroll_down!(5));
+    assert_eq!(formatc!("{'scd}"), codes::scroll_down!(1));
+    assert_eq!(formatc!("{'forward_tab2}"), codes::cursor_forward_tab!(2));
+    assert_eq!(formatc!("{'ftab}"), codes::cursor_forward_tab!(1));
 
     assert_eq!(formatc!("{'move_up_scrl}"), codes::UP_SCRL);
     assert_eq!(formatc!("{'save_cur}"), codes::CUR_SAVE);
@@ -89,6 +103,12 @@ fn test_formatc_codes() {
     assert_eq!(formatc!("{'load_cur}"), codes::CUR_LOAD);
     assert_eq!(formatc!("{'load}"), codes::CUR_LOAD);
     assert_eq!(formatc!("{'l}"), codes::CUR_LOAD);
+    assert_eq!(formatc!("{'set_tab}"), codes::SET_TAB_STOP);
+    assert_eq!(formatc!("{'stab}"), codes::SET_TAB_STOP);
+    assert_eq!(formatc!("{'clear_tab}"), codes::CLEAR_TAB_STOP);
+    assert_eq!(formatc!("{'ctab}"), codes::CLEAR_TAB_STOP);
+    assert_eq!(formatc!("{'clear_all_tabs}"), codes::CLEAR_ALL_TAB_STOPS);
+    assert_eq!(formatc!("{'ctabs}"), codes::CLEAR_ALL_TAB_STOPS);
 
     // Erase
     assert_eq!(formatc!("{'erase_to_end}"), codes::ERASE_TO_END);
@@ -230,6 +250,11 @@ fn test_formatc_codes() {
     assert_eq!(formatc!("{'bg56}"), codes::bg256!(56));
     assert_eq!(formatc!("{'ucolor56}"), codes::underline256!(56));
     assert_eq!(formatc!("{'uc56}"), codes::underline256!(56));
+    assert_eq!(
+        formatc!("{'ucolor#123456}"),
+        codes::underline_rgb!(0x12, 0x34, 0x56)
+    );
+    assert_eq!(formatc!("{'uc#abc _uc}"), formatc!("{'#aabbccu _uc}"));
 
     // Other
     assert_eq!(formatc!("{'line_wrap}"), codes::ENABLE_LINE_WRAP);
@@ -253,3 +278,64 @@ fn test_formatc_codes() {
     assert_eq!(formatc!("{'clear}"), formatc!("{'e mt}"));
     assert_eq!(formatc!("{'cls}"), formatc!("{'e mt}"));
 }
+
+#[test]
+fn test_with_color_choice() {
+    assert_eq!(color_choice(), ColorChoice::Auto);
+
+    let r = with_color_choice(ColorChoice::Never, || {
+        assert!(!use_color(true));
+        with_color_choice(ColorChoice::Always, || {
+            assert!(use_color(false));
+            // The override is per thread.
+            std::thread::spawn(|| assert_eq!(scoped_color_choice(), None))
+                .join()
+                .unwrap();
+        });
+        assert_eq!(color_choice(), ColorChoice::Never);
+        5
+    });
+    assert_eq!(r, 5);
+    assert_eq!(scoped_color_choice(), None);
+
+    // The choice is restored also on panic.
+    _ = std::panic::catch_unwind(|| {
+        with_color_choice(ColorChoice::Never, || panic!("test"))
+    });
+    assert_eq!(scoped_color_choice(), None);
+}
+
+#[test]
+fn test_colorize_str() {
+    assert_eq!(
+        colorize_str("{'bold #f00}a{'_} {x} {'mt5,2 #00ff00_}").unwrap(),
+        "\x1b[1m\x1b[38;2;255;0;0ma\x1b[0m {x} \x1b[2;5H\x1b[48;2;0;255;0m"
+    );
+    assert_eq!(colorize_str("{'r}{}").unwrap(), formatc!("{'r}{{}}"));
+    assert_eq!(uncolor_str("{'bold #f00}a{'_} {x}").unwrap(), "a {x}");
+
+    let err = colorize_str("{'nonsense}").unwrap_err();
+    assert_eq!(err.to_string(), "Unknown color format variable nonsense");
+    assert!(colorize_str("{'fg300}").is_err());
+    assert!(colorize_str("{'red").is_err());
+    assert!(uncolor_str("{'red").is_err());
+}
+
+mod theme {
+    termal::define_style!(pub err = "red bold", pub(crate) dim = "#888");
+}
+
+#[test]
+fn test_named_styles() {
+    use theme::*;
+    termal::define_style!(local = "u");
+
+    assert_eq!(
+        formatc!("{'@err}{}{'_} {'@dim i}{x}{'@local}", 5, x = "a"),
+        formatc!("{'red bold}{}{'_} {'#888 i}{x}{'u}", 5, x = "a"),
+    );
+    assert_eq!(formatmc!(false, "{'@err}e{'_}"), "e");
+    assert_eq!(formatmc!(true, "{'@err}e{'_}"), formatc!("{'r bold}e{'_}"));
+    assert!(colorize_str("{'@err}").is_err());
+    assert_eq!(uncolor_str("{'@err}a").unwrap(), "a");
+}
@@ -1,6 +1,6 @@
 use termal::{
-    codes::{self, CursorStyle, Selection},
-    Rgb,
+    codes::{self, CursorStyle, Multiplexer, Selection},
+    Rgb, Rgba,
 };
 
 #[test]
@@ -58,6 +58,12 @@ fn macros() {
     assert_eq!(codes::insert_chars!(0), "");
     assert_eq!(codes::delete_chars!(5), "\x1b[5P");
     assert_eq!(codes::delete_chars!(0), "");
+    assert_eq!(codes::scroll_up!(5), "\x1b[5S");
+    assert_eq!(codes::scroll_up!(0), "");
+    assert_eq!(codes::scroll_down!(5), "\x1b[5T");
+    assert_eq!(codes::scroll_down!(0), "");
+    assert_eq!(codes::cursor_forward_tab!(2), "\x1b[2I");
+    assert_eq!(codes::row!(5), "\x1b[5d");
     assert_eq!(codes::insert_columns!(5), "\x1b[5'}");
     assert_eq!(codes::insert_columns!(0), "");
     assert_eq!(codes::delete_columns!(5), "\x1b[5'~");
@@ -96,6 +102,17 @@ fn macros() {
 
 #[test]
 fn functions() {
+    assert_eq!(
+        codes::to_8bit("\x1b[5A\x1b]0;a\x1b\\"),
+        b"\x9b5A\x9d0;a\x9c"
+    );
+    assert_eq!(codes::to_8bit("\x1b7\x1b"), b"\x1b7\x1b");
+
+    assert_eq!(codes::checked_scroll_region(2, 5, 10).unwrap(), "\x1b[2;5r");
+    assert!(codes::checked_scroll_region(0, 5, 10).is_err());
+    assert!(codes::checked_scroll_region(5, 5, 10).is_err());
+    assert!(codes::checked_scroll_region(2, 11, 10).is_err());
+
     assert_eq!(
         codes::set_cursor(CursorStyle::Block(Some(true))),
         "\x1b[0 q"
@@ -147,6 +164,7 @@ fn functions() {
         codes::request_selectoin([Selection::Select, Selection::Cut0]),
         "\x1b]52;s0;?\x1b\\"
     );
+    codes::set_multiplexer(Some(Multiplexer::None));
     assert_eq!(
         codes::set_selection([], b"hello"),
         "\x1b]52;;aGVsbG8=\x1b\\"
@@ -155,4 +173,178 @@ fn functions() {
         codes::set_selection([Selection::Select, Selection::Cut0], b"hello"),
         "\x1b]52;s0;aGVsbG8=\x1b\\"
     );
+    assert_eq!(codes::set_selection([], b""), "\x1b]52;;\x1b\\");
+    assert_eq!(codes::set_selection([], b"h"), "\x1b]52;;aA==\x1b\\");
+    assert_eq!(codes::set_selection([], b"he"), "\x1b]52;;aGU=\x1b\\");
+    assert_eq!(
+        codes::set_selection([], [0xff, 0xfe, 0, 0x3e]),
+        "\x1b]52;;//4APg==\x1b\\"
+    );
+    codes::set_multiplexer(Some(Multiplexer::Tmux));
+    assert_eq!(
+        codes::set_selection([], b"hello"),
+        "\x1bPtmux;\x1b\x1b]52;;aGVsbG8=\x1b\x1b\\\x1b\\"
+    );
+    codes::set_multiplexer(None);
+
+    assert_eq!(codes::set_icon_name("i\x1b\\"), "\x1b]1;i\\\x1b\\");
+    assert_eq!(codes::set_window_title_and_icon(5), "\x1b]0;5\x1b\\");
+    assert_eq!(codes::PUSH_WINDOW_TITLE, "\x1b[22;2t");
+    assert_eq!(codes::POP_WINDOW_TITLE, "\x1b[23;2t");
+
+    assert_eq!(codes::MAXIMIZE_WINDOW, "\x1b[9;1t");
+    assert_eq!(codes::move_window!(10, 20), "\x1b[3;10;20t");
+    assert_eq!(codes::resize_window!(80, 24), "\x1b[8;24;80t");
+    let w = 640;
+    assert_eq!(codes::resize_window_px!(w, 0), "\x1b[4;0;640t");
+}
+
+#[test]
+fn cursor_save_support() {
+    codes::set_cursor_save_support(Some(false));
+    assert!(!codes::supports_cursor_save());
+    codes::set_cursor_save_support(Some(true));
+    assert!(codes::supports_cursor_save());
+    codes::set_cursor_save_support(None);
+}
+
+#[test]
+fn write_codes() {
+    let six = 6;
+    let mut buf = String::new();
+
+    termal::write_csi!(&mut buf, 'L').unwrap();
+    termal::write_csi!(&mut buf, 'L', 1, six).unwrap();
+    termal::write_graphic!(&mut buf).unwrap();
+    termal::write_graphic!(&mut buf, 1, six).unwrap();
+    assert_eq!(buf, "\x1b[L\x1b[1;6L\x1b[m\x1b[1;6m");
+
+    buf.clear();
+    codes::write_move_to(&mut buf, 5, six).unwrap();
+    codes::write_move_up(&mut buf, 0).unwrap();
+    codes::write_move_down(&mut buf, six).unwrap();
+    codes::write_move_right(&mut buf, 1).unwrap();
+    codes::write_move_left(&mut buf, 2).unwrap();
+    codes::write_column(&mut buf, 3).unwrap();
+    assert_eq!(buf, "\x1b[6;5H\x1b[6B\x1b[1C\x1b[2D\x1b[3G");
+
+    buf.clear();
+    codes::write_fg(&mut buf, 1, 2, 3).unwrap();
+    codes::write_bg(&mut buf, 4, 5, 6).unwrap();
+    codes::write_fg256(&mut buf, 7).unwrap();
+    codes::write_bg256(&mut buf, 8).unwrap();
+    assert_eq!(
+        buf,
+        "\x1b[38;2;1;2;3m\x1b[48;2;4;5;6m\x1b[38;5;7m\x1b[48;5;8m"
+    );
+}
+
+#[test]
+fn rgb_from_hex() {
+    assert_eq!(
+        Rgb::from_hex("#123456").unwrap(),
+        Rgb::new(0x12, 0x34, 0x56)
+    );
+    assert_eq!(Rgb::from_hex("abc").unwrap(), Rgb::new(0xaa, 0xbb, 0xcc));
+    assert_eq!(Rgb::from_hex("#12").unwrap(), Rgb::new(0x12, 0x12, 0x12));
+    assert_eq!(Rgb::from_hex("#1").unwrap(), Rgb::new(0x11, 0x11, 0x11));
+    assert_eq!(Rgb::from_hex("#abc8").unwrap(), Rgb::new(0xaa, 0xbb, 0xcc));
+    assert_eq!(
+        Rgb::from_hex("#12345678").unwrap(),
+        Rgb::new(0x12, 0x34, 0x56)
+    );
+    assert!(Rgb::from_hex("#12345").is_err());
+    assert!(Rgb::from_hex("#12g").is_err());
+    assert!(Rgb::from_hex("").is_err());
+
+    const RED: Option<Rgb> = Rgb::parse_hex("#f00");
+    assert_eq!(RED, Some(Rgb::new(0xff, 0, 0)));
+}
+
+#[test]
+fn rgb_blending() {
+    let red = Rgb::new(255, 0, 0);
+    let blue = Rgb::new(0, 0, 255);
+
+    assert_eq!(red.lerp(blue, 0.), red);
+    assert_eq!(red.lerp(blue, 1.), blue);
+    assert_eq!(red.lerp(blue, 0.5), Rgb::new(128, 0, 128));
+    assert_eq!(red.lerp(blue, 2.), blue);
+
+    assert_eq!(Rgba::new(255, 0, 0, 255).over(blue), red);
+    assert_eq!(Rgba::new(255, 0, 0, 0).over(blue), blue);
+    assert_eq!(Rgba::new(255, 255, 255, 51).over(Rgb::<u8>::BLACK).r, 51);
+    assert_eq!(Rgba::from(red), Rgba::new(255, 0, 0, 255));
+
+    assert_eq!(Rgb::<u8>::BLACK.luminance(), 0.);
+    assert_eq!(Rgb::<u8>::WHITE.luminance(), 1.);
+    assert!(
+        (Rgb::<u8>::BLACK.contrast_ratio(Rgb::<u8>::WHITE) - 21.).abs() < 1e-4
+    );
+    assert_eq!(red.contrast_ratio(red), 1.);
+
+    assert_eq!(
+        Rgb::new(20, 20, 40).contrasting_text_color(),
+        Rgb::<u8>::WHITE
+    );
+    assert_eq!(
+        Rgb::new(240, 230, 140).contrasting_text_color(),
+        Rgb::<u8>::BLACK
+    );
+}
+
+#[test]
+fn chunked() {
+    assert_eq!(
+        codes::chunked(codes::DCS, "abc", 10, |_, _| ""),
+        "\x1bPabc\x1b\\"
+    );
+    assert_eq!(
+        codes::chunked(codes::APC, "", 10, |_, _| "G"),
+        "\x1b_G\x1b\\"
+    );
+    assert_eq!(
+        codes::chunked(codes::APC, "ab\x1b\\cd", 3, |i, last| format!(
+            "{i}{}",
+            if last { "." } else { "," }
+        )),
+        "\x1b_0,ab\x1b\\\x1b_1.\\cd\x1b\\"
+    );
+    assert_eq!(
+        codes::chunked(codes::DCS, "áb", 1, |_, _| ""),
+        "\x1bPá\x1b\\\x1bPb\x1b\\"
+    );
+}
+
+#[test]
+fn notify() {
+    assert_eq!(
+        codes::notify("a;b\x1b", "c;d\x07"),
+        "\x1b]9;ab: c;d\x1b\\\x1b]777;notify;ab;c;d\x1b\\"
+    );
+    assert_eq!(
+        codes::notify("", "done"),
+        "\x1b]9;done\x1b\\\x1b]777;notify;;done\x1b\\"
+    );
+}
+
+#[test]
+fn passthrough() {
+    let seq = "\x1b_Ga=T;AAAA\x1b\\";
+    assert_eq!(Multiplexer::None.wrap(seq), seq);
+    assert_eq!(
+        Multiplexer::Tmux.wrap(seq),
+        "\x1bPtmux;\x1b\x1b_Ga=T;AAAA\x1b\x1b\\\x1b\\"
+    );
+    assert_eq!(
+        Multiplexer::Screen.wrap(seq),
+        "\x1bP\x1b_Ga=T;AAAA\x1b\x1b\\\x1bP\\\x1b\\"
+    );
+
+    // Long sequences are split for screen.
+    let long = "a".repeat(1000);
+    let wrapped = codes::screen_passthrough(&long);
+    assert_eq!(wrapped.matches(codes::DCS).count(), 2);
+    assert_eq!(wrapped.replace(codes::DCS, "").replace(codes::ST, ""), long);
+    assert_eq!(codes::screen_passthrough(""), "");
 }
@@ -0,0 +1,61 @@
+use std::env;
+
+use termal::{
+    color_choice, env_color_choice, formatac, formatc, global_color_choice,
+    lazyc, set_color_choice, use_color, with_color_choice, ColorChoice,
+};
+
+// The environment and the global choice are shared by the whole process, so
+// everything is in single test.
+#[test]
+fn test_color_choice() {
+    for v in ["NO_COLOR", "CLICOLOR", "CLICOLOR_FORCE"] {
+        env::remove_var(v);
+    }
+    assert_eq!(env_color_choice(), ColorChoice::Auto);
+    assert!(use_color(true));
+    assert!(!use_color(false));
+
+    env::set_var("NO_COLOR", "");
+    assert!(use_color(true));
+    env::set_var("NO_COLOR", "1");
+    assert_eq!(env_color_choice(), ColorChoice::Never);
+    assert!(!use_color(true));
+    assert_eq!(formatac!("{'r}a{'_}"), "a");
+    // Explicit choice has priority over the environment.
+    assert!(ColorChoice::Always.use_color(false));
+    env::remove_var("NO_COLOR");
+
+    env::set_var("CLICOLOR", "0");
+    assert_eq!(env_color_choice(), ColorChoice::Never);
+    env::set_var("CLICOLOR_FORCE", "1");
+    assert_eq!(env_color_choice(), ColorChoice::Always);
+    assert!(use_color(false));
+    env::set_var("CLICOLOR_FORCE", "0");
+    assert_eq!(env_color_choice(), ColorChoice::Never);
+    env::remove_var("CLICOLOR");
+    env::remove_var("CLICOLOR_FORCE");
+
+    set_color_choice(ColorChoice::Always);
+    assert_eq!(global_color_choice(), ColorChoice::Always);
+    assert_eq!(color_choice(), ColorChoice::Always);
+    assert_eq!(formatac!("{'r}{}", 1), "\x1b[91m1");
+    with_color_choice(ColorChoice::Never, || {
+        assert_eq!(color_choice(), ColorChoice::Never);
+    });
+    std::thread::spawn(|| assert_eq!(color_choice(), ColorChoice::Always))
+        .join()
+        .unwrap();
+
+    // Lazy values decide when they are displayed.
+    let name = String::from("x");
+    let msg = lazyc!("{'r}{name}{'_} {}", 5);
+    assert_eq!(msg.to_string(), formatc!("{'r}x{'_} 5"));
+    assert_eq!(format!("{msg:?}"), "LazyColored(\"x 5\")");
+
+    set_color_choice(ColorChoice::Never);
+    assert!(!use_color(true));
+    assert_eq!(msg.to_string(), "x 5");
+    set_color_choice(ColorChoice::Auto);
+    assert_eq!(color_choice(), ColorChoice::Auto);
+}
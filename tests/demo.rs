@@ -0,0 +1,40 @@
+use termal::{
+    codes, demo,
+    raw::{
+        events::{Key, KeyCode, Modifiers},
+        testing::ScriptedInput,
+    },
+};
+
+#[test]
+fn test_color_chart() {
+    let pages = demo::color_chart();
+    assert_eq!(pages.len(), 2);
+    for page in pages {
+        assert!(page.lines().count() <= 23);
+        assert!(page.lines().all(|l| l.is_empty()
+            || l.ends_with(':')
+            || l.ends_with(codes::RESET)));
+    }
+}
+
+#[test]
+fn test_style_matrix() {
+    let m = demo::style_matrix();
+    assert!(m.contains(&format!("{}{}Text", codes::BOLD, codes::RED_DARK_FG)));
+}
+
+#[test]
+fn test_key_tester() {
+    let mut term = ScriptedInput::new()
+        .key(Key::mcode(KeyCode::Up, Modifiers::CONTROL))
+        .key(Key::mcode(KeyCode::Char('c'), Modifiers::CONTROL))
+        .key(Key::code(KeyCode::Down))
+        .terminal();
+    demo::key_tester(&mut term).unwrap();
+
+    let out = term.io().written_str();
+    assert!(out.contains("\r\nctrl+up: "));
+    assert!(!out.contains("down"));
+    assert!(term.io().has_input());
+}
@@ -1,8 +1,14 @@
 use termal::{
-    raw::events::{
-        mouse::{self, Mouse},
-        AmbigousEvent, AnyEvent, Event, Key, KeyCode, Modifiers, StateChange,
-        Status, TermAttr, TermFeatures, TermType,
+    codes::input,
+    raw::{
+        events::{
+            encode_event,
+            mouse::{self, Mouse},
+            AmbigousEvent, AnyEvent, Event, Key, KeyBinding, KeyCode, KeyMap,
+            Modifiers, Parser, StateChange, Status, StatusCode, TermAttr,
+            TermFeatures, TermName, TermType,
+        },
+        testing::ScriptedInput,
     },
     Rgb,
 };
@@ -101,6 +107,19 @@ fn test_unknown() {
     );
 }
 
+#[test]
+fn test_osc_numeric() {
+    // OSC with only numeric arguments must not loop forever.
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1b]10\x1b\\"),
+        AmbigousEvent::unknown(b"\x1b]10\x1b\\")
+    );
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1b]52;12\x07"),
+        AmbigousEvent::status(Status::SelectionData(vec![]))
+    );
+}
+
 #[test]
 fn test_key() {
     assert_eq!(
@@ -192,6 +211,47 @@ fn test_key() {
     );
 }
 
+#[test]
+fn test_input_codes() {
+    let keys = [
+        (input::UP, KeyCode::Up, Modifiers::NONE),
+        (input::APP_DOWN, KeyCode::Down, Modifiers::NONE),
+        (input::RXVT_SHIFT_RIGHT, KeyCode::Right, Modifiers::SHIFT),
+        (input::RXVT_CTRL_LEFT, KeyCode::Left, Modifiers::CONTROL),
+        (input::HOME, KeyCode::Home, Modifiers::NONE),
+        (input::APP_HOME, KeyCode::Home, Modifiers::NONE),
+        (input::VT_HOME, KeyCode::Home, Modifiers::NONE),
+        (input::RXVT_HOME, KeyCode::Home, Modifiers::NONE),
+        (input::END, KeyCode::End, Modifiers::NONE),
+        (input::APP_END, KeyCode::End, Modifiers::NONE),
+        (input::VT_END, KeyCode::End, Modifiers::NONE),
+        (input::RXVT_END, KeyCode::End, Modifiers::NONE),
+        (input::DELETE, KeyCode::Delete, Modifiers::NONE),
+        (input::PAGE_DOWN, KeyCode::PgDown, Modifiers::NONE),
+        (input::BACK_TAB, KeyCode::Tab, Modifiers::SHIFT),
+        (input::F1, KeyCode::F1, Modifiers::NONE),
+        (input::VT_F1, KeyCode::F1, Modifiers::NONE),
+        (input::LINUX_F1, KeyCode::F1, Modifiers::NONE),
+        (input::LINUX_F5, KeyCode::F5, Modifiers::NONE),
+        (input::F12, KeyCode::F12, Modifiers::NONE),
+    ];
+    for (code, key, mods) in keys {
+        assert_eq!(
+            AmbigousEvent::from_code(code.as_bytes()),
+            AmbigousEvent::key(Key::mcode(key, mods)),
+            "{code:?}",
+        );
+    }
+
+    assert_eq!(
+        encode_event(&Event::KeyPress(Key::mcode(
+            KeyCode::Tab,
+            Modifiers::SHIFT
+        ))),
+        input::BACK_TAB.as_bytes(),
+    );
+}
+
 #[test]
 fn test_ambiguous() {
     assert_eq!(
@@ -399,7 +459,26 @@ fn test_status() {
 
     assert_eq!(
         AmbigousEvent::from_code(b"\x1bP>|My Terminal\x1b\\"),
-        AmbigousEvent::status(Status::TerminalName("My Terminal".to_string())),
+        AmbigousEvent::status(Status::TerminalName(TermName {
+            name: "My Terminal".into(),
+            version: None,
+        })),
+    );
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1bP>|XTerm(367)\x1b\\"),
+        AmbigousEvent::status(Status::TerminalName(TermName {
+            name: "XTerm".into(),
+            version: Some("367".into()),
+        })),
+    );
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1bP>|tmux 3.3a\x1b\\"),
+        AmbigousEvent::status(Status::TerminalName(TermName {
+            name: "tmux".into(),
+            version: Some("3.3a".into()),
+        })),
     );
 
     assert_eq!(
@@ -452,6 +531,84 @@ fn test_status() {
         AmbigousEvent::from_code(b"\x1b]52;;aGVsbG8gdGhlcmU=\x1b\\"),
         AmbigousEvent::status(Status::SelectionData(b"hello there".into())),
     );
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1b]52;c;aGU=\x1b\\"),
+        AmbigousEvent::status(Status::SelectionData(b"he".into())),
+    );
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1b]52;c;//4APg\x1b\\"),
+        AmbigousEvent::status(Status::SelectionData(vec![
+            0xff, 0xfe, 0, 0x3e
+        ])),
+    );
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1b]52;c;aGV*\x1b\\"),
+        AmbigousEvent::unknown(b"\x1b]52;c;aGV*\x1b\\"),
+    );
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1bP1$r0;38:2::1:2:3m\x1b\\"),
+        AmbigousEvent::status(Status::Setting(Some("0;38:2::1:2:3m".into()))),
+    );
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1bP0$r\x1b\\"),
+        AmbigousEvent::status(Status::Setting(None)),
+    );
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1bP1+r436F=323536;544E=6B\x1b\\"),
+        AmbigousEvent::status(Status::TermCap {
+            name: "Co".into(),
+            value: Some("256".into()),
+        }),
+    );
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1bP1+r5463\x1b\\"),
+        AmbigousEvent::status(Status::TermCap {
+            name: "Tc".into(),
+            value: Some("".into()),
+        }),
+    );
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1bP0+r787878\x1b\\"),
+        AmbigousEvent::status(Status::TermCap {
+            name: "xxx".into(),
+            value: None,
+        }),
+    );
+
+    assert!(matches!(
+        AmbigousEvent::from_code(b"\x1bP1+r43x\x1b\\").event,
+        AnyEvent::Unknown(_),
+    ));
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1b]l1: vim\x1b\\"),
+        AmbigousEvent::status(Status::WindowTitle("1: vim".into())),
+    );
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1b]Lvim\x07"),
+        AmbigousEvent::status(Status::IconName("vim".into())),
+    );
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1b[2t"),
+        AmbigousEvent::status(Status::WindowMinimized(true)),
+    );
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1b[3;100;50t"),
+        AmbigousEvent::status(Status::WindowPosition { x: 100, y: 50 }),
+    );
+
+    assert_eq!(
+        AmbigousEvent::from_code(b"\x1b[9;60;200t"),
+        AmbigousEvent::status(Status::ScreenSize { w: 200, h: 60 }),
+    );
 }
 
 #[test]
@@ -478,4 +635,295 @@ fn test_other() {
         AmbigousEvent::from_code(b"\x1b[O"),
         AmbigousEvent::event(Event::FocusLost),
     );
+
+    assert_eq!(Event::Focus.focus(), Some(true));
+    assert_eq!(Event::FocusLost.focus(), Some(false));
+    assert_eq!(Event::KeyPress(Key::verbatim('a')).focus(), None);
+}
+
+#[test]
+fn test_status_display() {
+    let s = Status::CursorPosition { x: 5, y: 7 };
+    assert_eq!(s.code(), StatusCode::CursorPosition);
+    assert_eq!(s.code() as u32, 3);
+    assert_eq!(s.to_string(), "cursor position: 5, 7");
+
+    let s = Status::TerminalName(TermName::parse("kitty(0.26.5)"));
+    assert_eq!(s.code(), StatusCode::TerminalName);
+    assert_eq!(s.to_string(), "terminal name: kitty 0.26.5");
+
+    let s = Status::DefaultFgColor(Rgb::new(0xffff, 0, 0));
+    assert_eq!(s.to_string(), "default fg color: rgb:f/0/0");
+
+    let s = Status::TermCap {
+        name: "Co".into(),
+        value: Some("256".into()),
+    };
+    assert_eq!(s.code() as u32, 23);
+    assert_eq!(s.to_string(), "termcap Co: \"256\"");
+}
+
+#[test]
+fn test_key_binding() {
+    let parse = |s: &str| s.parse::<KeyBinding>().unwrap();
+
+    assert_eq!(
+        parse("ctrl+shift+p"),
+        KeyBinding::new(
+            KeyCode::Char('p'),
+            Modifiers::CONTROL | Modifiers::SHIFT
+        )
+    );
+    assert_eq!(
+        parse("Alt+Enter"),
+        KeyBinding::new(KeyCode::Enter, Modifiers::ALT)
+    );
+    assert_eq!(
+        parse("P"),
+        KeyBinding::new(KeyCode::Char('p'), Modifiers::SHIFT)
+    );
+    assert_eq!(parse("f5"), KeyBinding::from(KeyCode::F5));
+    assert_eq!(
+        parse("ctrl++"),
+        KeyBinding::new(KeyCode::Char('+'), Modifiers::CONTROL)
+    );
+    assert!("ctrl+nothing".parse::<KeyBinding>().is_err());
+    assert!("hyper+a".parse::<KeyBinding>().is_err());
+    assert!("f21".parse::<KeyBinding>().is_err());
+
+    assert_eq!(parse("ctrl+shift+p").to_string(), "ctrl+shift+p");
+    assert_eq!(parse("alt+pgup").to_string(), "alt+pgup");
+
+    let mut map = KeyMap::new();
+    map.bind_str("ctrl+h", 1).unwrap();
+    map.bind_str("shift+a", 2).unwrap();
+    assert_eq!(map.get_ambigous(&AmbigousEvent::from_code(b"A")), Some(&2));
+    // `\x08` is backspace, but it may also be ctrl+h
+    assert_eq!(
+        map.get_ambigous(&AmbigousEvent::from_code(b"\x08")),
+        Some(&1)
+    );
+    assert_eq!(map.get_ambigous(&AmbigousEvent::from_code(b"a")), None);
+}
+
+#[test]
+fn test_encode_event() {
+    let events = [
+        Event::KeyPress(Key::verbatim('x')),
+        Event::KeyPress(Key::mcode(KeyCode::Char('w'), Modifiers::CONTROL)),
+        Event::KeyPress(Key::mcode(KeyCode::Right, Modifiers::ALT)),
+        Event::KeyPress(Key::mcode(KeyCode::F20, Modifiers::SHIFT)),
+        Event::Mouse(Mouse {
+            button: mouse::Button::None,
+            event: mouse::Event::ScrollDown,
+            modifiers: Modifiers::SHIFT,
+            x: 5,
+            y: 7,
+        }),
+        Event::Mouse(Mouse {
+            button: mouse::Button::Left,
+            event: mouse::Event::Move,
+            modifiers: Modifiers::NONE,
+            x: 1,
+            y: 2,
+        }),
+        Event::Focus,
+        Event::FocusLost,
+        Event::StateChange(StateChange::BracketedPasteStart),
+        Event::StateChange(StateChange::BracketedPasteEnd),
+        Event::Status(Status::Ok),
+        Event::Status(Status::CursorPosition { x: 20, y: 10 }),
+        Event::Status(Status::Attributes(TermAttr::new(
+            TermType::Vt220,
+            TermFeatures::SIXEL_GRAPHICS | TermFeatures::ANSI_COLOR,
+        ))),
+        Event::Status(Status::Attributes(TermAttr::new(
+            TermType::Vt100,
+            TermFeatures::NONE,
+        ))),
+        Event::Status(Status::TerminalName(TermName::parse("foot(1.2.3)"))),
+        Event::Status(Status::TextAreaSizePx { w: 800, h: 600 }),
+        Event::Status(Status::TextAreaSize { w: 80, h: 24 }),
+        Event::Status(Status::CharSize { w: 10, h: 20 }),
+        Event::Status(Status::SixelColors(256)),
+        Event::Status(Status::SixelSize { w: 1000, h: 1000 }),
+        Event::Status(Status::ColorCodeColor {
+            code: 3,
+            color: Rgb::<u16>::new(0x1234, 0xabcd, 0),
+        }),
+        Event::Status(Status::DefaultFgColor(Rgb::<u16>::new(1, 2, 3))),
+        Event::Status(Status::DefaultBgColor(Rgb::<u16>::new(4, 5, 6))),
+        Event::Status(Status::CursorColor(Rgb::<u16>::new(7, 8, 9))),
+        Event::Status(Status::SelectionData(b"hello".to_vec())),
+        Event::Status(Status::Setting(Some("0;1m".into()))),
+        Event::Status(Status::Setting(None)),
+        Event::Status(Status::TermCap {
+            name: "TN".into(),
+            value: Some("xterm-kitty".into()),
+        }),
+        Event::Status(Status::TermCap {
+            name: "Ms".into(),
+            value: None,
+        }),
+        Event::Status(Status::WindowTitle("title".into())),
+        Event::Status(Status::IconName("".into())),
+        Event::Status(Status::WindowMinimized(false)),
+        Event::Status(Status::WindowPosition { x: 3, y: 4 }),
+        Event::Status(Status::ScreenSizePx { w: 1920, h: 1080 }),
+        Event::Status(Status::ScreenSize { w: 240, h: 67 }),
+    ];
+
+    for e in events {
+        let code = e.to_ansi();
+        assert_eq!(code, encode_event(&e));
+        assert_eq!(
+            AmbigousEvent::from_code(&code).event,
+            AnyEvent::Known(e.clone()),
+            "{:?}",
+            String::from_utf8_lossy(&code)
+        );
+    }
+
+    assert_eq!(
+        encode_event(&Event::KeyPress(Key::code(KeyCode::F2))),
+        b"\x1bOQ"
+    );
+}
+
+#[cfg(feature = "crossterm")]
+#[test]
+fn test_crossterm_interop() {
+    use termal::{crossterm::event as ct, error::Error};
+
+    let keys = [
+        Key::verbatim('a'),
+        Key::new(KeyCode::Char('a'), Modifiers::SHIFT, 'A'),
+        Key::mcode(KeyCode::Char('c'), Modifiers::CONTROL),
+        Key::mcode(KeyCode::Left, Modifiers::ALT | Modifiers::META),
+        Key::mcode(KeyCode::Tab, Modifiers::SHIFT),
+        Key::code(KeyCode::F12),
+        Key::new(KeyCode::Space, Modifiers::NONE, ' '),
+    ];
+    for k in keys {
+        let ct_key: ct::KeyEvent = k.into();
+        assert_eq!(Key::try_from(ct_key).unwrap(), k);
+    }
+
+    let ct_key =
+        ct::KeyEvent::new(ct::KeyCode::BackTab, ct::KeyModifiers::SHIFT);
+    assert_eq!(
+        Event::try_from(ct::Event::Key(ct_key)).unwrap(),
+        Event::KeyPress(Key::mcode(KeyCode::Tab, Modifiers::SHIFT))
+    );
+    assert_eq!(
+        ct::KeyEvent::from(Key::new(
+            KeyCode::Char('a'),
+            Modifiers::SHIFT,
+            'A'
+        ))
+        .code,
+        ct::KeyCode::Char('A')
+    );
+
+    let mouse = Mouse {
+        button: mouse::Button::Left,
+        event: mouse::Event::Move,
+        modifiers: Modifiers::CONTROL,
+        x: 1,
+        y: 20,
+    };
+    let ct_mouse = ct::MouseEvent::try_from(mouse).unwrap();
+    assert_eq!(
+        ct_mouse.kind,
+        ct::MouseEventKind::Drag(ct::MouseButton::Left)
+    );
+    assert_eq!((ct_mouse.column, ct_mouse.row), (0, 19));
+    assert_eq!(Mouse::try_from(ct_mouse).unwrap(), mouse);
+
+    assert_eq!(
+        Event::try_from(ct::Event::FocusGained).unwrap(),
+        Event::Focus
+    );
+    assert_eq!(
+        Event::try_from(ct::Event::Resize(80, 24)).unwrap(),
+        Event::Resize { w: 80, h: 24 }
+    );
+    assert_eq!(
+        ct::Event::try_from(Event::Resize { w: 80, h: 24 }).unwrap(),
+        ct::Event::Resize(80, 24)
+    );
+    assert!(matches!(
+        ct::Event::try_from(Event::Status(Status::Ok)),
+        Err(Error::NoEquivalent)
+    ));
+    let release = ct::KeyEvent::new_with_kind(
+        ct::KeyCode::Enter,
+        ct::KeyModifiers::NONE,
+        ct::KeyEventKind::Release,
+    );
+    assert!(Key::try_from(release).is_err());
+}
+
+#[test]
+fn test_parser() {
+    let input: &[u8] = b"a\xc3\xa1\x1b[A\x1bOP\x1b\x1b[B\x1bx\x1b[<0;5;6M\
+        \x1b[M #$\x1bP>|xterm(1)\x1b\\\x1b]11;rgb:0/0/0\x07\x1b[200~a\r\
+        \x1b[201~\x9bC";
+
+    // Parses the same events as terminal.
+    let mut t = ScriptedInput::new().bytes(input).terminal();
+    let mut expected = vec![];
+    while t.has_buffered_input() || t.io().has_input() {
+        expected.push(t.read_ambigous().unwrap());
+    }
+    let mut parser = Parser::new();
+    assert_eq!(parser.feed(input).collect::<Vec<_>>(), expected);
+    assert!(parser.pending().is_empty());
+
+    // The result doesn't depend on how the input is split.
+    let mut parser = Parser::new();
+    let mut res = vec![];
+    for b in input {
+        res.extend(parser.feed(&[*b]));
+    }
+    assert_eq!(res, expected);
+
+    let mut parser = Parser::new();
+    assert_eq!(parser.feed(b"\x1b]11;rgb").count(), 0);
+    assert_eq!(parser.pending(), b"\x1b]11;rgb");
+    assert_eq!(parser.feed(b"\x1b").count(), 0);
+    assert_eq!(
+        parser.feed(b"\\").next().unwrap().event,
+        AnyEvent::Unknown(b"\x1b]11;rgb\x1b\\".into())
+    );
+    assert_eq!(parser.flush(), None);
+
+    assert_eq!(parser.feed(b"\x1b").count(), 0);
+    assert_eq!(
+        parser.flush().unwrap().event,
+        AnyEvent::Known(Event::KeyPress(Key::code(KeyCode::Esc)))
+    );
+    assert_eq!(parser.feed(b"\x1b[").count(), 0);
+    assert_eq!(parser.flush().unwrap(), AmbigousEvent::from_code(b"\x1b["));
+
+    // Function keys in the linux console.
+    let keys = [
+        (input::LINUX_F1, KeyCode::F1),
+        (input::LINUX_F2, KeyCode::F2),
+        (input::LINUX_F3, KeyCode::F3),
+        (input::LINUX_F4, KeyCode::F4),
+        (input::LINUX_F5, KeyCode::F5),
+    ];
+    for (code, key) in keys {
+        let mut parser = Parser::new();
+        let evt = AnyEvent::Known(Event::KeyPress(Key::code(key)));
+        let res: Vec<_> = parser.feed(code.as_bytes()).collect();
+        assert_eq!(res.len(), 1, "{code:?}");
+        assert_eq!(res[0].event, evt);
+        assert_eq!(parser.feed(b"\x1b[[").count(), 0);
+        assert_eq!(
+            parser.feed(&code.as_bytes()[3..]).next().unwrap().event,
+            evt
+        );
+    }
 }
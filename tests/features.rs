@@ -0,0 +1,31 @@
+use std::{env, process::Command};
+
+/// Gets the dependencies of `termal_core` built with the given features.
+fn core_deps(features: &str) -> Vec<String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let out = Command::new(cargo)
+        .args(["tree", "-p", "termal_core", "--no-default-features"])
+        .args(["--features", features])
+        .args(["-e", "normal", "--prefix", "none", "--offline"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    String::from_utf8(out.stdout)
+        .unwrap()
+        .lines()
+        .skip(1)
+        .map(|l| l.to_string())
+        .collect()
+}
+
+#[test]
+fn test_no_features_no_deps() {
+    assert_eq!(core_deps(""), Vec::<String>::new());
+    assert_eq!(core_deps("term_image,terminfo"), Vec::<String>::new());
+    assert!(!core_deps("raw").is_empty());
+}
@@ -0,0 +1,44 @@
+use termal::fill::{
+    char_width, str_width, write_fill_line, write_repeat_to_width,
+};
+
+#[test]
+fn test_width() {
+    assert_eq!(char_width('a'), 1);
+    assert_eq!(char_width('界'), 2);
+    assert_eq!(char_width('\u{301}'), 0);
+    assert_eq!(char_width('\n'), 0);
+    assert_eq!(str_width("a界b"), 4);
+}
+
+#[test]
+fn test_repeat_to_width() {
+    let rep = |s: &str, w, r| {
+        let mut res = String::new();
+        let written = write_repeat_to_width(&mut res, s, w, r);
+        (res, written)
+    };
+
+    assert_eq!(rep("-", 3, true), ("---".into(), 3));
+    assert_eq!(rep("-", 10, true), ("-\x1b[9b".into(), 10));
+    assert_eq!(rep("-", 10, false), ("-".repeat(10), 10));
+    assert_eq!(rep("界", 5, false), ("界界".into(), 4));
+    assert_eq!(rep("界", 20, true), ("界\x1b[9b".into(), 20));
+    assert_eq!(rep("a界", 7, true), ("a界a界a".into(), 7));
+    assert_eq!(rep("", 7, true), ("".into(), 0));
+    assert_eq!(rep("ab", 0, true), ("".into(), 0));
+}
+
+#[test]
+fn test_fill_line() {
+    let fill = |s: &str, w, r| {
+        let mut res = String::new();
+        write_fill_line(&mut res, s, w, r);
+        res
+    };
+
+    assert_eq!(fill("=", 4, false), "====");
+    assert_eq!(fill("界", 3, false), "界 ");
+    assert_eq!(fill("a界", 5, false), "a界a ");
+    assert_eq!(fill("", 3, false), "   ");
+}
@@ -0,0 +1,47 @@
+use termal::{
+    columns::{ColumnPrinter, ColumnSpec},
+    glyphs::GlyphSet,
+    wrap::visible_width,
+};
+
+#[test]
+fn test_glyphs_for_terminal() {
+    let unicode = GlyphSet::UNICODE;
+    assert_eq!(
+        GlyphSet::for_terminal(Some("en_US.UTF-8"), Some("xterm")),
+        unicode
+    );
+    assert_eq!(GlyphSet::for_terminal(Some("C.utf8"), None), unicode);
+    assert_eq!(
+        GlyphSet::for_terminal(Some("C"), Some("xterm-256color")),
+        GlyphSet::DEC
+    );
+    assert_eq!(GlyphSet::for_terminal(None, Some("linux")), GlyphSet::DEC);
+    assert_eq!(GlyphSet::for_terminal(None, Some("dumb")), GlyphSet::ASCII);
+    assert_eq!(GlyphSet::for_terminal(Some("C"), None), GlyphSet::ASCII);
+}
+
+#[test]
+fn test_glyph_widths() {
+    for g in [GlyphSet::UNICODE, GlyphSet::ASCII, GlyphSet::DEC] {
+        assert_eq!(visible_width(g.horizontal), 1);
+        assert_eq!(visible_width(g.cross), 1);
+        assert_eq!(visible_width(g.bar_filled), 1);
+        assert!(g.spinner.iter().all(|s| visible_width(s) == 1));
+    }
+    assert_eq!(GlyphSet::DEC.horizontal, "\x1b(0q\x1b(B");
+}
+
+#[test]
+fn test_column_ellipsis() {
+    let mut cols = ColumnPrinter::new(&[ColumnSpec::left().max_width(6)])
+        .glyphs(GlyphSet::ASCII);
+    cols.push_row(["longname"]);
+    cols.push_row(["abcdefg"]);
+    assert_eq!(cols.flush(), "lon...\nabc...\n");
+
+    let mut cols = ColumnPrinter::new(&[ColumnSpec::left().max_width(2)])
+        .glyphs(GlyphSet::ASCII);
+    cols.push_row(["abc"]);
+    assert_eq!(cols.flush(), "ab\n");
+}
@@ -0,0 +1,156 @@
+use std::{cell::Cell, marker::PhantomData};
+
+use termal::{
+    codes::{self, bg, fg, Multiplexer},
+    image::{
+        fit_to_cells, push_sixel, push_texel_half, push_texel_quater,
+        push_texel_thumbnail, FitMode, Image, RawImg,
+    },
+    raw::{testing::ScriptedInput, ImageOptions, ImageProtocol, TermSize},
+    Rgb,
+};
+
+/// Image that is not `Sync`.
+struct NotSync(RawImg, PhantomData<Cell<()>>);
+
+impl Image for NotSync {
+    fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    fn get_pixel(&self, x: usize, y: usize) -> Rgb {
+        self.0.get_pixel(x, y)
+    }
+}
+
+#[test]
+fn test_rows_order() {
+    let img = RawImg::from_gray(vec![10, 20, 30, 40], 1, 4);
+
+    let mut res = String::new();
+    push_texel_half(&img, &mut res, "\n", Some(1), Some(2));
+    assert_eq!(
+        res,
+        [
+            fg!(20, 20, 20),
+            bg!(10, 10, 10),
+            "▄",
+            codes::RESET,
+            "\n",
+            fg!(40, 40, 40),
+            bg!(30, 30, 30),
+            "▄",
+        ]
+        .concat()
+    );
+
+    codes::set_multiplexer(Some(Multiplexer::None));
+    let img = RawImg::from_gray([[0; 12], [255; 12]].concat(), 2, 12);
+    let mut res = String::new();
+    push_sixel(&mut res, &img);
+    assert!(res.ends_with("#0~~$-#255~~$-\x1b\\"));
+}
+
+#[test]
+fn test_not_sync() {
+    // Encoders accept images that are not `Sync` regardless of the feature
+    // `rayon`.
+    let img =
+        NotSync(RawImg::from_gray(vec![10; 12 * 12], 12, 12), PhantomData);
+    let mut res = String::new();
+    push_sixel(&mut res, &img);
+    push_texel_half(&img, &mut res, "\n", None, None);
+    push_texel_quater(&img, &mut res, "\n", None, None);
+    push_texel_thumbnail(&img, &mut res, 1, 1, 4, 4);
+    assert!(!res.is_empty());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par() {
+    use termal::image::{
+        push_sixel_par, push_texel_half_par, push_texel_quater_par,
+        push_texel_thumbnail_par,
+    };
+
+    let data = (0..40 * 30 * 3).map(|i| (i * 7 % 256) as u8).collect();
+    let img = RawImg::from_rgb(data, 40, 30);
+
+    let (mut seq, mut par) = (String::new(), String::new());
+    push_sixel(&mut seq, &img);
+    push_sixel_par(&mut par, &img);
+    push_texel_half(&img, &mut seq, "\n", Some(20), None);
+    push_texel_half_par(&img, &mut par, "\n", Some(20), None);
+    push_texel_quater(&img, &mut seq, "\n", None, Some(7));
+    push_texel_quater_par(&img, &mut par, "\n", None, Some(7));
+    push_texel_thumbnail(&img, &mut seq, 3, 2, 10, 5);
+    push_texel_thumbnail_par(&img, &mut par, 3, 2, 10, 5);
+    assert_eq!(seq, par);
+}
+
+#[test]
+fn test_fit_to_cells() {
+    let size = TermSize {
+        char_width: 100,
+        char_height: 50,
+        pixel_width: 1000,
+        pixel_height: 1000,
+    };
+    assert_eq!(size.cell_size(), Some((10, 20)));
+
+    assert_eq!(fit_to_cells(400, 200, size, FitMode::Contain), (100, 25));
+    assert_eq!(fit_to_cells(200, 400, size, FitMode::Contain), (50, 50));
+    assert_eq!(fit_to_cells(200, 400, size, FitMode::Shrink), (20, 20));
+    assert_eq!(fit_to_cells(200, 400, size, FitMode::Width), (100, 100));
+    assert_eq!(fit_to_cells(200, 400, size, FitMode::Height), (50, 50));
+    assert_eq!(fit_to_cells(1, 1000, size, FitMode::Contain), (1, 50));
+    assert_eq!(fit_to_cells(0, 10, size, FitMode::Contain), (0, 0));
+
+    // Unknown size in pixels uses cells with aspect ratio 1:2.
+    let size = TermSize {
+        pixel_width: 0,
+        pixel_height: 0,
+        ..size
+    };
+    assert_eq!(size.cell_size(), None);
+    assert_eq!(fit_to_cells(400, 400, size, FitMode::Contain), (100, 50));
+    let size = size.with_cell_size(10, 10);
+    assert_eq!(fit_to_cells(400, 400, size, FitMode::Contain), (50, 50));
+}
+
+#[test]
+fn test_show_image() {
+    codes::set_multiplexer(Some(Multiplexer::None));
+    let img = RawImg::from_gray(vec![10, 20, 30, 40], 2, 2);
+
+    let mut t = ScriptedInput::new().terminal();
+    t.io_mut().set_size(4, 3);
+    let opts = ImageOptions::default().protocol(ImageProtocol::TexelHalf);
+    t.show_image(&img, opts).unwrap();
+    let out = t.io_mut().take_written();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.matches('▄').count(), 8);
+    assert!(out.ends_with(&(codes::RESET.to_string() + t.newline())));
+
+    t.show_image(&img, opts.rect(3, 2, 4, 4)).unwrap();
+    let out = String::from_utf8(t.io_mut().take_written()).unwrap();
+    assert!(out.starts_with(&(codes::CUR_SAVE.to_string() + "\x1b[3;3H")));
+    assert!(out.contains("\x1b[4;3H"));
+    assert!(!out.contains('\n'));
+    assert!(out.ends_with(codes::CUR_LOAD));
+
+    // Auto detects sixel and asks for the size of cells.
+    let mut t = ScriptedInput::new()
+        .text("\x1b[?62;4c")
+        .text("\x1b[6;12;6t")
+        .terminal();
+    t.io_mut().set_size(4, 3);
+    t.show_image(&img, ImageOptions::default()).unwrap();
+    let out = t.io().written_str();
+    assert!(out.starts_with("\x1b[c\x1b[16t\x1bPq"));
+    assert!(out.ends_with("\x1b\\"));
+}
@@ -0,0 +1,92 @@
+use termal::raw::legacy::{Erase, LegacyOp, LegacyTranslator};
+
+#[test]
+fn test_legacy_colors() {
+    let mut t = LegacyTranslator::new();
+    assert_eq!(
+        t.feed(b"\x1b[1;31;104mA\x1b[0m\x1b[39;49;7;27;22m"),
+        [
+            LegacyOp::Bold(true),
+            LegacyOp::Fg(Some(1)),
+            LegacyOp::Bg(Some(12)),
+            LegacyOp::Text(b"A".to_vec()),
+            LegacyOp::Reset,
+            LegacyOp::Fg(None),
+            LegacyOp::Bg(None),
+            LegacyOp::Reverse(true),
+            LegacyOp::Reverse(false),
+            LegacyOp::Bold(false),
+        ]
+    );
+    assert_eq!(t.feed(b"\x1b[m"), [LegacyOp::Reset]);
+    assert_eq!(
+        t.feed(b"\x1b[38;5;196;48;2;250;250;250;38:2::0:0:120m"),
+        [
+            LegacyOp::Fg(Some(9)),
+            LegacyOp::Bg(Some(15)),
+            LegacyOp::Fg(Some(4)),
+        ]
+    );
+}
+
+#[test]
+fn test_legacy_cursor() {
+    let mut t = LegacyTranslator::new();
+    assert_eq!(
+        t.feed(b"\x1b[A\x1b[3B\x1b[2C\x1b[D\x1b[2E\x1b[5G\x1b[H\x1b[4;2f"),
+        [
+            LegacyOp::MoveBy { x: 0, y: -1 },
+            LegacyOp::MoveBy { x: 0, y: 3 },
+            LegacyOp::MoveBy { x: 2, y: 0 },
+            LegacyOp::MoveBy { x: -1, y: 0 },
+            LegacyOp::MoveBy { x: 0, y: 2 },
+            LegacyOp::MoveTo {
+                x: Some(0),
+                y: None
+            },
+            LegacyOp::MoveTo {
+                x: Some(4),
+                y: None
+            },
+            LegacyOp::MoveTo {
+                x: Some(0),
+                y: Some(0)
+            },
+            LegacyOp::MoveTo {
+                x: Some(1),
+                y: Some(3)
+            },
+        ]
+    );
+    assert_eq!(
+        t.feed(b"\x1b[J\x1b[2J\x1b[1K\x1b[K"),
+        [
+            LegacyOp::Erase(Erase::ToScreenEnd),
+            LegacyOp::Erase(Erase::Screen),
+            LegacyOp::Erase(Erase::ToLineStart),
+            LegacyOp::Erase(Erase::ToLineEnd),
+        ]
+    );
+}
+
+#[test]
+fn test_legacy_unsupported() {
+    let mut t = LegacyTranslator::new();
+    assert_eq!(
+        t.feed(b"a\x1b[?25l\x1b]0;title\x07b\x1bP$qm\x1b\\c\x1b7\x1b(Bd"),
+        [
+            LegacyOp::Text(b"a".to_vec()),
+            LegacyOp::Text(b"b".to_vec()),
+            LegacyOp::Text(b"c".to_vec()),
+            LegacyOp::Text(b"d".to_vec()),
+        ]
+    );
+
+    // Codes may be split between writes.
+    assert_eq!(t.feed(b"x\x1b"), [LegacyOp::Text(b"x".to_vec())]);
+    assert_eq!(t.feed(b"[3"), []);
+    assert_eq!(
+        t.feed(b"2my"),
+        [LegacyOp::Fg(Some(2)), LegacyOp::Text(b"y".to_vec())]
+    );
+}
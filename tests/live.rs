@@ -0,0 +1,60 @@
+use termal::{codes, live::LiveRegion};
+
+#[test]
+fn test_live_region() {
+    let mut out = vec![];
+    let mut live = LiveRegion::new(&mut out, 2, 10).unwrap();
+    live.set_cursor_save(true);
+    assert_eq!(live.len(), 2);
+    live.log("first").unwrap();
+    live.set_line(1, "50%");
+    live.set_line(5, "ignored");
+    assert!(live.is_dirty());
+    live.draw().unwrap();
+    assert!(!live.is_dirty());
+    live.draw_if_dirty().unwrap();
+    assert_eq!(live.line(1), Some("50%"));
+    live.finish().unwrap();
+
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(
+        out,
+        [
+            "\n\n\x1b[1;8r\x1b[8;1H",
+            "\r\nfirst",
+            "\x1b7\x1b[?7l\x1b[9;1H\x1b[2K\x1b[0m\x1b[10;1H\x1b[2K50%\x1b[0m",
+            "\x1b[?7h\x1b8",
+            codes::RESET_SCROLL_REGION,
+            "\x1b[10;1H\r\n",
+        ]
+        .concat()
+    );
+
+    assert!(LiveRegion::new(vec![], 10, 10).is_err());
+}
+
+#[test]
+fn test_live_region_no_cursor_save() {
+    let mut out = vec![];
+    let mut live = LiveRegion::new(&mut out, 1, 5).unwrap();
+    live.set_cursor_save(false);
+    live.log("first").unwrap();
+    live.set_line(0, "1/3");
+    live.draw().unwrap();
+    live.log("second").unwrap();
+    drop(live);
+
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(
+        out,
+        [
+            "\n\x1b[1;4r\x1b[4;1H",
+            "\r\nfirst",
+            "\x1b[?7l\x1b[5;1H\x1b[2K1/3\x1b[0m\x1b[?7h\x1b[4;1H",
+            "\r\nsecond",
+            codes::RESET_SCROLL_REGION,
+            "\x1b[5;1H\r\n",
+        ]
+        .concat()
+    );
+}
@@ -0,0 +1,110 @@
+use std::{io::Write, time::Duration};
+
+use termal::{
+    codes,
+    raw::{
+        active_modes,
+        events::{Event, Key, KeyCode, ModeState, Status},
+        modify_active_modes,
+        testing::ScriptedInput,
+        Encoding, MouseMode, RequestPolicy, TuiSession,
+    },
+};
+
+// The tracked modes are global, so everything is tested in single test.
+#[test]
+fn test_active_modes() {
+    modify_active_modes(|m| m.clear());
+
+    let mut t = ScriptedInput::new().terminal();
+    t.set_request_policy(RequestPolicy::fixed(Duration::ZERO));
+    write!(
+        t,
+        "{}{}{}{}",
+        codes::ENABLE_MOUSE_XY_DRAG_TRACKING,
+        codes::ENABLE_MOUSE_XY_EXT,
+        codes::ENABLE_BRACKETED_PASTE_MODE,
+        codes::HIDE_CURSOR,
+    )
+    .unwrap();
+    t.set_alternate_scroll(true).unwrap();
+
+    let modes = t.active_modes();
+    assert_eq!(modes.mouse_mode(), Some(MouseMode::Drag));
+    assert_eq!(modes.mouse_encoding(), Encoding::Sgr);
+    assert!(modes.bracketed_paste());
+    assert!(modes.alternate_scroll());
+    assert!(!modes.alternate_buffer());
+    assert_eq!(modes.get(25), Some(false));
+    assert_eq!(modes.get(1049), None);
+    assert!(!modes.is_verified(2004));
+    assert_eq!(
+        modes.enabled().collect::<Vec<_>>(),
+        [1002, 1006, 1007, 2004]
+    );
+
+    write!(t, "\x1b[?1002;1006l").unwrap();
+    assert_eq!(active_modes().mouse_mode(), None);
+    assert_eq!(active_modes().mouse_encoding(), Encoding::Default);
+
+    t.io_mut()
+        .push_input(ScriptedInput::new().text("\x1b[?2004;2$y"));
+    assert_eq!(t.request_mode(2004).unwrap(), Some(ModeState::Reset));
+    let modes = active_modes();
+    assert!(!modes.bracketed_paste());
+    assert!(modes.is_verified(2004));
+
+    assert_eq!(t.request_mode(1007).unwrap(), None);
+    assert!(active_modes().alternate_scroll());
+
+    // Wheel is translated to arrows only in alternate buffer.
+    t.io_mut().push_input(
+        ScriptedInput::new()
+            .text("\x1b[<64;5;7M")
+            .text("\x1b[<65;5;7M"),
+    );
+    assert!(matches!(t.read().unwrap(), Event::Mouse(_)));
+    write!(t, "{}", codes::ENABLE_ALTERNATIVE_BUFFER).unwrap();
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Down)));
+
+    write!(t, "{}", codes::FULL_RESET).unwrap();
+    assert_eq!(active_modes().iter().count(), 0);
+
+    // Alternate buffer enabled by other means than the terminal is also
+    // considered.
+    let mut t = ScriptedInput::new()
+        .text("\x1b[<64;5;7M")
+        .text("\x1b[<65;5;7M")
+        .terminal();
+    t.set_alternate_scroll(true).unwrap();
+    assert!(!t.is_alternate_buffer());
+    let session = TuiSession::builder()
+        .alternate_buffer(true)
+        .enter()
+        .unwrap();
+    assert!(t.is_alternate_buffer());
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Up)));
+    session.leave().unwrap();
+    assert!(!t.is_alternate_buffer());
+    assert!(matches!(t.read().unwrap(), Event::Mouse(_)));
+
+    // Codes split across writes are tracked.
+    t.write_all(b"\x1b[?10").unwrap();
+    t.write_all(b"49h").unwrap();
+    assert!(active_modes().alternate_buffer());
+    assert!(t.is_alternate_buffer());
+    t.write_all(b"a\x1b").unwrap();
+    t.write_all(b"[?1049;2004").unwrap();
+    t.write_all(b"l").unwrap();
+    assert_eq!(active_modes().get(1049), Some(false));
+    assert_eq!(active_modes().get(2004), Some(false));
+    assert!(!t.is_alternate_buffer());
+    write!(t, "{}", codes::FULL_RESET).unwrap();
+
+    let status = Status::Mode {
+        mode: 1049,
+        state: ModeState::PermanentlySet,
+    };
+    assert_eq!(Event::Status(status.clone()).to_ansi(), b"\x1b[?1049;3$y");
+    assert_eq!(status.to_string(), "mode 1049: permanently set");
+}
@@ -0,0 +1,54 @@
+#![cfg(unix)]
+
+use std::{
+    io::{Read, Write},
+    process::Command,
+};
+
+use termal::raw::{pty::Pty, IoProvider};
+
+fn read_all(pty: &mut Pty) -> String {
+    let mut out = vec![];
+    // Reading fails with error once the child exits.
+    _ = pty.read_to_end(&mut out);
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[test]
+fn test_pty_output() {
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", "test -t 0 && test -t 1 && stty size"]);
+    let mut pty = Pty::spawn_sized(cmd, 100, 30).unwrap();
+    assert!(pty.wait().unwrap().success());
+    assert_eq!(read_all(&mut pty), "30 100\r\n");
+}
+
+#[test]
+fn test_pty_provider() {
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", "stty raw -echo && echo ok && sleep 5"]);
+    let mut pty = Pty::spawn_sized(cmd, 50, 20).unwrap();
+    let io = pty.provider().unwrap();
+    let size = io.term_size().unwrap();
+    assert_eq!((size.char_width, size.char_height), (50, 20));
+
+    let mut out = vec![];
+    let mut buf = [0; 64];
+    while !out.ends_with(b"ok\n") {
+        let len = pty.read(&mut buf).unwrap();
+        out.extend_from_slice(&buf[..len]);
+    }
+    assert!(io.is_out_raw());
+    pty.child_mut().kill().unwrap();
+    pty.wait().unwrap();
+}
+
+#[test]
+fn test_pty_input() {
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", "read a; echo \"got $a\""]);
+    let mut pty = Pty::spawn(cmd).unwrap();
+    pty.write_all(b"hello\n").unwrap();
+    assert!(pty.wait().unwrap().success());
+    assert_eq!(read_all(&mut pty), "hello\r\ngot hello\r\n");
+}
@@ -0,0 +1,88 @@
+use std::io::{Read, Write};
+
+use termal::raw::{
+    recording::{
+        play, read_cast, CastEvent, CastEventKind, CastHeader, CastWriter,
+        Recorder,
+    },
+    testing::ScriptedInput,
+};
+
+#[test]
+fn test_record_terminal() {
+    let mut header = CastHeader::new(100, 30);
+    header.title = Some("te\"st".into());
+    header.env = vec![("TERM".into(), "xterm".into())];
+    let cast = CastWriter::new(vec![], &header).unwrap();
+    let term = ScriptedInput::new().text("ab").terminal();
+    let mut rec = Recorder::new(term, cast);
+
+    // The character `č` is split between two writes.
+    rec.write_all(b"\x1b[31mhi\n\xc4").unwrap();
+    rec.write_all(b"\x8d").unwrap();
+    let mut buf = [0; 2];
+    rec.read_exact(&mut buf).unwrap();
+    rec.cast_mut().resize(80, 24).unwrap();
+    rec.cast_mut().marker("end").unwrap();
+
+    let (term, cast) = rec.into_parts();
+    assert_eq!(term.io().written(), "\x1b[31mhi\nč".as_bytes());
+    let data = cast.into_inner().unwrap();
+    let (h, events) = read_cast(data.as_slice()).unwrap();
+    assert_eq!(h, header);
+
+    let events: Vec<_> =
+        events.into_iter().map(|e| (e.kind, e.data)).collect();
+    assert_eq!(
+        events,
+        [
+            (CastEventKind::Output, "\x1b[31mhi\n".to_string()),
+            (CastEventKind::Output, "č".to_string()),
+            (CastEventKind::Input, "ab".to_string()),
+            (CastEventKind::Resize, "80x24".to_string()),
+            (CastEventKind::Marker, "end".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_record_pty_directions() {
+    let cast = CastWriter::new(vec![], &CastHeader::new(80, 24)).unwrap();
+    let mut rec = Recorder::pty(&b"out"[..], cast);
+    let mut buf = vec![];
+    rec.read_to_end(&mut buf).unwrap();
+
+    let (_, cast) = rec.into_parts();
+    let data = cast.into_inner().unwrap();
+    let (_, events) = read_cast(data.as_slice()).unwrap();
+    assert_eq!(events[0].kind, CastEventKind::Output);
+    assert_eq!(events[0].data, "out");
+}
+
+#[test]
+fn test_read_cast() {
+    let cast = r#"{"version": 2, "width": 80, "height": 24, "idle_time_limit": 0.5}
+[0.25, "o", "a\u001b[1m😀"]
+[1.5, "i", "x"]
+
+[3.0, "o", "b"]
+"#;
+    let (header, events) = read_cast(cast.as_bytes()).unwrap();
+    assert_eq!(header.idle_time_limit, Some(0.5));
+    assert_eq!(
+        events[0],
+        CastEvent {
+            time: 0.25,
+            kind: CastEventKind::Output,
+            data: "a\x1b[1m😀".into(),
+        }
+    );
+    assert_eq!(events.len(), 3);
+
+    let mut out = vec![];
+    play(cast.as_bytes(), &mut out, 1000.).unwrap();
+    assert_eq!(out, "a\x1b[1m😀b".as_bytes());
+
+    assert!(read_cast(r#"{"version": 1}"#.as_bytes()).is_err());
+    assert!(read_cast("{\"version\": 2}\n[0, \"o\"]".as_bytes()).is_err());
+}
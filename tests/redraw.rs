@@ -0,0 +1,27 @@
+use std::{thread, time::Duration};
+
+use termal::redraw::RedrawScheduler;
+
+#[test]
+fn test_redraw_scheduler() {
+    let sched = RedrawScheduler::with_burst(10., 2);
+    let shared = sched.clone();
+
+    assert!(sched.request());
+    assert!(shared.request());
+    assert!(!sched.request());
+    assert!(!shared.request());
+    assert!(shared.is_pending());
+    assert!(sched.wait_time() > Duration::ZERO);
+    assert!(!sched.request_pending());
+
+    thread::sleep(sched.wait_time() + Duration::from_millis(1));
+    assert!(sched.request_pending());
+    assert!(!shared.is_pending());
+    assert!(!sched.request_pending());
+
+    sched.set_rate(0.);
+    assert_eq!(shared.rate(), 0.);
+    assert!((0..10).all(|_| shared.request()));
+    assert_eq!(sched.wait_time(), Duration::ZERO);
+}
@@ -0,0 +1,160 @@
+use std::fmt::Write;
+
+use termal::{
+    codes, formatc, Attrs, Color, ResetPolicy, Rgb, Style, StyledWriter,
+};
+
+#[test]
+fn test_style_code() {
+    assert_eq!(Style::new().code(), "");
+    assert_eq!(Style::from(Attrs::BOLD).code(), codes::BOLD);
+    assert_eq!(
+        Style::new()
+            .fg(Color::Basic(1))
+            .bg(Color::Basic(12))
+            .attr(Attrs::BLINK | Attrs::INVISIBLE)
+            .code(),
+        "\x1b[5;8;31;104m"
+    );
+    assert_eq!(
+        Style::new().fg((1, 2, 3)).underline_color(5).code(),
+        "\x1b[38;2;1;2;3;58;5;5m"
+    );
+    assert_eq!(
+        Style::from(Attrs::OVERLINE | Attrs::STRIKETROUGH).code(),
+        "\x1b[9;53m"
+    );
+}
+
+#[test]
+fn test_style_transition() {
+    let bold = Style::from(Attrs::BOLD | Attrs::FAINT | Attrs::ITALIC);
+    let faint = Style::from(Attrs::FAINT | Attrs::ITALIC);
+    assert_eq!(bold.transition(&faint), "\x1b[22;2m");
+    assert_eq!(faint.transition(&bold), codes::BOLD);
+    assert_eq!(bold.transition(&bold), "");
+
+    let under = Style::from(Attrs::UNDERLINE).fg(Color::Basic(1));
+    let dunder = Style::from(Attrs::DOUBLE_UNDERLINE).fg(Color::Basic(1));
+    assert_eq!(under.transition(&dunder), "\x1b[24;21m");
+    // Full reset is shorter here.
+    assert_eq!(
+        Style::from(Attrs::UNDERLINE)
+            .transition(&Style::from(Attrs::DOUBLE_UNDERLINE)),
+        "\x1b[0;21m"
+    );
+
+    let all = Style::from(Attrs::ALL);
+    assert_eq!(all.reset_code(), codes::RESET);
+    assert_eq!(
+        Style::from(Attrs::BLINK | Attrs::OVERLINE)
+            .transition(&Style::from(Attrs::OVERLINE)),
+        codes::RESET_BLINKING
+    );
+
+    let red = Style::new().fg(Color::Basic(1));
+    assert_eq!(red.transition(&Style::new()), codes::RESET);
+    assert_eq!(
+        red.attr(Attrs::BOLD).transition(&Style::from(Attrs::BOLD)),
+        codes::RESET_FG
+    );
+    assert_eq!(
+        red.attr(Attrs::ITALIC)
+            .transition(&Style::from(Attrs::ITALIC).bg(Color::Basic(1))),
+        "\x1b[39;41m"
+    );
+}
+
+#[test]
+fn test_rgb_codes() {
+    let c = Rgb::new(1, 2, 3);
+    assert_eq!(c.fg(), codes::fg!(1, 2, 3));
+    assert_eq!(c.bg(), codes::bg!(1, 2, 3));
+    assert_eq!(c.underline(), codes::underline_rgb!(1, 2, 3));
+    assert_eq!(c.underline(), Color::Rgb(c).underline_code());
+}
+
+#[test]
+fn test_underline_styles() {
+    assert_eq!(codes::CURLY_UNDERLINE, "\x1b[4:3m");
+    assert_eq!(
+        formatc!("{'curly}a{'dotted}b{'dashed}c{'_u}"),
+        "\x1b[4:3ma\x1b[4:4mb\x1b[4:5mc\x1b[24m"
+    );
+    assert_eq!(Style::from(Attrs::DOTTED_UNDERLINE).code(), "\x1b[4:4m");
+    assert_eq!(
+        Style::from(Attrs::UNDERLINE)
+            .transition(&Style::from(Attrs::CURLY_UNDERLINE | Attrs::BOLD)),
+        "\x1b[0;1;4:3m"
+    );
+
+    let mut style = Style::new();
+    assert!(style.apply_sgr("\x1b[1;4:3;38:2::1:2:3;48;5;4m"));
+    assert_eq!(
+        style,
+        Style::new()
+            .attr(Attrs::BOLD | Attrs::CURLY_UNDERLINE)
+            .fg((1, 2, 3))
+            .bg(4)
+    );
+    assert!(style.apply_sgr("\x1b[4:0;22;58:2:5:6:7;39m"));
+    assert_eq!(style, Style::new().bg(4).underline_color((5, 6, 7)));
+    assert!(style.apply_sgr("\x1b[21m"));
+    assert_eq!(style.attrs, Attrs::DOUBLE_UNDERLINE);
+    assert!(style.apply_sgr("\x1b[m"));
+    assert!(style.is_default());
+    assert!(!style.apply_sgr("\x1b[?25h"));
+}
+
+#[test]
+fn test_styled_writer() {
+    let mut w = StyledWriter::new(String::new());
+    w.set_style(Style::new().fg(Color::Basic(2)).attr(Attrs::BOLD));
+    // Style without text is not written.
+    w.set_style(Color::Basic(1));
+    write!(w, "a").unwrap();
+    w.modify_style(|s| s.attrs |= Attrs::ITALIC);
+    write!(w, "b").unwrap();
+    w.write_styled(Style::new(), "c").unwrap();
+    write!(w, "").unwrap();
+    assert_eq!(
+        w.style(),
+        Style::new().fg(Color::Basic(1)).attr(Attrs::ITALIC)
+    );
+    assert_eq!(w.finish().unwrap(), "\x1b[31ma\x1b[3mb\x1b[0mc");
+}
+
+#[test]
+fn test_styled_writer_drop() {
+    let mut s = String::new();
+    {
+        let mut w = StyledWriter::new(&mut s);
+        w.set_style(Attrs::UNDERLINE);
+        write!(w, "x").unwrap();
+        w.reset_style();
+    }
+    assert_eq!(s, "\x1b[4mx\x1b[0m");
+
+    let w = StyledWriter::<String>::default();
+    assert_eq!(w.finish().unwrap(), "");
+}
+
+#[test]
+fn test_styled_writer_reset_policy() {
+    let mut w = StyledWriter::new(String::new());
+    w.set_style(Style::new().bg(Color::Basic(4)));
+    write!(w, "a\nb\n").unwrap();
+    w.reset_style();
+    writeln!(w, "c").unwrap();
+    assert_eq!(
+        w.finish().unwrap(),
+        "\x1b[44ma\x1b[0m\n\x1b[44mb\x1b[0m\nc\n"
+    );
+
+    let mut w = StyledWriter::new(String::new());
+    w.set_reset_policy(ResetPolicy::Keep);
+    assert_eq!(w.reset_policy(), ResetPolicy::Keep);
+    w.set_style(Style::new().bg(Color::Basic(4)));
+    write!(w, "a\nb").unwrap();
+    assert_eq!(w.finish().unwrap(), "\x1b[44ma\nb\x1b[0m");
+}
@@ -1,8 +1,18 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, io::Write};
 
 use termal::{
+    columns::{ColumnPrinter, ColumnSpec},
+    fill::str_width,
     formatc,
-    term_text::{TermText, TermTextSpan},
+    glyphs::GlyphSet,
+    sanitize, strip_unsafe,
+    term_text::{
+        apply_bidi, concat_styled, is_rtl, layout, position, strip_stream,
+        BidiPolicy, Line, StripWriter, TermText, TermTextMetadata,
+        TermTextSpan,
+    },
+    wrap::{wrap, wrap_lines, WrapOptions},
+    Attrs, Color, ResetPolicy, Sanitized, Style,
 };
 
 #[test]
@@ -31,4 +41,377 @@ fn test_term_text() {
     assert_eq!(sf(&text, |_| false), "");
     assert_eq!(sf(&text, |c| c.is_control()), formatc!("{'r}{'_}"));
     assert_eq!(sf(&text, |c| !c.is_control()), "Textíček");
+
+    // Sub-parameters separated with `:` are part of the sequence.
+    let s = formatc!("{'curly #010203}a{'_}");
+    assert_eq!(TermText::new(&s).strip_control(), "a");
+}
+
+#[test]
+fn test_cache_metadata() {
+    let s = formatc!("Text{'r}íček{'_}");
+
+    let text = TermText::new(&s);
+    assert!(text.try_get_metadata().is_none());
+    assert_eq!(text.strip_control(), "Textíček");
+    let meta = text.try_get_metadata().unwrap();
+    assert_eq!(meta.chars, s.chars().count());
+    assert_eq!(meta.control_chars, 9);
+    assert_eq!(meta.control_bytes, 9);
+
+    let text = TermText::new(&s);
+    assert_eq!(text.to_string_cache(), s);
+    let meta = text.try_get_metadata().unwrap();
+    assert_eq!(meta.chars, s.chars().count());
+    assert_eq!(meta.control_chars, 9);
+    assert_eq!(meta.control_bytes, 9);
+
+    // Cached metadata is used and it is not recomputed. The metadata is
+    // intentionally wrong, so that it is visible if it is used.
+    let fake = TermTextMetadata {
+        chars: 100,
+        control_chars: 10,
+        control_bytes: 0,
+    };
+    let text = unsafe { TermText::from_metadata(&s, fake) };
+    assert_eq!(text.strip_control(), "Textíček");
+    assert_eq!(text.to_string_cache(), s);
+    assert_eq!(text.to_string(), s);
+    assert_eq!(text.display_char_cnt(), 90);
+    let text = text.reference().to_owned();
+    assert_eq!(text.control_char_cnt(), 10);
+    let meta = text.try_get_metadata().unwrap();
+    assert_eq!(meta.chars, 100);
+    assert_eq!(meta.control_bytes, 0);
+}
+
+#[test]
+fn test_spans_edge_cases() {
+    let spans = |s: &str| -> Vec<(String, usize, bool)> {
+        TermText::new(s)
+            .spans()
+            .map(|s| (s.text().to_string(), s.chars(), s.is_control()))
+            .collect()
+    };
+    let sp = |s: &str, c| (s.to_string(), s.chars().count(), c);
+
+    assert_eq!(
+        spans("příliš žluťoučký kůň\x7fx"),
+        [
+            sp("příliš žluťoučký kůň", false),
+            sp("\x7f", true),
+            sp("x", false)
+        ]
+    );
+    assert_eq!(
+        spans("a\x1bOá\x1b]8;;ú\x1b\\b\x1b[1;2"),
+        [
+            sp("a", false),
+            sp("\x1bOá", true),
+            sp("\x1b]8;;ú\x1b\\", true),
+            sp("b", false),
+            sp("\x1b[1;2", true),
+        ]
+    );
+    assert_eq!(
+        spans("\x1b(0q\x1b7\x1bé\x1b"),
+        [
+            sp("\x1b(0", true),
+            sp("q", false),
+            sp("\x1b7", true),
+            sp("\x1b", true),
+            sp("é", false),
+            sp("\x1b", true),
+        ]
+    );
+    assert_eq!(spans("\x1b_unterminated"), [sp("\x1b_unterminated", true)]);
+
+    // Metadata and stripping agree with the spans.
+    let long = "ab\tcd ".repeat(20) + &formatc!("{'r}éé{'_}\n").repeat(10);
+    for s in ["", "abc", "\x1bOá", long.as_str()] {
+        let text = TermText::new(s);
+        let (chars, control): (usize, usize) =
+            text.spans().fold((0, 0), |(a, c), s| {
+                (a + s.chars(), c + s.is_control() as usize * s.chars())
+            });
+        assert_eq!(text.char_cnt(), chars);
+        assert_eq!(text.control_char_cnt(), control);
+        let stripped: String = text
+            .spans()
+            .filter(|s| !s.is_control())
+            .map(|s| s.text())
+            .collect();
+        assert_eq!(TermText::new(s).strip_control(), stripped);
+        assert_eq!(TermText::new(s).to_string_cache(), s);
+    }
+}
+
+#[test]
+fn test_sanitize() {
+    let evil = "a\x1b]0;title\x1b\\b\x1b[2J\u{9b}31mc\r\x08\td\n";
+    assert_eq!(
+        sanitize(evil),
+        "a\\x1b]0;title\\x1b\\b\\x1b[2J\\u{9b}31mc\\r\\x08\td\n"
+    );
+    assert_eq!(strip_unsafe(evil), "ab31mc\td\n");
+    assert_eq!(
+        strip_unsafe("\x1b]0;title\x07hello\nworld\n"),
+        "hello\nworld\n"
+    );
+    assert_eq!(sanitize("příliš"), "příliš");
+
+    let s = formatc!("{'r}{}{'_}", Sanitized("x\x1b[0my"));
+    assert_eq!(s, formatc!("{'r}x\\x1b[0my{'_}"));
+}
+
+#[test]
+fn test_strip_stream() {
+    let data = "a\x1b]0;title\x1b\\b\x1b[2J\u{9b}31mc\r\x08\td\n\
+        \x1b(0x\x1bOčy\x1b\x07ž\x1b\x1b[1m\u{a0}z\x1bP\x1b\x1b\\end\
+        \x1b]0;title\x07hello\n\x1b]8;;http://a.b\x07link\x1b]8;;\x07\n";
+    let stripped = strip_unsafe(data);
+    assert_eq!(stripped, "ab31mc\td\nxyž\u{a0}zendhello\nlink\n");
+
+    let mut out = vec![];
+    let len = strip_stream(data.as_bytes(), &mut out).unwrap();
+    assert_eq!(out, stripped.as_bytes());
+    assert_eq!(len, stripped.len() as u64);
+
+    // Sequences split at any position.
+    for chunk in 1..data.len() {
+        let mut w = StripWriter::new(vec![]);
+        for c in data.as_bytes().chunks(chunk) {
+            w.write_all(c).unwrap();
+        }
+        assert!(!w.is_in_sequence());
+        assert_eq!(w.written(), stripped.len() as u64);
+        assert_eq!(w.into_inner(), stripped.as_bytes(), "chunk {chunk}");
+    }
+
+    let mut w = StripWriter::new(vec![]);
+    w.write_all(b"a\x1b]8;;http").unwrap();
+    assert!(w.is_in_sequence());
+    assert_eq!(w.get_ref(), b"a");
+}
+
+#[test]
+fn test_wrap() {
+    let opts = WrapOptions::new(10);
+    assert_eq!(
+        wrap_lines("hello there general kenobi", &opts),
+        ["hello", "there", "general", "kenobi"]
+    );
+    assert_eq!(wrap("abcdefghijklm xy", &opts), "abcdefghij\nklm xy");
+    assert_eq!(wrap("界界界界界界", &opts), "界界界界界\n界");
+
+    // Styled indents and styles that continue on the next line.
+    let opts = WrapOptions::new(9)
+        .initial_indent(formatc!("{'gr}*{'_} "))
+        .subsequent_indent("  ");
+    assert_eq!(
+        wrap(&formatc!("{'r}aaa bbb{'_} ccc"), &opts),
+        formatc!("{'gr}*{'_} {'r}aaa bbb{'_}\n  ccc")
+    );
+    assert_eq!(
+        wrap(&formatc!("{'r}aaa bbb ccc{'_} d"), &opts),
+        formatc!("{'gr}*{'_} {'r}aaa bbb{'_}\n  {'r}ccc{'_} d")
+    );
+    let opts = opts.reset_policy(ResetPolicy::Keep);
+    assert_eq!(
+        wrap(&formatc!("{'r}aaa bbb ccc{'_} d"), &opts),
+        formatc!("{'gr}*{'_} {'r}aaa bbb\n  ccc{'_} d")
+    );
+
+    let opts = WrapOptions::new(10).indent("> ").preserve_indent(true);
+    assert_eq!(
+        wrap_lines("a b c d e\n  f g h i\n\n", &opts),
+        ["> a b c d", "> e", ">   f g h", ">   i", "> ", "> "]
+    );
+
+    // Break opportunities.
+    let opts = WrapOptions::new(8);
+    assert_eq!(wrap("a b c d\u{a0}e f", &opts), "a b c\nd\u{a0}e f");
+    let opts = WrapOptions::new(10);
+    assert_eq!(
+        wrap("see wrap\u{ad}ping\u{ad}ly", &opts),
+        "see wrap-\npingly"
+    );
+    assert_eq!(
+        wrap("x long\u{200b}words\u{200b}joined", &opts),
+        "x long\nwords\njoined"
+    );
+    assert_eq!(wrap("wrap\u{ad}ping", &opts), "wrapping");
+    assert_eq!(wrap("abcdefghijkl\u{ad}m", &opts), "abcdefghij\nklm");
+}
+
+#[test]
+fn test_layout() {
+    let line = |start, end, width, wrapped| Line {
+        start,
+        end,
+        width,
+        wrapped,
+    };
+
+    assert_eq!(layout("", 5), [line(0, 0, 0, false)]);
+    assert_eq!(
+        layout("abcdefg\n\nab", 5),
+        [
+            line(0, 5, 5, true),
+            line(5, 7, 2, false),
+            line(8, 8, 0, false),
+            line(9, 11, 2, false),
+        ]
+    );
+    // Full line doesn't wrap until the next character.
+    assert_eq!(layout("abcde", 5), [line(0, 5, 5, false)]);
+    assert_eq!(layout("abcde\n", 5).len(), 2);
+    // Wide character that doesn't fit moves to the next line.
+    assert_eq!(
+        layout("abcd界", 5),
+        [line(0, 4, 4, true), line(4, 7, 2, false)]
+    );
+    assert_eq!(layout("abcdefg", 0), [line(0, 7, 7, false)]);
+
+    // Control sequences have zero width and stay on the line.
+    let text = formatc!("{'r}abc{'_}de{'b}f");
+    let lines = TermText::new(&text).layout(5);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].text(&text), formatc!("{'r}abc{'_}de{'b}"));
+    assert_eq!(lines[1].text(&text), "f");
+
+    assert_eq!(position(&text, &lines, 0), (0, 0));
+    assert_eq!(position(&text, &lines, text.find('c').unwrap()), (0, 2));
+    assert_eq!(position(&text, &lines, text.find('f').unwrap()), (1, 0));
+    assert_eq!(position(&text, &lines, text.len()), (1, 1));
+
+    let text = "ab\ncd";
+    let lines = layout(text, 5);
+    assert_eq!(position(text, &lines, 2), (0, 2));
+    assert_eq!(position(text, &lines, 3), (1, 0));
+    assert_eq!(lines[1].column(text, 0), 0);
+}
+
+#[test]
+fn test_slice_display() {
+    let s = formatc!("ab{'r}cd{'b}ef{'_}gh");
+    let text = TermText::new(&s);
+    assert_eq!(text.slice_display(..).as_str(), s);
+    assert_eq!(text.slice_display(0..0).as_str(), "");
+    assert_eq!(text.slice_display(10..).as_str(), "");
+    assert_eq!(text.slice_display(..2).as_str(), "ab");
+    assert_eq!(text.slice_display(..3).as_str(), formatc!("ab{'r}c{'_}"));
+    assert_eq!(
+        text.slice_display(3..5).as_str(),
+        formatc!("{'r}d{'b}e{'_}")
+    );
+    assert_eq!(text.slice_display(6..).as_str(), "gh");
+
+    let (a, b) = text.split_at_display(4);
+    assert_eq!(a.as_str(), formatc!("ab{'r}cd{'_}"));
+    assert_eq!(b.as_str(), formatc!("{'b}ef{'_}gh"));
+    assert_eq!(str_width(&a.strip_control()), 4);
+
+    // Cut wide characters are replaced with spaces.
+    let text = TermText::new("a界b界");
+    assert_eq!(text.slice_display(2..).as_str(), " b界");
+    assert_eq!(text.slice_display(..2).as_str(), "a ");
+    assert_eq!(text.slice_display(2..5).as_str(), " b ");
+    assert_eq!(text.slice_display(1..=2).as_str(), "界");
+    let s = formatc!("{'rb}界");
+    assert_eq!(
+        TermText::new(&s).slice_display(..1).as_str(),
+        formatc!("{'rb} {'_}")
+    );
+}
+
+#[test]
+fn test_ensure_reset() {
+    let s = formatc!("{'r bold}a{'_}b");
+    let mut text = TermText::chached(&s);
+    assert!(text.end_style().is_default());
+    text.ensure_reset();
+    assert_eq!(text.as_str(), s);
+
+    let mut text = TermText::chached(formatc!("{'r bold}a{'_bold}b"));
+    assert_eq!(text.end_style(), Style::new().fg(Color::Basic(9)));
+    text.ensure_reset();
+    assert_eq!(text.as_str(), formatc!("{'r bold}a{'_bold}b{'_}"));
+    assert_eq!(text.control_char_cnt(), text.char_cnt() - 2);
+
+    assert_eq!(
+        concat_styled([formatc!("{'r}a"), formatc!("{'u}b{'_}"), "c".into()])
+            .as_str(),
+        formatc!("{'r}a{'_}{'u}b{'_}c")
+    );
+    assert_eq!(concat_styled(Vec::<&str>::new()).as_str(), "");
+}
+
+#[test]
+fn test_bidi() {
+    assert!(is_rtl('א') && is_rtl('ب'));
+    assert!(!is_rtl('a') && !is_rtl(' '));
+    assert!(TermText::new(formatc!("{'r}a שלום")).contains_rtl());
+    assert!(!TermText::new(formatc!("{'r}hello")).contains_rtl());
+
+    // Combining marks and isolates have no width.
+    assert_eq!(str_width("בְּ"), 1);
+    assert_eq!(str_width("\u{2068}אב\u{2069}"), 2);
+
+    let line = formatc!("> {'r}אב גד{'_}, x");
+    assert_eq!(apply_bidi(&line, BidiPolicy::Logical), line);
+    assert_eq!(
+        apply_bidi(&line, BidiPolicy::Visual),
+        formatc!("> {'r}דג בא{'_}, x")
+    );
+    assert_eq!(
+        apply_bidi(&line, BidiPolicy::Isolate),
+        formatc!("> {'r}\u{2068}אב גד\u{2069}{'_}, x")
+    );
+    assert_eq!(apply_bidi("בְּא", BidiPolicy::Visual), "אבְּ");
+
+    // Each line is reordered separately.
+    let opts = WrapOptions::new(5).bidi(BidiPolicy::Visual);
+    assert_eq!(wrap_lines("אב גד הו", &opts), ["דג בא", "וה"]);
+}
+
+#[test]
+fn test_column_printer() {
+    let mut cols = ColumnPrinter::new(&[
+        ColumnSpec::left().header("NAME").max_width(6),
+        ColumnSpec::center().min_width(5).style(Attrs::BOLD),
+        ColumnSpec::right(),
+    ])
+    .separator("|")
+    .glyphs(GlyphSet::UNICODE);
+
+    cols.push_row(["a", "b", "1"]);
+    cols.push_row([formatc!("{'r}longname{'_}"), "c".into()]);
+    assert_eq!(cols.len(), 2);
+    assert_eq!(
+        cols.flush(),
+        formatc!(
+            "NAME  |     |\n\
+            a     |  {'bold}b{'_}  |1\n\
+            {'r}longn…{'_}{'_}|  {'bold}c{'_}  |\n"
+        )
+    );
+    assert!(cols.is_empty());
+
+    // The widths are kept between batches.
+    cols.push_row(["x", "y", "100"]);
+    assert_eq!(cols.widths(), [6, 5, 3]);
+    assert_eq!(cols.flush(), formatc!("x     |  {'bold}y{'_}  |100\n"));
+
+    cols.reset_widths();
+    assert_eq!(cols.widths(), [0, 5, 0]);
+
+    let mut cols = ColumnPrinter::new(&[
+        ColumnSpec::left(),
+        ColumnSpec::left().style(Attrs::BOLD),
+    ])
+    .reset_policy(ResetPolicy::Keep);
+    cols.push_row(["a", "b"]);
+    cols.push_row(["c", "d"]);
+    assert_eq!(cols.flush(), formatc!("a  {'bold}b\n{'_}c  {'bold}d\n{'_}"));
 }
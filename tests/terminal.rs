@@ -1,9 +1,35 @@
-use std::time::Duration;
+use std::{io::Write, time::Duration};
 
 use common::BufProvider;
 use termal::{
+    codes::{self, CursorStyle},
+    diagnostics::Report,
     error::Error,
-    raw::{events::AmbigousEvent, Terminal},
+    glyphs::GlyphSet,
+    image::{push_texel_thumbnail, RawImg},
+    raw::{
+        debug::key_inspector_on,
+        events::{
+            mouse::{self, Mouse},
+            AmbigousEvent, Event, Key, KeyCode, Modifiers, Status, StatusCode,
+            TermFeatures, TermType,
+        },
+        is_raw_mode_enabled, is_terminal_raw,
+        readers::{
+            CancelPolicy, EditAction, EditMode, ModeIndicator, ReadConf,
+            Select, TermRead, Validation,
+        },
+        request::{
+            char_size_on, cursor_position_on, cursor_style_on,
+            device_attributes_on, query_setting_on, supports_sixel_on,
+            try_request_no_raw, verify_truecolor_on, window_title_on, Setting,
+            SettingKind, Support,
+        },
+        testing::ScriptedInput,
+        Encoding, IoProvider, MouseMode, NewlinePolicy, RequestPolicy,
+        RequestStats, Terminal, TtyProvider, TuiSession,
+    },
+    Attrs, Color, Rgb, Style,
 };
 
 mod common;
@@ -30,6 +56,15 @@ fn test_basic() {
     assert!(t.has_input()); // Eof is input
 }
 
+#[test]
+fn test_external_raw_mode() {
+    let mut t = Terminal::external(BufProvider::new(&[]));
+    assert!(t.is_external_raw_mode());
+    assert_eq!(t.is_out_raw(), is_terminal_raw());
+    t.set_external_raw_mode(false);
+    assert!(!t.is_external_raw_mode());
+}
+
 #[test]
 fn test_read_raw() {
     let mut t = Terminal::new(BufProvider::new(&[b"ab", b"cd"]));
@@ -83,6 +118,434 @@ fn test_edit_line() {
     assert_eq!(t.edit_line("there").unwrap(), "hello there");
 }
 
+#[test]
+fn test_select() {
+    let key = |c| Key::code(c);
+    let items = ["a", "b", "c", "d", "e"];
+
+    let mut t = ScriptedInput::new()
+        .key(key(KeyCode::Down))
+        .text("j")
+        .key(key(KeyCode::Up))
+        .key(key(KeyCode::Enter))
+        .terminal();
+    assert_eq!(t.select("pick:", items).unwrap(), 1);
+    assert!(t
+        .io()
+        .written_str()
+        .ends_with("pick:\x1b[0m b\x1b[0m\r\n\x1b[?25h"));
+
+    // Scrolling and jumping to the end.
+    let mut t = ScriptedInput::new()
+        .key(key(KeyCode::End))
+        .key(key(KeyCode::PgUp))
+        .key(key(KeyCode::Enter))
+        .terminal();
+    let mut select = Select::new("pick:", items)
+        .height(2)
+        .glyphs(GlyphSet::UNICODE);
+    assert_eq!(select.read(&mut t).unwrap(), 2);
+    let out = t.io().written_str();
+    let last = out.rsplit("pick:").nth(1).unwrap();
+    assert!(last.contains("> c") && last.contains("↓ \x1b[0md"));
+    assert!(!last.contains('e'));
+
+    let mut t = ScriptedInput::new()
+        .key(key(KeyCode::Space))
+        .key(key(KeyCode::Down))
+        .key(key(KeyCode::Down))
+        .key(key(KeyCode::Space))
+        .key(key(KeyCode::Down))
+        .key(key(KeyCode::Space))
+        .key(key(KeyCode::Enter))
+        .terminal();
+    let mut select = Select::new("pick:", items).checked([3]);
+    assert_eq!(select.read_multi(&mut t).unwrap(), [0, 2]);
+    assert!(t.io().written_str().contains("pick:\x1b[0m a, c\x1b[0m"));
+
+    let ctrl = Key::mcode(KeyCode::Char('c'), Modifiers::CONTROL);
+    let mut t = ScriptedInput::new().key(ctrl).terminal();
+    assert!(matches!(t.select("pick:", items), Err(Error::Interrupted)));
+    let mut t = ScriptedInput::new().terminal();
+    assert!(matches!(
+        t.select("pick:", [""; 0]),
+        Err(Error::NothingToSelect)
+    ));
+}
+
+#[test]
+fn test_select_preview() {
+    let img = RawImg::from_rgb(vec![255; 2 * 2 * 3], 2, 2);
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1b[?10;1R\x1b[0n")
+        .key(Key::code(KeyCode::Down))
+        .key(Key::code(KeyCode::Enter))
+        .terminal();
+    let mut select = Select::new("pick:", ["a", "bcd", "e"])
+        .preview(|i| (i == 0).then(|| img.clone()));
+    assert_eq!(select.read(&mut t).unwrap(), 1);
+
+    // The preview is right of the list on the rows of the list.
+    let mut thumb = String::new();
+    push_texel_thumbnail(&img, &mut thumb, 7, 8, 12, 3);
+    let preview = format!("{thumb}{}", codes::move_to!(1, 10));
+    let out = t.io().written_str();
+    assert_eq!(out.matches(&preview).count(), 1);
+
+    // The preview is erased with the list and the second item has none.
+    let (_, rest) = out.split_once(&preview).unwrap();
+    assert!(rest.contains(&format!("\r{}", codes::move_up!(3))));
+    assert!(rest.contains(codes::ERASE_TO_END));
+    assert!(!rest.contains('▄'));
+}
+
+#[test]
+fn test_prompt_confirm() {
+    let mut t = ScriptedInput::new().text("\r").terminal();
+    assert!(t.prompt_confirm("go?", true).unwrap());
+    assert!(t.io().written_str().ends_with("go? [Y/n] \r\n"));
+
+    let mut t = ScriptedInput::new().text("what\r").text("YES\r").terminal();
+    assert!(t.prompt_confirm("go?", false).unwrap());
+    assert!(t.io().written_str().contains("Answer `y` or `n`."));
+
+    let mut t = ScriptedInput::new().text("\r").terminal();
+    assert!(!t.prompt_confirm("go?", false).unwrap());
+    let mut t = ScriptedInput::new().text(" n \r").terminal();
+    assert!(!t.prompt_confirm("go?", true).unwrap());
+
+    let mut t = Terminal::new(BufProvider::eof_panic(1, &[b"what\r"]));
+    assert!(matches!(t.prompt_confirm("go?", true), Err(Error::Eof)));
+}
+
+#[test]
+fn test_prompt_parse() {
+    let mut t = ScriptedInput::new()
+        .text("abc\r")
+        .text("-1\r")
+        .text(" 42\r")
+        .terminal();
+    assert_eq!(t.prompt_parse::<u32>("num: ").unwrap(), 42);
+    let out = t.io().written_str();
+    assert_eq!(out.matches("invalid digit found in string").count(), 2);
+    assert_eq!(out.matches("num: ").count(), 3);
+
+    let ctrl = Key::mcode(KeyCode::Char('c'), Modifiers::CONTROL);
+    let mut t = ScriptedInput::new().key(ctrl).terminal();
+    assert!(matches!(
+        t.prompt_parse::<u32>("num: "),
+        Err(Error::Interrupted)
+    ));
+
+    let mut t = Terminal::new(BufProvider::eof_panic(1, &[b"abc\r"]));
+    assert!(matches!(t.prompt_parse::<u32>("num: "), Err(Error::Eof)));
+    let mut t = Terminal::new(BufProvider::eof_panic(2, &[b"abc"]));
+    assert!(matches!(t.prompt_parse::<u32>("num: "), Err(Error::Eof)));
+}
+
+#[test]
+fn test_resize_events() {
+    let is_char =
+        |e: Event, c| matches!(e, Event::KeyPress(k) if k.key_char == Some(c));
+
+    let mut t = ScriptedInput::new().text("a").text("b").terminal();
+    t.io_mut().set_size(80, 24);
+    t.set_resize_events(true);
+    assert!(is_char(t.read().unwrap(), 'a'));
+    t.io_mut().set_size(100, 30);
+    assert_eq!(t.read().unwrap(), Event::Resize { w: 100, h: 30 });
+    assert!(is_char(t.read().unwrap(), 'b'));
+
+    t.io_mut().set_size(90, 30);
+    assert_eq!(
+        t.read_timeout(Duration::ZERO).unwrap(),
+        Some(Event::Resize { w: 90, h: 30 })
+    );
+    assert_eq!(t.read_timeout(Duration::from_millis(10)).unwrap(), None);
+
+    t.set_resize_events(false);
+    t.io_mut().set_size(80, 24);
+    assert_eq!(t.read_timeout(Duration::ZERO).unwrap(), None);
+}
+
+#[test]
+fn test_validator() {
+    let validator = |input: &[char]| {
+        let input: String = input.iter().collect();
+        match "hello".strip_prefix(&input) {
+            Some(rest) => Validation::new().hint(rest),
+            None => Validation::invalid().style(Color::Ansi256(1)),
+        }
+    };
+
+    let mut t = ScriptedInput::new()
+        .text("h")
+        .key(Key::code(KeyCode::Right))
+        .text("\r")
+        .terminal();
+    let mut reader = TermRead::lines(&mut t);
+    reader.set_validator(validator);
+    assert_eq!(reader.read_str().unwrap(), "hello");
+    let out = t.io().written_str();
+    assert!(out.contains("ello\x1b[0m\x1b[4D"));
+    assert!(out.ends_with("\x1b[Jhello"));
+
+    // The hint is erased when the input ends.
+    let mut t = ScriptedInput::new().text("he").text("\r").terminal();
+    let mut reader = TermRead::lines(&mut t);
+    reader.set_validator(validator);
+    assert_eq!(reader.read_str().unwrap(), "he");
+    assert!(t.io().written_str().ends_with("\x1b[0m\x1b[3D\x1b[J"));
+
+    let mut t = ScriptedInput::new().text("x").text("\r").terminal();
+    let mut reader = TermRead::lines(&mut t);
+    reader.set_validator(validator);
+    assert_eq!(reader.read_str().unwrap(), "x");
+    assert!(t.io().written_str().contains("\x1b[38;5;1mx\x1b[0m"));
+}
+
+#[test]
+fn test_read_cancel() {
+    let ctrl = |c| Key::mcode(KeyCode::Char(c), Modifiers::CONTROL);
+
+    let mut t = ScriptedInput::new().text("ab").key(ctrl('c')).terminal();
+    assert!(matches!(t.read_line(), Err(Error::Interrupted)));
+
+    let mut t = ScriptedInput::new().key(ctrl('d')).terminal();
+    assert!(matches!(t.read_line(), Err(Error::Eof)));
+
+    let mut t = ScriptedInput::new()
+        .text("ab")
+        .key(Key::code(KeyCode::Left))
+        .key(ctrl('d'))
+        .text("\r")
+        .terminal();
+    assert_eq!(t.read_line().unwrap(), "a");
+
+    let mut s = "keep".to_string();
+    let mut t = ScriptedInput::new().key(ctrl('c')).terminal();
+    assert!(matches!(t.edit_line_in(&mut s), Err(Error::Interrupted)));
+    assert_eq!(s, "keep");
+
+    let mut t = ScriptedInput::new()
+        .text("ab")
+        .key(ctrl('c'))
+        .key(ctrl('d'))
+        .text("\r")
+        .terminal();
+    let mut r = TermRead::from_config(
+        &mut t,
+        KeyCode::Enter,
+        ReadConf {
+            cancel: CancelPolicy::NONE,
+            ..Default::default()
+        },
+    );
+    assert_eq!(r.read_str().unwrap(), "ab");
+
+    let mut t = Terminal::new(BufProvider::eof_panic(1, &[b""]));
+    let mut r = TermRead::lines(&mut t);
+    r.set_cancel_policy(CancelPolicy::ALL);
+    assert!(matches!(r.read_str(), Err(Error::Eof)));
+}
+
+#[test]
+fn test_read_bindings() {
+    let ctrl = |c| Key::mcode(KeyCode::Char(c), Modifiers::CONTROL);
+
+    // Kill line, transpose and kill word.
+    let mut t = ScriptedInput::new()
+        .text("hello wrold")
+        .key(ctrl('t'))
+        .key(Key::code(KeyCode::Home))
+        .key(Key::code(KeyCode::Right))
+        .key(ctrl('k'))
+        .text(" my")
+        .key(ctrl('w'))
+        .text("\r")
+        .terminal();
+    assert_eq!(t.read_line().unwrap(), "h ");
+
+    let mut t = ScriptedInput::new()
+        .text("ab cd")
+        .key(ctrl('b'))
+        .key(ctrl('u'))
+        .text("\r")
+        .terminal();
+    assert_eq!(t.read_line().unwrap(), "d");
+
+    // Rebound cancel and accept with custom action.
+    let mut t = ScriptedInput::new()
+        .text("ab")
+        .key(ctrl('c'))
+        .key(ctrl('x'))
+        .key(Key::code(KeyCode::Esc))
+        .terminal();
+    let mut r = TermRead::lines(&mut t);
+    r.bindings_mut().unbind(ctrl('c'));
+    r.bindings_mut()
+        .bind_str("esc", EditAction::Accept)
+        .unwrap();
+    r.bindings_mut().bind(ctrl('x'), EditAction::Custom(3));
+    r.set_custom_action(|id, input, pos| {
+        input.insert(0, char::from_digit(id, 10).unwrap());
+        *pos = 0;
+    });
+    assert_eq!(r.read_str().unwrap(), "3ab");
+
+    let mut t = ScriptedInput::new()
+        .text("ab")
+        .key(Key::code(KeyCode::Esc))
+        .terminal();
+    let mut bindings = EditAction::default_bindings();
+    bindings.bind(KeyCode::Esc, EditAction::Cancel);
+    let mut r = TermRead::from_config(
+        &mut t,
+        KeyCode::Enter,
+        ReadConf {
+            bindings: Some(bindings),
+            ..Default::default()
+        },
+    );
+    assert!(matches!(r.read_str(), Err(Error::Interrupted)));
+}
+
+#[test]
+fn test_read_vi() {
+    let esc = || Key::code(KeyCode::Esc);
+    let vi = || ReadConf::default().edit_mode(EditMode::Vi);
+
+    // `bx` deletes `w`, `ia` inserts `a` before `o`, `$a!` appends `!`.
+    let mut t = ScriptedInput::new()
+        .text("hello wrld")
+        .key(esc())
+        .text("bxiao")
+        .key(esc())
+        .text("$a!\r")
+        .terminal();
+    let mut r = TermRead::from_config(&mut t, KeyCode::Enter, vi());
+    assert_eq!(r.read_str().unwrap(), "hello aorld!");
+
+    // Unbound keys are ignored in normal mode, `S` changes the line.
+    let mut t = ScriptedInput::new()
+        .text("abc")
+        .key(esc())
+        .text("qXSxy\r")
+        .terminal();
+    let mut r = TermRead::from_config(&mut t, KeyCode::Enter, vi());
+    assert_eq!(r.read_str().unwrap(), "xy");
+    assert!(!r.is_vi_normal());
+
+    // The indicator is shown before the prompt.
+    let mut t = ScriptedInput::new()
+        .text("a")
+        .key(esc())
+        .text("\r")
+        .terminal();
+    let conf = ReadConf {
+        prompt: "> ".into(),
+        ..vi().mode_indicator(ModeIndicator::new("[I]", "[N]"))
+    };
+    let mut r = TermRead::from_config(&mut t, KeyCode::Enter, conf);
+    assert_eq!(r.read_str().unwrap(), "a");
+    let out = t.io().written_str();
+    assert!(out.contains("[I]> "));
+    assert!(out.ends_with("[N]> a\x1b[1D\x1b[1C"));
+
+    // Esc inserts nothing in emacs mode.
+    let mut t = ScriptedInput::new()
+        .text("a")
+        .key(esc())
+        .text("\r")
+        .terminal();
+    assert_eq!(t.read_line().unwrap(), "a");
+}
+
+#[test]
+fn test_read_prompt_marks() {
+    let mut t = ScriptedInput::new().text("ls\r").terminal();
+    let conf = ReadConf {
+        prompt: "$ ".into(),
+        ..Default::default()
+    }
+    .prompt_marks(true);
+    let mut r = TermRead::from_config(&mut t, KeyCode::Enter, conf);
+    assert_eq!(r.read_str().unwrap(), "ls");
+    let out = t.io().written_str();
+    assert!(out.contains("\x1b]133;A\x1b\\$ \x1b]133;B\x1b\\"));
+    assert!(out.ends_with(codes::COMMAND_OUTPUT_START));
+
+    let mut t = ScriptedInput::new().text("ls\r").terminal();
+    let conf = ReadConf {
+        prompt: "$ ".into(),
+        ..Default::default()
+    };
+    let mut r = TermRead::from_config(&mut t, KeyCode::Enter, conf);
+    assert_eq!(r.read_str().unwrap(), "ls");
+    assert!(!t.io().written_str().contains("\x1b]133"));
+}
+
+#[test]
+fn test_read_resize() {
+    let mut t = ScriptedInput::new().text("\r").terminal();
+    let conf = ReadConf {
+        prompt: "> ".into(),
+        edit: "abcdefg".chars().collect(),
+        edit_pos: Some(3),
+        ..Default::default()
+    };
+    let mut r = TermRead::from_config(&mut t, KeyCode::Enter, conf);
+    r.queue([Event::Resize { w: 4, h: 10 }]);
+    r.reshow().unwrap();
+    assert_eq!(r.finish().unwrap(), "abcdefg");
+    // The cursor is moved to the start of the prompt within the new width
+    // and everything is reprinted.
+    assert!(t
+        .io()
+        .written_str()
+        .ends_with("\r\x1b[1A\x1b[J> abcdefg\x1b[1A\x1b[1B"));
+}
+
+#[test]
+fn test_read_kill_ring() {
+    let ctrl = |c| Key::mcode(KeyCode::Char(c), Modifiers::CONTROL);
+    let alt = |c| Key::mcode(KeyCode::Char(c), Modifiers::ALT);
+
+    // Consecutive kills are joined, `alt+y` yanks the older kill.
+    let mut t = ScriptedInput::new()
+        .text("one two three")
+        .key(ctrl('w'))
+        .key(ctrl('w'))
+        .text("x")
+        .key(ctrl('u'))
+        .key(ctrl('y'))
+        .key(ctrl('y'))
+        .key(alt('y'))
+        .text("\r")
+        .terminal();
+    assert_eq!(t.read_line().unwrap(), "one xtwo three");
+
+    // `alt+y` does nothing if the previous action wasn't yank.
+    let mut t = ScriptedInput::new()
+        .text("ab")
+        .key(ctrl('u'))
+        .text("c")
+        .key(alt('y'))
+        .key(ctrl('y'))
+        .text("\r")
+        .terminal();
+    assert_eq!(t.read_line().unwrap(), "cab");
+
+    // Bracketed paste is inserted verbatim without the control characters.
+    let mut t = ScriptedInput::new()
+        .text("ad")
+        .key(Key::code(KeyCode::Left))
+        .text("\x1b[200~b\r\x03c\x1b[201~")
+        .text("\r")
+        .terminal();
+    assert_eq!(t.read_line().unwrap(), "abcd");
+}
+
 #[test]
 fn test_events() {
     let mut t = Terminal::new(BufProvider::new(&[
@@ -139,3 +602,871 @@ fn test_events() {
     assert_eq!(t.read_ambigous().unwrap(), AmbigousEvent::from_code(b"l"));
     assert!(matches!(t.read_ambigous(), Err(Error::StdInEof)));
 }
+
+#[test]
+fn test_linux_function_keys() {
+    let mut t = ScriptedInput::new()
+        .text(codes::input::LINUX_F1)
+        .text(codes::input::LINUX_F2)
+        .text("\x1b[[")
+        .text("C")
+        .text(codes::input::LINUX_F4)
+        .text(codes::input::LINUX_F5)
+        .text("a")
+        .terminal();
+    for key in [
+        KeyCode::F1,
+        KeyCode::F2,
+        KeyCode::F3,
+        KeyCode::F4,
+        KeyCode::F5,
+    ] {
+        assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(key)));
+    }
+    assert!(matches!(
+        t.read().unwrap(),
+        Event::KeyPress(Key {
+            key_char: Some('a'),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_wheel_to_arrows() {
+    let mut t = Terminal::new(BufProvider::new(&[
+        b"\x1b[<64;5;7M\x1b[<65;5;7M\x1b[<80;5;7M\x1b[<0;5;7M",
+    ]));
+    t.set_wheel_to_arrows(true);
+    assert!(t.is_wheel_to_arrows());
+    // Wheel is translated only in alternate buffer.
+    t.write_all(codes::DISABLE_ALTERNATIVE_BUFFER.as_bytes())
+        .unwrap();
+    assert!(matches!(t.read().unwrap(), Event::Mouse(_)));
+    t.write_all(codes::ENABLE_ALTERNATIVE_BUFFER.as_bytes())
+        .unwrap();
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Down)));
+    assert_eq!(
+        t.read().unwrap(),
+        Event::KeyPress(Key::mcode(KeyCode::Up, Modifiers::CONTROL))
+    );
+    assert_eq!(
+        t.read().unwrap(),
+        Event::Mouse(Mouse {
+            button: mouse::Button::Left,
+            event: mouse::Event::Down,
+            modifiers: Modifiers::NONE,
+            x: 5,
+            y: 7,
+        })
+    );
+
+    let mut t = Terminal::new(BufProvider::new(&[b"\x1b[<64;5;7M"]));
+    t.write_all(codes::ENABLE_ALTERNATIVE_BUFFER.as_bytes())
+        .unwrap();
+    t.set_alternate_scroll(true).unwrap();
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Up)));
+}
+
+#[test]
+fn test_alternate_scroll() {
+    let mut t = Terminal::new(BufProvider::new(&[
+        b"\x1b[<64;5;7M\x1b[<65;5;7M\x1b[<65;5;7M",
+    ]));
+    t.set_alternate_scroll(true).unwrap();
+    assert!(t.is_alternate_scroll());
+    assert!(!t.is_wheel_to_arrows());
+    t.write_all(codes::DISABLE_ALTERNATIVE_BUFFER.as_bytes())
+        .unwrap();
+    assert!(!t.is_alternate_buffer());
+
+    // Buffer switched through this terminal takes precedence over buffer
+    // switched by other terminal. See `tests/modes.rs` for the state shared
+    // by the process.
+    let mut other = Terminal::new(BufProvider::new(&[]));
+    other
+        .write_all(codes::ENABLE_ALTERNATIVE_BUFFER.as_bytes())
+        .unwrap();
+    assert!(other.is_alternate_buffer());
+    assert!(!t.is_alternate_buffer());
+    assert!(matches!(t.read().unwrap(), Event::Mouse(_)));
+    other
+        .write_all(codes::DISABLE_ALTERNATIVE_BUFFER.as_bytes())
+        .unwrap();
+
+    t.write_all(codes::ENABLE_ALTERNATIVE_BUFFER.as_bytes())
+        .unwrap();
+    assert!(t.is_alternate_buffer());
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Down)));
+
+    t.write_all(codes::DISABLE_ALTERNATIVE_BUFFER.as_bytes())
+        .unwrap();
+    assert!(!t.is_alternate_buffer());
+    assert!(matches!(t.read().unwrap(), Event::Mouse(_)));
+}
+
+#[test]
+fn test_mouse_capture_codes() {
+    assert_eq!(MouseMode::Drag.enable_code(), "\x1b[?1002h");
+    assert_eq!(MouseMode::All.disable_code(), "\x1b[?1003l");
+    assert_eq!(Encoding::Default.enable_code(), "");
+    assert_eq!(Encoding::Sgr.enable_code(), "\x1b[?1006h");
+    assert_eq!(Encoding::SgrPixels.disable_code(), "\x1b[?1016l");
+}
+
+#[test]
+fn test_tui_session_codes() {
+    let conf = TuiSession::builder()
+        .alternate_buffer(true)
+        .alternate_scroll(true)
+        .hide_cursor(true)
+        .mouse(MouseMode::Press, Encoding::Sgr);
+    assert_eq!(
+        conf.enable_code(),
+        "\x1b[?1049h\x1b[?1007h\x1b[?25l\x1b[?1000h\x1b[?1006h"
+    );
+    assert_eq!(
+        conf.disable_code(),
+        "\x1b[?1006l\x1b[?1000l\x1b[?25h\x1b[?1007l\x1b[?1049l"
+    );
+    assert_eq!(TuiSession::builder().enable_code(), "");
+}
+
+#[test]
+fn test_request() {
+    let mut t = Terminal::new(BufProvider::new(&[b"a", b"\x1b[?3;5R", b"b"]));
+    let res = t.request(codes::REQUEST_CURSOR_POSITION2, |e| match e {
+        Event::Status(Status::CursorPosition { x, y }) => Some((*x, *y)),
+        _ => None,
+    });
+    assert_eq!(res.unwrap(), Some((5, 3)));
+    assert!(t.request_policy().rtt().is_some());
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::verbatim('a')));
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::verbatim('b')));
+
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1b]lmy title\x1b\\")
+        .text("c")
+        .terminal();
+    let timeout = Duration::from_millis(10);
+    assert_eq!(
+        window_title_on(&mut t, timeout).unwrap().as_deref(),
+        Some("my title")
+    );
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::verbatim('c')));
+    assert_eq!(window_title_on(&mut t, timeout).unwrap(), None);
+    assert_timeouts_restored(&t);
+
+    let mut t = ScriptedInput::new()
+        .text("\x1bP1+r436F=38\x1b\\")
+        .terminal();
+    assert_eq!(t.request_termcap("Co").unwrap().as_deref(), Some("8"));
+    assert!(t.io().written_str().ends_with("\x1bP+q436F\x1b\\"));
+}
+
+#[test]
+fn test_queue() {
+    let mut t = ScriptedInput::new().terminal();
+    t.queue(codes::HIDE_CURSOR);
+    t.queue("ab");
+    assert_eq!(t.queued(), "\x1b[?25lab");
+    assert!(t.io().written().is_empty());
+
+    // Direct writes keep the order.
+    t.print("c").unwrap();
+    assert_eq!(t.io().written_str(), "\x1b[?25labc");
+    assert_eq!(t.queued(), "");
+
+    t.queue("d");
+    t.clear_queued();
+    t.queue("e");
+    t.flush_queued().unwrap();
+    assert_eq!(t.io().written_str(), "\x1b[?25labce");
+}
+
+#[test]
+fn test_queue_write_error() {
+    /// Output that fails the given number of writes.
+    struct Failing {
+        fail: usize,
+        out: Vec<u8>,
+    }
+
+    impl Write for Failing {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.fail != 0 {
+                self.fail -= 1;
+                return Err(std::io::ErrorKind::WouldBlock.into());
+            }
+            self.out.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let out = Failing {
+        fail: 1,
+        out: vec![],
+    };
+    let mut t = Terminal::from_read(std::io::empty(), out);
+    t.queue("frame");
+    assert!(t.flush_queued().is_err());
+    assert_eq!(t.queued(), "frame");
+    assert!(t.io_mut().get_out().out.is_empty());
+
+    t.flush_queued().unwrap();
+    assert_eq!(t.queued(), "");
+    assert_eq!(t.io_mut().get_out().out, b"frame");
+}
+
+#[test]
+fn test_request_policy() {
+    let mut p = RequestPolicy::default();
+    assert_eq!(p.timeout(), Duration::from_secs(1));
+    p.record(Duration::from_millis(1));
+    assert_eq!(p.timeout(), Duration::from_millis(20));
+    p.reset();
+    p.record(Duration::from_millis(200));
+    assert_eq!(p.timeout(), Duration::from_millis(800));
+    p.record(Duration::from_secs(10));
+    assert_eq!(p.timeout(), Duration::from_secs(2));
+
+    let p = RequestPolicy::fixed(Duration::from_millis(50));
+    assert_eq!(p.timeout(), Duration::from_millis(50));
+}
+
+#[test]
+fn test_request_retry() {
+    let mut t = ScriptedInput::new().bytes(*b"\x1b[?3;5R").terminal();
+    let policy = t.request_policy_mut();
+    policy.retries = 2;
+    policy.retry_delay = Duration::from_millis(1);
+
+    let pos = |e: &Event| match e {
+        Event::Status(Status::CursorPosition { x, y }) => Some((*x, *y)),
+        _ => None,
+    };
+    assert_eq!(
+        t.request(codes::REQUEST_CURSOR_POSITION2, pos).unwrap(),
+        Some((5, 3))
+    );
+    // Not retried, because the terminal didn't respond to it before.
+    assert_eq!(
+        t.request_status(codes::REQUEST_STATUS_REPORT, StatusCode::Ok)
+            .unwrap(),
+        None
+    );
+    t.io_mut().take_written();
+    assert_eq!(
+        t.request(codes::REQUEST_CURSOR_POSITION2, pos).unwrap(),
+        None
+    );
+    assert_eq!(
+        t.io().written_str(),
+        codes::REQUEST_CURSOR_POSITION2.repeat(3)
+    );
+
+    assert_eq!(
+        *t.request_stats(),
+        RequestStats {
+            requests: 3,
+            responses: 1,
+            timeouts: 4,
+            retries: 2,
+            retry_successes: 0,
+        }
+    );
+    t.reset_request_stats();
+    assert_eq!(*t.request_stats(), RequestStats::default());
+}
+
+#[test]
+fn test_timeout_retry() {
+    let timeout = Duration::from_millis(1);
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1b]lmy title\x1b\\")
+        .terminal();
+    let policy = t.request_policy_mut();
+    policy.retries = 2;
+    policy.retry_delay = Duration::from_millis(1);
+
+    assert!(window_title_on(&mut t, timeout).unwrap().is_some());
+    t.io_mut().take_written();
+    // Retried, because the terminal responded to it before.
+    assert_eq!(window_title_on(&mut t, timeout).unwrap(), None);
+    assert_eq!(t.io().written_str(), codes::REQUEST_WINDOW_TITLE.repeat(3));
+    assert_eq!(t.request_stats().retries, 2);
+    assert_eq!(t.request_policy().retries, 2);
+    assert_eq!(t.request_policy().initial_timeout, Duration::from_secs(1));
+    assert!(t.request_policy().rtt().is_some());
+}
+
+#[test]
+fn test_verify_truecolor() {
+    let timeout = Duration::from_millis(10);
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1bP1$r0;38:2::17:34:51m\x1b\\")
+        .bytes(*b"\x1bP1$r38;2;17;34;51m\x1b\\")
+        .bytes(*b"\x1bP1$r0;38;5;23m\x1b\\")
+        .bytes(*b"\x1bP0$r\x1b\\")
+        .terminal();
+
+    assert_eq!(verify_truecolor_on(&mut t, timeout).unwrap(), Some(true));
+    assert_eq!(verify_truecolor_on(&mut t, timeout).unwrap(), Some(true));
+    assert_eq!(verify_truecolor_on(&mut t, timeout).unwrap(), Some(false));
+    assert_eq!(verify_truecolor_on(&mut t, timeout).unwrap(), None);
+    assert_eq!(verify_truecolor_on(&mut t, timeout).unwrap(), None);
+    assert_timeouts_restored(&t);
+
+    let written = t.io().written_str();
+    assert!(written.starts_with("\x1b[38;2;17;34;51m\x1bP$qm\x1b\\\x1b[39m"));
+
+    // The policy is restored also on error.
+    let mut t = Terminal::new(BufProvider::new(&[]));
+    assert!(verify_truecolor_on(&mut t, timeout).is_err());
+    assert_eq!(*t.request_policy(), RequestPolicy::default());
+}
+
+#[test]
+fn test_cursor_position() {
+    let timeout = Duration::from_millis(10);
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1b[?3;5R")
+        .bytes(*b"\x1b[0n")
+        .bytes(*b"\x1b[0n")
+        .bytes(*b"\x1b[1;2R")
+        .text("a")
+        .terminal();
+
+    assert_eq!(cursor_position_on(&mut t, timeout).unwrap(), Some((5, 3)));
+    assert_eq!(t.io_mut().take_written(), b"\x1b[?6n\x1b[5n");
+    // The terminal doesn't support the unambiguous request, so the other
+    // request is sent without waiting for the timeout. Its response is
+    // ambiguous with shift + F3, but it is not reported as key press.
+    assert_eq!(cursor_position_on(&mut t, timeout).unwrap(), Some((2, 1)));
+    assert_eq!(t.io_mut().take_written(), b"\x1b[?6n\x1b[5n\x1b[6n");
+    assert_eq!(t.request_stats().timeouts, 0);
+    // The response to the status report is not returned.
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::verbatim('a')));
+    assert_timeouts_restored(&t);
+
+    // Terminal that doesn't respond waits only once.
+    assert_eq!(cursor_position_on(&mut t, timeout).unwrap(), None);
+    assert_eq!(t.io().written_str(), "\x1b[?6n\x1b[5n");
+    assert_eq!(t.request_stats().timeouts, 1);
+}
+
+#[test]
+fn test_char_size() {
+    let timeout = Duration::from_millis(10);
+    let mut t = ScriptedInput::new().text("\x1b[6;20;10t").terminal();
+
+    assert_eq!(char_size_on(&mut t, timeout).unwrap(), Some((10, 20)));
+    assert_eq!(t.io().written_str(), "\x1b[16t");
+    assert_eq!(char_size_on(&mut t, timeout).unwrap(), None);
+    assert_timeouts_restored(&t);
+}
+
+#[test]
+fn test_device_attributes() {
+    let timeout = Duration::from_millis(10);
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1b[?64;1;4;22;28;42c")
+        .terminal();
+
+    let attr = device_attributes_on(&mut t, timeout).unwrap().unwrap();
+    assert_eq!(attr.typ, TermType::Vt420);
+    assert_eq!(
+        attr.features,
+        TermFeatures::COLUMNS132
+            | TermFeatures::SIXEL_GRAPHICS
+            | TermFeatures::ANSI_COLOR
+            | TermFeatures::RECTANGULAR_EDITING
+            | TermFeatures::ISO_LATIN2
+    );
+    assert_eq!(attr.ids(), [64, 1, 4, 22, 28, 42]);
+    assert_eq!(t.io().written_str(), "\x1b[c");
+    assert_eq!(device_attributes_on(&mut t, timeout).unwrap(), None);
+}
+
+#[test]
+fn test_cursor_style() {
+    let timeout = Duration::from_millis(10);
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1bP1$r4 q\x1b\\")
+        .bytes(*b"\x1bP1$r q\x1b\\")
+        .bytes(*b"\x1bP0$r\x1b\\")
+        .terminal();
+
+    assert_eq!(
+        cursor_style_on(&mut t, timeout).unwrap(),
+        Some(CursorStyle::Underline(false))
+    );
+    assert_eq!(
+        cursor_style_on(&mut t, timeout).unwrap(),
+        Some(CursorStyle::Block(Some(true)))
+    );
+    assert_eq!(cursor_style_on(&mut t, timeout).unwrap(), None);
+    assert_eq!(cursor_style_on(&mut t, timeout).unwrap(), None);
+    assert_eq!(t.io().written_str(), codes::REQUEST_CURSOR_STYLE.repeat(4));
+
+    for i in 0..7 {
+        let style = CursorStyle::from_code(i).unwrap();
+        assert_eq!(style.code(), i);
+        assert_eq!(codes::set_cursor(style), format!("\x1b[{i} q"));
+    }
+    assert_eq!(CursorStyle::from_code(7), None);
+}
+
+#[test]
+fn test_query_setting() {
+    let timeout = Duration::from_millis(10);
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1bP1$r0;1;38;2;1;2;3m\x1b\\")
+        .bytes(*b"\x1bP1$r2;20r\x1b\\")
+        .bytes(*b"\x1bP1$r6 q\x1b\\")
+        .bytes(*b"\x1bP1$r6 q\x1b\\")
+        .terminal();
+
+    assert_eq!(
+        query_setting_on(&mut t, SettingKind::Sgr, timeout).unwrap(),
+        Some(Setting::Sgr(Style::new().attr(Attrs::BOLD).fg((1, 2, 3))))
+    );
+    assert_eq!(
+        query_setting_on(&mut t, SettingKind::ScrollRegion, timeout).unwrap(),
+        Some(Setting::ScrollRegion(2..=20))
+    );
+    assert_eq!(
+        query_setting_on(&mut t, SettingKind::CursorStyle, timeout).unwrap(),
+        Some(Setting::CursorStyle(CursorStyle::Bar(false)))
+    );
+    assert_eq!(
+        query_setting_on(&mut t, SettingKind::ScrollRegion, timeout).unwrap(),
+        None
+    );
+    assert_eq!(
+        t.io().written_str(),
+        [
+            codes::REQUEST_SGR,
+            codes::REQUEST_SCROLL_REGION,
+            codes::REQUEST_CURSOR_STYLE,
+            codes::REQUEST_SCROLL_REGION,
+        ]
+        .concat()
+    );
+    assert_timeouts_restored(&t);
+}
+
+#[test]
+fn test_supports_sixel() {
+    let timeout = Duration::from_millis(10);
+    let mut t = ScriptedInput::new().bytes(*b"\x1b[?62;4;22c").terminal();
+    assert_eq!(supports_sixel_on(&mut t, timeout).unwrap(), Support::Yes);
+
+    // Sixel is not reported in the attributes, but the terminal knows the
+    // number of sixel colors.
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1b[?62;22c")
+        .bytes(*b"\x1b[?1;0;256S")
+        .terminal();
+    assert_eq!(supports_sixel_on(&mut t, timeout).unwrap(), Support::Yes);
+    assert_eq!(t.io().written_str(), "\x1b[c\x1b[?1;1;1S");
+
+    let mut t = ScriptedInput::new().bytes(*b"\x1b[?62;22c").terminal();
+    assert_eq!(supports_sixel_on(&mut t, timeout).unwrap(), Support::No);
+    assert_eq!(Support::No.known(), Some(false));
+    assert_eq!(Support::from(None), Support::Unknown);
+}
+
+#[test]
+fn test_cursor_stack() {
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1b[?3;5R\x1b[0n")
+        .bytes(*b"\x1b[?10;1R\x1b[0n")
+        .terminal();
+
+    assert_eq!(t.push_cursor().unwrap(), Some((5, 3)));
+    assert_eq!(t.push_cursor().unwrap(), Some((1, 10)));
+    // The terminal doesn't respond.
+    assert_eq!(t.push_cursor().unwrap(), None);
+    assert_eq!(t.cursor_stack_len(), 3);
+
+    t.io_mut().take_written();
+    assert_eq!(t.pop_cursor().unwrap(), None);
+    assert_eq!(t.pop_cursor().unwrap(), Some((1, 10)));
+    assert_eq!(t.pop_cursor().unwrap(), Some((5, 3)));
+    assert_eq!(t.pop_cursor().unwrap(), None);
+    assert_eq!(t.io().written_str(), "\x1b[10;1H\x1b[3;5H");
+}
+
+#[test]
+fn test_record_input() {
+    let mut t = ScriptedInput::new().bytes(*b"\x1b[Aab").terminal();
+    t.set_record_input(true);
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Up)));
+    assert_eq!(t.take_recorded_input(), b"\x1b[A");
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::verbatim('a')));
+    assert_eq!(t.take_recorded_input(), b"a");
+    t.set_record_input(false);
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::verbatim('b')));
+    assert_eq!(t.take_recorded_input(), b"");
+}
+
+#[test]
+fn test_key_inspector() {
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1bOP")
+        .key(Key::mcode(KeyCode::Char('c'), Modifiers::CONTROL))
+        .terminal();
+    key_inspector_on(&mut t).unwrap();
+    assert!(!t.is_recording_input());
+
+    let out = t.io().written_str();
+    assert!(
+        out.contains("\r\n\"\\x1bOP\" (1b 4f 50)\r\n  chosen: KeyPress(Key {")
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn test_tty_provider() {
+    use std::{
+        io::{Read, Write},
+        os::fd::OwnedFd,
+    };
+
+    let (in_r, mut in_w) = std::io::pipe().unwrap();
+    let (mut out_r, out_w) = std::io::pipe().unwrap();
+    let io = TtyProvider::from_files(
+        OwnedFd::from(in_r).into(),
+        OwnedFd::from(out_w).into(),
+    );
+    let mut t = Terminal::new(io);
+    assert!(!t.is_in_terminal());
+    assert!(!t.io().is_out_raw());
+    assert_eq!(t.io().term_size(), None);
+    assert!(!t.wait_for_input(Duration::ZERO).unwrap());
+
+    in_w.write_all(b"\x1b[Ax").unwrap();
+    assert!(t.wait_for_input(Duration::from_secs(1)).unwrap());
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Up)));
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::verbatim('x')));
+
+    t.print("hello").unwrap();
+    drop(t);
+    let mut out = String::new();
+    out_r.read_to_string(&mut out).unwrap();
+    assert_eq!(out, "hello");
+}
+
+#[test]
+fn test_request_no_raw() {
+    // Tests don't run in raw terminal.
+    if !is_terminal_raw() {
+        assert!(matches!(
+            try_request_no_raw(codes::REQUEST_TERMINAL_NAME, Duration::ZERO),
+            Err(Error::RawModeRequired)
+        ));
+    }
+}
+
+#[test]
+fn test_with_scroll_region() {
+    let mut t = ScriptedInput::new().terminal();
+    t.io_mut().set_size(80, 10);
+    let res = t.with_scroll_region(2..=5, |t| {
+        t.write_all(b"a")?;
+        Ok(5)
+    });
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(t.io_mut().take_written(), b"\x1b[2;5ra\x1b[0;0r");
+
+    let res =
+        t.with_scroll_region(2..=5, |_| Err::<(), _>(Error::Interrupted));
+    assert!(matches!(res, Err(Error::Interrupted)));
+    assert_eq!(t.io_mut().take_written(), b"\x1b[2;5r\x1b[0;0r");
+
+    assert!(t.with_scroll_region(0..=2, |_| Ok(5)).is_err());
+    assert!(t.with_scroll_region(2..=11, |_| Ok(5)).is_err());
+    assert!(t.io().written().is_empty());
+    assert!(t.with_scroll_region(2..=10, |_| Ok(5)).is_ok());
+
+    // Without known size, the region is not limited.
+    let mut t = Terminal::new(BufProvider::new(&[]));
+    assert_eq!(t.with_scroll_region(2..=500, |_| Ok(5)).unwrap(), 5);
+}
+
+#[test]
+fn test_synchronized() {
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x1b[?2026;2$y")
+        .bytes(*b"\x1b[?2026;0$y")
+        .terminal();
+    assert_eq!(t.supports_synchronized_update().unwrap(), Some(true));
+    assert_eq!(t.supports_synchronized_update().unwrap(), Some(false));
+
+    t.io_mut().take_written();
+    let res = t.synchronized(|t| {
+        t.print("a")?;
+        Err::<(), _>(Error::Interrupted)
+    });
+    assert!(res.is_err());
+    assert_eq!(t.io().written_str(), "\x1b[?2026ha\x1b[?2026l");
+    assert_eq!(codes::REQUEST_SYNCHRONIZED_UPDATE, "\x1b[?2026$p");
+}
+
+#[test]
+fn test_esc_timeout() {
+    let input = || ScriptedInput::new().bytes(*b"\x1b").bytes(*b"[A");
+
+    let mut t = input().terminal();
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Esc)));
+
+    let mut t = input().bytes(*b"\x1b").terminal();
+    t.set_esc_timeout(Duration::from_millis(10));
+    assert_eq!(t.esc_timeout(), Duration::from_millis(10));
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Up)));
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Esc)));
+}
+
+#[test]
+fn test_read_provider() {
+    let (input, mut writer) = std::io::pipe().unwrap();
+    let mut t = Terminal::from_read(input, vec![]);
+
+    assert!(!t.wait_for_input(Duration::from_millis(10)).unwrap());
+    writer.write_all(b"a\x1b[A").unwrap();
+    assert!(t.wait_for_input(Duration::from_secs(5)).unwrap());
+    assert_eq!(
+        t.read().unwrap(),
+        Event::KeyPress(Key::new(KeyCode::Char('a'), Modifiers::NONE, 'a'))
+    );
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Up)));
+
+    t.print("hi").unwrap();
+    assert_eq!(t.io_mut().get_out().as_slice(), b"hi");
+
+    drop(writer);
+    assert!(t.wait_for_input(Duration::from_secs(5)).unwrap());
+    assert!(matches!(t.read(), Err(Error::StdInEof)));
+}
+
+#[test]
+fn test_split() {
+    let mut t = ScriptedInput::new().key(Key::code(KeyCode::Up)).terminal();
+    t.queue("queued");
+    let (mut reader, mut writer) = t.split().unwrap();
+
+    let handle = std::thread::spawn(move || {
+        writer.println("a\nb").unwrap();
+        writer
+    });
+    assert_eq!(
+        reader.read().unwrap(),
+        Event::KeyPress(Key::code(KeyCode::Up))
+    );
+    let writer = handle.join().unwrap();
+
+    assert_eq!(writer.io().written_str(), "a\r\nb\r\n");
+    assert_eq!(reader.io().written_str(), "queued");
+}
+
+#[test]
+fn test_visual_bell() {
+    let mut t = ScriptedInput::new().terminal();
+    t.visual_bell(Duration::from_millis(1)).unwrap();
+    t.request_attention().unwrap();
+    assert_eq!(
+        t.io().written_str(),
+        "\x1b[?5h\x1b[?5l\x1b]1337;RequestAttention=yes\x1b\\"
+    );
+}
+
+#[test]
+fn test_diagnostics_probe() {
+    let mut t = Terminal::new(BufProvider::new(&[
+        b"\x1b[?62;4;22c",
+        b"\x1bP>|XTerm(367)\x1b\\",
+        b"\x1bP1$r0;38;5;17m\x1b\\",
+        b"\x1b[?1;0;256S",
+    ]));
+    let mut report = Report::default();
+    report.probe(&mut t);
+    assert_eq!(report.sixel(), Some(true));
+    assert_eq!(report.sixel_colors, Some(256));
+    assert_eq!(report.truecolor_verified, Some(false));
+    assert!(!report.truecolor());
+    assert!(!report.notifications());
+    assert_eq!(report.term_name.unwrap().name, "XTerm");
+    assert!(!t.has_buffered_input());
+
+    let mut t = Terminal::new(BufProvider::new(&[
+        b"\x1b[?62;22c",
+        b"\x1bP>|WezTerm 20240203\x1b\\",
+    ]));
+    let mut report = Report::default();
+    report.probe(&mut t);
+    assert!(report.notifications());
+
+    let report = Report {
+        term_program: Some("iTerm.app".into()),
+        ..Default::default()
+    };
+    assert!(report.notifications());
+    assert!(report
+        .entries()
+        .contains(&("TERM_PROGRAM", "iTerm.app".into())));
+}
+
+#[test]
+fn test_8bit_controls() {
+    let mut t = Terminal::new(BufProvider::new(&[
+        b"\x9b?3;5R",
+        b"\x9d10;rgb:ffff/0000/0000\x9c",
+        b"\x9bA",
+    ]));
+    t.set_8bit_controls(true);
+    assert_eq!(
+        t.read().unwrap(),
+        Event::Status(Status::CursorPosition { x: 5, y: 3 })
+    );
+    assert_eq!(
+        t.read().unwrap(),
+        Event::Status(Status::DefaultFgColor(Rgb::new(0xffff, 0, 0)))
+    );
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::code(KeyCode::Up)));
+}
+
+#[test]
+fn test_c1_and_double_escape_input() {
+    let mut t = ScriptedInput::new()
+        .bytes(*b"\x9bA")
+        .bytes(*b"\x9b1;5D")
+        .bytes(*b"\x8fP")
+        .bytes(*b"\x1b\x1b[A")
+        .bytes(*b"\x1b\x1bOH")
+        .bytes(*b"\x1b\x1b[3~")
+        .bytes(*b"\x1b\x1b[?3;5R")
+        .bytes(*b"\x1b\x1b")
+        .text("a")
+        .terminal();
+
+    let keys = [
+        Key::code(KeyCode::Up),
+        Key::mcode(KeyCode::Left, Modifiers::CONTROL),
+        Key::code(KeyCode::F1),
+        Key::mcode(KeyCode::Up, Modifiers::ALT),
+        Key::mcode(KeyCode::Home, Modifiers::ALT),
+        Key::mcode(KeyCode::Delete, Modifiers::ALT),
+    ];
+    for k in keys {
+        assert_eq!(t.read().unwrap(), Event::KeyPress(k));
+    }
+    assert_eq!(
+        t.read().unwrap(),
+        Event::Status(Status::CursorPosition { x: 5, y: 3 })
+    );
+    assert_eq!(
+        t.read().unwrap(),
+        Event::KeyPress(Key::mcode(KeyCode::Esc, Modifiers::ALT))
+    );
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::verbatim('a')));
+}
+
+#[test]
+fn test_newline_policy() {
+    let mut t = Terminal::new(BufProvider::new(&[]));
+    assert_eq!(t.newline_policy(), NewlinePolicy::Platform);
+    assert_eq!(t.newline(), "\r\n");
+    t.set_newline_policy(NewlinePolicy::Off);
+    assert_eq!(t.newline(), "\n");
+    t.set_newline_policy(NewlinePolicy::Crlf);
+    assert_eq!(t.newline(), "\r\n");
+
+    assert!(!NewlinePolicy::Off.translates(true));
+    assert!(NewlinePolicy::Crlf.translates(false));
+    assert!(NewlinePolicy::Platform.translates(true));
+    assert!(!NewlinePolicy::Platform.translates(false));
+
+    // Raw mode can't be enabled without terminal.
+    let mut t = ScriptedInput::new().terminal();
+    t.set_newline_policy(NewlinePolicy::RawMode);
+    assert!(!is_raw_mode_enabled());
+    assert!(!NewlinePolicy::RawMode.translates(true));
+    assert_eq!(t.newline(), "\n");
+    t.println("a\nb").unwrap();
+    assert_eq!(t.io().written_str(), "a\nb\n");
+}
+
+#[test]
+fn test_scripted_input() {
+    let keys = [
+        Key::verbatim('a'),
+        Key::new(KeyCode::Char('a'), Modifiers::SHIFT, 'A'),
+        Key::mcode(KeyCode::Char('c'), Modifiers::CONTROL),
+        Key::mcode(KeyCode::Char('x'), Modifiers::ALT),
+        Key::mcode(KeyCode::Up, Modifiers::NONE),
+        Key::mcode(KeyCode::Left, Modifiers::CONTROL | Modifiers::SHIFT),
+        Key::mcode(KeyCode::Home, Modifiers::ALT),
+        Key::code(KeyCode::F1),
+        Key::mcode(KeyCode::F4, Modifiers::CONTROL),
+        Key::code(KeyCode::F5),
+        Key::mcode(KeyCode::F12, Modifiers::SHIFT),
+        Key::code(KeyCode::Delete),
+        Key::mcode(KeyCode::PgDown, Modifiers::CONTROL),
+        Key::code(KeyCode::Backspace),
+        Key::code(KeyCode::Tab),
+    ];
+    let mouse = Mouse {
+        button: mouse::Button::Right,
+        event: mouse::Event::Up,
+        modifiers: Modifiers::CONTROL,
+        x: 300,
+        y: 20,
+    };
+
+    let mut input = ScriptedInput::new();
+    for k in keys {
+        input = input.key(k);
+    }
+    let mut t = input.mouse(mouse).typed("hi").terminal();
+    t.io_mut().eof_panic(1);
+
+    for k in keys {
+        let Event::KeyPress(r) = t.read().unwrap() else {
+            panic!("Expected key press.");
+        };
+        assert!(r.same_key(&k), "{r:?} != {k:?}");
+    }
+    assert_eq!(t.read().unwrap(), Event::Mouse(mouse));
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::verbatim('h')));
+    assert_eq!(t.read().unwrap(), Event::KeyPress(Key::verbatim('i')));
+    assert!(!t.io().has_input());
+    assert!(t.io().read_bytes().ends_with(b"\x1b[<18;300;20mhi"));
+}
+
+#[test]
+fn test_recording_io_provider() {
+    let mut t = ScriptedInput::new().text("ab").terminal();
+    assert_eq!(t.read_byte().unwrap(), b'a');
+    assert_eq!(t.io().read_bytes(), b"ab");
+
+    t.println("one\ntwo").unwrap();
+    assert_eq!(t.io().written_str(), "one\r\ntwo\r\n");
+    assert_eq!(t.io_mut().take_written(), b"one\r\ntwo\r\n");
+
+    t.io_mut().set_out_raw(false);
+    t.print("one\ntwo").unwrap();
+    assert_eq!(t.io().written(), b"one\ntwo");
+}
+
+/// Checks that the timeouts of the default policy are restored after request
+/// with custom timeout and that the measured round-trip time is kept.
+fn assert_timeouts_restored<T: IoProvider>(t: &Terminal<T>) {
+    let mut policy = *t.request_policy();
+    assert!(policy.rtt().is_some());
+    policy.reset();
+    assert_eq!(policy, RequestPolicy::default());
+}
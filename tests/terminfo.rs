@@ -0,0 +1,87 @@
+use termal::{
+    codes,
+    terminfo::{BoolCap, Capabilities, NumCap, StrCap},
+};
+
+/// Builds compiled terminfo entry with 16 bit numbers.
+fn entry(
+    names: &str,
+    bools: &[bool],
+    nums: &[i16],
+    strs: &[(usize, &str)],
+) -> Vec<u8> {
+    let str_cnt = strs.iter().map(|(i, _)| i + 1).max().unwrap_or_default();
+    let mut offsets = vec![-1_i16; str_cnt];
+    let mut table = vec![];
+    for (i, s) in strs {
+        offsets[*i] = table.len() as i16;
+        table.extend_from_slice(s.as_bytes());
+        table.push(0);
+    }
+
+    let mut res = vec![];
+    let push =
+        |n: i16, res: &mut Vec<u8>| res.extend_from_slice(&n.to_le_bytes());
+    for n in [
+        0o432,
+        names.len() as i16 + 1,
+        bools.len() as i16,
+        nums.len() as i16,
+        str_cnt as i16,
+        table.len() as i16,
+    ] {
+        push(n, &mut res);
+    }
+    res.extend_from_slice(names.as_bytes());
+    res.push(0);
+    res.extend(bools.iter().map(|b| *b as u8));
+    if res.len() % 2 == 1 {
+        res.push(0);
+    }
+    for n in nums.iter().chain(&offsets) {
+        push(*n, &mut res);
+    }
+    res.extend(table);
+    res
+}
+
+#[test]
+fn test_parse() {
+    let data = entry(
+        "test|test terminal",
+        &[false, true],
+        &[80, -1, 24],
+        &[
+            (StrCap::EnterCaMode as usize, "\x1b[?47h"),
+            (StrCap::Bell as usize, "\x07"),
+            (StrCap::ClearScreen as usize, "\x1b[H\x1b[J$<50>"),
+        ],
+    );
+    let caps = Capabilities::parse(&data).unwrap();
+
+    assert_eq!(caps.names(), ["test", "test terminal"]);
+    assert!(caps.get_bool(BoolCap::AutoRightMargin));
+    assert!(!caps.get_bool(BoolCap::BackColorErase));
+    assert_eq!(caps.get_num(NumCap::Columns), Some(80));
+    assert_eq!(caps.get_num(NumCap::Lines), Some(24));
+    assert_eq!(caps.get_num(NumCap::MaxColors), None);
+
+    assert_eq!(caps.get_str(StrCap::EnterCaMode), Some("\x1b[?47h"));
+    assert_eq!(caps.get_str(StrCap::ExitCaMode), None);
+    assert_eq!(caps.code(StrCap::ClearScreen), "\x1b[H\x1b[J");
+    assert_eq!(
+        caps.code(StrCap::ExitCaMode),
+        codes::DISABLE_ALTERNATIVE_BUFFER
+    );
+    assert!(caps.differs(StrCap::EnterCaMode));
+    assert!(!caps.differs(StrCap::Bell));
+}
+
+#[test]
+fn test_invalid() {
+    assert!(Capabilities::parse(b"").is_err());
+    assert!(Capabilities::parse(b"\x1a\x01\x05\x00").is_err());
+    let mut data = entry("test", &[], &[], &[(1, "\x07")]);
+    data[0] = 0;
+    assert!(Capabilities::parse(&data).is_err());
+}
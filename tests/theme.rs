@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use termal::{
+    colorize_str,
+    columns::{ColumnPrinter, ColumnSpec},
+    formatc, formatmc,
+    raw::{testing::ScriptedInput, RequestPolicy},
+    theme::{
+        detect_palette_on, is_dark, modify_styles, palette, set_palette,
+        set_styles, style, styles, Palette, StyleSet,
+    },
+    Attrs, Color, Rgb, Style,
+};
+
+// The palette is global, so everything is tested in single test.
+#[test]
+fn test_palette() {
+    set_palette(Palette::DARK);
+    assert_eq!(
+        formatc!("{'t:error bold}{}{'_}", 5),
+        format!("{}\x1b[1m5\x1b[0m", Palette::DARK.error.fg_code())
+    );
+    assert_eq!(
+        formatc!("{'t:dim_}{x}{'t:dim}", x = 1,),
+        format!(
+            "{}1{}",
+            Palette::DARK.dim.bg_code(),
+            Palette::DARK.dim.fg_code()
+        )
+    );
+    assert_eq!(formatmc!(false, "{'t:success}ok{'_}"), "ok");
+
+    let mut light = Palette::LIGHT;
+    *light.get_mut("error").unwrap() = Color::Basic(1);
+    set_palette(light);
+    assert_eq!(formatc!("{'t:error}e"), "\x1b[31me");
+    assert_eq!(termal::proc::colorize!("{'t:error}e"), "\x1b[31me");
+    assert_eq!(colorize_str("{'t:error}e").unwrap(), "\x1b[31me");
+    assert!(colorize_str("{'t:nonsense}").is_err());
+    assert!(colorize_str("{'t:error,}").is_err());
+
+    assert!(is_dark(Rgb::new(0x1e1e, 0x1e1e, 0x1e1e)));
+    assert!(!is_dark(Rgb::new(0xffff, 0xffff, 0xeeee)));
+
+    let mut t = ScriptedInput::new()
+        .text("\x1b]11;rgb:1e1e/1e1e/1e1e\x1b\\")
+        .terminal();
+    t.set_request_policy(RequestPolicy::fixed(Duration::ZERO));
+    assert_eq!(detect_palette_on(&mut t).unwrap(), Palette::DARK);
+    assert_eq!(palette(), Palette::DARK);
+
+    // No response keeps the palette.
+    set_palette(Palette::LIGHT);
+    assert_eq!(detect_palette_on(&mut t).unwrap(), Palette::LIGHT);
+}
+
+// The theme styles are global, so everything is tested in single test.
+#[test]
+fn test_styles() {
+    set_styles(
+        StyleSet::new()
+            .with("table", Attrs::BOLD)
+            .with("progress.bar.filled", Color::Basic(2)),
+    );
+    modify_styles(|s| s.set("select.highlight", Attrs::INVERSE));
+    assert_eq!(styles().iter().count(), 3);
+
+    let overrides = StyleSet::new()
+        .with("table.header", Attrs::ITALIC)
+        .with("progress.bar", Color::Basic(1));
+    assert_eq!(style("table.header", None), Some(Attrs::BOLD.into()));
+    assert_eq!(
+        style("table.header", Some(&overrides)),
+        Some(Attrs::ITALIC.into())
+    );
+    // The more specific key in the theme wins over less specific override.
+    assert_eq!(
+        style("progress.bar.filled", Some(&overrides)),
+        Some(Style::new().fg(Color::Basic(2)))
+    );
+    assert_eq!(
+        style("progress.bar.empty", Some(&overrides)),
+        Some(Style::new().fg(Color::Basic(1)))
+    );
+    assert_eq!(style("list", Some(&overrides)), None);
+
+    let mut cols = ColumnPrinter::new(&[ColumnSpec::left().header("A")]);
+    cols.push_row(["a"]);
+    assert_eq!(cols.flush(), "\x1b[1mA\x1b[0m\na\n");
+
+    let mut cols = ColumnPrinter::new(&[ColumnSpec::left().header("A")])
+        .styles(StyleSet::new().with("table.header", Attrs::ITALIC));
+    assert_eq!(cols.flush(), "\x1b[3mA\x1b[0m\n");
+
+    set_styles(StyleSet::new());
+    let mut cols = ColumnPrinter::new(&[ColumnSpec::left().header("A")]);
+    assert_eq!(cols.flush(), "A\n");
+}
+
+#[test]
+fn test_palette_crate_path() {
+    let expand = |s: &str| {
+        termal_core::proc::colorize(s.parse().unwrap())
+            .map(|r| r.to_string().replace(' ', ""))
+    };
+
+    let res = expand(r#"crate = ::termal_core; "{'t:error}e""#).unwrap();
+    assert!(res.contains(r#"::termal_core::theme::palette_code("error")"#));
+    let res = expand(r#""{'t:error}e""#).unwrap();
+    assert!(res.contains(r#"::termal::theme::palette_code("error")"#));
+
+    assert!(expand(r#"crate ::termal_core; "{'t:error}""#).is_err());
+    assert!(expand(r#"crate = ::termal_core "{'t:error}""#).is_err());
+}